@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     ffi::OsString,
     fs::File,
     io,
@@ -8,20 +9,24 @@ use std::{
 
 use {
     anyhow::Context,
-    bstr::{BStr, ByteSlice},
+    bstr::{BStr, BString, ByteSlice},
     jiff::{
-        Zoned, civil, fmt,
+        SignedDuration, Timestamp, ToSpan, Zoned, civil, fmt,
         tz::{self, Offset},
     },
 };
 
 use crate::{
     args::{Configurable, Usage},
-    datetime::{DateTime, DateTimeFlexible},
+    datetime::{
+        DateTime, DateTimeFlexible, FlexibleKind, check_rfc2822_zone,
+        rfc2822_unknown_offset,
+    },
     ical::ByWeekday,
     locale::StrtimeConfig,
-    parse::{BytesExt, FromBytes},
+    parse::{BytesExt, FromBytes, split_time_zone_annotation},
     span::TimeSpan,
+    tag::MaybeTagged,
     timezone::TimeZone,
 };
 
@@ -254,9 +259,13 @@ padded.
 
 `%p`: Whether the time is in the AM or PM, uppercase.
 
-`%Q`: An IANA time zone identifier, or `%z` if one doesn't exist.
+`%Q`: An IANA time zone identifier, e.g., `America/New_York`. When the
+datetime has only a fixed offset (for example, it was parsed from
+`2025-03-15T10:23-04:00`) or its time zone is unknown (for example, it was
+parsed with a `Z` offset), this formats the offset like `%z` instead.
 
-`%:Q`: An IANA time zone identifier, or `%:z` if one doesn't exist.
+`%:Q`: An IANA time zone identifier, or the offset like `%:z` when there is no
+identifier.
 
 `%q`: The quarter of the year.
 
@@ -269,6 +278,15 @@ environment variable. Supported when formatting only.
 
 `%s`: A Unix timestamp, in seconds.
 
+`%3s`, `%6s`, `%9s`: A Unix timestamp, in milliseconds, microseconds and
+nanoseconds, respectively. Like `%s`, any finer precision is truncated toward
+zero. For example, `%6s` formats `2024-07-20T16:20:55.123456789Z` as
+`1721492455123456`. Unlike other directives, these numbers select a unit
+instead of a minimum amount of padding.
+
+`%Ns`: Equivalent to `%9s`. This is not the same as `%N` followed by a
+literal `s`.
+
 `%T`: Equivalent to `%H:%M:%S`.
 
 `%t`: Formats as a tab character. Parses arbitrary whitespace.
@@ -295,7 +313,8 @@ environment variable. Supported when formatting only.
 
 `%y`: A two-digit year. Represents only 1969-2068. Zero padded.
 
-`%Z`: A time zone abbreviation. Supported when formatting only.
+`%Z`: A time zone abbreviation, e.g., `EDT`. Use `%Q` for the full IANA time
+zone identifier. Supported when formatting only.
 
 `%z`: A time zone offset in the format `[+-]HHMM[SS]`.
 
@@ -377,7 +396,10 @@ the `-r/--relative` flag.
 
 Otherwise, an `strptime`-style format string may be given. For example, the
 format string `%A %Y-%m-%d %H:%M:%S %:z %Q` would parse
-`Saturday 2025-03-15 10:23:00 -04:00 America/New_York`.
+`Saturday 2025-03-15 10:23:00 -04:00 America/New_York`. Input may also end with
+an RFC 9557 time zone annotation that isn't part of the format string. For
+example, the format string `%s` would parse `1721507455[Europe/London]` as the
+Unix timestamp `1721507455` in the `Europe/London` time zone.
 
 In general, the `strptime` format directives supported should generally
 match what you'd expect from your POSIX `strptime` implementation. However,
@@ -439,6 +461,14 @@ padded.
 
 `%s`: A Unix timestamp, in seconds.
 
+`%3s`, `%6s`, `%9s`: A Unix timestamp, in milliseconds, microseconds and
+nanoseconds, respectively. For example, `%6s` parses `1721492455123456` as
+`2024-07-20T16:20:55.123456Z`. Unlike other directives, these numbers select a
+unit instead of a maximum number of digits.
+
+`%Ns`: Equivalent to `%9s`. This is not the same as `%N` followed by a
+literal `s`.
+
 `%T`: Equivalent to `%H:%M:%S`.
 
 `%t`: Formats as a tab character. Parses arbitrary whitespace.
@@ -448,7 +478,10 @@ padded.
 
 `%u`: The day of the week beginning with Monday at `1`.
 
-`%V`: Week number in the ISO 8601 week-based calendar. Zero padded.
+`%V`: Week number in the ISO 8601 week-based calendar. Zero padded. When
+parsed with `%G` but without a weekday (e.g., `%u`), the date is the Monday
+that starts the week. For example, `%G-W%V` parses `2025-W01` as
+`2024-12-30`.
 
 `%W`: Week number. Week 1 is the first week starting with a Monday. Zero
 padded.
@@ -519,6 +552,168 @@ default, `%d` will only try to consume at most 2 digits.
             .map(DateTime::from)
     }
 
+    /// Parses a datetime and returns a label for the format that matched.
+    ///
+    /// For most formats, the label is just the name of the format. For the
+    /// flexible format, the label describes which of the formats it supports
+    /// was actually recognized.
+    pub fn parse_detect(
+        &self,
+        relative: &DateTime,
+        dt: &BStr,
+    ) -> anyhow::Result<(DateTime, Cow<'_, str>)> {
+        let label = match *self {
+            Format::Strtime(ref fmt) => Cow::Borrowed(&**fmt),
+            Format::Flexible => {
+                let (parsed, kind) =
+                    DateTimeFlexible::parse_relative_kind(relative.get(), dt)
+                        .with_context(|| {
+                            format!(
+                                "parsing datetime `{}` for format {} failed",
+                                dt, self,
+                            )
+                        })?;
+                return Ok((
+                    DateTime::from(parsed),
+                    Cow::Borrowed(kind.label()),
+                ));
+            }
+            _ => Cow::Owned(self.to_string()),
+        };
+        Ok((self.parse(relative, dt)?, label))
+    }
+
+    /// Like `Format::parse_detect`, but accepts an offset that is
+    /// inconsistent with the time zone parsed alongside it.
+    ///
+    /// For example, `2024-07-20T12:00-08[America/New_York]` has an offset of
+    /// `-08`, but New York is at `-04` at that instant. Normal parsing always
+    /// rejects such datetimes. Here, the offset takes priority and the
+    /// inconsistency is returned, so that callers can decide whether to
+    /// report or reject it.
+    pub fn parse_detect_lenient(
+        &self,
+        relative: &DateTime,
+        dt: &BStr,
+    ) -> anyhow::Result<(DateTime, Cow<'_, str>, Option<OffsetMismatch>)> {
+        let Some(mismatch) = self.offset_mismatch(dt) else {
+            let (parsed, label) = self.parse_detect(relative, dt)?;
+            return Ok((parsed, label, None));
+        };
+        let label = match *self {
+            Format::Strtime(ref fmt) => Cow::Borrowed(&**fmt),
+            Format::Flexible => Cow::Borrowed(FlexibleKind::Rfc9557.label()),
+            _ => Cow::Owned(self.to_string()),
+        };
+        Ok((mismatch.datetime.clone(), label, Some(mismatch)))
+    }
+
+    /// Looks for an offset that is inconsistent with the time zone parsed
+    /// alongside it.
+    ///
+    /// This only looks at the RFC 9557, flexible and `strptime`-like formats,
+    /// since they are the only ones that can parse both an offset and a time
+    /// zone. A critical time zone annotation, e.g., `[!America/New_York]`,
+    /// is never reported, since RFC 9557 requires rejecting it.
+    ///
+    /// If the input doesn't parse, then this returns `None` and the error is
+    /// left to normal parsing.
+    fn offset_mismatch(&self, dt: &BStr) -> Option<OffsetMismatch> {
+        let (civil, stated, tz, zone) = match *self {
+            Format::Rfc9557 | Format::Flexible => {
+                let pieces = fmt::temporal::Pieces::parse(dt).ok()?;
+                let Some(fmt::temporal::PiecesOffset::Numeric(ref off)) =
+                    pieces.offset()
+                else {
+                    return None;
+                };
+                let ann = pieces.time_zone_annotation()?;
+                if ann.is_critical() {
+                    return None;
+                }
+                let zone = match ann.kind() {
+                    fmt::temporal::TimeZoneAnnotationKind::Named(name) => {
+                        name.as_str().to_string()
+                    }
+                    fmt::temporal::TimeZoneAnnotationKind::Offset(off) => {
+                        off.to_string()
+                    }
+                    _ => return None,
+                };
+                let time = pieces.time().unwrap_or(civil::Time::midnight());
+                let civil = pieces.date().to_datetime(time);
+                (civil, off.offset(), ann.to_time_zone().ok()?, zone)
+            }
+            Format::Strtime(ref fmt) => {
+                let tm = fmt::strtime::parse(fmt.as_bytes(), dt).ok()?;
+                let (stated, zone) = (tm.offset()?, tm.iana_time_zone()?);
+                let tz = tz::TimeZone::get(zone).ok()?;
+                (tm.to_datetime().ok()?, stated, tz, zone.to_string())
+            }
+            _ => return None,
+        };
+        let timestamp = stated.to_timestamp(civil).ok()?;
+        let expected = tz.to_offset(timestamp);
+        if stated == expected {
+            return None;
+        }
+        Some(OffsetMismatch {
+            input: dt.to_owned(),
+            stated,
+            expected,
+            zone,
+            datetime: DateTime::from(timestamp.to_zoned(tz)),
+        })
+    }
+
+    /// Returns true when the given input parses in this format as a date
+    /// without a time of day.
+    ///
+    /// Such inputs are normally given a time of midnight. This only looks at
+    /// the RFC 9557, RFC 3339, flexible and `strptime`-like formats, since
+    /// the others always require a time. For the flexible format, relative
+    /// datetimes are never considered date-only, even when they have no
+    /// time, like `tomorrow`.
+    ///
+    /// If the input doesn't parse, then this returns `false` and the error
+    /// is left to normal parsing.
+    pub fn is_date_only(&self, dt: &BStr) -> bool {
+        let pieces_date_only = |dt: &[u8]| {
+            fmt::temporal::Pieces::parse(dt)
+                .is_ok_and(|pieces| pieces.time().is_none())
+        };
+        match *self {
+            Format::Rfc9557 | Format::Rfc3339 => pieces_date_only(dt),
+            Format::Flexible => {
+                if let Ok(pieces) = fmt::temporal::Pieces::parse(dt) {
+                    return pieces.time().is_none();
+                }
+                match crate::datetime::rewrite_iso8601_date(dt) {
+                    Ok(Some((rewritten, _))) => pieces_date_only(&rewritten),
+                    _ => false,
+                }
+            }
+            Format::Strtime(ref fmt) => {
+                let tm = match fmt::strtime::parse(fmt.as_bytes(), dt) {
+                    Ok(tm) => tm,
+                    Err(_) => {
+                        let Some((rest, _)) = split_time_zone_annotation(dt)
+                        else {
+                            return false;
+                        };
+                        let Ok(tm) = fmt::strtime::parse(fmt.as_bytes(), rest)
+                        else {
+                            return false;
+                        };
+                        tm
+                    }
+                };
+                tm.hour().is_none() && tm.timestamp().is_none()
+            }
+            _ => false,
+        }
+    }
+
     fn format_impl(
         &self,
         config: &StrtimeConfig,
@@ -539,6 +734,9 @@ default, `%d` will only try to consume at most 2 digits.
                         .to_string()
                 }
             }
+            Format::Rfc2822 if zdt.time_zone().is_unknown() => RFC2822
+                .timestamp_to_string(&zdt.timestamp())
+                .context("RFC 2822 formatting failed")?,
             Format::Rfc2822 => RFC2822
                 .zoned_to_string(zdt)
                 .context("RFC 2822 formatting failed")?,
@@ -547,7 +745,8 @@ default, `%d` will only try to consume at most 2 digits.
                 .context("RFC 9110 formatting failed")?,
             Format::Strtime(ref fmt) => {
                 let tm = fmt::strtime::BrokenDownTime::from(zdt);
-                tm.to_string_with_config(config, &**fmt)?
+                let fmt = with_epoch_directives(fmt, zdt.timestamp());
+                tm.to_string_with_config(config, &*fmt)?
             }
             Format::Flexible => anyhow::bail!(
                 "flexible format not allowed when formatting a datetime",
@@ -609,7 +808,7 @@ default, `%d` will only try to consume at most 2 digits.
                 let date = pieces.date();
                 let time = pieces.time().unwrap_or(civil::Time::midnight());
                 let dt = date.to_datetime(time);
-                let zdt = match offset {
+                match offset {
                     fmt::temporal::PiecesOffset::Zulu => {
                         dt.to_zoned(tz::TimeZone::unknown())?
                     }
@@ -623,42 +822,32 @@ default, `%d` will only try to consume at most 2 digits.
                     unk => {
                         anyhow::bail!("unrecognized parsed offset: {unk:?}")
                     }
-                };
-                zdt
+                }
             }
             // N.B. Jiff doesn't have a dedicated RFC 9110 parser. But
             // RFC 2822 subsumes it. I'm not really sure it's worth being
             // precise about *parsing* RFC 9110, since you usually want to
             // be flexible.
             Format::Rfc2822 | Format::Rfc9110 => {
-                RFC2822_PARSER.parse_zoned(dt)?
+                let zdt = RFC2822_PARSER.parse_zoned(dt)?;
+                check_rfc2822_zone(dt)?;
+                rfc2822_unknown_offset(dt, zdt)
             }
             Format::Strtime(ref fmt) => {
-                let tm = fmt::strtime::parse(fmt.as_bytes(), dt)?;
-                match tm.to_zoned() {
+                let err = match parse_strtime(fmt, dt, &crate::TZ) {
                     Ok(zdt) => return Ok(zdt),
-                    Err(err) => {
-                        // If we parsed an offset or an IANA time zone
-                        // identifier but still couldn't get a `Zoned`, then
-                        // the error is probably legit and we should bubble
-                        // it up. Otherwise, we can try some more things.
-                        if tm.offset().is_some()
-                            || tm.iana_time_zone().is_some()
-                        {
-                            return Err(err.into());
-                        }
-                    }
-                }
-                // If we can't get even a civil datetime from a broken down
-                // time, then we're kinda hosed. Not much we can do.
-                //
-                // Note that this routine is "smart." It knows to use midnight
-                // if civil time isn't present. It will also automatically
-                // convert, e.g., ISO 8601 week dates to Gregorian dates.
-                let dt = tm.to_datetime()?;
-                // We interpret civil datetimes without offset/time-zone info
-                // as local time.
-                dt.to_zoned(crate::TZ.clone())?
+                    Err(err) => err,
+                };
+                // If the input has a trailing RFC 9557 time zone annotation
+                // that the format doesn't account for, then try again
+                // without it and use the annotation as the time zone. This
+                // is mostly useful for giving Unix timestamps a time zone,
+                // e.g., `1721507455[Europe/London]` with `%s`.
+                let Some((rest, name)) = split_time_zone_annotation(dt) else {
+                    return Err(err);
+                };
+                let tz = TEMPORAL_PARSER.parse_time_zone(name)?;
+                parse_strtime(fmt, rest, &tz)?.with_time_zone(tz)
             }
             Format::Flexible => {
                 DateTimeFlexible::parse_relative(relative, dt)?.into()
@@ -667,6 +856,175 @@ default, `%d` will only try to consume at most 2 digits.
     }
 }
 
+/// Parses a datetime with the given `strptime` format.
+///
+/// When the datetime doesn't include an offset or time zone, it's interpreted
+/// as a civil datetime in the time zone given.
+fn parse_strtime(
+    fmt: &str,
+    dt: &[u8],
+    tz: &tz::TimeZone,
+) -> anyhow::Result<Zoned> {
+    let Some((before, directive, unit, after)) = split_epoch_directive(fmt)
+    else {
+        return parse_strtime_jiff(fmt, dt, tz);
+    };
+    // Jiff doesn't know about `%Ns` and friends, so we find the integer it
+    // corresponds to ourselves. Then we parse everything else with Jiff by
+    // putting the whole seconds of the timestamp in its place with `%s`. The
+    // fractional seconds are added back at the end.
+    let start = if before.is_empty() {
+        0
+    } else {
+        fmt::strtime::BrokenDownTime::parse_prefix(before, dt)?.1
+    };
+    let (ts, len) = parse_epoch_integer(&dt[start..], directive, unit)?;
+    let mut rewritten = BString::from(&dt[..start]);
+    rewritten.extend_from_slice(ts.as_second().to_string().as_bytes());
+    rewritten.extend_from_slice(&dt[start + len..]);
+    let zdt =
+        parse_strtime_jiff(&format!("{before}%s{after}"), &rewritten, tz)?;
+    let subsec = SignedDuration::from_nanos(ts.subsec_nanosecond().into());
+    Ok(zdt.checked_add(subsec)?)
+}
+
+/// Parses a datetime with the given `strptime` format using only the
+/// directives supported by Jiff.
+fn parse_strtime_jiff(
+    fmt: &str,
+    dt: &[u8],
+    tz: &tz::TimeZone,
+) -> anyhow::Result<Zoned> {
+    let mut tm = fmt::strtime::parse(fmt.as_bytes(), dt)?;
+    // An ISO 8601 week date without a weekday, e.g., `2024-W29` parsed with
+    // `%G-W%V`, refers to the first day of that week. Jiff requires the
+    // weekday, so fill it in when it's missing.
+    if tm.iso_week_year().is_some()
+        && tm.iso_week().is_some()
+        && tm.weekday().is_none()
+        && tm.year().is_none()
+    {
+        tm.set_weekday(Some(civil::Weekday::Monday));
+    }
+    match tm.to_zoned() {
+        Ok(zdt) => return Ok(zdt),
+        Err(err) => {
+            // If we parsed an offset or an IANA time zone identifier but
+            // still couldn't get a `Zoned`, then the error is probably legit
+            // and we should bubble it up. Otherwise, we can try some more
+            // things.
+            if tm.offset().is_some() || tm.iana_time_zone().is_some() {
+                return Err(err.into());
+            }
+        }
+    }
+    // If we can't get even a civil datetime from a broken down time, then
+    // we're kinda hosed. Not much we can do.
+    //
+    // Note that this routine is "smart." It knows to use midnight if civil
+    // time isn't present. It will also automatically convert, e.g., ISO 8601
+    // week dates to Gregorian dates.
+    let dt = tm.to_datetime()?;
+    Ok(dt.to_zoned(tz.clone())?)
+}
+
+/// The `strftime` directives for Unix timestamps in units finer than
+/// seconds.
+///
+/// Jiff doesn't support these directives, so bttf handles them before
+/// handing the rest of a format string off to Jiff. (Jiff would otherwise
+/// treat, e.g., `%3s` as `%s` padded to 3 digits, and `%Ns` as `%N`
+/// followed by a literal `s`.)
+const EPOCH_DIRECTIVES: &[(&str, EpochUnit)] = &[
+    ("%3s", EpochUnit::Millisecond),
+    ("%6s", EpochUnit::Microsecond),
+    ("%9s", EpochUnit::Nanosecond),
+    ("%Ns", EpochUnit::Nanosecond),
+];
+
+/// Splits the given format string around its first `%3s`, `%6s`, `%9s` or
+/// `%Ns` directive.
+///
+/// The directive found is returned along with the unit of the timestamp it
+/// corresponds to. If the format string doesn't contain any of these
+/// directives, then this returns `None`. Note that `%%Ns` is a literal `%`
+/// followed by `Ns`.
+fn split_epoch_directive(
+    fmt: &str,
+) -> Option<(&str, &'static str, EpochUnit, &str)> {
+    let mut i = 0;
+    while let Some(offset) = fmt[i..].find('%') {
+        let at = i + offset;
+        if fmt[at..].starts_with("%%") {
+            i = at + 2;
+            continue;
+        }
+        for &(directive, unit) in EPOCH_DIRECTIVES {
+            if fmt[at..].starts_with(directive) {
+                let after = &fmt[at + directive.len()..];
+                return Some((&fmt[..at], directive, unit, after));
+            }
+        }
+        i = at + 1;
+    }
+    None
+}
+
+/// Replaces every `%3s`, `%6s`, `%9s` or `%Ns` directive in the given format
+/// string with the timestamp given, in the corresponding unit.
+///
+/// Like `%s`, any precision finer than the unit is truncated toward zero.
+fn with_epoch_directives(fmt: &str, ts: Timestamp) -> Cow<'_, str> {
+    if split_epoch_directive(fmt).is_none() {
+        return Cow::Borrowed(fmt);
+    }
+    let mut replaced = String::new();
+    let mut rest = fmt;
+    while let Some((before, _, unit, after)) = split_epoch_directive(rest) {
+        replaced.push_str(before);
+        replaced.push_str(&unit.timestamp_to_int(ts).to_string());
+        rest = after;
+    }
+    replaced.push_str(rest);
+    Cow::Owned(replaced)
+}
+
+/// Parses an integer Unix timestamp from the beginning of the input given.
+///
+/// Like Jiff's `%s`, any leading whitespace is skipped and the integer may
+/// have a sign. This returns the timestamp along with the number of bytes
+/// of input that it occupied. The directive is only used for error messages.
+fn parse_epoch_integer(
+    dt: &[u8],
+    directive: &str,
+    unit: EpochUnit,
+) -> anyhow::Result<(Timestamp, usize)> {
+    let start = dt.iter().take_while(|b| b.is_ascii_whitespace()).count();
+    let mut end = start;
+    if dt.get(end).is_some_and(|&b| b == b'-' || b == b'+') {
+        end += 1;
+    }
+    let digits = dt[end..].iter().take_while(|b| b.is_ascii_digit()).count();
+    anyhow::ensure!(
+        digits > 0,
+        "{directive} failed: expected a Unix timestamp in {unit}, \
+         but found no digits",
+        unit = unit.name(),
+    );
+    end += digits;
+    let ts = unit
+        .parse(&dt[start..end])
+        .and_then(|number| unit.to_timestamp(number))
+        .with_context(|| {
+            format!(
+                "{directive} failed: invalid Unix timestamp `{}` in {unit}",
+                dt[start..end].as_bstr(),
+                unit = unit.name(),
+            )
+        })?;
+    Ok((ts, end))
+}
+
 impl std::str::FromStr for Format {
     type Err = anyhow::Error;
 
@@ -701,6 +1059,107 @@ impl std::fmt::Display for Format {
     }
 }
 
+/// An offset that is inconsistent with the time zone parsed alongside it.
+///
+/// This is returned by `Format::parse_detect_lenient`. Its `Display` impl
+/// describes the inconsistency, including both the stated and expected
+/// offsets.
+#[derive(Clone, Debug)]
+pub struct OffsetMismatch {
+    input: BString,
+    stated: Offset,
+    expected: Offset,
+    zone: String,
+    /// The instant given by the stated offset, in the time zone parsed.
+    datetime: DateTime,
+}
+
+impl std::fmt::Display for OffsetMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "datetime `{input}` has offset `{stated}`, but time zone \
+             `{zone}` has offset `{expected}` at that instant",
+            input = self.input,
+            stated = self.stated,
+            zone = self.zone,
+            expected = self.expected,
+        )
+    }
+}
+
+/// The output mode selected by `--output`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Output {
+    /// Print each datetime as a line of text.
+    #[default]
+    Text,
+    /// Print each datetime as a JSON object on its own line.
+    Json,
+}
+
+impl Output {
+    pub const USAGE: Usage = Usage::flag(
+        "--output <mode>",
+        "Print datetimes as `text` (default) or `json`.",
+        r#"
+Print datetimes as `text` (default) or `json`.
+
+With `json`, each datetime is printed as a JSON object on its own line (also
+known as NDJSON). The object has the following fields:
+
+`timestamp`: the instant of the datetime in UTC, e.g.,
+`2024-07-20T20:30:55Z`.
+
+`zoned`: the datetime with its offset and time zone, e.g.,
+`2024-07-20T16:30:55-04:00[America/New_York]`.
+
+`offset`: the offset of the datetime, e.g., `-04:00`.
+
+`epoch_seconds`: the number of whole seconds since the Unix epoch.
+
+`epoch_nanoseconds`: the number of nanoseconds since the Unix epoch.
+
+Commands that format datetimes, like `bttf time fmt`, also include a
+`formatted` field with the formatted datetime.
+
+JSON output cannot be used with tagged data, since tag values must be strings
+for tagged data to be usable by other commands. Use `bttf untag --output json`
+to print tagged data as JSON instead.
+"#,
+    );
+
+    /// Returns true when JSON output was requested.
+    pub fn is_json(&self) -> bool {
+        matches!(*self, Output::Json)
+    }
+
+    /// Returns an error when JSON output was requested for tagged data.
+    pub fn check<T>(&self, datum: &MaybeTagged<'_, T>) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !(self.is_json() && matches!(*datum, MaybeTagged::Tagged(_))),
+            "--output json cannot be used with tagged data \
+             (use `bttf untag --output json` to print tagged data as JSON)",
+        );
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Output {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Output> {
+        Ok(match s {
+            "text" => Output::Text,
+            "json" => Output::Json,
+            unk => anyhow::bail!(
+                "unrecognized output mode `{unk}`, \
+                 expected `text` or `json`",
+            ),
+        })
+    }
+}
+
 /// Provides parsing for the English name of a month.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct Month(i8);
@@ -712,6 +1171,12 @@ impl Month {
     }
 }
 
+impl Default for Month {
+    fn default() -> Month {
+        Month(1)
+    }
+}
+
 impl std::str::FromStr for Month {
     type Err = anyhow::Error;
 
@@ -721,7 +1186,7 @@ impl std::str::FromStr for Month {
                 format!("failed to parse `{s}` as an integer month")
             })?;
             anyhow::ensure!(
-                1 <= month && month <= 12,
+                (1..=12).contains(&month),
                 "parsed `{month}` as an integer month, but it's not \
                  in the required range of `1..=12`",
             );
@@ -962,6 +1427,26 @@ impl std::str::FromStr for Unit {
     }
 }
 
+impl std::fmt::Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        use jiff::Unit::*;
+
+        let name = match self.unit {
+            Year => "years",
+            Month => "months",
+            Week => "weeks",
+            Day => "days",
+            Hour => "hours",
+            Minute => "minutes",
+            Second => "seconds",
+            Millisecond => "milliseconds",
+            Microsecond => "microseconds",
+            Nanosecond => "nanoseconds",
+        };
+        write!(f, "{name}")
+    }
+}
+
 /// A scrappy comma delimited sequence of values.
 ///
 /// This type doesn't have any requirements on `T` other than that it can be
@@ -1152,17 +1637,19 @@ impl std::str::FromStr for ByWeekdays {
 /// This is similar to `Unit`, but:
 ///
 /// * Does not support nanoseconds, since Jiff doesn't support smaller than
-/// nanosecond precision. Therefore, "start of"/"end of" nanosecond doesn't
-/// really make sense.
+///   nanosecond precision. Therefore, "start of"/"end of" nanosecond doesn't
+///   really make sense.
 /// * Does not support "week," and instead requires "week-sunday" or
-/// "week-monday." Otherwise, the "start" or "end" of a week is ambiguous.
+///   "week-monday." Otherwise, the "start" or "end" of a week is ambiguous.
 ///
 /// It seems likely it might make sense to support other things in the future
 /// as well, but I'd like to wait for use cases.
 #[derive(Clone, Copy, Debug)]
 pub enum Of {
     Year,
+    Quarter,
     Month,
+    Week,
     WeekSunday,
     WeekMonday,
     Day,
@@ -1181,14 +1668,21 @@ impl Of {
 Find the start of this unit relative to a datetime. This can be a calendar or
 a time unit.
 
-Calendar units are years, months, weeks that start on Sunday, weeks that start
-on Monday or days. Here are the different ways that each calendar unit can be
+Calendar units are years, quarters, months, weeks, weeks that start on Sunday,
+weeks that start on Monday or days. Quarters are three month periods, where
+the first quarter starts in the month given by `--fiscal-start`, which defaults
+to January. Plain weeks start on the weekday given by `--week-start`, which
+defaults to Monday. Here are the different ways that each calendar unit can be
 spelled:
 
 years, year, yrs, yr, y
 
+quarters, quarter, qtrs, qtr, q
+
 months, month, mos, mo
 
+weeks, week, wks, wk, w
+
 week-sunday, wk-sunday, w-sunday
 
 week-monday, wk-monday, w-monday
@@ -1217,14 +1711,21 @@ microseconds, microsecond, micros, micro, usecs, µsecs, usec, µsec, us, µs
 Find the start of this unit relative to a datetime. This can be a calendar or
 a time unit.
 
-Calendar units are years, months, weeks that start on Sunday, weeks that start
-on Monday or days. Here are the different ways that each calendar unit can be
+Calendar units are years, quarters, months, weeks, weeks that start on Sunday,
+weeks that start on Monday or days. Quarters are three month periods, where
+the first quarter starts in the month given by `--fiscal-start`, which defaults
+to January. Plain weeks start on the weekday given by `--week-start`, which
+defaults to Monday. Here are the different ways that each calendar unit can be
 spelled:
 
 years, year, yrs, yr, y
 
+quarters, quarter, qtrs, qtr, q
+
 months, month, mos, mo
 
+weeks, week, wks, wk, w
+
 week-sunday, wk-sunday, w-sunday
 
 week-monday, wk-monday, w-monday
@@ -1246,19 +1747,30 @@ microseconds, microsecond, micros, micro, usecs, µsecs, usec, µsec, us, µs
 "#,
     );
 
-    pub fn start(&self, dt: &DateTime) -> anyhow::Result<DateTime> {
+    /// Returns the start of this unit containing the datetime given.
+    ///
+    /// The week start is only used for `Of::Week`, and the month starting
+    /// the fiscal year (in the range `1..=12`) is only used for
+    /// `Of::Quarter`.
+    pub fn start(
+        &self,
+        dt: &DateTime,
+        week_start: civil::Weekday,
+        fiscal_start: i8,
+    ) -> anyhow::Result<DateTime> {
+        let start_of_week = |week_start| {
+            dt.get().tomorrow()?.nth_weekday(-1, week_start)?.start_of_day()
+        };
         let zdt = dt.get();
         let zdt = match *self {
             Of::Year => zdt.first_of_year()?.start_of_day()?,
-            Of::Month => zdt.first_of_month()?.start_of_day()?,
-            Of::WeekSunday => zdt
-                .tomorrow()?
-                .nth_weekday(-1, civil::Weekday::Sunday)?
-                .start_of_day()?,
-            Of::WeekMonday => zdt
-                .tomorrow()?
-                .nth_weekday(-1, civil::Weekday::Monday)?
+            Of::Quarter => first_of_quarter(zdt.date(), fiscal_start)?
+                .to_zoned(zdt.time_zone().clone())?
                 .start_of_day()?,
+            Of::Month => zdt.first_of_month()?.start_of_day()?,
+            Of::Week => start_of_week(week_start)?,
+            Of::WeekSunday => start_of_week(civil::Weekday::Sunday)?,
+            Of::WeekMonday => start_of_week(civil::Weekday::Monday)?,
             Of::Day => zdt.start_of_day()?,
             Of::Hour => {
                 zdt.with().minute(0).second(0).subsec_nanosecond(0).build()?
@@ -1273,19 +1785,35 @@ microseconds, microsecond, micros, micro, usecs, µsecs, usec, µsec, us, µs
         Ok(zdt.into())
     }
 
-    pub fn end(&self, dt: &DateTime) -> anyhow::Result<DateTime> {
+    /// Returns the end of this unit containing the datetime given.
+    ///
+    /// The week start is only used for `Of::Week`, and the month starting
+    /// the fiscal year (in the range `1..=12`) is only used for
+    /// `Of::Quarter`.
+    pub fn end(
+        &self,
+        dt: &DateTime,
+        week_start: civil::Weekday,
+        fiscal_start: i8,
+    ) -> anyhow::Result<DateTime> {
+        let end_of_week = |week_start: civil::Weekday| {
+            dt.get()
+                .yesterday()?
+                .nth_weekday(1, week_start.previous())?
+                .end_of_day()
+        };
         let zdt = dt.get();
         let zdt = match *self {
             Of::Year => zdt.last_of_year()?.end_of_day()?,
-            Of::Month => zdt.last_of_month()?.end_of_day()?,
-            Of::WeekSunday => zdt
-                .yesterday()?
-                .nth_weekday(1, civil::Weekday::Saturday)?
-                .end_of_day()?,
-            Of::WeekMonday => zdt
-                .yesterday()?
-                .nth_weekday(1, civil::Weekday::Sunday)?
+            Of::Quarter => first_of_quarter(zdt.date(), fiscal_start)?
+                .checked_add(2.months())?
+                .last_of_month()
+                .to_zoned(zdt.time_zone().clone())?
                 .end_of_day()?,
+            Of::Month => zdt.last_of_month()?.end_of_day()?,
+            Of::Week => end_of_week(week_start)?,
+            Of::WeekSunday => end_of_week(civil::Weekday::Sunday)?,
+            Of::WeekMonday => end_of_week(civil::Weekday::Monday)?,
             Of::Day => zdt.end_of_day()?,
             Of::Hour => zdt
                 .with()
@@ -1315,7 +1843,9 @@ impl std::str::FromStr for Of {
         // This is what's recognized by the friendly duration format.
         let of = match &*s.to_lowercase() {
             "years" | "year" | "yrs" | "yr" | "y" => Year,
+            "quarters" | "quarter" | "qtrs" | "qtr" | "q" => Quarter,
             "months" | "month" | "mos" | "mo" => Month,
+            "weeks" | "week" | "wks" | "wk" | "w" => Week,
             "week-sunday" | "wk-sunday" | "w-sunday" => WeekSunday,
             "week-monday" | "wk-monday" | "w-monday" => WeekMonday,
             "days" | "day" | "d" => Day,
@@ -1332,6 +1862,19 @@ impl std::str::FromStr for Of {
     }
 }
 
+/// Returns the first day of the quarter containing the date given.
+///
+/// Quarters are three month periods aligned such that a quarter starts in
+/// the month given (in the range `1..=12`), which is the start of the fiscal
+/// year.
+fn first_of_quarter(
+    date: civil::Date,
+    fiscal_start: i8,
+) -> anyhow::Result<civil::Date> {
+    let into_quarter = (date.month() - fiscal_start).rem_euclid(3);
+    Ok(date.first_of_month().checked_sub(into_quarter.months())?)
+}
+
 /// Provides parsing for Jiff's `RoundMode` type.
 #[derive(Clone, Debug)]
 pub struct RoundMode {
@@ -1346,8 +1889,15 @@ impl RoundMode {
 This flag specifies how to perform rounding. That is, this flag specifies how
 to treat the remainder when rounding either datetimes or time spans.
 
+A tie occurs when the remainder is exactly half of the rounding increment. For
+example, `1m30s` is a tie when rounding to the nearest minute. The modes
+prefixed with `half-` round to the nearest allowed value and differ only in how
+they break ties. The other modes always round in the same direction, regardless
+of the size of the remainder, and so don't treat ties specially.
+
 The default for this flag is `half-expand`, which does rounding like how you
-were probably taught in school. The legal values are:
+were probably taught in school: ties are rounded away from zero. The legal
+values are:
 
 `ceil`: rounds toward positive infinity. For negative time spans and datetimes,
 this option will make the value smaller, which could be unexpected. To round
@@ -1360,7 +1910,8 @@ value larger, which could be unexpected. To round towards zero, use `trunc`.
 `expand`: rounds away from zero like `ceil` for positive time spans and
 datetimes, and like `floor` for negative spans and datetimes.
 
-`trunc`: rounds toward zero, chopping off any fractional part of a unit.
+`trunc`: rounds toward zero, chopping off any fractional part of a unit. This
+never rounds away from zero, even when the remainder is a tie or more.
 
 `half-ceil`: rounds to the nearest allowed value like `half-expand`, but when
 there is a tie, round towards positive infinity like `ceil`.
@@ -1372,6 +1923,7 @@ there is a tie, round towards negative infinity like `floor`.
 and the smallest unit. When there is a tie, round away from zero like `ceil`
 for positive time spans and datetimes and like `floor` for negative time spans
 and datetimes. This corresponds to how rounding is often taught in school.
+This mode may also be spelled `half-up`.
 
 `half-trunc`: rounds to the nearest allowed value like `half-expand`, but when
 there is a tie, round towards zero like `trunc`.
@@ -1380,7 +1932,7 @@ there is a tie, round towards zero like `trunc`.
 there is a tie, round towards the value that is an even multiple of the
 rounding increment. For example, with a rounding increment of 3, the number 10
 would round up to 12 instead of down to 9, because 12 is an even multiple of 3,
-where as 9 is is an odd multiple.
+whereas 9 is an odd multiple.
 "#,
     );
 
@@ -1410,7 +1962,7 @@ impl std::str::FromStr for RoundMode {
             "trunc" => Trunc,
             "half-ceil" => HalfCeil,
             "half-floor" => HalfFloor,
-            "half-expand" => HalfExpand,
+            "half-expand" | "half-up" => HalfExpand,
             "half-trunc" => HalfTrunc,
             "half-even" => HalfEven,
             unk => anyhow::bail!("unrecognized rounding mode: `{unk}`"),
@@ -1495,6 +2047,116 @@ impl From<PathBuf> for FileOrStdin {
     }
 }
 
+/// The `--now` flag, which overrides the current time.
+///
+/// The current time is read lazily into `crate::NOW`, and this often happens
+/// while a command's arguments are still being parsed (e.g., when parsing
+/// the value of `-r/--relative`). So this flag must come before any other flag
+/// that takes a datetime. Otherwise, an error is returned instead of silently
+/// ignoring it.
+///
+/// This flag is accepted by commands that use the current time, and may also
+/// be given before the command name to apply to any command, e.g.,
+/// `bttf --now 2024-01-01 time fmt now`.
+#[derive(Clone, Debug)]
+pub struct Now;
+
+impl Now {
+    pub const USAGE: Usage = Usage::flag(
+        "--now <datetime>",
+        "Override the current time for this invocation.",
+        r#"
+Override the current time for this invocation.
+
+This sets the datetime used for `now` and for datetimes relative to the current
+time, like `-1d` or `next thurs`. It takes precedence over the `BTTF_NOW`
+environment variable. A datetime without an offset is interpreted in your
+system time zone, and a relative datetime is interpreted relative to the
+actual current time.
+
+This flag must come before any other flag that takes a datetime, like
+`-r/--relative`. It may also be given before the command name, e.g.,
+`bttf --now 2024-01-01 span since 2023-12-31`.
+"#,
+    );
+
+    /// Removes all leading `--now` flags from the given CLI arguments.
+    ///
+    /// The remaining arguments are returned along with the value of the last
+    /// `--now` flag given, if any. Only flags that come before the first
+    /// argument that isn't a `--now` flag (i.e., the command name) are
+    /// removed. Everything after that is returned unchanged, so that, e.g.,
+    /// arguments meant for a command run by `bttf tag exec` are never eaten.
+    pub fn extract(
+        args: impl IntoIterator<Item = OsString>,
+    ) -> anyhow::Result<(Option<jiff::Timestamp>, Vec<OsString>)> {
+        let mut now = None;
+        let mut it = args.into_iter().peekable();
+        while let Some(arg) = it.peek() {
+            let value = if arg == "--now" {
+                it.next();
+                it.next().ok_or_else(|| {
+                    anyhow::anyhow!("--now: missing argument for option")
+                })?
+            } else if let Some(value) =
+                arg.to_str().and_then(|arg| arg.strip_prefix("--now="))
+            {
+                let value = OsString::from(value);
+                it.next();
+                value
+            } else {
+                break;
+            };
+            now = Some(Now::parse(&value).context("--now")?);
+        }
+        Ok((now, it.collect()))
+    }
+
+    /// Sets the current time for this invocation to the given timestamp.
+    ///
+    /// This returns an error if the current time has already been used.
+    pub fn set(now: jiff::Timestamp) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !crate::NOW_INITIALIZED.load(std::sync::atomic::Ordering::SeqCst),
+            "--now must come before any other flag that takes a datetime",
+        );
+        *crate::NOW_OVERRIDE.lock().unwrap() = Some(now);
+        Ok(())
+    }
+
+    /// Parses the value of a `--now` flag into a timestamp.
+    fn parse(value: &std::ffi::OsStr) -> anyhow::Result<jiff::Timestamp> {
+        let Some(value) = value.to_str() else {
+            let err = lexopt::Error::NonUnicodeValue(value.into());
+            return Err(anyhow::Error::from(err));
+        };
+        let actual = jiff::Timestamp::now().to_zoned(crate::TZ.clone());
+        let dt = DateTimeFlexible::parse_relative(&actual, value.as_bytes())?;
+        Ok(DateTime::from(dt).get().timestamp())
+    }
+}
+
+impl Configurable for Now {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("now") => {
+                let value = p.value().context("--now")?;
+                Now::set(Now::parse(&value).context("--now")?)?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        &[Now::USAGE]
+    }
+}
+
 /// A helper type for parsing a flag indicating the "number of threads" to use.
 ///
 /// This should be used in commands that support parallelism, so that users
@@ -1524,17 +2186,16 @@ available cores to use.
         if let Some(threads) = self.count {
             return threads;
         }
-        let available = match std::thread::available_parallelism() {
+        match std::thread::available_parallelism() {
             Ok(available) => available,
             Err(err) => {
                 log::warn!(
                     "failed to query available parallelism, \
                      falling back to single threaded mode: {err}",
                 );
-                return NonZero::<usize>::MIN;
+                NonZero::<usize>::MIN
             }
-        };
-        available
+        }
     }
 }
 
@@ -1737,3 +2398,99 @@ impl FromBytes for Op {
         }
     }
 }
+
+/// The unit of an integer Unix timestamp.
+#[derive(Clone, Copy, Debug)]
+pub enum EpochUnit {
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+}
+
+impl EpochUnit {
+    /// Guesses the unit of an integer Unix timestamp from its magnitude.
+    ///
+    /// This assumes that the timestamp is for a datetime within a few
+    /// thousand years of today. So for example, `1742000000` is interpreted
+    /// as seconds while `1742000000000` is interpreted as milliseconds.
+    pub fn detect(number: i64) -> EpochUnit {
+        match number.unsigned_abs() {
+            0..100_000_000_000 => EpochUnit::Second,
+            100_000_000_000..100_000_000_000_000 => EpochUnit::Millisecond,
+            100_000_000_000_000..100_000_000_000_000_000 => {
+                EpochUnit::Microsecond
+            }
+            _ => EpochUnit::Nanosecond,
+        }
+    }
+
+    /// Returns the plural name of this unit, e.g., `milliseconds`.
+    fn name(&self) -> &'static str {
+        match *self {
+            EpochUnit::Second => "seconds",
+            EpochUnit::Millisecond => "milliseconds",
+            EpochUnit::Microsecond => "microseconds",
+            EpochUnit::Nanosecond => "nanoseconds",
+        }
+    }
+
+    /// Parses an integer timestamp in this unit.
+    ///
+    /// This returns an error if the timestamp isn't an integer or if it's
+    /// out of the range supported by bttf.
+    pub fn parse(&self, raw: &[u8]) -> anyhow::Result<i64> {
+        let number: i64 = raw
+            .to_str()
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .context("expected an integer")?;
+        self.to_timestamp(number)?;
+        Ok(number)
+    }
+
+    /// Converts an integer timestamp in this unit to a Jiff timestamp.
+    ///
+    /// This returns an error if it's out of the range supported by bttf.
+    pub fn to_timestamp(self, number: i64) -> anyhow::Result<Timestamp> {
+        Ok(match self {
+            EpochUnit::Second => Timestamp::from_second(number)?,
+            EpochUnit::Millisecond => Timestamp::from_millisecond(number)?,
+            EpochUnit::Microsecond => Timestamp::from_microsecond(number)?,
+            EpochUnit::Nanosecond => {
+                Timestamp::from_nanosecond(i128::from(number))?
+            }
+        })
+    }
+
+    /// Converts a Jiff timestamp to an integer timestamp in this unit.
+    ///
+    /// Any fractional part is truncated toward zero, just like the `%s`
+    /// strftime directive.
+    pub fn timestamp_to_int(self, ts: Timestamp) -> i128 {
+        match self {
+            EpochUnit::Second => i128::from(ts.as_second()),
+            EpochUnit::Millisecond => i128::from(ts.as_millisecond()),
+            EpochUnit::Microsecond => i128::from(ts.as_microsecond()),
+            EpochUnit::Nanosecond => ts.as_nanosecond(),
+        }
+    }
+}
+
+impl FromBytes for EpochUnit {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<EpochUnit> {
+        Ok(match s {
+            b"s" => EpochUnit::Second,
+            b"ms" => EpochUnit::Millisecond,
+            b"us" => EpochUnit::Microsecond,
+            b"ns" => EpochUnit::Nanosecond,
+            unk => anyhow::bail!(
+                "unknown epoch unit `{unk}`, \
+                 expected one of `s`, `ms`, `us` or `ns`",
+                unk = unk.as_bstr(),
+            ),
+        })
+    }
+}
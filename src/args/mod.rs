@@ -121,9 +121,7 @@ pub fn configure(
     Ok(())
 }
 
-fn collect_usage_for_args<'a>(
-    targets: &[&mut dyn Configurable],
-) -> Vec<Usage> {
+fn collect_usage_for_args(targets: &[&mut dyn Configurable]) -> Vec<Usage> {
     let mut usages = vec![];
     for t in targets.iter() {
         usages.extend(t.usage().iter().copied().filter(|u| !u.flag));
@@ -133,9 +131,7 @@ fn collect_usage_for_args<'a>(
     usages
 }
 
-fn collect_usage_for_flags<'a>(
-    targets: &[&mut dyn Configurable],
-) -> Vec<Usage> {
+fn collect_usage_for_flags(targets: &[&mut dyn Configurable]) -> Vec<Usage> {
     // Include `-h/--help` and `--version` everywhere.
     let mut usages = vec![Help::USAGE, Version::USAGE];
     for t in targets.iter() {
@@ -210,7 +206,7 @@ where
     let strv = match osv.to_str() {
         Some(strv) => strv,
         None => {
-            let err = lexopt::Error::NonUnicodeValue(osv.into());
+            let err = lexopt::Error::NonUnicodeValue(osv);
             return Err(anyhow::Error::from(err).context(flag_name));
         }
     };
@@ -317,7 +313,7 @@ impl Usage {
         let mut result = String::new();
         for (i, usage) in usages.iter().enumerate() {
             if i > 0 {
-                writeln!(result, "").unwrap();
+                writeln!(result).unwrap();
             }
             writeln!(result, "    {}", usage.format).unwrap();
             for (i, paragraph) in usage.long.trim().split("\n\n").enumerate() {
@@ -106,23 +106,7 @@ impl MaybeTaggedArguments {
             MaybeTagged<'static, Cow<'static, BStr>>,
         ) -> anyhow::Result<bool>,
     ) -> anyhow::Result<()> {
-        self.0.try_map(|arg| match arg {
-            Argument::Positional(arg) => {
-                f(MaybeTagged::Untagged(Cow::Owned(arg)))
-            }
-            Argument::StdinLine(line) => {
-                let maybe_tagged = line
-                    .content()
-                    .parse::<MaybeTagged<'static, Cow<'static, BStr>>>()?;
-                f(maybe_tagged)
-            }
-            Argument::StdinLineBuf(line) => {
-                let maybe_tagged = line
-                    .content()
-                    .parse::<MaybeTagged<'static, Cow<'static, BStr>>>()?;
-                f(maybe_tagged)
-            }
-        })
+        self.0.try_map(|arg| f(arg.to_maybe_tagged()?))
     }
 }
 
@@ -151,6 +135,13 @@ pub struct Arguments {
 }
 
 impl Arguments {
+    /// Returns true when no positional arguments were given.
+    ///
+    /// When this is true, `try_map` reads arguments from stdin.
+    pub fn is_empty(&self) -> bool {
+        self.positional.is_empty()
+    }
+
     /// Run the given function over each argument read from the CLI.
     ///
     /// If there were no positional arguments, then this tries to read them
@@ -310,6 +301,21 @@ impl<'a> Argument<'a> {
         }
     }
 
+    /// Parse this argument into possibly tagged raw bytes.
+    ///
+    /// Positional arguments are never tagged, but lines from stdin may be.
+    pub fn to_maybe_tagged(
+        &self,
+    ) -> anyhow::Result<MaybeTagged<'static, Cow<'static, BStr>>> {
+        match *self {
+            Argument::Positional(ref arg) => {
+                Ok(MaybeTagged::Untagged(Cow::Owned(arg.clone())))
+            }
+            Argument::StdinLine(line) => line.content().parse(),
+            Argument::StdinLineBuf(ref line) => line.content().parse(),
+        }
+    }
+
     /// Parse this argument into a possibly tagged time span.
     pub fn to_span(&self) -> anyhow::Result<MaybeTagged<'static, TimeSpan>> {
         match *self {
@@ -419,7 +425,7 @@ impl<'a> Argument<'a> {
 
     /// Returns a line number associated with this argument, if it was parsed
     /// from stdin.
-    fn line_number(&self) -> Option<usize> {
+    pub fn line_number(&self) -> Option<usize> {
         match *self {
             Argument::Positional(_) => None,
             Argument::StdinLine(line) => Some(line.number()),
@@ -4,11 +4,11 @@ mod time;
 mod tz;
 mod untag;
 
-const USAGE: &'static str = "\
+const USAGE: &str = "\
 A simple utility for doing datetime arithmetic, parsing and formatting.
 
 USAGE:
-    bttf <command> ...
+    bttf [--now <datetime>] <command> ...
 
 COMMANDS:
     span   Tools for manipulating time spans/durations
@@ -16,12 +16,15 @@ COMMANDS:
     tag    Tag arbitrary data with datetimes or spans
     tz     Commands for working directly with time zones
     untag  Remove tags from previously tagged data
+
+OPTIONS:
+    --now <datetime>  Override the current time for this invocation
 ";
 
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     // For convenience, running `bttf` with no arguments prints the current
     // time in a somewhat nice format (roughly matches `date` on my system).
-    if p.try_raw_args().map_or(false, |args| args.as_slice().is_empty()) {
+    if p.try_raw_args().is_some_and(|args| args.as_slice().is_empty()) {
         use crate::{args::flags::Format, datetime::DateTime};
         use std::io::Write;
 
@@ -0,0 +1,323 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    jiff::{SignedDuration, Span, SpanRound, Unit},
+};
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    span::TimeSpan,
+};
+
+const USAGE_ADD: &str = r#"
+Add spans together.
+
+This prints the sum of all spans given. Unlike `bttf time add`, which adds a
+span to a datetime, this adds spans to other spans.
+
+By default, the structure of calendar units (years, months, weeks and days) is
+preserved. That is, each calendar unit is summed on its own, so `1mo` plus
+`1d` is `1mo 1d` and never some number of days. Time units (hours and smaller)
+are summed as a duration and then balanced up to the largest time unit found
+in any span. So `1h30m` plus `45m` is `2h 15m`, and `90m` plus `30m` is
+`120m`.
+
+Since every unit in a span must have the same sign, a sum like `1mo` plus
+`-1d` can't be represented without converting months to days. In this case,
+use `--balance` with a relative datetime given by `-r/--relative`.
+
+This accepts one or more spans as positional arguments. When no positional
+arguments are given, then line delimited spans are read from stdin. For tagged
+data, every tag is added. Spans may be in either the friendly or ISO 8601
+duration format.
+
+This command is also available as `bttf span sum`.
+
+USAGE:
+    bttf span add <span>...
+    bttf span add < line delimited <span>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Add two durations:
+
+        $ bttf span add 1h30m 45m
+        2h 15m
+
+    %snip-start%
+
+    Calendar units are kept separate from one another:
+
+        $ bttf span add 1y2mo 10mo 5d 12h
+        1y 12mo 5d 12h
+
+    Use `--balance` to combine units relative to a particular datetime:
+
+        $ bttf span add --balance -r 2025-01-01 1y2mo 10mo 5d 12h
+        2y 5d 12h
+        $ bttf span add --balance -r 2025-01-01 1mo -1d
+        30d
+
+    Add up the durations of every line on stdin:
+
+        $ printf '1m30s\n2m15s\n45s\n' | bttf span add
+        4m 30s
+
+    Or, equivalently, with friendly and ISO 8601 durations mixed together:
+
+        $ printf 'PT1M30S\n2m15s\n45s\n' | bttf span sum
+        4m 30s
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+const USAGE_SUBTRACT: &str = r#"
+Subtract spans from the first span.
+
+This prints the first span given minus every other span given. Unlike
+`bttf time add` with a negative span, which subtracts a span from a datetime,
+this subtracts spans from other spans. The result may be negative.
+
+By default, the structure of calendar units (years, months, weeks and days) is
+preserved. That is, each calendar unit is subtracted on its own, so `2mo` minus
+`1mo` is `1mo` and never some number of days. Time units (hours and smaller)
+are subtracted as a duration and then balanced up to the largest time unit
+found in any span. So `2h` minus `30m` is `1h 30m`.
+
+Since every unit in a span must have the same sign, a difference like `1mo`
+minus `1d` can't be represented without converting months to days. In this
+case, use `--balance` with a relative datetime given by `-r/--relative`.
+
+This accepts one or more spans as positional arguments. When no positional
+arguments are given, then line delimited spans are read from stdin. For tagged
+data, every tag is subtracted from the first.
+
+USAGE:
+    bttf span subtract <span>...
+    bttf span subtract < line delimited <span>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Subtract one duration from another:
+
+        $ bttf span subtract 2h 30m
+        1h 30m
+
+    %snip-start%
+
+    The result is negative when more is subtracted than there was to begin
+    with:
+
+        $ bttf span subtract 1h 90m
+        30m ago
+
+    Use `--balance` to combine units relative to a particular datetime:
+
+        $ bttf span subtract --balance -r 2025-02-01 1mo 1d
+        27d
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn add(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    run(p, USAGE_ADD, false)
+}
+
+pub fn subtract(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    run(p, USAGE_SUBTRACT, true)
+}
+
+fn run(
+    p: &mut lexopt::Parser,
+    usage: &'static str,
+    subtract: bool,
+) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut spans = positional::Spans::default();
+    args::configure(p, usage, &mut [&mut config, &mut spans])?;
+
+    let mut operands = vec![];
+    spans.try_map(|datum| {
+        datum.try_map(|span| {
+            // Every span after the first is negated when subtracting.
+            if subtract && !operands.is_empty() {
+                operands.push(TimeSpan::from(span.get().negate()));
+            } else {
+                operands.push(span);
+            }
+            Ok(())
+        })?;
+        Ok(true)
+    })?;
+    let result = if config.balance {
+        config.balanced_sum(&operands)?
+    } else {
+        sum(&operands)?
+    };
+    writeln!(std::io::stdout(), "{result}")?;
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    balance: bool,
+    relative: Option<DateTime>,
+}
+
+impl Config {
+    /// Adds the given spans using Jiff's span arithmetic.
+    ///
+    /// Calendar units are resolved relative to the datetime given by
+    /// `-r/--relative`, which is required when any span has calendar units.
+    fn balanced_sum(&self, spans: &[TimeSpan]) -> anyhow::Result<TimeSpan> {
+        let mut total = Span::new();
+        for span in spans {
+            total = match self.relative {
+                Some(ref relative) => total
+                    .checked_add((*span.get(), relative.get()))
+                    .with_context(|| {
+                        format!(
+                            "failed to add span `{span}` \
+                             relative to `{relative}`",
+                        )
+                    })?,
+                None => total.checked_add(*span.get()).with_context(|| {
+                    format!(
+                        "failed to add span `{span}`, which may require \
+                         a relative datetime given by -r/--relative",
+                    )
+                })?,
+            };
+        }
+        Ok(total.into())
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("balance") => {
+                self.balance = true;
+            }
+            lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
+                let relative: DateTimeFlexible =
+                    args::parse(p, "-r/--relative")?;
+                self.relative = Some(relative.into());
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const BALANCE: Usage = Usage::flag(
+            "--balance",
+            "Combine units across the calendar and time boundary.",
+            r#"
+Combine units across the calendar and time boundary.
+
+By default, each calendar unit is combined on its own, and only time units are
+balanced. With this flag, spans are combined using span arithmetic, which
+allows units to overflow into bigger units and allows combining spans whose
+units have different signs, like `1mo` and `-1d`. The largest unit of the
+result is the largest unit found in any span.
+
+When any span has calendar units (years, months, weeks or days), this requires
+a relative datetime given by `-r/--relative`.
+"#,
+        );
+
+        &[TimeSpan::ARG_OR_STDIN, BALANCE, TimeSpan::RELATIVE_FLAG]
+    }
+}
+
+/// Adds the given spans while preserving the structure of calendar units.
+///
+/// Each calendar unit is summed independently. Time units are summed as a
+/// duration and balanced up to the largest time unit in any of the spans.
+///
+/// This returns an error if the units of the sum have mixed signs, since
+/// that can't be represented by a single span.
+fn sum(spans: &[TimeSpan]) -> anyhow::Result<TimeSpan> {
+    let (mut years, mut months, mut weeks, mut days) = (0i64, 0i64, 0i64, 0);
+    let mut duration = SignedDuration::ZERO;
+    let mut largest_time_unit = None;
+    for span in spans {
+        let s = span.get();
+        years += i64::from(s.get_years());
+        months += i64::from(s.get_months());
+        weeks += i64::from(s.get_weeks());
+        days += i64::from(s.get_days());
+        let time = s.years(0).months(0).weeks(0).days(0);
+        // This can't fail since there are no calendar units left.
+        let time = SignedDuration::try_from(time)?;
+        duration = duration
+            .checked_add(time)
+            .with_context(|| format!("adding span `{span}` overflowed"))?;
+        if let Some(unit) = largest_time(s) {
+            largest_time_unit = largest_time_unit.max(Some(unit));
+        }
+    }
+    let largest = largest_time_unit.unwrap_or(Unit::Hour);
+    let time = Span::try_from(duration)
+        .and_then(|span| span.round(SpanRound::new().largest(largest)))
+        .with_context(|| {
+            format!("failed to convert duration `{duration:#}` to a span")
+        })?;
+
+    let signs = [years, months, weeks, days, i64::from(time.signum())]
+        .map(i64::signum);
+    let negative = signs.contains(&-1);
+    if negative && signs.contains(&1) {
+        anyhow::bail!(
+            "result has units with mixed signs, which can only be \
+             combined with --balance (and a relative datetime given by \
+             -r/--relative for calendar units)",
+        );
+    }
+    let time = time.abs();
+    let total = Span::new()
+        .try_years(years.abs())
+        .and_then(|span| span.try_months(months.abs()))
+        .and_then(|span| span.try_weeks(weeks.abs()))
+        .and_then(|span| span.try_days(days.abs()))
+        .and_then(|span| span.try_hours(time.get_hours()))
+        .and_then(|span| span.try_minutes(time.get_minutes()))
+        .and_then(|span| span.try_seconds(time.get_seconds()))
+        .and_then(|span| span.try_milliseconds(time.get_milliseconds()))
+        .and_then(|span| span.try_microseconds(time.get_microseconds()))
+        .and_then(|span| span.try_nanoseconds(time.get_nanoseconds()))
+        .context("sum of spans is out of range")?;
+    Ok(if negative { total.negate() } else { total }.into())
+}
+
+/// Returns the largest non-zero time unit (hours or smaller) in the span.
+fn largest_time(span: &Span) -> Option<Unit> {
+    let units = [
+        (Unit::Hour, i64::from(span.get_hours())),
+        (Unit::Minute, span.get_minutes()),
+        (Unit::Second, span.get_seconds()),
+        (Unit::Millisecond, span.get_milliseconds()),
+        (Unit::Microsecond, span.get_microseconds()),
+        (Unit::Nanosecond, span.get_nanoseconds()),
+    ];
+    units.into_iter().find(|&(_, value)| value != 0).map(|(unit, _)| unit)
+}
@@ -1,12 +1,14 @@
 use std::io::Write;
 
+use jiff::Unit;
+
 use crate::{
-    args::{self, Usage, positional},
+    args::{self, Usage, flags, positional},
     round::TimeSpanBalance,
-    span::TimeSpan,
+    span::{TimeSpan, UNITS, unit_value},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Balance spans to a specified largest unit. When a unit isn't given, it defaults
 to `years`, which is the largest unit supported by bttf.
 
@@ -85,6 +87,15 @@ EXAMPLES:
         $ bttf span since -r 2025-02-28 2025-03-31 | bttf span balance
         30d 23h
 
+    The `--breakdown` flag prints each unit of the balanced span as a separate
+    field, which can be easier to feed into other tools:
+
+        $ bttf span balance --breakdown --omit-zero 1mo15d2h
+        months=1 days=15 hours=2
+
+        $ bttf span balance --breakdown --json -l hours 90m
+        {"hours":1,"minutes":30,"seconds":0,"milliseconds":0,"microseconds":0,"nanoseconds":0}
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -98,28 +109,205 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut spans = positional::Spans::default();
     args::configure(p, USAGE, &mut [&mut balancer, &mut config, &mut spans])?;
 
+    anyhow::ensure!(
+        config.breakdown || (!config.json && !config.omit_zero),
+        "--json and --omit-zero can only be used with --breakdown",
+    );
+    anyhow::ensure!(
+        config.breakdown || config.smallest.is_none(),
+        "-s/--smallest can only be used with --breakdown \
+         (use `bttf span round` to round spans)",
+    );
     let mut wtr = std::io::stdout().lock();
     spans.try_map(|datum| {
-        let balanced = datum.try_map(|span| balancer.balance(&span))?;
-        balanced.write(&mut wtr)?;
+        if !config.breakdown {
+            let balanced = datum.try_map(|span| balancer.balance(&span))?;
+            balanced.write(&mut wtr)?;
+            writeln!(wtr)?;
+            return Ok(true);
+        }
+        let breakdown = datum.try_map(|span| {
+            let balanced = match config.smallest {
+                None => balancer.balance(&span)?,
+                Some(smallest) => balancer.balance_to(&span, smallest)?,
+            };
+            config.breakdown(balancer.largest(), balanced.get())
+        })?;
+        breakdown.write(&mut wtr)?;
         writeln!(wtr)?;
         Ok(true)
     })
 }
 
 #[derive(Debug, Default)]
-struct Config {}
+struct Config {
+    breakdown: bool,
+    json: bool,
+    omit_zero: bool,
+    smallest: Option<Unit>,
+}
+
+impl Config {
+    /// Returns the given balanced span broken down into one field per unit.
+    ///
+    /// Only units less than or equal to `largest` and greater than or equal
+    /// to the unit given by `-s/--smallest` are included. Weeks are only
+    /// included when `largest` is exactly weeks, since balancing never
+    /// produces weeks otherwise.
+    fn breakdown(
+        &self,
+        largest: Unit,
+        span: &jiff::Span,
+    ) -> anyhow::Result<String> {
+        let smallest = self.smallest.unwrap_or(Unit::Nanosecond);
+        let fields = UNITS
+            .iter()
+            .copied()
+            .filter(|&unit| {
+                unit <= largest
+                    && unit >= smallest
+                    && (unit != Unit::Week || largest == Unit::Week)
+            })
+            .map(|unit| (unit, unit_value(span, unit)))
+            .filter(|&(_, value)| !self.omit_zero || value != 0);
+        if self.json {
+            let mut breakdown = Breakdown::default();
+            for (unit, value) in fields {
+                breakdown.set(unit, value);
+            }
+            return Ok(serde_json::to_string(&breakdown)?);
+        }
+        let pairs: Vec<String> = fields
+            .map(|(unit, value)| {
+                format!("{}={value}", flags::Unit::from(unit))
+            })
+            .collect();
+        Ok(pairs.join(" "))
+    }
+}
+
+/// A span broken down into one field per unit, as printed by `--json`.
+///
+/// Units that aren't part of the breakdown are `None` and omitted.
+#[derive(Debug, Default, serde::Serialize)]
+struct Breakdown {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    years: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    months: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weeks: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hours: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minutes: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seconds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    milliseconds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    microseconds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nanoseconds: Option<i64>,
+}
+
+impl Breakdown {
+    /// Sets the value of the field for the given unit.
+    fn set(&mut self, unit: Unit, value: i64) {
+        let field = match unit {
+            Unit::Year => &mut self.years,
+            Unit::Month => &mut self.months,
+            Unit::Week => &mut self.weeks,
+            Unit::Day => &mut self.days,
+            Unit::Hour => &mut self.hours,
+            Unit::Minute => &mut self.minutes,
+            Unit::Second => &mut self.seconds,
+            Unit::Millisecond => &mut self.milliseconds,
+            Unit::Microsecond => &mut self.microseconds,
+            Unit::Nanosecond => &mut self.nanoseconds,
+        };
+        *field = Some(value);
+    }
+}
 
 impl args::Configurable for Config {
     fn configure(
         &mut self,
-        _: &mut lexopt::Parser,
-        _: &mut lexopt::Arg,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
-        Ok(false)
+        match *arg {
+            lexopt::Arg::Long("breakdown") => {
+                self.breakdown = true;
+            }
+            lexopt::Arg::Long("json") => {
+                self.json = true;
+            }
+            lexopt::Arg::Long("omit-zero") => {
+                self.omit_zero = true;
+            }
+            lexopt::Arg::Short('s') | lexopt::Arg::Long("smallest") => {
+                let unit: flags::Unit = args::parse(p, "-s/--smallest")?;
+                self.smallest = Some(unit.get());
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
     }
 
     fn usage(&self) -> &[Usage] {
-        &[TimeSpan::ARG_OR_STDIN]
+        const BREAKDOWN: Usage = Usage::flag(
+            "--breakdown",
+            "Print each unit of the balanced span as a separate field.",
+            r#"
+Print each unit of the balanced span as a separate field.
+
+Instead of printing the balanced span in the "friendly" format, this prints
+a sequence of `unit=value` pairs separated by a space, e.g.,
+`years=0 months=1 days=15 hours=2`. Units are printed in descending order,
+starting with the unit given to `-l/--largest` and ending with the unit given
+to `-s/--smallest` (or nanoseconds by default).
+Negative spans have negative values for each of their units.
+"#,
+        );
+
+        const JSON: Usage = Usage::flag(
+            "--json",
+            "Print the breakdown as a JSON object.",
+            r#"
+Print the breakdown as a JSON object.
+
+This requires `--breakdown`. Each unit is a key in the object whose value is
+an integer. For example, `{"hours":1,"minutes":30}`.
+"#,
+        );
+
+        const OMIT_ZERO: Usage = Usage::flag(
+            "--omit-zero",
+            "Omit units that are zero from the breakdown.",
+            r#"
+Omit units that are zero from the breakdown.
+
+This requires `--breakdown`. By default, every unit from the largest unit down
+to nanoseconds is printed, even when it is zero.
+"#,
+        );
+
+        const SMALLEST: Usage = Usage::flag(
+            "-s/--smallest <unit>",
+            "Set the smallest unit in the breakdown.",
+            r#"
+Set the smallest unit in the breakdown.
+
+This requires `--breakdown`. The balanced span is rounded to this unit, in the
+same way as `bttf span round -s <unit>`, and the breakdown stops at this unit
+instead of at nanoseconds. The unit may be spelled in the same ways as for the
+`-l/--largest` flag.
+"#,
+        );
+
+        &[TimeSpan::ARG_OR_STDIN, BREAKDOWN, JSON, OMIT_ZERO, SMALLEST]
     }
 }
@@ -0,0 +1,193 @@
+use std::io::Write;
+
+use jiff::SignedDuration;
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    span::TimeSpan,
+};
+
+const USAGE: &str = r#"
+Clamp spans to a range.
+
+Each span less than the span given to `--min` is replaced with the `--min`
+span, and each span greater than the span given to `--max` is replaced with the
+`--max` span. Spans within the range, including spans equal to either bound,
+are printed with their units unchanged. At least one of `--min` or `--max` must
+be given.
+
+Spans are compared by their absolute duration. Spans with calendar units
+(years, months, weeks or days) require a relative datetime given by
+`-r/--relative`. Spans with only time units never need a relative datetime.
+
+This accepts one or more spans as positional arguments. When no positional
+arguments are given, then line delimited spans are read from stdin.
+
+USAGE:
+    bttf span clamp --min <span> --max <span> <span>...
+    bttf span clamp --min <span> --max <span> < line delimited <span>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Cap retry delays between 100 milliseconds and 30 seconds:
+
+        $ bttf span clamp --min 100ms --max 30s 10ms 2s 1m
+        100ms
+        2s
+        30s
+
+    %snip-start%
+
+    Spans within the range keep their original units, even when they're
+    written differently than the bounds:
+
+        $ bttf span clamp --max 2h 90m 3h
+        90m
+        2h
+
+    Clamp spans with calendar units relative to a particular datetime. In
+    New York, 2025-03-09 was only 23 hours long, so `1d` is less than `24h`
+    relative to that date:
+
+        $ bttf span clamp -r '2025-03-09[America/New_York]' --min 24h 1d
+        24h
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut spans = positional::Spans::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut spans])?;
+
+    let bounds = config.bounds()?;
+    let mut wtr = std::io::stdout().lock();
+    spans.try_map(|datum| {
+        let clamped = datum.try_map(|span| {
+            let duration = span.to_duration(config.relative.as_ref())?;
+            if let Some((ref min, min_duration)) = bounds.min
+                && duration < min_duration
+            {
+                return Ok(min.clone());
+            }
+            if let Some((ref max, max_duration)) = bounds.max
+                && duration > max_duration
+            {
+                return Ok(max.clone());
+            }
+            Ok(span)
+        })?;
+        clamped.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+/// The bounds to clamp spans to, along with their absolute durations.
+#[derive(Debug)]
+struct Bounds {
+    min: Option<(TimeSpan, SignedDuration)>,
+    max: Option<(TimeSpan, SignedDuration)>,
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    min: Option<TimeSpan>,
+    max: Option<TimeSpan>,
+    relative: Option<DateTime>,
+}
+
+impl Config {
+    /// Returns the bounds given to `--min` and `--max`.
+    ///
+    /// This returns an error if neither was given or if the minimum is
+    /// greater than the maximum.
+    fn bounds(&self) -> anyhow::Result<Bounds> {
+        anyhow::ensure!(
+            self.min.is_some() || self.max.is_some(),
+            "at least one of --min or --max is required",
+        );
+        let relative = self.relative.as_ref();
+        let min = match self.min {
+            None => None,
+            Some(ref span) => {
+                Some((span.clone(), span.to_duration(relative)?))
+            }
+        };
+        let max = match self.max {
+            None => None,
+            Some(ref span) => {
+                Some((span.clone(), span.to_duration(relative)?))
+            }
+        };
+        if let (Some((min, min_duration)), Some((max, max_duration))) =
+            (&min, &max)
+        {
+            anyhow::ensure!(
+                min_duration <= max_duration,
+                "--min span `{min}` must not be greater than \
+                 --max span `{max}`",
+            );
+        }
+        Ok(Bounds { min, max })
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("min") => {
+                self.min = Some(args::parse(p, "--min")?);
+            }
+            lexopt::Arg::Long("max") => {
+                self.max = Some(args::parse(p, "--max")?);
+            }
+            lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
+                let relative: DateTimeFlexible =
+                    args::parse(p, "-r/--relative")?;
+                self.relative = Some(relative.into());
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const MIN: Usage = Usage::flag(
+            "--min <span>",
+            "The smallest span to print.",
+            r#"
+The smallest span to print.
+
+Spans less than this are replaced with this span, exactly as it was given.
+When not given, spans are not bounded from below. This must not be greater
+than `--max`.
+"#,
+        );
+
+        const MAX: Usage = Usage::flag(
+            "--max <span>",
+            "The largest span to print.",
+            r#"
+The largest span to print.
+
+Spans greater than this are replaced with this span, exactly as it was given.
+When not given, spans are not bounded from above. This must not be less than
+`--min`.
+"#,
+        );
+
+        &[TimeSpan::ARG_OR_STDIN, MIN, MAX, TimeSpan::RELATIVE_FLAG]
+    }
+}
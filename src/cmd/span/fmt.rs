@@ -3,16 +3,18 @@ use std::io::Write;
 use {
     anyhow::Context,
     bstr::ByteSlice,
-    jiff::{Unit, fmt::friendly},
+    jiff::{RoundMode, Span, SpanRound, Unit, fmt::friendly},
 };
 
 use crate::{
     args::{self, Usage, flags, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    locale::{Locale, SpanLocaleFormatter},
     parse::FromBytes,
-    span::TimeSpan,
+    span::{TimeSpan, UNITS, unit_value},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Format spans into the "friendly" format.
 
 This permits controlling a number of different settings that influence the
@@ -43,6 +45,16 @@ EXAMPLES:
 
     %snip-start%
 
+    Or get the same result with a single preset style:
+
+        $ bttf span fmt '75y5mo22d' --style long --comma
+        75 years, 5 months, 22 days
+
+    Use the most terse style, which is useful for status lines:
+
+        $ bttf span fmt '1h30m' --style compact
+        1h30m
+
     To use fractional seconds instead of writing out sub-second units:
 
         $ bttf span fmt '30s123ms456us789ns' -f secs
@@ -58,6 +70,32 @@ EXAMPLES:
         $ bttf span fmt '5d2h30m10s' --hms
         5d 02:30:10
 
+    And drop the `HH:MM:SS` part when it's all zeros:
+
+        $ bttf span fmt '5d' --hms --compact
+        5d
+
+    Keep only the two largest units, truncating the rest:
+
+        $ bttf span fmt '2d3h45m40s' --max-units 2
+        2d 3h
+
+    Or round the dropped units into the last unit kept:
+
+        $ bttf span fmt '2d3h45m40s' --max-units 2 --round
+        2d 4h
+
+    Always write a sign, which is useful for lining up columns:
+
+        $ bttf span fmt --sign always 1h30m -- -45m
+        +1h 30m
+        -45m
+
+    Write out unit names in a particular language:
+
+        $ bttf span fmt '2h30m' --locale fr
+        2 heures 30 minutes
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -68,38 +106,159 @@ OPTIONS:
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
     let mut spans = positional::Spans::default();
-    args::configure(p, USAGE, &mut [&mut config, &mut spans])?;
+    let mut now = flags::Now;
+    args::configure(p, USAGE, &mut [&mut config, &mut now, &mut spans])?;
 
+    let localized = config.localized()?;
+    let zero_unit = config.zero_unit.unwrap_or(Unit::Second);
     let printer = config.printer();
+    let compact_printer = printer.clone().hours_minutes_seconds(false);
     let mut wtr = std::io::stdout().lock();
     spans.try_map(|datum| {
-        let formatted =
-            datum.try_map(|span| Ok(printer.span_to_string(span.get())))?;
+        let formatted = datum.try_map(|span| {
+            let span = &config.limit_units(span.get())?;
+            let span =
+                &if config.direction.never { span.abs() } else { *span };
+            if let Some(ref localized) = localized {
+                return Ok(localized.format(span, zero_unit, config.comma));
+            }
+            // The friendly format already omits zero units, except for the
+            // `HH:MM:SS` part when `--hms` is given. So when `--compact` is
+            // given and that part would be all zeros, we drop it by printing
+            // the span without `--hms`. A zero span has no calendar units,
+            // so it is always printed as usual.
+            if config.compact && config.hms && is_calendar_only(span) {
+                return Ok(compact_printer.span_to_string(span));
+            }
+            Ok(printer.span_to_string(span))
+        })?;
         formatted.write(&mut wtr)?;
         writeln!(wtr)?;
         Ok(true)
     })
 }
 
+/// Returns the given span with the given unit set to zero.
+fn set_unit_zero(span: Span, unit: Unit) -> Span {
+    match unit {
+        Unit::Year => span.years(0),
+        Unit::Month => span.months(0),
+        Unit::Week => span.weeks(0),
+        Unit::Day => span.days(0),
+        Unit::Hour => span.hours(0),
+        Unit::Minute => span.minutes(0),
+        Unit::Second => span.seconds(0),
+        Unit::Millisecond => span.milliseconds(0),
+        Unit::Microsecond => span.microseconds(0),
+        Unit::Nanosecond => span.nanoseconds(0),
+    }
+}
+
+/// Returns true when the given span has at least one non-zero calendar unit
+/// (days or greater) and all of its time units are zero.
+fn is_calendar_only(span: &jiff::Span) -> bool {
+    let mut nonzero =
+        UNITS.iter().filter(|&&unit| unit_value(span, unit) != 0).peekable();
+    nonzero.peek().is_some() && nonzero.all(|&unit| unit >= Unit::Day)
+}
+
 #[derive(Debug, Default)]
 struct Config {
-    designator: Designator,
-    spacing: Spacing,
+    style: Option<Style>,
+    designator: Option<Designator>,
+    spacing: Option<Spacing>,
     direction: Direction,
     fractional: FractionalUnit,
     comma: bool,
+    compact: bool,
     hms: bool,
+    locale: Option<Locale>,
     padding: Padding,
     precision: Precision,
     zero_unit: Option<Unit>,
+    max_units: Option<usize>,
+    round: bool,
+    relative: Option<DateTime>,
 }
 
 impl Config {
+    /// Returns a formatter for writing out unit names in the locale given by
+    /// `--locale`, if any.
+    ///
+    /// This returns an error if any formatting flags that only apply to the
+    /// "friendly" format were given along with a locale that has localized
+    /// unit names.
+    fn localized(&self) -> anyhow::Result<Option<SpanLocaleFormatter>> {
+        let Some(ref locale) = self.locale else { return Ok(None) };
+        let Some(localized) = locale.to_span_formatter()? else {
+            return Ok(None);
+        };
+        let conflicts = [
+            ("--style", self.style.is_some()),
+            ("-d/--designator", self.designator.is_some()),
+            ("-s/--spacing", self.spacing.is_some()),
+            (
+                "--sign",
+                !matches!(self.direction.kind, friendly::Direction::Auto),
+            ),
+            ("-f/--fractional", self.fractional.0.is_some()),
+            ("--compact", self.compact),
+            ("--hms", self.hms),
+            ("--pad", self.padding.0.is_some()),
+            ("--precision", self.precision.0.is_some()),
+        ];
+        if let Some(&(flag, _)) = conflicts.iter().find(|&&(_, given)| given) {
+            anyhow::bail!("{flag} cannot be used with --locale");
+        }
+        Ok(Some(localized))
+    }
+
+    /// Drops all but the `--max-units` largest non-zero units from the given
+    /// span. When `--round` is given, the dropped units are rounded into the
+    /// smallest unit kept. Otherwise, they are truncated.
+    fn limit_units(&self, span: &Span) -> anyhow::Result<Span> {
+        let Some(max) = self.max_units else { return Ok(*span) };
+        let nonzero: Vec<Unit> = UNITS
+            .iter()
+            .copied()
+            .filter(|&unit| unit_value(span, unit) != 0)
+            .collect();
+        if nonzero.len() <= max {
+            return Ok(*span);
+        }
+        let (largest, smallest) = (nonzero[0], nonzero[max - 1]);
+        if !self.round {
+            return Ok(nonzero[max..]
+                .iter()
+                .fold(*span, |span, &unit| set_unit_zero(span, unit)));
+        }
+        let relative = match self.relative {
+            Some(ref relative) => relative.clone(),
+            None => DateTime::now(),
+        };
+        let options = SpanRound::new()
+            .largest(largest)
+            .smallest(smallest)
+            .mode(RoundMode::HalfExpand)
+            .relative(relative.get());
+        span.round(options).with_context(|| {
+            format!("failed to round span `{span}` relative to `{relative}`")
+        })
+    }
+
     fn printer(&self) -> friendly::SpanPrinter {
+        // Explicitly given designators and spacing always override whatever
+        // is set by `--style`, regardless of the order of the flags.
+        let (designator, spacing) = match self.style {
+            None => (Designator::default(), Spacing::default()),
+            Some(ref style) => (style.designator(), style.spacing()),
+        };
+        let designator = self.designator.as_ref().unwrap_or(&designator);
+        let spacing = self.spacing.as_ref().unwrap_or(&spacing);
         let mut printer = friendly::SpanPrinter::new()
-            .designator(self.designator.0)
-            .spacing(self.spacing.0)
-            .direction(self.direction.0)
+            .designator(designator.0)
+            .spacing(spacing.0)
+            .direction(self.direction.kind)
             .fractional(self.fractional.0)
             .comma_after_designator(self.comma)
             .hours_minutes_seconds(self.hms)
@@ -120,10 +279,14 @@ impl args::Configurable for Config {
     ) -> anyhow::Result<bool> {
         match *arg {
             lexopt::Arg::Short('d') | lexopt::Arg::Long("designator") => {
-                self.designator = args::parse_bytes(p, "-d/--designator")?;
+                self.designator =
+                    Some(args::parse_bytes(p, "-d/--designator")?);
             }
             lexopt::Arg::Short('s') | lexopt::Arg::Long("spacing") => {
-                self.spacing = args::parse_bytes(p, "-s/--spacing")?;
+                self.spacing = Some(args::parse_bytes(p, "-s/--spacing")?);
+            }
+            lexopt::Arg::Long("style") => {
+                self.style = Some(args::parse_bytes(p, "--style")?);
             }
             lexopt::Arg::Long("sign") => {
                 self.direction = args::parse_bytes(p, "--sign")?;
@@ -134,9 +297,15 @@ impl args::Configurable for Config {
             lexopt::Arg::Long("comma") => {
                 self.comma = true;
             }
+            lexopt::Arg::Long("compact") => {
+                self.compact = true;
+            }
             lexopt::Arg::Long("hms") => {
                 self.hms = true;
             }
+            lexopt::Arg::Long("locale") => {
+                self.locale = Some(args::parse(p, "--locale")?);
+            }
             lexopt::Arg::Long("pad") => {
                 self.padding = args::parse(p, "--pad")?;
             }
@@ -147,6 +316,22 @@ impl args::Configurable for Config {
                 let unit: flags::Unit = args::parse(p, "--zero-unit")?;
                 self.zero_unit = Some(unit.get());
             }
+            lexopt::Arg::Long("max-units") => {
+                let max: usize = args::parse(p, "--max-units")?;
+                anyhow::ensure!(
+                    max > 0,
+                    "--max-units must be greater than zero",
+                );
+                self.max_units = Some(max);
+            }
+            lexopt::Arg::Long("round") => {
+                self.round = true;
+            }
+            lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
+                let relative: DateTimeFlexible =
+                    args::parse(p, "-r/--relative")?;
+                self.relative = Some(relative.into());
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -179,6 +364,49 @@ is often best combined with `--designator=verbose` and
 "#,
         );
 
+        const COMPACT: Usage = Usage::flag(
+            "--compact",
+            "Omit zero components when formatting with `--hms`.",
+            r#"
+Omit zero components when formatting with `--hms`.
+
+The friendly format never writes units with a value of zero. For example,
+`2d30m` is formatted as `2d 30m` and not `2d 0h 30m 0s`. But when `--hms` is
+given, the `HH:MM:SS` component is always written, even when all of its units
+are zero. When this flag is given, that component is omitted if all of its
+units are zero. For example, `2d` is formatted as `2d` instead of
+`2d 00:00:00`.
+
+This never results in empty output. A span of length zero is always written
+with at least one unit. (See `--zero-unit`.)
+"#,
+        );
+
+        const LOCALE: Usage = Usage::flag(
+            "--locale <tag>",
+            "Write out unit names in the given locale.",
+            r#"
+Write out unit names in the given locale.
+
+The locale is given as a BCP 47 language tag, like `fr` or `de-CH`. When
+given, each non-zero unit is written out in full using its localized name.
+For example, `2h30m` is formatted as `2 heures 30 minutes` with `--locale fr`.
+Localized unit names are currently available for English (`en`), French
+(`fr`), German (`de`) and Spanish (`es`).
+
+Only `--comma`, `--zero-unit`, `--sign never`, `--max-units` and `--round`
+may be used with localized output. Giving any other formatting flag, like
+`--hms` or `-d/--designator`, results in an error. Since localized output is
+not in the "friendly" format, it generally cannot be parsed back into a span
+by bttf.
+
+The special `und` locale is the same as not giving this flag at all. Unlike
+`%c` and similar directives for datetimes, this does not use the `BTTF_LOCALE`
+environment variable. This flag requires that bttf was compiled with the
+`locale` feature.
+"#,
+        );
+
         const ZERO_UNIT: Usage = Usage::flag(
             "--zero-unit <unit>",
             "Set the unit to use for spans of length zero.",
@@ -192,16 +420,67 @@ This defaults to `second`.
 "#,
         );
 
+        const MAX_UNITS: Usage = Usage::flag(
+            "--max-units <number>",
+            "Keep only the given number of largest non-zero units.",
+            r#"
+Keep only the given number of largest non-zero units.
+
+All other non-zero units are dropped. For example, `2d3h45m40s` is formatted
+as `2d 3h` with `--max-units 2`. By default, the dropped units are truncated.
+Use `--round` to round them into the smallest unit kept instead.
+
+Unlike `--compact`, which only omits units that are zero, this drops units
+with non-zero values. And unlike `span round`, the units kept depend on each
+span. Spans with at most the given number of non-zero units are not changed.
+"#,
+        );
+
+        const ROUND: Usage = Usage::flag(
+            "--round",
+            "Round dropped units when using `--max-units`.",
+            r#"
+Round dropped units when using `--max-units`.
+
+When given, the units dropped by `--max-units` are rounded into the smallest
+unit kept, with ties rounding away from zero. For example, `2d3h45m40s` is
+formatted as `2d 4h` with `--max-units 2 --round`. Rounding may carry into
+larger units, which can result in fewer units than requested. For example,
+`1h59m40s` is formatted as `2h` with `--max-units 2 --round`.
+
+This has no effect without `--max-units`.
+"#,
+        );
+
+        const RELATIVE: Usage = Usage::flag(
+            "-r/--relative <datetime>",
+            "The datetime that `--round` is relative to.",
+            r#"
+The datetime that `--round` is relative to.
+
+Rounding spans with calendar units requires a reference point, since the
+length of days, weeks, months and years can vary. This defaults to the
+current time in your system's time zone. It is only used when `--round` is
+given.
+"#,
+        );
+
         &[
             TimeSpan::ARG_OR_STDIN,
             Designator::USAGE,
             Spacing::USAGE,
+            Style::USAGE,
             Direction::USAGE,
             FractionalUnit::USAGE,
             COMMA,
+            COMPACT,
             HMS,
+            LOCALE,
+            MAX_UNITS,
             Padding::USAGE,
             Precision::USAGE,
+            RELATIVE,
+            ROUND,
             ZERO_UNIT,
         ]
     }
@@ -312,7 +591,78 @@ impl FromBytes for Spacing {
 }
 
 #[derive(Clone, Debug)]
-struct Direction(friendly::Direction);
+enum Style {
+    Compact,
+    Short,
+    Long,
+}
+
+impl Style {
+    const USAGE: Usage = Usage::flag(
+        "--style <kind>",
+        "Set the designators and spacing from a preset style.",
+        r#"
+Set the designators and spacing from a preset style.
+
+This is a convenient way of setting both `-d/--designator` and `-s/--spacing`.
+When either of those flags is also given, it overrides the corresponding
+setting from the style, regardless of the order in which the flags are given.
+
+The possible values and their behavior are:
+
+`compact`: Uses compact designators and no spaces. For example, `1h30m`. This
+is useful in terse output, like status lines. This is equivalent to
+`-d compact -s none`.
+
+`short`: Uses short designators with spaces between units. For example,
+`1hr 30mins`. This is equivalent to `-d short -s units`.
+
+`long`: Writes out the full word of each unit, with spaces between unit values
+and designators. For example, `1 hour 30 minutes`. Designators are singular or
+plural based on their unit value. This is useful in human-facing prose. This
+is equivalent to `-d verbose -s units-and-designators`.
+"#,
+    );
+
+    fn designator(&self) -> Designator {
+        Designator(match *self {
+            Style::Compact => friendly::Designator::Compact,
+            Style::Short => friendly::Designator::Short,
+            Style::Long => friendly::Designator::Verbose,
+        })
+    }
+
+    fn spacing(&self) -> Spacing {
+        Spacing(match *self {
+            Style::Compact => friendly::Spacing::None,
+            Style::Short => friendly::Spacing::BetweenUnits,
+            Style::Long => friendly::Spacing::BetweenUnitsAndDesignators,
+        })
+    }
+}
+
+impl FromBytes for Style {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<Style> {
+        let style = match s {
+            b"compact" => Style::Compact,
+            b"short" => Style::Short,
+            b"long" => Style::Long,
+            unk => {
+                anyhow::bail!("unknown style `{unk}`", unk = unk.as_bstr())
+            }
+        };
+        Ok(style)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Direction {
+    kind: friendly::Direction,
+    /// When true, spans are formatted without any sign at all.
+    never: bool,
+}
 
 impl Direction {
     const USAGE: Usage = Usage::flag(
@@ -332,18 +682,30 @@ calendar units (days and greater) are zero. Otherwise, this is equivalent to
 it is written, it is written as a prefix of the formatted span.
 
 `force-prefix`: When set, a prefix sign is always written, with `-` for
-negative spans and `+` for all non-negative spans. The sign is always written
-as a prefix of the formatted span.
+negative spans and `+` for all non-negative spans (including zero spans). The
+sign is always written as a prefix of the formatted span.
+
+`always`: An alias for `force-prefix`. This is useful for lining up columns of
+spans that may be positive or negative.
+
+`never`: When set, no sign is ever written. That is, only the magnitude of the
+span is written, and negative spans are formatted the same as their positive
+counterparts.
 
 `suffix`: When set, a sign is only written when the span is negative. And when
 it is written, it is written as a suffix via a trailing `ago` string.
+
+A prefix sign always precedes the entire formatted span, regardless of the
+other formatting options. For example, with `--hms`, `-5d2h` is formatted as
+`-5d 02:00:00` with `--sign prefix`. Similarly, a suffix always follows the
+entire formatted span. Only `auto` and `never` may be used with `--locale`.
 "#,
     );
 }
 
 impl Default for Direction {
     fn default() -> Direction {
-        Direction(friendly::Direction::Auto)
+        Direction { kind: friendly::Direction::Auto, never: false }
     }
 }
 
@@ -351,17 +713,23 @@ impl FromBytes for Direction {
     type Err = anyhow::Error;
 
     fn from_bytes(s: &[u8]) -> anyhow::Result<Direction> {
-        let d = match s {
+        let kind = match s {
             b"auto" => friendly::Direction::Auto,
             b"prefix" => friendly::Direction::Sign,
-            b"force-prefix" => friendly::Direction::ForceSign,
+            b"force-prefix" | b"always" => friendly::Direction::ForceSign,
             b"suffix" => friendly::Direction::Suffix,
+            b"never" => {
+                return Ok(Direction {
+                    kind: friendly::Direction::Auto,
+                    never: true,
+                });
+            }
             unk => anyhow::bail!(
                 "unknown direction/sign option `{unk}`",
                 unk = unk.as_bstr()
             ),
         };
-        Ok(Direction(d))
+        Ok(Direction { kind, never: false })
     }
 }
 
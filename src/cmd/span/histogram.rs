@@ -0,0 +1,397 @@
+use std::io::Write;
+
+use {anyhow::Context, jiff::SignedDuration};
+
+use crate::{
+    args::{self, Usage, flags::CommaSequence, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    span::TimeSpan,
+};
+
+const USAGE: &str = r#"
+Count spans in buckets.
+
+Each span is converted to an absolute duration and counted in the bucket it
+falls into. Buckets are defined by the edges given to `--buckets`. Given edges
+`e1,e2,...,eN`, a span is counted in the bucket `[ei, ei+1)` when it is greater
+than or equal to `ei` and less than `ei+1`. Spans less than the first edge or
+greater than or equal to the last edge are counted in two additional buckets
+at either end. Every bucket is printed, even when its count is zero.
+
+Use `--percentiles` to also print a summary of the distribution of spans. This
+makes this command useful as a quick profiler for latency logs.
+
+Spans with calendar units (years, months, weeks or days) require a relative
+datetime given by `-r/--relative`. Spans with only time units never need a
+relative datetime.
+
+This accepts one or more spans as positional arguments. When no positional
+arguments are given, then line delimited spans are read from stdin. For tagged
+data, every tag is counted.
+
+USAGE:
+    bttf span histogram --buckets <span-list> <span>...
+    bttf span histogram --buckets <span-list> < line delimited <span>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Count latencies in buckets:
+
+        $ bttf span histogram --buckets 1ms,10ms,100ms,1s \
+            500us 2ms 5ms 40ms 250ms 2s
+        < 1ms          1
+        [1ms, 10ms)    2
+        [10ms, 100ms)  1
+        [100ms, 1s)    1
+        >= 1s          1
+
+    %snip-start%
+
+    Print cumulative counts, where each count includes the counts of all
+    buckets before it:
+
+        $ bttf span histogram --buckets 10ms,1s --cumulative 2ms 5ms 40ms 2s
+        < 10ms      2
+        [10ms, 1s)  3
+        >= 1s       4
+
+    Print percentiles of the distribution along with the buckets:
+
+        $ bttf span histogram --buckets 10ms --percentiles 50,99 \
+            2ms 5ms 40ms 2s
+        < 10ms   2
+        >= 10ms  2
+        p50: 5ms
+        p99: 2s
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut spans = positional::Spans::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut spans])?;
+
+    let mut histogram = config.histogram()?;
+    spans.try_map(|datum| {
+        datum.try_map(|span| {
+            let duration = span.to_duration(config.relative.as_ref())?;
+            histogram.add(duration);
+            Ok(())
+        })?;
+        Ok(true)
+    })?;
+    let mut wtr = std::io::stdout().lock();
+    if config.json {
+        histogram.write_json(&mut wtr, &config)?;
+    } else {
+        histogram.write_table(&mut wtr, &config)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    buckets: Option<CommaSequence<TimeSpan>>,
+    relative: Option<DateTime>,
+    cumulative: bool,
+    percentiles: Option<CommaSequence<Percentile>>,
+    json: bool,
+}
+
+impl Config {
+    /// Creates an empty histogram from the bucket edges given to
+    /// `--buckets`.
+    ///
+    /// This returns an error if `--buckets` wasn't given or if its edges
+    /// aren't strictly increasing.
+    fn histogram(&self) -> anyhow::Result<Histogram> {
+        let Some(ref buckets) = self.buckets else {
+            anyhow::bail!("--buckets is required");
+        };
+        let mut edges: Vec<(TimeSpan, SignedDuration)> = vec![];
+        for span in buckets.iter() {
+            let duration = span.to_duration(self.relative.as_ref())?;
+            if let Some((prev, prev_duration)) = edges.last() {
+                anyhow::ensure!(
+                    *prev_duration < duration,
+                    "bucket edges must be strictly increasing, \
+                     but `{span}` is not greater than `{prev}`",
+                );
+            }
+            edges.push((span.clone(), duration));
+        }
+        let counts = vec![0; edges.len() + 1];
+        let durations = self.percentiles.as_ref().map(|_| vec![]);
+        Ok(Histogram { edges, counts, durations })
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("buckets") => {
+                self.buckets = Some(args::parse(p, "--buckets")?);
+            }
+            lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
+                let relative: DateTimeFlexible =
+                    args::parse(p, "-r/--relative")?;
+                self.relative = Some(relative.into());
+            }
+            lexopt::Arg::Long("cumulative") => {
+                self.cumulative = true;
+            }
+            lexopt::Arg::Long("percentiles") => {
+                self.percentiles = Some(args::parse(p, "--percentiles")?);
+            }
+            lexopt::Arg::Long("json") => {
+                self.json = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const BUCKETS: Usage = Usage::flag(
+            "--buckets <span-list>",
+            "A comma separated list of bucket edges, e.g., `1ms,10ms,1s`.",
+            r#"
+A comma separated list of bucket edges, e.g., `1ms,10ms,1s`.
+
+This flag is required. The edges must be strictly increasing. Each bucket
+includes its lower edge and excludes its upper edge. So with `1ms,10ms`, a
+span of exactly `10ms` is counted in the bucket after `[1ms, 10ms)`.
+
+Since edges are separated by commas, an edge can't use a comma as a decimal
+separator. Use a period instead, e.g., `1.5s`.
+"#,
+        );
+
+        const CUMULATIVE: Usage = Usage::flag(
+            "--cumulative",
+            "Print cumulative counts.",
+            r#"
+Print cumulative counts.
+
+When this flag is given, each bucket's count includes the counts of all
+buckets before it. So the count of each bucket is the number of spans less
+than its upper edge, and the count of the last bucket is the total number of
+spans.
+"#,
+        );
+
+        const PERCENTILES: Usage = Usage::flag(
+            "--percentiles <number-list>",
+            "Also print percentiles, e.g., `50,95,99`.",
+            r#"
+Also print percentiles, e.g., `50,95,99`.
+
+Each percentile must be a number from `0` to `100`, and may have a fractional
+part, e.g., `99.9`. Percentiles are computed exactly using the nearest-rank
+method, so each one is always one of the spans given. They are printed after
+the buckets, in the order given. Nothing is printed for percentiles when there
+are no spans.
+
+This requires keeping every span in memory.
+"#,
+        );
+
+        const JSON: Usage = Usage::flag(
+            "--json",
+            "Print the histogram as a JSON object.",
+            r#"
+Print the histogram as a JSON object.
+
+The object has a `buckets` key whose value is an array of buckets. Each bucket
+has `start`, `end` and `count` keys. The `start` of the first bucket and the
+`end` of the last bucket are `null`. The object also has a `percentiles` key
+whose value is an array of objects with `percentile` and `span` keys. The
+array is empty unless `--percentiles` is given.
+"#,
+        );
+
+        &[
+            TimeSpan::ARG_OR_STDIN,
+            BUCKETS,
+            TimeSpan::RELATIVE_FLAG,
+            CUMULATIVE,
+            PERCENTILES,
+            JSON,
+        ]
+    }
+}
+
+/// Counts of durations in buckets.
+#[derive(Debug)]
+struct Histogram {
+    /// The bucket edges, in strictly increasing order.
+    edges: Vec<(TimeSpan, SignedDuration)>,
+    /// The count for each bucket. There is always one more bucket than
+    /// there are edges.
+    counts: Vec<u64>,
+    /// Every duration added, when percentiles were requested.
+    durations: Option<Vec<SignedDuration>>,
+}
+
+impl Histogram {
+    /// Counts the given duration in the bucket it falls into.
+    fn add(&mut self, duration: SignedDuration) {
+        let bucket = self.edges.partition_point(|&(_, edge)| edge <= duration);
+        self.counts[bucket] += 1;
+        if let Some(ref mut durations) = self.durations {
+            durations.push(duration);
+        }
+    }
+
+    /// Returns a label for each bucket along with its count.
+    fn rows(&self, cumulative: bool) -> Vec<(String, u64)> {
+        let mut rows = vec![];
+        let mut total = 0;
+        for (i, &count) in self.counts.iter().enumerate() {
+            total += count;
+            let label = match (i.checked_sub(1), self.edges.get(i)) {
+                (None, Some((end, _))) => format!("< {end}"),
+                (Some(start), Some((end, _))) => {
+                    format!("[{}, {end})", self.edges[start].0)
+                }
+                (Some(start), None) => format!(">= {}", self.edges[start].0),
+                // There's always at least one edge.
+                (None, None) => unreachable!(),
+            };
+            rows.push((label, if cumulative { total } else { count }));
+        }
+        rows
+    }
+
+    /// Returns each of the given percentiles along with its duration.
+    ///
+    /// If percentiles weren't requested or if there are no durations, then
+    /// this returns an empty list.
+    fn percentiles(
+        &mut self,
+        config: &Config,
+    ) -> Vec<(Percentile, SignedDuration)> {
+        let (Some(percentiles), Some(durations)) =
+            (config.percentiles.as_ref(), self.durations.as_mut())
+        else {
+            return vec![];
+        };
+        if durations.is_empty() {
+            return vec![];
+        }
+        durations.sort();
+        percentiles
+            .iter()
+            .map(|&percentile| {
+                (percentile, durations[percentile.rank(durations.len())])
+            })
+            .collect()
+    }
+
+    /// Writes this histogram as an aligned table followed by any requested
+    /// percentiles.
+    fn write_table(
+        &mut self,
+        mut wtr: impl Write,
+        config: &Config,
+    ) -> anyhow::Result<()> {
+        const MIN_SPACE: usize = 2;
+
+        let rows = self.rows(config.cumulative);
+        // Both of these are always non-empty.
+        let label_len = rows.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
+        let count_len =
+            rows.iter().map(|(_, c)| c.to_string().len()).max().unwrap_or(0);
+        for (label, count) in rows {
+            let padlen = MIN_SPACE + (label_len - label.len());
+            writeln!(wtr, "{label}{}{count:>count_len$}", " ".repeat(padlen))?;
+        }
+        for (percentile, duration) in self.percentiles(config) {
+            writeln!(wtr, "p{percentile}: {duration:#}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes this histogram as a JSON object.
+    fn write_json(
+        &mut self,
+        mut wtr: impl Write,
+        config: &Config,
+    ) -> anyhow::Result<()> {
+        let mut buckets = vec![];
+        let mut total = 0;
+        for (i, &count) in self.counts.iter().enumerate() {
+            total += count;
+            let start = i.checked_sub(1).map(|i| &self.edges[i].0);
+            let end = self.edges.get(i).map(|(end, _)| end);
+            buckets.push(serde_json::json!({
+                "start": start,
+                "end": end,
+                "count": if config.cumulative { total } else { count },
+            }));
+        }
+        let percentiles: Vec<_> = self
+            .percentiles(config)
+            .into_iter()
+            .map(|(percentile, duration)| {
+                serde_json::json!({
+                    "percentile": percentile.0,
+                    "span": format!("{duration:#}"),
+                })
+            })
+            .collect();
+        let value = serde_json::json!({
+            "buckets": buckets,
+            "percentiles": percentiles,
+        });
+        serde_json::to_writer(&mut wtr, &value)?;
+        writeln!(wtr)?;
+        Ok(())
+    }
+}
+
+/// A percentile from `0` to `100`, inclusive.
+#[derive(Clone, Copy, Debug)]
+struct Percentile(f64);
+
+impl Percentile {
+    /// Returns the index of this percentile in a sorted list of the given
+    /// length using the nearest-rank method.
+    ///
+    /// `len` must be greater than zero.
+    fn rank(&self, len: usize) -> usize {
+        // The rank is 1-indexed, and the 0th percentile is the minimum.
+        let rank = (self.0 / 100.0 * len as f64).ceil() as usize;
+        rank.clamp(1, len) - 1
+    }
+}
+
+impl std::fmt::Display for Percentile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for Percentile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Percentile> {
+        let percentile: f64 = s
+            .parse()
+            .ok()
+            .filter(|p: &f64| (0.0..=100.0).contains(p))
+            .context("percentile must be a number from 0 to 100")?;
+        Ok(Percentile(percentile))
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
     span::TimeSpan,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Format spans into the ISO 8601 duration format.
 
 This will permit weeks to be combined with other units, which is not permitted
@@ -54,6 +54,15 @@ EXAMPLES:
         $ bttf span iso8601 2000ms
         PT2S
 
+    Some systems expect durations that are a whole number of weeks to be
+    written in week form. The `-w/--weeks` flag does this for any span made
+    up of only weeks or days that sum to a whole number of weeks, and falls
+    back to the usual format otherwise:
+
+        $ bttf span iso8601 -w 14d 15d
+        P2W
+        P15D
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -69,17 +78,52 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let printer = config.printer();
     let mut wtr = std::io::stdout().lock();
     spans.try_map(|datum| {
-        let formatted =
-            datum.try_map(|span| Ok(printer.span_to_string(span.get())))?;
+        let formatted = datum.try_map(|span| {
+            let mut span = *span.get();
+            if config.weeks {
+                span = to_weeks(span).unwrap_or(span);
+            }
+            Ok(printer.span_to_string(&span))
+        })?;
         formatted.write(&mut wtr)?;
         writeln!(wtr)?;
         Ok(true)
     })
 }
 
+/// Returns an equivalent span expressed only in units of weeks.
+///
+/// This only succeeds when the given span is non-zero and consists only of
+/// weeks and days, where the days (combined with the weeks) sum to a whole
+/// number of weeks. Otherwise, `None` is returned.
+///
+/// This treats all days as 7 days to a week, which is always true in the
+/// civil calendar. So no relative datetime is needed.
+fn to_weeks(span: jiff::Span) -> Option<jiff::Span> {
+    let others = [
+        i64::from(span.get_years()),
+        i64::from(span.get_months()),
+        i64::from(span.get_hours()),
+        span.get_minutes(),
+        span.get_seconds(),
+        span.get_milliseconds(),
+        span.get_microseconds(),
+        span.get_nanoseconds(),
+    ];
+    if span.is_zero() || others.iter().any(|&unit| unit != 0) {
+        return None;
+    }
+    let days = i64::from(span.get_weeks()) * 7 + i64::from(span.get_days());
+    if days % 7 != 0 {
+        return None;
+    }
+    jiff::Span::new().try_weeks(days / 7).ok()
+}
+
 #[derive(Debug, Default)]
 struct Config {
     lowercase: bool,
+    weeks: bool,
 }
 
 impl Config {
@@ -98,6 +142,9 @@ impl args::Configurable for Config {
             lexopt::Arg::Short('l') | lexopt::Arg::Long("lowercase") => {
                 self.lowercase = true;
             }
+            lexopt::Arg::Short('w') | lexopt::Arg::Long("weeks") => {
+                self.weeks = true;
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -116,6 +163,22 @@ supported by the Tempora ISO 8601 duration format.
 "#,
         );
 
-        &[TimeSpan::ARG_OR_STDIN, LOWERCASE]
+        const WEEKS: Usage = Usage::flag(
+            "-w/--weeks",
+            "Write whole week spans in week form, e.g., `P2W`.",
+            r#"
+Write spans that are a whole number of weeks in week form, e.g., `P2W`.
+
+ISO 8601 permits a duration to be written in units of weeks, but only when
+weeks are the sole unit in the duration. When this flag is given, any span
+consisting of only weeks and days that add up to a whole number of weeks is
+written in week form. For example, `14d` is written as `P2W`.
+
+Spans that cannot be written in week form, like `15d` or `1w12h`, are
+formatted as they would be without this flag.
+"#,
+        );
+
+        &[TimeSpan::ARG_OR_STDIN, LOWERCASE, WEEKS]
     }
 }
@@ -1,33 +1,59 @@
+mod add;
 mod balance;
+mod clamp;
 mod fmt;
+mod histogram;
 mod iso8601;
+mod overlap;
+mod parse;
 mod round;
+mod scale;
 mod since;
+mod stats;
+mod total;
 mod until;
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Commands for working with calendar and time durations.
 
 USAGE:
     bttf span <command> ...
 
 COMMANDS:
-    balance  Change the largest non-zero unit in a span
-    fmt      Format a span as a "friendly" duration
-    iso8601  Format span as an ISO 8601 duration
-    round    Round a span
-    since    Calculate a span since a datetime
-    until    Calculate a span until a datetime
+    add        Add spans together (also available as `sum`)
+    balance    Change the largest non-zero unit in a span
+    clamp      Clamp spans to a range
+    fmt        Format a span as a "friendly" duration
+    histogram  Count spans in buckets
+    iso8601    Format span as an ISO 8601 duration
+    overlap    Calculate the length of overlap between intervals
+    parse      Parse and validate spans
+    round      Round a span
+    scale      Multiply or divide a span by a factor
+    since      Calculate a span since a datetime
+    stats      Print summary statistics for spans
+    subtract   Subtract spans from the first span
+    total      Print the total number of a single unit in spans
+    until      Calculate a span until a datetime
 "#;
 
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let cmd = crate::args::next_as_command(USAGE, p)?;
     match &*cmd {
+        "add" | "sum" => add::add(p),
         "balance" => balance::run(p),
+        "clamp" => clamp::run(p),
         "fmt" => fmt::run(p),
+        "histogram" => histogram::run(p),
         "iso8601" => iso8601::run(p),
+        "overlap" => overlap::run(p),
+        "parse" => parse::run(p),
         "round" => round::run(p),
+        "scale" => scale::run(p),
         "since" => since::run(p),
+        "stats" => stats::run(p),
+        "subtract" => add::subtract(p),
+        "total" => total::run(p),
         "until" => until::run(p),
         unk => anyhow::bail!("unrecognized command '{}'", unk),
     }
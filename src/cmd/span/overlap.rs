@@ -0,0 +1,232 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    bstr::ByteSlice,
+    jiff::{SignedDuration, Span, SpanRound, Timestamp, Unit},
+    lexopt::{Arg, Parser},
+};
+
+use crate::{
+    args::{self, Usage, flags},
+    datetime::{DateTime, DateTimeFlexible},
+    parse::{BufReadExt, BytesExt},
+    span::TimeSpan,
+};
+
+const USAGE: &str = r#"
+Calculate the length of the overlap between two intervals.
+
+This reads line delimited data from a file or stdin, where each line contains
+four datetimes separated by tabs: the start and end of the first interval,
+followed by the start and end of the second interval. For each line, the
+length of time during which both intervals overlap is printed as a span. When
+the intervals don't overlap, or only touch at one instant, a zero span is
+printed. Alternatively, a single pair of intervals may be given with the `--a`
+and `--b` flags, in which case nothing is read from stdin.
+
+The line format is the same as the output of `bttf time overlap`. So the two
+commands can be combined to find out how much each pair of overlapping
+intervals overlaps by.
+
+The overlap is computed from the instants of each datetime. This means the
+length of the overlap is the actual amount of time that elapsed, even when a
+daylight saving time transition occurs during it. The span printed always has
+units of hours or smaller.
+
+USAGE:
+    bttf span overlap <path>
+    bttf span overlap < line delimited <datetime>\t<datetime>\t<datetime>\t<datetime>
+    bttf span overlap --a <datetime>,<datetime> --b <datetime>,<datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Find how long two meetings overlap:
+
+        $ bttf span overlap --a 2025-03-15T09:00,2025-03-15T10:00 \
+            --b 2025-03-15T09:30,2025-03-15T11:00
+        30m
+
+    %snip-start%
+
+    Find how much each pair of overlapping intervals overlaps by:
+
+        $ bttf time overlap --all-pairs events.tsv | bttf span overlap
+
+    Intervals that don't overlap have an overlap of zero:
+
+        $ bttf span overlap --a 2025-03-15T09:00,2025-03-15T10:00 \
+            --b 2025-03-15T10:00,2025-03-15T11:00
+        0s
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let mut wtr = std::io::stdout().lock();
+    match (config.a, config.b) {
+        (Some(a), Some(b)) => {
+            writeln!(wtr, "{}", overlap(&a, &b)?)?;
+            return Ok(());
+        }
+        (None, None) => {}
+        _ => anyhow::bail!("--a and --b must be given together"),
+    }
+    let result = config.input.reader()?.for_byte_line(|line| {
+        let (a, b) = parse_line(line.content())
+            .with_context(|| format!("line {}", line.number()))?;
+        writeln!(wtr, "{}", overlap(&a, &b)?)?;
+        Ok(true)
+    });
+    result.with_context(|| format!("{}", config.input.display()))?;
+    Ok(())
+}
+
+/// Returns the length of time during which both intervals overlap.
+///
+/// This is zero when the intervals are disjoint or merely touch.
+fn overlap(a: &Interval, b: &Interval) -> anyhow::Result<TimeSpan> {
+    let start = a.start.max(b.start);
+    let end = a.end.min(b.end);
+    let duration = if start < end {
+        start.duration_until(end)
+    } else {
+        SignedDuration::ZERO
+    };
+    let span = Span::try_from(duration)
+        .and_then(|span| span.round(SpanRound::new().largest(Unit::Hour)))
+        .with_context(|| {
+            format!("failed to convert duration `{duration:#}` to a span")
+        })?;
+    Ok(span.into())
+}
+
+/// Parses two intervals from four tab separated datetimes.
+fn parse_line(content: &[u8]) -> anyhow::Result<(Interval, Interval)> {
+    let fields: Vec<&[u8]> = content.split_str("\t").collect();
+    anyhow::ensure!(
+        fields.len() == 4,
+        "expected four tab separated datetimes, but got `{}`",
+        content.as_bstr(),
+    );
+    let mut datetimes =
+        fields.iter().map(|field| field.trim_ascii().parse::<DateTime>());
+    let mut next = || datetimes.next().unwrap();
+    let a = Interval::new(next()?, next()?)?;
+    let b = Interval::new(next()?, next()?)?;
+    Ok((a, b))
+}
+
+/// A single interval, given by its start and end.
+#[derive(Clone, Debug)]
+struct Interval {
+    start: Timestamp,
+    end: Timestamp,
+}
+
+impl Interval {
+    /// Creates a new interval, or returns an error if the end precedes the
+    /// start.
+    fn new(start: DateTime, end: DateTime) -> anyhow::Result<Interval> {
+        anyhow::ensure!(
+            start.get().timestamp() <= end.get().timestamp(),
+            "interval end `{end}` precedes its start `{start}`",
+        );
+        Ok(Interval {
+            start: start.get().timestamp(),
+            end: end.get().timestamp(),
+        })
+    }
+}
+
+impl std::str::FromStr for Interval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Interval> {
+        let (start, end) = s.split_once(',').with_context(|| {
+            format!("interval `{s}` must be written as `<start>,<end>`")
+        })?;
+        let start: DateTimeFlexible = start.trim().parse()?;
+        let end: DateTimeFlexible = end.trim().parse()?;
+        Interval::new(start.into(), end.into())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    input: flags::FileOrStdin,
+    a: Option<Interval>,
+    b: Option<Interval>,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut Parser,
+        arg: &mut Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            Arg::Long("a") => {
+                self.a = Some(args::parse(p, "--a")?);
+            }
+            Arg::Long("b") => {
+                self.b = Some(args::parse(p, "--b")?);
+            }
+            Arg::Value(ref mut v) => {
+                self.input.set(std::mem::take(v))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const PATH: Usage = Usage::arg(
+            "<path>",
+            "A file path to read pairs of intervals from.",
+            r#"
+A file path to read pairs of intervals from.
+
+Each line must contain four datetimes separated by tabs: the start and end of
+the first interval, followed by the start and end of the second interval. An
+interval whose end precedes its start is an error. In lieu of a specific file
+path, users may also pass line delimited data into stdin.
+"#,
+        );
+
+        const A: Usage = Usage::flag(
+            "--a <datetime>,<datetime>",
+            "The start and end of the first interval.",
+            r#"
+The start and end of the first interval.
+
+The start and end are separated by a comma, and so must not contain commas
+themselves. Any datetime format accepted on the command line may be used. This
+must be given along with `--b`. When given, nothing is read from stdin.
+"#,
+        );
+
+        const B: Usage = Usage::flag(
+            "--b <datetime>,<datetime>",
+            "The start and end of the second interval.",
+            r#"
+The start and end of the second interval.
+
+The start and end are separated by a comma, and so must not contain commas
+themselves. Any datetime format accepted on the command line may be used. This
+must be given along with `--a`. When given, nothing is read from stdin.
+"#,
+        );
+
+        &[PATH, A, B]
+    }
+}
@@ -0,0 +1,307 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    bstr::ByteSlice,
+    jiff::{
+        Span,
+        fmt::{
+            friendly::{
+                Designator, Direction, FractionalUnit, Spacing, SpanPrinter,
+            },
+            temporal,
+        },
+    },
+};
+
+use crate::{
+    args::{self, Usage, positional},
+    parse::BytesExt,
+    span::TimeSpan,
+};
+
+const USAGE: &str = r#"
+Parse and validate spans.
+
+This accepts one or more strings to parse as positional arguments. When no
+positional arguments are given, then line delimited strings are read from
+stdin. Each string is parsed as a span in either the ISO 8601 duration format
+or the "friendly" format. When successful, the span is printed in the
+"friendly" format. Otherwise, an error is reported for the first string that
+isn't a valid span (including its line number when reading from stdin) and
+bttf exits with a non-zero status.
+
+Unlike other commands that accept spans, this command makes it possible to
+reject spans that are valid but written in a lenient form via `--strict`. This
+makes it useful for checking duration literals, e.g., in configuration files
+as part of continuous integration.
+
+USAGE:
+    bttf span parse <string>...
+    bttf span parse < line delimited <string>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Parse spans in different formats and print them in a normalized form:
+
+        $ bttf span parse PT1H30M '2 hours, 5 minutes ago' 1hr30mins
+        1h 30m
+        2h 5m ago
+        1h 30m
+
+    %snip-start%
+
+    Check that every span in a file is written in a strict form:
+
+        $ bttf span parse --strict < timeouts.txt > /dev/null
+
+    Units must be written from largest to smallest and cannot be repeated:
+
+        $ bttf span parse 1h1h
+        invalid time span `1h1h`: failed to parse input in the "friendly"
+        duration format: found value with unit hour after unit hour, but units
+        must be written from largest to smallest (and they can't be repeated)
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut args = positional::MaybeTaggedArguments::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut args])?;
+
+    let mut wtr = std::io::stdout().lock();
+    args.try_map(|datum| {
+        let parsed = datum.try_map(|arg| {
+            let span: TimeSpan = arg
+                .parse()
+                .with_context(|| format!("invalid time span `{arg}`"))?;
+            if config.strict {
+                check_strict(&arg)?;
+                check_canonical(&arg, span.get())?;
+            }
+            Ok(span)
+        })?;
+        parsed.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })?;
+    Ok(())
+}
+
+/// Returns an error if the given span, which must already be known to be
+/// valid, is written in a lenient form.
+///
+/// What is considered lenient is documented by the `--strict` flag.
+fn check_strict(span: &[u8]) -> anyhow::Result<()> {
+    let span_bstr = span.as_bstr();
+    anyhow::ensure!(
+        span.trim_ascii_end() == span,
+        "span `{span_bstr}` has trailing whitespace",
+    );
+    let unsigned = span.strip_prefix(b"+").or(span.strip_prefix(b"-"));
+    let unsigned = unsigned.unwrap_or(span);
+    if unsigned.first().is_some_and(|&b| b == b'P' || b == b'p') {
+        anyhow::ensure!(
+            !span.contains(&b'p') && !span.contains(&b't'),
+            "ISO 8601 span `{span_bstr}` must use uppercase `P` and `T` \
+             designators",
+        );
+        let designators = unsigned[1..]
+            .iter()
+            .copied()
+            .filter(|&b| b.is_ascii_alphabetic() && b != b'T');
+        let (mut upper, mut lower) = (false, false);
+        for b in designators {
+            upper |= b.is_ascii_uppercase();
+            lower |= b.is_ascii_lowercase();
+        }
+        anyhow::ensure!(
+            !(upper && lower),
+            "ISO 8601 span `{span_bstr}` mixes uppercase and lowercase unit \
+             designators",
+        );
+        return Ok(());
+    }
+    anyhow::ensure!(
+        !span.contains_str("  "),
+        "span `{span_bstr}` has consecutive spaces",
+    );
+    let invalid = span.iter().find(|&&b| b.is_ascii_whitespace() && b != b' ');
+    if let Some(&b) = invalid {
+        anyhow::bail!(
+            "span `{span_bstr}` contains whitespace other than a space: `{}`",
+            [b].as_bstr().escape_bytes(),
+        );
+    }
+    Ok(())
+}
+
+/// Returns an error if the given span isn't written exactly as bttf would
+/// print it in one of the styles it supports.
+///
+/// This is a round trip check: the span parsed from `arg` is printed in each
+/// supported style, and `arg` must be equivalent to one of them. The styles
+/// supported are ISO 8601 (with either uppercase or lowercase unit
+/// designators) and the friendly format with verbose or compact unit
+/// designators. The friendly format permits any spacing, direction, comma,
+/// fractional or `HH:MM:SS` setting, since these are all choices that
+/// `bttf span fmt` offers.
+///
+/// This is meant to run after `check_strict`, which gives better error
+/// messages for the most common ways of failing this check.
+fn check_canonical(arg: &[u8], span: &Span) -> anyhow::Result<()> {
+    let unsigned = arg.strip_prefix(b"+").or(arg.strip_prefix(b"-"));
+    if unsigned.unwrap_or(arg).first() == Some(&b'P') {
+        for lowercase in [false, true] {
+            let printer = temporal::SpanPrinter::new().lowercase(lowercase);
+            if printer.span_to_string(span).as_bytes() == arg {
+                return Ok(());
+            }
+        }
+        anyhow::bail!(
+            "ISO 8601 span `{arg}` is not in canonical form, \
+             expected `{canonical}`",
+            arg = arg.as_bstr(),
+            canonical = temporal::SpanPrinter::new().span_to_string(span),
+        );
+    }
+    // We only try the settings that could possibly produce `arg`, which
+    // avoids printing the span hundreds of times for every input.
+    let commas: &[bool] =
+        if arg.contains(&b',') { &[true, false] } else { &[false] };
+    let hms: &[bool] = if arg.contains(&b':') { &[true] } else { &[false] };
+    let fractionals: &[Option<FractionalUnit>] = if arg.contains(&b'.') {
+        &[
+            Some(FractionalUnit::Hour),
+            Some(FractionalUnit::Minute),
+            Some(FractionalUnit::Second),
+            Some(FractionalUnit::Millisecond),
+            Some(FractionalUnit::Microsecond),
+        ]
+    } else {
+        &[None]
+    };
+    for designator in [Designator::Compact, Designator::Verbose] {
+        for spacing in [
+            Spacing::None,
+            Spacing::BetweenUnits,
+            Spacing::BetweenUnitsAndDesignators,
+        ] {
+            for direction in
+                [Direction::Sign, Direction::ForceSign, Direction::Suffix]
+            {
+                for &comma in commas {
+                    for &hms in hms {
+                        for &fractional in fractionals {
+                            let printer = SpanPrinter::new()
+                                .designator(designator)
+                                .spacing(spacing)
+                                .direction(direction)
+                                .comma_after_designator(comma)
+                                .hours_minutes_seconds(hms)
+                                .fractional(fractional);
+                            if printer.span_to_string(span).as_bytes() == arg {
+                                return Ok(());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    anyhow::bail!(
+        "span `{arg}` is not in canonical form, expected, e.g., \
+         `{canonical}` or `{verbose}`",
+        arg = arg.as_bstr(),
+        canonical = TimeSpan::from(*span),
+        verbose = SpanPrinter::new()
+            .designator(Designator::Verbose)
+            .spacing(Spacing::BetweenUnitsAndDesignators)
+            .span_to_string(span),
+    );
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    strict: bool,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        _: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("strict") => {
+                self.strict = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const STRING: Usage = Usage::arg(
+            "<string>",
+            "A string to parse as a span, e.g., `1h30m` or `PT1H30M`.",
+            r#"
+A string to parse as a span, e.g., `1h30m` or `PT1H30M`.
+
+Strings can either be passed as positional arguments or as line delimited data
+on stdin, but not both. Tagged data on stdin is also supported, in which case,
+every tag is parsed as a span.
+"#,
+        );
+
+        const STRICT: Usage = Usage::flag(
+            "--strict",
+            "Reject spans written in a lenient form.",
+            r#"
+Reject spans written in a lenient form.
+
+Spans that can't be parsed at all are always rejected. For example, spans
+with units written out of order or repeated, like `1m1h` or `1h1h`, are never
+valid.
+
+When this flag is given, a span must also be written exactly as bttf would
+print it. That is, printing the parsed span must reproduce the original
+string for at least one of the following styles:
+
+ISO 8601, with either uppercase or lowercase unit designators, e.g., `PT1H30M`
+or `P1dT1h` (as with `bttf span iso8601 -l`).
+
+The friendly format with compact or verbose unit designators, e.g., `1h 30m`
+or `1 hour, 30 minutes`. Any of the spacing, sign, comma, fractional and
+`HH:MM:SS` styles offered by `bttf span fmt` are permitted.
+
+For example, the following are rejected:
+
+Trailing whitespace, e.g., `1h ` given as a positional argument or on stdin.
+
+ISO 8601 durations with a lowercase `P` or `T`, e.g., `p1d` or `P1Dt1h`.
+
+ISO 8601 durations that mix uppercase and lowercase unit designators, e.g.,
+`PT1H30m` or `P1DT1h`.
+
+Friendly durations with whitespace other than a single space between
+components, e.g., `1h  30m` or `1h<TAB>30m`.
+
+Friendly durations with abbreviated unit designators, e.g., `1hr` or
+`30 mins`, or that mix designator styles, e.g., `1 hour, 30m`.
+
+Zero units that bttf wouldn't print, e.g., `0h` or `1h 0m` instead of `0s` or
+`1h`.
+"#,
+        );
+
+        &[STRING, STRICT]
+    }
+}
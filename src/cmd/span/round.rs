@@ -6,7 +6,7 @@ use crate::{
     span::TimeSpan,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Round spans to a specified smallest and largest unit. When units aren't given,
 the smallest unit defaults to nanoseconds (no rounding is done) and the largest
 unit defaults to the largest unit in the span.
@@ -16,6 +16,14 @@ resolve the actual physical time duration (e.g., some months are longer than
 others). By default, the relative datetime is the current time, but it may be
 set via the `-r/--relative` flag.
 
+The default rounding mode is `half-expand`. This rounds to the nearest value
+allowed by the smallest unit and rounding increment. When a span is exactly
+halfway between two allowed values (a tie), it is rounded away from zero. For
+example, `1m30s` rounds to `2m` and `-1m30s` rounds to `-2m`. To round ties
+toward zero instead, use `-m half-trunc`. To always round toward zero, even
+when the remainder is more than half, use `-m trunc`. See `-m/--mode` for all
+rounding modes.
+
 This accepts one or more spans as positional arguments. When no positional
 arguments are given, then line delimited spans are read from stdin.
 
@@ -41,6 +49,19 @@ EXAMPLES:
 
     %snip-start%
 
+    Ties are rounded away from zero by default. Use `half-trunc` to round
+    them toward zero instead:
+
+        $ bttf span round 2h30m30s -s minute
+        2h 31m
+        $ bttf span round 2h30m30s -s minute -m half-trunc
+        2h 30m
+
+    Round a duration up to the next 15 minute block, e.g., for billing:
+
+        $ bttf span round 1h7m -s minute -i 15 -m ceil
+        1h 15m
+
     This command can be quite useful in making time spans a bit more human
     friendly. For example, getting a span since some date from now can result
     in somewhat unwieldy spans:
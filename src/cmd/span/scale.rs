@@ -0,0 +1,272 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    jiff::{SignedDuration, Span, SpanRound, Unit},
+};
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    span::{TimeSpan, UNITS, unit_value},
+};
+
+const USAGE: &str = r#"
+Multiply or divide spans by a factor.
+
+Each span is converted to an absolute duration, multiplied by the factor given
+to `--by` and then converted back into a span. The largest unit of the result
+is the same as the largest unit of the original span. The result is precise to
+the nanosecond.
+
+Scaling spans with calendar units (years, months, weeks or days) is ill-defined
+without knowing which calendar units are being scaled. For example, half of a
+month might be 14 days or 15.5 days. So spans with calendar units require a
+relative datetime given by `-r/--relative`. Spans with only time units never
+need a relative datetime.
+
+This accepts one or more spans as positional arguments. When no positional
+arguments are given, then line delimited spans are read from stdin.
+
+USAGE:
+    bttf span scale --by <factor> <span>...
+    bttf span scale --by <factor> < line delimited <span>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Find half of a duration:
+
+        $ bttf span scale --by 0.5 2h15m
+        1h 7m 30s
+
+    %snip-start%
+
+    Divide a duration into thirds:
+
+        $ bttf span scale --by 1/3 1h
+        20m
+
+    Round the result to the smallest unit in the original span:
+
+        $ bttf span scale --by 0.3 5m
+        1m 30s
+        $ bttf span scale --by 0.3 --round 5m
+        2m
+
+    Calendar units are scaled relative to a particular datetime:
+
+        $ bttf span scale --by 0.5 -r 2025-02-01 1mo
+        14d
+        $ bttf span scale --by 0.5 -r 2025-03-01 1mo
+        15d 12h 30m
+
+    (March 2025 in New York has 743 hours because of the transition to
+    daylight saving time, and 371.5 hours after the start of March is
+    12:30 on March 16.)
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut spans = positional::Spans::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut spans])?;
+
+    let Some(ref factor) = config.by else {
+        anyhow::bail!("--by is required");
+    };
+    let mut wtr = std::io::stdout().lock();
+    spans.try_map(|datum| {
+        let scaled = datum.try_map(|span| config.scale(factor, &span))?;
+        scaled.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    by: Option<Factor>,
+    relative: Option<DateTime>,
+    round: bool,
+}
+
+impl Config {
+    /// Scales the given span by the given factor.
+    fn scale(
+        &self,
+        factor: &Factor,
+        span: &TimeSpan,
+    ) -> anyhow::Result<TimeSpan> {
+        let Some((largest, smallest)) = units(span.get()) else {
+            // A zero span is always zero when scaled.
+            return Ok(span.clone());
+        };
+        let duration = span.to_duration(self.relative.as_ref())?;
+        let scaled = factor.apply(duration).with_context(|| {
+            format!("scaling span `{span}` by {factor} overflowed")
+        })?;
+        let mut options = SpanRound::new().largest(largest);
+        if self.round {
+            options = options.smallest(smallest);
+        }
+        if let Some(ref relative) = self.relative {
+            options = options.relative(relative.get());
+        }
+        let result = Span::try_from(scaled)
+            .and_then(|scaled| scaled.round(options))
+            .with_context(|| {
+                format!("failed to convert scaled span `{span}` to a span")
+            })?;
+        Ok(result.into())
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("by") => {
+                self.by = Some(args::parse(p, "--by")?);
+            }
+            lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
+                let relative: DateTimeFlexible =
+                    args::parse(p, "-r/--relative")?;
+                self.relative = Some(relative.into());
+            }
+            lexopt::Arg::Long("round") => {
+                self.round = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const BY: Usage = Usage::flag(
+            "--by <factor>",
+            "The factor to multiply each span by, e.g., `2`, `0.5` or `1/3`.",
+            r#"
+The factor to multiply each span by, e.g., `2`, `0.5` or `1/3`.
+
+This flag is required. The factor may be an integer, a decimal number or a
+fraction written as `<numerator>/<denominator>`. To divide a span, use a
+fraction or a decimal number less than `1`. A negative factor negates the span.
+
+Scaling is exact. When the scaled duration isn't a whole number of
+nanoseconds, it is rounded to the nearest nanosecond, with ties rounding away
+from zero.
+"#,
+        );
+
+        const ROUND: Usage = Usage::flag(
+            "--round",
+            "Round the result to the smallest unit of the original span.",
+            r#"
+Round the result to the smallest unit of the original span.
+
+For example, scaling `5m` by `0.3` results in `1m 30s`. With this flag, the
+result is rounded to the nearest minute, which gives `2m`. Ties round away from
+zero.
+"#,
+        );
+
+        &[TimeSpan::ARG_OR_STDIN, BY, TimeSpan::RELATIVE_FLAG, ROUND]
+    }
+}
+
+/// A factor to scale spans by, represented as an exact fraction.
+///
+/// The denominator is always positive.
+#[derive(Clone, Debug)]
+struct Factor {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Factor {
+    /// Multiplies the given duration by this factor.
+    ///
+    /// The result is rounded to the nearest nanosecond, with ties rounding
+    /// away from zero. If the result overflows, then `None` is returned.
+    fn apply(&self, duration: SignedDuration) -> Option<SignedDuration> {
+        let product = duration.as_nanos().checked_mul(self.numerator)?;
+        let mut nanos = product / self.denominator;
+        // Compare the remainder against half of the denominator without
+        // doubling it, since that could overflow.
+        let remainder = (product % self.denominator).unsigned_abs();
+        if remainder >= self.denominator.unsigned_abs() - remainder {
+            nanos += product.signum();
+        }
+        SignedDuration::try_from_nanos_i128(nanos)
+    }
+}
+
+impl std::fmt::Display for Factor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl std::str::FromStr for Factor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Factor> {
+        fn integer(s: &str) -> anyhow::Result<i128> {
+            anyhow::ensure!(
+                !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()),
+                "expected an integer, decimal number or fraction",
+            );
+            s.parse().context("factor is too big")
+        }
+
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (numerator, denominator) =
+            if let Some((numer, denom)) = unsigned.split_once('/') {
+                (integer(numer)?, integer(denom)?)
+            } else if let Some((whole, frac)) = unsigned.split_once('.') {
+                let scale = u32::try_from(frac.len())
+                    .ok()
+                    .and_then(|len| 10i128.checked_pow(len))
+                    .context("factor has too many decimal places")?;
+                let whole = if whole.is_empty() { 0 } else { integer(whole)? };
+                let frac = integer(frac)?;
+                let numer = whole
+                    .checked_mul(scale)
+                    .and_then(|n| n.checked_add(frac))
+                    .context("factor is too big")?;
+                (numer, scale)
+            } else {
+                (integer(unsigned)?, 1)
+            };
+        anyhow::ensure!(denominator != 0, "factor has a zero denominator");
+        let numerator = if negative { -numerator } else { numerator };
+        Ok(Factor { numerator, denominator })
+    }
+}
+
+/// Returns the largest and smallest units with a non-zero value in the span.
+///
+/// If the span is zero, then `None` is returned.
+fn units(span: &Span) -> Option<(Unit, Unit)> {
+    let nonzero = |&&unit: &&Unit| unit_value(span, unit) != 0;
+    let largest = *UNITS.iter().find(nonzero)?;
+    let smallest = *UNITS.iter().rev().find(nonzero)?;
+    Some((largest, smallest))
+}
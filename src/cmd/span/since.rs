@@ -5,7 +5,7 @@ use crate::{
     datetime::{DateTime, DateTimeFlexible},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Calculate a span since some datetime.
 
 By default, the largest non-zero units of the span returned are hours. To get
@@ -13,6 +13,11 @@ spans with calendar units, use the `-l/--largest` flag to specify the largest
 units that you want. The reason that hours are used by default is because it
 makes the operation reversible. (See below for examples.)
 
+The `-s/--smallest` flag can be used to round the span to a particular unit.
+The largest unit must be at least as big as the smallest unit. When only the
+smallest unit is given and it is bigger than hours, then the largest unit is
+set to the smallest unit.
+
 This accepts one or more datetimes as positional arguments. When no positional
 arguments are given, then line delimited datetimes are read from stdin.
 
@@ -41,11 +46,17 @@ EXAMPLES:
         $ bttf span since -l year '2019-02-03T22:30-05[America/New_York]'
         6y 1mo 14d 23h 19m 57s 554ms 37µs 46ns
 
+    Or, round it to the nearest day:
+
+        $ bttf span since -l year -s day '2019-02-03T22:30-05[America/New_York]'
+        6y 1mo 15d
+
     Units up to hours are returned by default so that operations are
     reversible:
 
         $ bttf span since -r 2024-04-30 2024-05-31
         744h ago
+
         $ bttf time add 744h 2024-04-30
         2024-05-31T00:00:00-04:00[America/New_York]
 
@@ -57,6 +68,13 @@ EXAMPLES:
         $ bttf time add 1mo 2024-04-30
         2024-05-30T00:00:00-04:00[America/New_York]
 
+    Print the datetimes that the span was computed from. This is useful for
+    checking how each datetime was interpreted, e.g., which time zone it was
+    put in:
+
+        $ bttf span since --with-endpoints -r 2024-05-31 2024-04-30T12Z
+        2024-04-30T12:00:00Z[Etc/Unknown] → 2024-05-31T00:00:00-04:00[America/New_York] = 736h
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -67,13 +85,23 @@ OPTIONS:
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
     let mut datetimes = positional::DateTimes::default();
-    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+    let mut now = flags::Now;
+    args::configure(p, USAGE, &mut [&mut config, &mut now, &mut datetimes])?;
 
-    let largest = config.largest.get();
+    let relative = config.relative.clone().unwrap_or_else(DateTime::now);
+    let (largest, smallest) =
+        DateTime::span_units(config.largest.as_ref(), &config.smallest)?;
     let mut wtr = std::io::stdout().lock();
     datetimes.try_map(|datum| {
-        let span = datum
-            .try_map(|datetime| config.relative.since(largest, &datetime))?;
+        let span = datum.try_map(|datetime| {
+            let span = relative.since(largest, smallest, &datetime)?;
+            if !config.with_endpoints {
+                return Ok(span.to_string());
+            }
+            // The span returned is from the datetime given to the relative
+            // datetime.
+            Ok(format!("{datetime} → {relative} = {span}",))
+        })?;
         span.write(&mut wtr)?;
         writeln!(wtr)?;
         Ok(true)
@@ -82,13 +110,20 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
 
 #[derive(Debug)]
 struct Config {
-    relative: DateTime,
-    largest: flags::Unit,
+    relative: Option<DateTime>,
+    largest: Option<flags::Unit>,
+    smallest: flags::Unit,
+    with_endpoints: bool,
 }
 
 impl Default for Config {
     fn default() -> Config {
-        Config { relative: DateTime::now(), largest: jiff::Unit::Hour.into() }
+        Config {
+            relative: None,
+            largest: None,
+            smallest: jiff::Unit::Nanosecond.into(),
+            with_endpoints: false,
+        }
     }
 }
 
@@ -102,10 +137,16 @@ impl args::Configurable for Config {
             lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
                 let relative: DateTimeFlexible =
                     args::parse(p, "-r/--relative")?;
-                self.relative = relative.into();
+                self.relative = Some(relative.into());
             }
             lexopt::Arg::Short('l') | lexopt::Arg::Long("largest") => {
-                self.largest = args::parse(p, "-l/--largest")?;
+                self.largest = Some(args::parse(p, "-l/--largest")?);
+            }
+            lexopt::Arg::Short('s') | lexopt::Arg::Long("smallest") => {
+                self.smallest = args::parse(p, "-s/--smallest")?;
+            }
+            lexopt::Arg::Long("with-endpoints") => {
+                self.with_endpoints = true;
             }
             _ => return Ok(false),
         }
@@ -113,10 +154,25 @@ impl args::Configurable for Config {
     }
 
     fn usage(&self) -> &[Usage] {
+        const WITH_ENDPOINTS: Usage = Usage::flag(
+            "--with-endpoints",
+            "Print the datetimes used to compute each span.",
+            r#"
+Print the datetimes used to compute each span.
+
+When enabled, each line is written as `start → end = span`, where `start` is
+the datetime given and `end` is the relative datetime. The datetimes are printed
+exactly as they were interpreted, which makes it easy to check things like
+the time zone they were put in or what `now` resolved to.
+"#,
+        );
+
         &[
             DateTime::ARG_OR_STDIN,
             DateTime::RELATIVE_FLAG,
             flags::Unit::LARGEST,
+            flags::Unit::SMALLEST,
+            WITH_ENDPOINTS,
         ]
     }
 }
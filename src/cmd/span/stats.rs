@@ -0,0 +1,213 @@
+use std::io::Write;
+
+use {anyhow::Context, jiff::SignedDuration};
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    span::TimeSpan,
+};
+
+const USAGE: &str = r#"
+Print summary statistics for spans.
+
+Each span is converted to an absolute duration. Then the count, sum, mean,
+median, minimum and maximum of all durations are printed, one per line, with
+each duration written as a "friendly" span with units of hours or smaller.
+When there is an even number of spans, the median is the mean of the two
+middle durations. When there are no spans, only the count and sum are printed.
+
+Spans with calendar units (years, months, weeks or days) require a relative
+datetime given by `-r/--relative`. Spans with only time units never need a
+relative datetime.
+
+This requires keeping every span in memory in order to compute the median.
+
+This accepts one or more spans as positional arguments. When no positional
+arguments are given, then line delimited spans are read from stdin. For tagged
+data, every tag is counted.
+
+USAGE:
+    bttf span stats <span>...
+    bttf span stats < line delimited <span>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Summarize some latencies:
+
+        $ bttf span stats 2ms 5ms 40ms 250ms
+        count: 4
+        sum: 297ms
+        mean: 74ms 250µs
+        median: 22ms 500µs
+        min: 2ms
+        max: 250ms
+
+    %snip-start%
+
+    Print the same statistics as a JSON object:
+
+        $ bttf span stats --json 2ms 5ms 40ms 250ms
+        {"count":4,"max":"250ms","mean":"74ms 250µs","median":"22ms 500µs","min":"2ms","sum":"297ms"}
+
+    Summarize spans with calendar units relative to a particular datetime:
+
+        $ bttf span stats -r '2025-03-01[America/New_York]' 1mo 1w
+        count: 2
+        sum: 911h
+        mean: 455h 30m
+        median: 455h 30m
+        min: 168h
+        max: 743h
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut spans = positional::Spans::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut spans])?;
+
+    let mut durations = vec![];
+    spans.try_map(|datum| {
+        datum.try_map(|span| {
+            durations.push(span.to_duration(config.relative.as_ref())?);
+            Ok(())
+        })?;
+        Ok(true)
+    })?;
+    let stats = Stats::new(durations)?;
+    let mut wtr = std::io::stdout().lock();
+    if config.json {
+        stats.write_json(&mut wtr)?;
+    } else {
+        stats.write_table(&mut wtr)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    relative: Option<DateTime>,
+    json: bool,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
+                let relative: DateTimeFlexible =
+                    args::parse(p, "-r/--relative")?;
+                self.relative = Some(relative.into());
+            }
+            lexopt::Arg::Long("json") => {
+                self.json = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const JSON: Usage = Usage::flag(
+            "--json",
+            "Print the statistics as a JSON object.",
+            r#"
+Print the statistics as a JSON object.
+
+The object has `count`, `sum`, `mean`, `median`, `min` and `max` keys. The
+count is an integer and every other value is a span written as a string. When
+there are no spans, the `mean`, `median`, `min` and `max` values are `null`.
+"#,
+        );
+
+        &[TimeSpan::ARG_OR_STDIN, TimeSpan::RELATIVE_FLAG, JSON]
+    }
+}
+
+/// Summary statistics computed from a list of durations.
+#[derive(Debug)]
+struct Stats {
+    count: usize,
+    sum: SignedDuration,
+    /// The mean, median, minimum and maximum, in that order. This is `None`
+    /// when there are no durations.
+    summary: Option<[SignedDuration; 4]>,
+}
+
+impl Stats {
+    /// Computes statistics for the given durations.
+    ///
+    /// This returns an error if the sum of the durations overflows.
+    fn new(mut durations: Vec<SignedDuration>) -> anyhow::Result<Stats> {
+        let count = durations.len();
+        let total: i128 = durations.iter().map(|d| d.as_nanos()).sum();
+        let sum =
+            SignedDuration::try_from_nanos_i128(total).with_context(|| {
+                format!(
+                    "sum of {count} span(s) overflows the maximum duration"
+                )
+            })?;
+        if durations.is_empty() {
+            return Ok(Stats { count, sum, summary: None });
+        }
+        durations.sort();
+        // The mean and median of a set of durations are always between the
+        // minimum and maximum, so these can never overflow.
+        let mean = SignedDuration::from_nanos_i128(total / (count as i128));
+        let mid = count / 2;
+        let median = if count.is_multiple_of(2) {
+            let (lo, hi) = (durations[mid - 1], durations[mid]);
+            SignedDuration::from_nanos_i128(
+                (lo.as_nanos() + hi.as_nanos()) / 2,
+            )
+        } else {
+            durations[mid]
+        };
+        let (min, max) = (durations[0], durations[count - 1]);
+        Ok(Stats { count, sum, summary: Some([mean, median, min, max]) })
+    }
+
+    /// Writes these statistics with one `label: value` line each.
+    fn write_table(&self, mut wtr: impl Write) -> anyhow::Result<()> {
+        writeln!(wtr, "count: {}", self.count)?;
+        writeln!(wtr, "sum: {:#}", self.sum)?;
+        let Some([mean, median, min, max]) = self.summary else {
+            return Ok(());
+        };
+        writeln!(wtr, "mean: {mean:#}")?;
+        writeln!(wtr, "median: {median:#}")?;
+        writeln!(wtr, "min: {min:#}")?;
+        writeln!(wtr, "max: {max:#}")?;
+        Ok(())
+    }
+
+    /// Writes these statistics as a JSON object.
+    fn write_json(&self, mut wtr: impl Write) -> anyhow::Result<()> {
+        let [mean, median, min, max] = match self.summary {
+            None => [const { None }; 4],
+            Some(summary) => summary.map(|d| Some(format!("{d:#}"))),
+        };
+        let value = serde_json::json!({
+            "count": self.count,
+            "sum": format!("{:#}", self.sum),
+            "mean": mean,
+            "median": median,
+            "min": min,
+            "max": max,
+        });
+        serde_json::to_writer(&mut wtr, &value)?;
+        writeln!(wtr)?;
+        Ok(())
+    }
+}
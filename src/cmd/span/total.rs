@@ -0,0 +1,171 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    jiff::{SpanTotal, Unit},
+};
+
+use crate::{
+    args::{self, Usage, flags, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    span::TimeSpan,
+};
+
+const USAGE: &str = r#"
+Print the total number of a single unit in spans.
+
+Where `bttf span balance` changes the largest unit of a span, this command
+expresses the entire span as a count of the unit given by `-u/--unit`. The
+count may be fractional. For example, `1d 3h 30m` is `27.5` hours.
+
+Calendar units (years, months, weeks and days) vary in length depending on
+when they occur. So a relative datetime given by `-r/--relative` is required
+when the unit given is a calendar unit, or when a span has non-zero years or
+months. Otherwise, days in a span are always treated as 24 hours and weeks as
+7 days.
+
+This accepts one or more spans as positional arguments. When no positional
+arguments are given, then line delimited spans are read from stdin.
+
+USAGE:
+    bttf span total -u <unit> <span>...
+    bttf span total -u <unit> < line delimited <span>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Find the total number of hours in a span:
+
+        $ bttf span total -u hours P1DT3H
+        27
+
+    %snip-start%
+
+    Fractional totals are printed as decimal numbers:
+
+        $ bttf span total -u minutes 1h30s
+        60.5
+
+    Calendar units require a relative datetime:
+
+        $ bttf span total -u days -r 2025-02-01 1mo
+        28
+        $ bttf span total -u days -r 2025-03-01 1mo
+        31
+
+    Or even the time zone, since days aren't always 24 hours:
+
+        $ bttf span total -u hours -r '2025-03-09T00-05[America/New_York]' 1d
+        23
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut spans = positional::Spans::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut spans])?;
+
+    let Some(ref unit) = config.unit else {
+        anyhow::bail!("-u/--unit is required");
+    };
+    anyhow::ensure!(
+        unit.get() < Unit::Day || config.relative.is_some(),
+        "a total in the calendar unit `{unit}` requires a relative datetime \
+         given by -r/--relative",
+    );
+    let mut wtr = std::io::stdout().lock();
+    spans.try_map(|datum| {
+        let total = datum
+            .try_map(|span| Ok(config.total(unit, &span)?.to_string()))?;
+        total.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    relative: Option<DateTime>,
+    unit: Option<flags::Unit>,
+}
+
+impl Config {
+    /// Returns the total number of the given unit in the given span.
+    fn total(
+        &self,
+        unit: &flags::Unit,
+        span: &TimeSpan,
+    ) -> anyhow::Result<f64> {
+        let total = match self.relative {
+            Some(ref relative) => span
+                .get()
+                .total((unit.get(), relative.get()))
+                .with_context(|| {
+                    format!(
+                        "failed to compute total {unit} of span `{span}` \
+                         relative to `{relative}`",
+                    )
+                })?,
+            None => {
+                let sp = span.get();
+                anyhow::ensure!(
+                    sp.get_years() == 0 && sp.get_months() == 0,
+                    "span `{span}` has non-zero years or months, which \
+                     requires a relative datetime given by -r/--relative",
+                );
+                let options = SpanTotal::from(unit.get()).days_are_24_hours();
+                sp.total(options).with_context(|| {
+                    format!("failed to compute total {unit} of span `{span}`",)
+                })?
+            }
+        };
+        Ok(total)
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
+                let relative: DateTimeFlexible =
+                    args::parse(p, "-r/--relative")?;
+                self.relative = Some(relative.into());
+            }
+            lexopt::Arg::Short('u') | lexopt::Arg::Long("unit") => {
+                self.unit = Some(args::parse(p, "-u/--unit")?);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const UNIT: Usage = Usage::flag(
+            "-u/--unit <unit>",
+            "The unit to express each span in, e.g., `hours`.",
+            r#"
+The unit to express each span in, e.g., `hours`.
+
+This flag is required. Calendar units are years, months, weeks or days. Time
+units are hours, minutes, seconds, milliseconds, microseconds or nanoseconds.
+Each unit may be spelled in the same ways as in the friendly duration format,
+e.g., `h`, `hr` or `hours`.
+
+When this is a calendar unit, a relative datetime must be given by
+`-r/--relative`.
+"#,
+        );
+
+        &[TimeSpan::ARG_OR_STDIN, UNIT, TimeSpan::RELATIVE_FLAG]
+    }
+}
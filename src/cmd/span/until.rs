@@ -5,7 +5,7 @@ use crate::{
     datetime::{DateTime, DateTimeFlexible},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Calculate a span until some datetime.
 
 By default, the largest non-zero units of the span returned are hours. To get
@@ -13,6 +13,11 @@ spans with calendar units, use the `-l/--largest` flag to specify the largest
 units that you want. The reason that hours are used by default is because it
 makes the operation reversible. (See below for examples.)
 
+The `-s/--smallest` flag can be used to round the span to a particular unit.
+The largest unit must be at least as big as the smallest unit. When only the
+smallest unit is given and it is bigger than hours, then the largest unit is
+set to the smallest unit.
+
 This accepts one or more datetimes as positional arguments. When no positional
 arguments are given, then line delimited datetimes are read from stdin.
 
@@ -45,7 +50,7 @@ EXAMPLES:
 
     Find the time, rounded to the nearest number of days, until June 30:
 
-        $ bttf span until 2025-06-30 | bttf span round -s days
+        $ bttf span until -s days 2025-06-30
         88d
 
     Units up to hours are returned by default so that operations are
@@ -74,13 +79,23 @@ OPTIONS:
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
     let mut datetimes = positional::DateTimes::default();
-    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+    let mut now = flags::Now;
+    args::configure(p, USAGE, &mut [&mut config, &mut now, &mut datetimes])?;
 
-    let largest = config.largest.get();
+    let relative = config.relative.clone().unwrap_or_else(DateTime::now);
+    let (largest, smallest) =
+        DateTime::span_units(config.largest.as_ref(), &config.smallest)?;
     let mut wtr = std::io::stdout().lock();
     datetimes.try_map(|datum| {
-        let span = datum
-            .try_map(|datetime| config.relative.until(largest, &datetime))?;
+        let span = datum.try_map(|datetime| {
+            let span = relative.until(largest, smallest, &datetime)?;
+            if !config.with_endpoints {
+                return Ok(span.to_string());
+            }
+            // The span returned is from the relative datetime to the
+            // datetime given.
+            Ok(format!("{relative} → {datetime} = {span}",))
+        })?;
         span.write(&mut wtr)?;
         writeln!(wtr)?;
         Ok(true)
@@ -89,13 +104,20 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
 
 #[derive(Debug)]
 struct Config {
-    relative: DateTime,
-    largest: flags::Unit,
+    relative: Option<DateTime>,
+    largest: Option<flags::Unit>,
+    smallest: flags::Unit,
+    with_endpoints: bool,
 }
 
 impl Default for Config {
     fn default() -> Config {
-        Config { relative: DateTime::now(), largest: jiff::Unit::Hour.into() }
+        Config {
+            relative: None,
+            largest: None,
+            smallest: jiff::Unit::Nanosecond.into(),
+            with_endpoints: false,
+        }
     }
 }
 
@@ -109,10 +131,16 @@ impl args::Configurable for Config {
             lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
                 let relative: DateTimeFlexible =
                     args::parse(p, "-r/--relative")?;
-                self.relative = relative.into();
+                self.relative = Some(relative.into());
             }
             lexopt::Arg::Short('l') | lexopt::Arg::Long("largest") => {
-                self.largest = args::parse(p, "-l/--largest")?;
+                self.largest = Some(args::parse(p, "-l/--largest")?);
+            }
+            lexopt::Arg::Short('s') | lexopt::Arg::Long("smallest") => {
+                self.smallest = args::parse(p, "-s/--smallest")?;
+            }
+            lexopt::Arg::Long("with-endpoints") => {
+                self.with_endpoints = true;
             }
             _ => return Ok(false),
         }
@@ -120,10 +148,25 @@ impl args::Configurable for Config {
     }
 
     fn usage(&self) -> &[Usage] {
+        const WITH_ENDPOINTS: Usage = Usage::flag(
+            "--with-endpoints",
+            "Print the datetimes used to compute each span.",
+            r#"
+Print the datetimes used to compute each span.
+
+When enabled, each line is written as `start → end = span`, where `start` is
+the relative datetime and `end` is the datetime given. The datetimes are printed
+exactly as they were interpreted, which makes it easy to check things like
+the time zone they were put in or what `now` resolved to.
+"#,
+        );
+
         &[
             DateTime::ARG_OR_STDIN,
             DateTime::RELATIVE_FLAG,
             flags::Unit::LARGEST,
+            flags::Unit::SMALLEST,
+            WITH_ENDPOINTS,
         ]
     }
 }
@@ -1,5 +1,5 @@
 use std::{
-    borrow::Cow, ffi::OsString, io::Write, process::Command, sync::Arc,
+    ffi::OsString, io::Write, num::NonZero, process::Command, sync::Arc,
 };
 
 use {
@@ -12,15 +12,35 @@ use crate::{
     parallel::Parallel,
     parse::{BufReadExt, LineBuf},
     tag::{Tag, Tagged},
+    template::{Item, Template},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Tag file paths by running arbitrary commands.
 
 This accepts a command name and zero or more arguments to pass to that command
-for each file path on stdin. The command is run for every file path. Any `{}`
-found in an argument is replaced with the file path. If no argument contains
-`{}`, then the file path is added as the final argument to the command.
+for each file path on stdin. The command is run for every file path (or for
+every group of file paths when `--batch` is given). Any `{}` found in an
+argument is replaced with the file path. Arguments may also use `{name}` and
+`{dir}` to refer to the final component of the file path and the
+directory containing it, respectively. If no argument contains a directive,
+then the file path is added as the final argument to the command.
+
+Since each argument is passed to the command directly, file paths containing
+spaces or other special characters don't need any quoting.
+
+By default, each line the command prints to stdout becomes a tag, anything it
+prints to stderr is ignored (unless the command fails, in which case stderr is
+included in the error) and the file path given on stdin is the data that is
+tagged. Use `--stderr-tag` to read tags from stderr instead, in which case
+whatever the command prints to stdout is passed through as the data. Or use
+`--stderr-tag=merge` to read tags from both streams, in which case the file
+path is the data.
+
+Commands are run concurrently, with at most `-j/--threads` of them running at
+any given time. This defaults to the number of available cores. Regardless of
+how many commands run at once, tagged data is always printed in the same order
+as the file paths given on stdin. Use `-j1` to run one command at a time.
 
 USAGE:
     bttf tag exec <command> [<arg>]... < line delimited <path>
@@ -33,6 +53,29 @@ EXAMPLES:
 
         git ls-files | bttf tag exec git log -n1 --format='%cI'
 
+    %snip-start%
+
+    Tag each file with the last commit datetime of the directory containing
+    it:
+
+        git ls-files | bttf tag exec git log -n1 --format='%cI' -- '{dir}'
+
+    Tag each file with its last modification datetime, but run `stat` once
+    for every 100 files instead of once per file:
+
+        git ls-files | bttf tag exec --batch 100 stat -c '%y'
+
+    Tag each file with a datetime from a slow tool, running at most 32 copies
+    of it at a time:
+
+        find ./ -type f | bttf tag exec -j32 slow-tool --datetime
+
+    Tag each file with a datetime that a tool prints to stderr:
+
+        ls *.log | bttf tag exec --stderr-tag sh -c 'last-event "$1"' sh
+
+    %snip-end%
+
 POSITIONAL ARGUMENTS:
 %args%
 OPTIONS:
@@ -44,11 +87,20 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     args::configure(p, USAGE, &mut [&mut config])?;
 
     let command_parts = config.command_parts()?;
+    let batch = config.batch;
+    let tag_stream = config.tag_stream;
+    anyhow::ensure!(
+        batch.is_none() || !matches!(tag_stream, TagStream::Merge),
+        "--stderr-tag=merge cannot be used with --batch, since lines \
+         from stdout and stderr can't be matched up with file paths",
+    );
     let mut wtr = std::io::stdout();
     let mut parallel = Parallel::new(
         config.threads.get(),
-        move |line: LineBuf| {
-            let mut cmd = command_parts.command(line.content())?;
+        move |lines: Vec<LineBuf>| {
+            let paths: Vec<&BStr> =
+                lines.iter().map(|line| line.content()).collect();
+            let mut cmd = command_parts.command(&paths)?;
             let output = cmd
                 .output()
                 .with_context(|| format!("failed to run {cmd:?}"))?;
@@ -60,8 +112,23 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
                 stderr = output.stderr.as_bstr(),
             );
 
-            let mut tagged = Tagged::new(line.full());
-            for (i, output_line) in output.stdout.lines().enumerate() {
+            // With `--stderr-tag`, whatever is printed to stdout is the data
+            // to tag. When nothing is printed, the file paths are the data.
+            let data: Option<Vec<&[u8]>> = match tag_stream {
+                TagStream::Stderr if !output.stdout.is_empty() => {
+                    Some(output.stdout.lines_with_terminator().collect())
+                }
+                _ => None,
+            };
+            let streams: &[&[u8]] = match tag_stream {
+                TagStream::Stdout => &[&output.stdout],
+                TagStream::Stderr => &[&output.stderr],
+                TagStream::Merge => &[&output.stdout, &output.stderr],
+            };
+            let mut tags = vec![];
+            let output_lines =
+                streams.iter().flat_map(|stream| stream.lines());
+            for (i, output_line) in output_lines.enumerate() {
                 let number = i + 1;
                 let tag = output_line.as_bstr();
                 let tag = tag.to_str().with_context(|| {
@@ -70,147 +137,263 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
                          tag {tag:?} is not valid UTF-8",
                     )
                 })?;
-                tagged = tagged.tag(Tag::new(tag.to_string()));
+                tags.push(tag);
+            }
+            if batch.is_none() {
+                let mut tagged = match data {
+                    None => Tagged::new(lines[0].full()),
+                    Some(_) => Tagged::new(output.stdout.as_bstr()),
+                };
+                for tag in tags {
+                    tagged = tagged.tag(Tag::new(tag.to_string()));
+                }
+                return Ok(vec![tagged.into_owned()]);
+            }
+            anyhow::ensure!(
+                tags.len() == lines.len(),
+                "command {cmd:?} printed {got} line(s) of output \
+                 for {expected} file path(s), but --batch requires \
+                 exactly one line of output per file path",
+                got = tags.len(),
+                expected = lines.len(),
+            );
+            if let Some(ref data) = data {
+                anyhow::ensure!(
+                    data.len() == lines.len(),
+                    "command {cmd:?} printed {got} line(s) to stdout \
+                     for {expected} file path(s), but --batch with \
+                     --stderr-tag requires that stdout is either empty \
+                     or has exactly one line per file path",
+                    got = data.len(),
+                    expected = lines.len(),
+                );
+            }
+            let mut batch = vec![];
+            for (i, (line, tag)) in lines.iter().zip(tags).enumerate() {
+                let mut tagged = match data {
+                    None => Tagged::new(line.full()),
+                    Some(ref data) => Tagged::new(data[i].as_bstr()),
+                };
+                if !tag.is_empty() {
+                    tagged = tagged.tag(Tag::new(tag.to_string()));
+                }
+                batch.push(tagged.into_owned());
             }
-            Ok(tagged.into_owned())
+            Ok(batch)
         },
-        move |tagged| {
-            tagged?.write(&mut wtr)?;
-            writeln!(wtr)?;
+        move |batch| {
+            for tagged in batch? {
+                tagged.write(&mut wtr)?;
+                writeln!(wtr)?;
+            }
             Ok(true)
         },
     );
 
-    let result1 = std::io::stdin()
-        .lock()
-        .for_byte_line(|line| parallel.send(line.to_owned()));
+    let batch_size = batch.map_or(1, NonZero::get);
+    let mut lines = vec![];
+    let result1 = std::io::stdin().lock().for_byte_line(|line| {
+        lines.push(line.to_owned());
+        if lines.len() < batch_size {
+            return Ok(true);
+        }
+        parallel.send(std::mem::take(&mut lines))
+    });
+    let result1 = result1.and_then(|()| {
+        if !lines.is_empty() {
+            parallel.send(lines)?;
+        }
+        Ok(())
+    });
     let result2 = parallel.wait();
     result1?;
     result2
 }
 
+/// Which output streams of a command are used for tags.
+#[derive(Clone, Copy, Debug, Default)]
+enum TagStream {
+    /// Only stdout. stderr is ignored.
+    #[default]
+    Stdout,
+    /// Only stderr. stdout, if not empty, is the data that is tagged.
+    Stderr,
+    /// The lines from stdout, followed by the lines from stderr. The file
+    /// path is the data that is tagged.
+    Merge,
+}
+
 /// The parts given that make up a command.
 #[derive(Clone, Debug, Default)]
 struct CommandParts {
-    parts: Arc<[BString]>,
+    program: BString,
+    args: Arc<[Arg]>,
+}
+
+/// A single argument to a command.
+#[derive(Clone, Debug)]
+struct Arg {
+    /// The argument exactly as it was given.
+    original: BString,
+    /// The argument parsed as a template. This is only present when the
+    /// argument contains at least one directive.
+    template: Option<Template<Directive>>,
+}
+
+/// A directive that can appear in an argument to a command.
+#[derive(Clone, Copy, Debug)]
+enum Directive {
+    /// The file path, as given on stdin. Written as `{}` or `{path}`.
+    Path,
+    /// The final component of the file path.
+    Name,
+    /// The file path without its final component.
+    Dir,
 }
 
 impl CommandParts {
     /// Create a new sequence of parts that make up a command.
     ///
-    /// The parts given may contain a `{}` somewhere, which will get replaced
-    /// with a file path when run.
+    /// The parts given (other than the program name) may contain directives
+    /// like `{}` or `{name}`, which will get replaced with information about
+    /// a file path when run.
     ///
-    /// If `parts` is empty, then this returns an error.
+    /// If `parts` is empty, or if any of the parts contains an invalid
+    /// directive, then this returns an error.
     fn new(parts: Vec<BString>) -> anyhow::Result<CommandParts> {
-        anyhow::ensure!(
-            !parts.is_empty(),
-            "command requires at least a program name",
-        );
-        Ok(CommandParts { parts: parts.into() })
+        let mut parts = parts.into_iter();
+        let program = parts
+            .next()
+            .context("command requires at least a program name")?;
+        let mut args = vec![];
+        for original in parts {
+            let template = Template::parse(&original, |name| {
+                Ok(match name.as_bytes() {
+                    b"" | b"path" => Directive::Path,
+                    b"name" => Directive::Name,
+                    b"dir" => Directive::Dir,
+                    _ => anyhow::bail!(
+                        "unrecognized format directive `{{{name}}}`, \
+                         allowed directives are `{{}}`, `{{path}}`, \
+                         `{{name}}` and `{{dir}}`",
+                    ),
+                })
+            })
+            .with_context(|| {
+                format!("invalid command argument `{}`", original.as_bstr())
+            })?;
+            let template = Some(template).filter(|t| t.has_directive());
+            args.push(Arg { original, template });
+        }
+        Ok(CommandParts { program, args: args.into() })
     }
 
     /// Creates a `std::process::Command` from these parts using the file
-    /// path given for interpolation.
+    /// paths given for interpolation.
     ///
-    /// Basically, all instances of `{}` outside of the program name (that
-    /// aren't escaped) are replaced with the given file path. If there are
-    /// no such instances, then the path is added on as the final part to the
-    /// command.
+    /// Basically, all directives outside of the program name are replaced
+    /// with each of the given file paths (or a part of them). That is, an
+    /// argument with directives is repeated once for each file path. If
+    /// there are no directives, then the paths are added on as the final
+    /// parts to the command. Arguments without any directives are passed
+    /// through as-is.
     ///
     /// This generally shouldn't fail, but in theory could, if any of the
     /// parts in the command are not valid UTF-8 on non-Unix environments.
     /// (If that did happen, then CLI parsing should have failed.)
-    fn command(&self, path: &BStr) -> anyhow::Result<Command> {
-        /// Replaces `{}` in `arg` with `path`.
-        ///
-        /// Returns `None` when no replacements are made. When `Some`
-        /// is returned, it is guaranteed that at least one replacement
-        /// was made.
-        fn replace(arg: &BStr, path: &BStr) -> Option<BString> {
-            enum State {
-                Default,
-                Backslash,
-                OpeningBrace,
-            }
-
-            // If there's no `{`, then we definitely have no replacement,
-            // so just bail early. We might still not have a replacement,
-            // e.g., if it's just `{` by itself or `{` is escaped. But this
-            // should be very rare.
-            if arg.find_byte(b'{').is_none() {
-                return None;
-            }
-            let mut state = State::Default;
-            let mut new = vec![];
-            for byte in arg.bytes() {
-                state = match (state, byte) {
-                    (State::Default, b'\\') => State::Backslash,
-                    (State::Default, b'{') => State::OpeningBrace,
-                    (State::Default, _) => {
-                        new.push(byte);
-                        State::Default
-                    }
-                    (State::Backslash, _) => {
-                        new.push(byte);
-                        State::Default
-                    }
-                    (State::OpeningBrace, b'}') => {
-                        new.extend_from_slice(path);
-                        State::Default
-                    }
-                    (State::OpeningBrace, _) => {
-                        new.push(b'{');
-                        new.push(byte);
-                        State::Default
-                    }
-                };
-            }
-            // If we ended up in an invalid state, then we do nothing. I kinda
-            // feel like we should probably report an error here, but it's not
-            // clear if it's worth doing.
-            match state {
-                State::Default => {}
-                State::Backslash | State::OpeningBrace => return None,
-            }
-            // It's possible we didn't ultimately do a replacement.
-            // In which case, we don't want to indicate that we did.
-            if new == arg { None } else { Some(BString::from(new)) }
-        }
-
-        let program = &self.parts[0];
+    fn command(&self, paths: &[&BStr]) -> anyhow::Result<Command> {
+        let program = &self.program;
         let program = program.to_os_str().with_context(|| {
             format!("program binary path {program:?} is not valid UTF-8")
         })?;
         let mut cmd = Command::new(program);
 
         let mut did_replacement = false;
-        for part in self.parts.iter().skip(1) {
-            let part = match replace(part.as_bstr(), path) {
-                None => Cow::Borrowed(part),
-                Some(part) => {
-                    did_replacement = true;
-                    Cow::Owned(part)
-                }
+        for arg in self.args.iter() {
+            let Some(ref template) = arg.template else {
+                let part = arg.original.to_os_str().with_context(|| {
+                    format!(
+                        "argument to command {part:?} is not valid UTF-8",
+                        part = arg.original,
+                    )
+                })?;
+                cmd.arg(part);
+                continue;
             };
-            let part = part.to_os_str().with_context(|| {
-                format!("argument to command {part:?} is not valid UTF-8")
-            })?;
-            cmd.arg(part);
+            did_replacement = true;
+            for &path in paths {
+                let part = interpolate(template, path);
+                let part = part.to_os_str().with_context(|| {
+                    format!("argument to command {part:?} is not valid UTF-8")
+                })?;
+                cmd.arg(part);
+            }
         }
         if !did_replacement {
-            let path = path.to_os_str().with_context(|| {
-                format!("path {path:?} given to command is not valid UTF-8")
-            })?;
-            cmd.arg(path);
+            for &path in paths {
+                let path = path.to_os_str().with_context(|| {
+                    format!(
+                        "path {path:?} given to command is not valid UTF-8"
+                    )
+                })?;
+                cmd.arg(path);
+            }
         }
         Ok(cmd)
     }
 }
 
+/// Interpolates the given file path into the template.
+fn interpolate(template: &Template<Directive>, path: &BStr) -> BString {
+    let mut new = BString::new(vec![]);
+    for item in template.items() {
+        match *item {
+            Item::Literal(ref literal) => new.extend_from_slice(literal),
+            Item::Directive(Directive::Path) => new.extend_from_slice(path),
+            Item::Directive(Directive::Name) => {
+                new.extend_from_slice(split_path(path).1)
+            }
+            Item::Directive(Directive::Dir) => {
+                new.extend_from_slice(split_path(path).0)
+            }
+        }
+    }
+    new
+}
+
+/// Splits a file path into its directory and final component.
+///
+/// Trailing separators are ignored. When there is no directory, `.` is
+/// returned for it, just like `dirname`. When the directory is the root,
+/// then the separator itself is returned.
+fn split_path(path: &BStr) -> (&[u8], &[u8]) {
+    fn is_sep(byte: u8) -> bool {
+        byte == b'/' || (cfg!(windows) && byte == b'\\')
+    }
+
+    let mut trimmed: &[u8] = path;
+    while trimmed.len() > 1 && is_sep(trimmed[trimmed.len() - 1]) {
+        trimmed = &trimmed[..trimmed.len() - 1];
+    }
+    let Some(i) = trimmed.iter().rposition(|&b| is_sep(b)) else {
+        return (b".", trimmed);
+    };
+    let name = &trimmed[i + 1..];
+    let mut dir = &trimmed[..i];
+    while dir.len() > 1 && is_sep(dir[dir.len() - 1]) {
+        dir = &dir[..dir.len() - 1];
+    }
+    if dir.is_empty() {
+        dir = &trimmed[..1];
+    }
+    (dir, name)
+}
+
 #[derive(Debug, Default)]
 struct Config {
+    batch: Option<NonZero<usize>>,
     command_parts: Vec<BString>,
+    tag_stream: TagStream,
     threads: flags::Threads,
 }
 
@@ -240,6 +423,28 @@ impl args::Configurable for Config {
         arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
         match *arg {
+            lexopt::Arg::Long("batch") => {
+                let size: usize = args::parse(p, "--batch")?;
+                let size = NonZero::new(size).context(
+                    "--batch: batch size must be greater than zero",
+                )?;
+                self.batch = Some(size);
+            }
+            lexopt::Arg::Long("stderr-tag") => {
+                let Some(value) = p.optional_value() else {
+                    self.tag_stream = TagStream::Stderr;
+                    return Ok(true);
+                };
+                self.tag_stream = match value.to_str() {
+                    Some("only") => TagStream::Stderr,
+                    Some("merge") => TagStream::Merge,
+                    _ => anyhow::bail!(
+                        "unrecognized --stderr-tag value `{}`, \
+                         expected `only` or `merge`",
+                        value.to_string_lossy(),
+                    ),
+                };
+            }
             lexopt::Arg::Short('j') | lexopt::Arg::Long("threads") => {
                 self.threads = args::parse(p, "-j/--threads")?;
             }
@@ -280,10 +485,21 @@ can be found in your `PATH` environment variable.
             r#"
 An argument to pass to <command>.
 
-If an argument contains `{}`, then it is substituted for a file path,
-regardless of where it appears. To write a literal `{` or `}`, use `\{` or
-`\}`, respectively. When no arguments contain a `{}`, then a file path is added
-as an additional argument.
+An argument may contain any number of the following directives, regardless of
+where they appear:
+
+`{}` or `{path}`: the file path exactly as it was given on stdin.
+
+`{name}`: the final component of the file path. For example, `bar.txt` for
+`foo/bar.txt`.
+
+`{dir}`: the file path without its final component. For example, `foo` for
+`foo/bar.txt`. When the file path has no directory, this is `.`.
+
+To write a literal `{`, `}` or `\`, use `\{`, `\}` or `\\`, respectively. An
+unrecognized directive or an unclosed brace results in an error. Arguments
+without any directives are passed to <command> as-is. When no arguments
+contain a directive, then a file path is added as an additional argument.
 "#,
         );
 
@@ -295,11 +511,65 @@ A file path to interpolate into <command>.
 
 File paths must be passed on stdin in a line delimited format.
 
-If an <arg> contains `{}`, then it is replaced with the file path. Otherwise,
-the file path is added to the end of the command invocation.
+If an <arg> contains a directive like `{}`, then it is replaced with the file
+path (or a part of it). Otherwise, the file path is added to the end of the
+command invocation.
+"#,
+        );
+
+        const BATCH: Usage = Usage::flag(
+            "--batch <size>",
+            "Pass up to this many file paths to each command invocation.",
+            r#"
+Pass up to this many file paths to each command invocation.
+
+By default, <command> is run once for every file path. This can be slow when
+there are many file paths and <command> is fast. With this flag, <command>
+is instead run once for every group of up to <size> file paths, similar to
+`xargs -n`.
+
+When this flag is given, <command> must print exactly one line of output for
+each file path it was given, in the same order as the file paths. Each line
+of output becomes the tag for the corresponding file path. An empty line means
+the file path gets no tag. If the number of lines printed doesn't match the
+number of file paths, then an error is reported.
+
+Arguments containing a directive like `{}` are repeated once for each file
+path in the group. When no arguments contain a directive, then all of the file
+paths in the group are added to the end of the command invocation.
+"#,
+        );
+
+        const STDERR_TAG: Usage = Usage::flag(
+            "--stderr-tag[=<mode>]",
+            "Read tags from what <command> prints to stderr.",
+            r#"
+Read tags from what <command> prints to stderr.
+
+By default, each line <command> prints to stdout becomes a tag, stderr is
+ignored and the file path is the data that gets tagged. This flag is useful
+for tools that print the interesting datetime to stderr while stdout carries
+other data.
+
+By default, or with `--stderr-tag=only`, tags are read only from stderr, and
+what <command> prints to stdout is passed through unchanged as the data that
+gets tagged. So when <command> writes to both streams, stderr always provides
+the tags and stdout always provides the data. If <command> prints nothing to
+stdout, then the file path is the data instead. With `--batch`, stdout must
+either be empty or have exactly one line per file path, and each line is the
+data for the corresponding file path.
+
+With `--stderr-tag=merge`, tags are read from both streams and the file path
+is the data that gets tagged. Since the two streams are captured separately,
+the order in which <command> wrote to them is lost. So the tags from stdout
+always come first, followed by the tags from stderr. This mode cannot be
+combined with `--batch`.
+
+Since the mode is optional, it must be attached with a `=`. A value given as
+a separate argument is interpreted as <command>.
 "#,
         );
 
-        &[COMMAND, ARG, PATH, flags::Threads::USAGE]
+        &[COMMAND, ARG, PATH, BATCH, STDERR_TAG, flags::Threads::USAGE]
     }
 }
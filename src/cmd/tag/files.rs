@@ -1,15 +1,17 @@
 use std::io::Write;
 
-use bstr::ByteSlice;
+use {anyhow::Context, bstr::ByteSlice};
 
 use crate::{
     args::{self, Usage, flags, positional},
+    datetime::{DateTime, DateTimeFlexible},
     extract::ExtractorBuilder,
     parallel::Parallel,
+    parse::BytesExt,
     tag::{Tag, Tagged},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Tag file paths by searching their contents via regex.
 
 By default, this will extract tags in well specified datetime formats.
@@ -45,6 +47,21 @@ EXAMPLES:
             | bttf tag files \
                 -e '<span class="post-meta">(?<tag>\S+ [0-9]{1,2}, [0-9]{4})'
 
+    Multiple patterns may be given, and each may select its own capture group
+    with `--capture`. This tags each file with every date found by either
+    pattern:
+
+        bttf tag files --all \
+            -e 'Date: (?<d>[0-9]{4}-[0-9]{2}-[0-9]{2})' --capture d \
+            -e 'Updated: (?<u>[0-9]{2}/[0-9]{2}/[0-9]{4})' --capture u \
+            *.txt
+
+    Find files containing a datetime from March 2025, tagging each with every
+    such datetime found:
+
+        bttf tag files --all --since 2025-03-01 --until 2025-03-31T23:59:59 \
+            *.log
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -64,6 +81,7 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     )?;
 
     let extractor = extractor.build()?;
+    let window = config.window();
     let mut wtr = std::io::stdout();
     let mut parallel = Parallel::new(
         config.threads.get(),
@@ -71,12 +89,19 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
             // SAFETY: We generally assume the file we're searching is
             // not going to change. Users generally assume responsibility
             // for this and can use --no-mmap if this isn't appropriate.
-            let data = unsafe { mmapper.open(arg.to_path()?)? };
+            let path = arg.to_path()?;
+            let data = unsafe { mmapper.open(path)? };
             let haystack = data.as_bytes();
             let mut tagged =
                 Tagged::new(arg.original_with_line_terminator().into_owned());
             for range in extractor.find_iter(haystack) {
                 let s = haystack[range.clone()].to_str()?.to_string();
+                let in_range = window
+                    .contains(&s)
+                    .with_context(|| format!("{}", path.display()))?;
+                if !in_range {
+                    continue;
+                }
                 // N.B. We explicitly do not attach the range here, because
                 // the range is only meant to be a range into the data in
                 // `Tagged`. But the data here is a file path. This is somewhat
@@ -84,11 +109,17 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
                 // useful. But alas.
                 tagged = tagged.tag(Tag::new(s));
             }
-            Ok(tagged)
+            // When filtering, files without any tags in range are omitted
+            // entirely.
+            if window.is_bounded() && tagged.tags().is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(tagged))
         },
-        move |result: anyhow::Result<Tagged<'static, String>>| {
+        move |result: anyhow::Result<Option<Tagged<'static, String>>>| {
             let tagged = match result {
-                Ok(tagged) => tagged,
+                Ok(None) => return Ok(true),
+                Ok(Some(tagged)) => tagged,
                 Err(err) => {
                     eprintln!("{err:#}");
                     return Ok(true);
@@ -109,6 +140,59 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
 #[derive(Debug, Default)]
 struct Config {
     threads: flags::Threads,
+    since: Option<DateTime>,
+    until: Option<DateTime>,
+}
+
+impl Config {
+    /// Returns the range of datetimes that tags must fall in.
+    fn window(&self) -> Window {
+        Window { since: self.since.clone(), until: self.until.clone() }
+    }
+}
+
+/// An inclusive range of datetimes, possibly unbounded at either end.
+#[derive(Clone, Debug)]
+struct Window {
+    since: Option<DateTime>,
+    until: Option<DateTime>,
+}
+
+impl Window {
+    /// Returns true when at least one end of this range is bounded.
+    fn is_bounded(&self) -> bool {
+        self.since.is_some() || self.until.is_some()
+    }
+
+    /// Returns true when the given tag, parsed as a datetime, falls within
+    /// this range.
+    ///
+    /// When this range is unbounded, this always returns true without
+    /// parsing the tag. Otherwise, this returns an error if the tag isn't a
+    /// valid datetime.
+    fn contains(&self, tag: &str) -> anyhow::Result<bool> {
+        if !self.is_bounded() {
+            return Ok(true);
+        }
+        let dt: DateTime = tag.as_bytes().parse().with_context(|| {
+            format!(
+                "failed to parse tag `{tag}` as a datetime \
+                 for --since/--until",
+            )
+        })?;
+        let ts = dt.get().timestamp();
+        if let Some(ref since) = self.since
+            && ts < since.get().timestamp()
+        {
+            return Ok(false);
+        }
+        if let Some(ref until) = self.until
+            && ts > until.get().timestamp()
+        {
+            return Ok(false);
+        }
+        Ok(true)
+    }
 }
 
 impl args::Configurable for Config {
@@ -121,6 +205,14 @@ impl args::Configurable for Config {
             lexopt::Arg::Short('j') | lexopt::Arg::Long("threads") => {
                 self.threads = args::parse(p, "-j/--threads")?;
             }
+            lexopt::Arg::Long("since") => {
+                let since: DateTimeFlexible = args::parse(p, "--since")?;
+                self.since = Some(since.into());
+            }
+            lexopt::Arg::Long("until") => {
+                let until: DateTimeFlexible = args::parse(p, "--until")?;
+                self.until = Some(until.into());
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -141,6 +233,36 @@ The search executes in "multi-line" mode. Which is to say, it runs as if the
 entire file is contiguously stored on the heap.
 "#,
         );
-        &[flags::Threads::USAGE, PATH]
+
+        const SINCE: Usage = Usage::flag(
+            "--since <datetime>",
+            "Only keep tags at or after this datetime.",
+            r#"
+Only keep tags at or after this datetime.
+
+When given, every tag extracted is parsed as a datetime and only tags at or
+after this datetime (inclusive) are kept. Files without any tags remaining are
+not printed at all. When `--all` isn't given, only the first match in each
+file is considered, so a file is printed only when its first match is in
+range.
+
+Tags must be valid datetimes in a format like RFC 9557, RFC 3339 or RFC 2822.
+This is always the case with `--auto datetime`. When a tag can't be parsed as
+a datetime, an error is reported for its file and searching continues.
+"#,
+        );
+
+        const UNTIL: Usage = Usage::flag(
+            "--until <datetime>",
+            "Only keep tags at or before this datetime.",
+            r#"
+Only keep tags at or before this datetime.
+
+This is like `--since`, except only tags at or before this datetime
+(inclusive) are kept. When both are given, tags must fall within both bounds.
+"#,
+        );
+
+        &[flags::Threads::USAGE, PATH, SINCE, UNTIL]
     }
 }
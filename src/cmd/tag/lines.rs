@@ -1,19 +1,22 @@
-use std::io::Write;
+use std::{borrow::Cow, collections::BTreeSet, io::Write};
 
 use {
     anyhow::Context,
     bstr::ByteSlice,
+    jiff::{SignedDuration, Timestamp, tz::TimeZone},
     lexopt::{Arg, Parser},
 };
 
 use crate::{
     args::{self, Usage, flags},
+    datetime::{DateTime, DateTimeFlexible},
     extract::ExtractorBuilder,
-    parse::BufReadExt,
+    parse::{BufReadExt, BytesExt},
+    span::TimeSpan,
     tag::{Tag, Tagged},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Tag line oriented data.
 
 This command iterates over lines in a single file provided as an argument, or
@@ -22,8 +25,9 @@ formats will be automatically extracted. Currently, this includes RFC 9557,
 RFC 3339, RFC 2822 and RFC 9110 timestamps.
 
 To extract arbitrary tags, use the `-e/--regex` flag to write your own regex.
-Then you can use `bttf time parse` to parse it into an actual point in time
-via strftime-like syntax.
+Then use `-f/--format` to parse each tag into an actual point in time via
+strftime-like syntax. Alternatively, you can pipe the tagged output into
+`bttf time parse`.
 
 USAGE:
     bttf tag lines <path>
@@ -40,6 +44,40 @@ EXAMPLES:
             | bttf time fmt -f '%B %-d, %Y at %H:%M:%S' \
             | bttf untag --substitute
 
+    %snip-start%
+
+    Print only the first line for each distinct datetime in a log file that
+    is already sorted chronologically. This uses a constant amount of memory:
+
+        bttf tag lines --dedupe-adjacent access.log | bttf untag
+
+    Print the first line in each 5 minute window of activity:
+
+        bttf tag lines --dedupe --dedupe-window 5m access.log | bttf untag
+
+    Extract datetimes in a custom format by matching them with a regex and
+    parsing the text captured by the `tag` group:
+
+        $ echo 'GET /index.html [2024/07/20 16.30.55] 200' \
+            | bttf tag lines \
+                -e '\[(?<tag>[0-9]{4}/[0-9]{2}/[0-9]{2} [0-9.]{8})\]' \
+                -f '%Y/%m/%d %H.%M.%S'
+        {"tags":[{"value":"2024-07-20T16:30:55-04:00[America/New_York]","range":[17,36]}],"data":{"text":"GET /index.html [2024/07/20 16.30.55] 200\n"}}
+
+    Only extract RFC 3339 timestamps, ignoring datetimes in other formats
+    that might appear elsewhere in each line:
+
+        bttf tag lines --timestamp-format rfc3339 access.log
+
+    Tag lines without any datetimes with one datetime per minute, starting
+    at midnight, so that they can be processed by other bttf commands:
+
+        $ printf 'foo\nbar\n' \
+            | bttf tag lines --synthetic --start 2025-03-15 --step 1m
+        {"tags":[{"value":"2025-03-15T00:00:00-04:00[America/New_York]","synthetic":true}],"data":{"text":"foo\n"}}
+        {"tags":[{"value":"2025-03-15T00:01:00-04:00[America/New_York]","synthetic":true}],"data":{"text":"bar\n"}}
+
+    %snip-end%
 POSITIONAL ARGUMENTS:
 %args%
 OPTIONS:
@@ -49,16 +87,49 @@ OPTIONS:
 pub fn run(p: &mut Parser) -> anyhow::Result<()> {
     let mut extractor = ExtractorBuilder::default();
     let mut config = Config::default();
-    args::configure(p, USAGE, &mut [&mut extractor, &mut config])?;
+    let mut now = flags::Now;
+    args::configure(p, USAGE, &mut [&mut extractor, &mut config, &mut now])?;
 
     let extractor = extractor.build()?;
+    let now = DateTime::now();
+    let mut dedupe = config.dedupe()?;
+    let synthetic = config.synthetic()?;
     let mut wtr = std::io::stdout().lock();
     let result = config.input.reader()?.for_byte_line(|line| {
         let haystack = line.content();
         let mut tagged = Tagged::new(line.full());
         for range in extractor.find_iter(haystack) {
             let s = haystack[range.clone()].to_str()?;
-            tagged = tagged.tag(Tag::new(s).with_range(range));
+            let value = match config.format {
+                None => Cow::Borrowed(s),
+                // Text that doesn't parse with the format given isn't a
+                // datetime, so it isn't tagged.
+                Some(ref format) => match format.parse(&now, s.into()) {
+                    Ok(dt) => Cow::Owned(dt.to_string()),
+                    Err(err) => {
+                        log::debug!("line {}: {err:#}", line.number());
+                        continue;
+                    }
+                },
+            };
+            tagged = tagged.tag(Tag::new(value).with_range(range));
+        }
+        if let Some(ref mut dedupe) = dedupe
+            && dedupe.is_duplicate(&tagged)?
+        {
+            return Ok(true);
+        }
+        if let Some(ref synthetic) = synthetic
+            && tagged.tags().is_empty()
+        {
+            let dt = synthetic.datetime(line.number()).with_context(|| {
+                format!(
+                    "line {}: failed to synthesize datetime",
+                    line.number()
+                )
+            })?;
+            let tag = Tag::new(Cow::Owned(dt.to_string()));
+            tagged = tagged.tag(tag.with_synthetic(true));
         }
         tagged.write(&mut wtr)?;
         writeln!(wtr)?;
@@ -68,18 +139,162 @@ pub fn run(p: &mut Parser) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Tracks the datetimes seen so far for removing duplicate lines.
+///
+/// The key for each line is the instant of its first tag. Lines without
+/// any tags, or whose first tag isn't a valid datetime, are never considered
+/// duplicates.
+#[derive(Debug)]
+struct Dedupe {
+    adjacent: bool,
+    window: Option<TimeSpan>,
+    /// When `adjacent` is false, this contains the instant of every line
+    /// printed. Otherwise, it contains at most the instant of the previous
+    /// line with a datetime tag.
+    seen: BTreeSet<Timestamp>,
+}
+
+impl Dedupe {
+    /// Returns true if the given line should be dropped because it has
+    /// already been seen.
+    fn is_duplicate(
+        &mut self,
+        tagged: &Tagged<Cow<'_, str>>,
+    ) -> anyhow::Result<bool> {
+        let Some(tag) = tagged.tags().first() else { return Ok(false) };
+        let Ok(dt) = tag.value().as_bytes().parse::<DateTime>() else {
+            return Ok(false);
+        };
+        let window = match self.window {
+            None => SignedDuration::ZERO,
+            Some(ref span) => span.get().to_duration(dt.get())?.abs(),
+        };
+        let ts = dt.get().timestamp();
+        let start = ts.checked_sub(window).unwrap_or(Timestamp::MIN);
+        let end = ts.checked_add(window).unwrap_or(Timestamp::MAX);
+        let duplicate = self.seen.range(start..=end).next().is_some();
+        if self.adjacent {
+            // For the adjacent case, we always compare with the previous
+            // datetime, regardless of whether it was printed or not. This
+            // means at most one instant is ever remembered.
+            self.seen.clear();
+            self.seen.insert(ts);
+        } else if !duplicate {
+            self.seen.insert(ts);
+        }
+        Ok(duplicate)
+    }
+}
+
+/// Generates datetimes for lines that don't have any.
+///
+/// The datetime for each line is derived only from its position in the
+/// input. So two inputs with the same number of lines get the same datetimes.
+#[derive(Debug)]
+struct Synthetic {
+    start: DateTime,
+    step: TimeSpan,
+}
+
+impl Synthetic {
+    /// Returns the synthetic datetime for the line with the given number.
+    ///
+    /// Line numbers start at `1`, which always corresponds to the start
+    /// datetime.
+    fn datetime(&self, line_number: usize) -> anyhow::Result<DateTime> {
+        let index = i64::try_from(line_number.saturating_sub(1))?;
+        let span = self.step.get().checked_mul(index)?;
+        Ok(DateTime::from(self.start.get().checked_add(span)?))
+    }
+}
+
 #[derive(Debug, Default)]
 struct Config {
     input: flags::FileOrStdin,
+    format: Option<flags::Format>,
+    dedupe: bool,
+    dedupe_adjacent: bool,
+    dedupe_window: Option<TimeSpan>,
+    synthetic: bool,
+    synthetic_start: Option<DateTime>,
+    synthetic_step: Option<TimeSpan>,
+}
+
+impl Config {
+    /// Returns a deduplicator if one was requested.
+    fn dedupe(&self) -> anyhow::Result<Option<Dedupe>> {
+        anyhow::ensure!(
+            !(self.dedupe && self.dedupe_adjacent),
+            "--dedupe and --dedupe-adjacent cannot be used together",
+        );
+        if !self.dedupe && !self.dedupe_adjacent {
+            anyhow::ensure!(
+                self.dedupe_window.is_none(),
+                "--dedupe-window requires either --dedupe or \
+                 --dedupe-adjacent",
+            );
+            return Ok(None);
+        }
+        Ok(Some(Dedupe {
+            adjacent: self.dedupe_adjacent,
+            window: self.dedupe_window.clone(),
+            seen: BTreeSet::new(),
+        }))
+    }
+
+    /// Returns a generator of synthetic datetimes if one was requested.
+    fn synthetic(&self) -> anyhow::Result<Option<Synthetic>> {
+        if !self.synthetic {
+            anyhow::ensure!(
+                self.synthetic_start.is_none(),
+                "--start requires --synthetic",
+            );
+            anyhow::ensure!(
+                self.synthetic_step.is_none(),
+                "--step requires --synthetic",
+            );
+            return Ok(None);
+        }
+        let start = self.synthetic_start.clone().unwrap_or_else(|| {
+            DateTime::from(Timestamp::UNIX_EPOCH.to_zoned(TimeZone::UTC))
+        });
+        let step = self
+            .synthetic_step
+            .clone()
+            .unwrap_or_else(|| jiff::Span::new().seconds(1).into());
+        Ok(Some(Synthetic { start, step }))
+    }
 }
 
 impl args::Configurable for Config {
     fn configure(
         &mut self,
-        _: &mut Parser,
+        p: &mut Parser,
         arg: &mut Arg,
     ) -> anyhow::Result<bool> {
         match *arg {
+            Arg::Short('f') | Arg::Long("format") => {
+                self.format = Some(args::parse(p, "-f/--format")?);
+            }
+            Arg::Long("dedupe") => {
+                self.dedupe = true;
+            }
+            Arg::Long("dedupe-adjacent") => {
+                self.dedupe_adjacent = true;
+            }
+            Arg::Long("dedupe-window") => {
+                self.dedupe_window = Some(args::parse(p, "--dedupe-window")?);
+            }
+            Arg::Long("synthetic") => {
+                self.synthetic = true;
+            }
+            Arg::Long("start") => {
+                let start: DateTimeFlexible = args::parse(p, "--start")?;
+                self.synthetic_start = Some(start.into());
+            }
+            Arg::Long("step") => {
+                self.synthetic_step = Some(args::parse(p, "--step")?);
+            }
             Arg::Value(ref mut v) => {
                 self.input.set(std::mem::take(v))?;
             }
@@ -99,6 +314,126 @@ In lieu of a specific file path, users may also pass line delimited data into
 stdin.
 "#,
         );
-        &[PATH]
+        const FORMAT: Usage = Usage::flag(
+            "-f/--format <format>",
+            "Parse each tag as a datetime in this format.",
+            r#"
+Parse each tag as a datetime in this format.
+
+When given, the text of each tag extracted is parsed as a datetime in this
+format, and the tag is replaced with the parsed datetime in the RFC 9557
+format. The range of the tag still refers to the original text in the line.
+Text that fails to parse is not tagged. So lines whose matches all fail to
+parse are printed without any tags.
+
+This is most useful with `-e/--regex` for extracting datetimes in formats
+that bttf doesn't recognize automatically. The format is usually an
+`strftime`-like format string, like `%Y/%m/%d %H.%M.%S`. It may also be one
+of `rfc9557`, `rfc3339`, `rfc2822`, `rfc9110` or `flexible`. See
+`bttf time parse --help` for the supported `strftime` directives. As with
+`bttf time parse`, datetimes parsed without a time zone or offset are put in
+your system's time zone.
+"#,
+        );
+
+        const DEDUPE: Usage = Usage::flag(
+            "--dedupe",
+            "Only print the first line for each distinct datetime.",
+            r#"
+Only print the first line for each distinct datetime.
+
+Lines are compared by the instant in time of their first tag. So for example,
+`2025-03-15T10:00Z` and `2025-03-15T06:00-04` are considered equal. Lines
+without any tags, or whose first tag is not a valid datetime, are always
+printed.
+
+Note that every distinct instant printed is kept in memory for the duration of
+the command. For very large inputs, consider `--dedupe-adjacent` instead,
+which uses a constant amount of memory.
+"#,
+        );
+
+        const DEDUPE_ADJACENT: Usage = Usage::flag(
+            "--dedupe-adjacent",
+            "Only remove lines with the same datetime as the previous line.",
+            r#"
+Only remove lines with the same datetime as the previous line.
+
+This is like `--dedupe`, except only consecutive runs of lines with equal
+datetimes are collapsed into their first line. This only requires remembering
+the datetime of the previous line, and so it uses a constant amount of memory.
+When the input is already sorted chronologically, this is equivalent to
+`--dedupe`.
+"#,
+        );
+
+        const DEDUPE_WINDOW: Usage = Usage::flag(
+            "--dedupe-window <span>",
+            "Treat datetimes within this span of each other as duplicates.",
+            r#"
+Treat datetimes within this span of each other as duplicates.
+
+This requires either `--dedupe` or `--dedupe-adjacent`. With `--dedupe`, a
+line is dropped when its datetime is within this span of the datetime of any
+line printed before it. With `--dedupe-adjacent`, a line is dropped when its
+datetime is within this span of the datetime of the previous line.
+
+When the span contains calendar units, it is interpreted relative to the
+datetime of each line.
+"#,
+        );
+
+        const SYNTHETIC: Usage = Usage::flag(
+            "--synthetic",
+            "Tag lines without a datetime with one derived from line order.",
+            r#"
+Tag lines without a datetime with one derived from line order.
+
+When enabled, each line without any tags is given a single synthetic datetime
+tag. The datetime for the Nth line is `--start` plus `N-1` times `--step`. This
+is computed from the position of the line in the input, regardless of whether
+other lines have tags. This lets other bttf commands operate on lines that lack
+timestamps, and is useful for aligning unrelated streams by position.
+
+Synthetic tags are marked with `"synthetic":true` in the tagged output, and
+don't have a range since they don't correspond to any part of the line. Use
+`bttf untag --drop-synthetic` to ignore them when untagging.
+"#,
+        );
+
+        const START: Usage = Usage::flag(
+            "--start <datetime>",
+            "The synthetic datetime for the first line.",
+            r#"
+The synthetic datetime for the first line.
+
+This requires `--synthetic`. When not given, this defaults to the Unix epoch,
+`1970-01-01T00:00:00Z`, in UTC.
+"#,
+        );
+
+        const STEP: Usage = Usage::flag(
+            "--step <span>",
+            "The span between synthetic datetimes of consecutive lines.",
+            r#"
+The span between synthetic datetimes of consecutive lines.
+
+This requires `--synthetic`. When not given, this defaults to `1s`. When the
+span contains calendar units, it is multiplied by the line index and then
+added to `--start`, so that, e.g., a step of `1mo` always lands on the same
+day of the month (when possible).
+"#,
+        );
+
+        &[
+            PATH,
+            FORMAT,
+            DEDUPE,
+            DEDUPE_ADJACENT,
+            DEDUPE_WINDOW,
+            SYNTHETIC,
+            START,
+            STEP,
+        ]
     }
 }
@@ -0,0 +1,225 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use {
+    anyhow::Context,
+    bstr::BString,
+    lexopt::{Arg, Parser},
+};
+
+use crate::{
+    args::{self, Usage, flags},
+    datetime::DateTime,
+    parse::{BytesExt, Line},
+    tag::MaybeTagged,
+};
+
+const USAGE: &str = r#"
+Merge tagged streams that are already sorted into one sorted stream.
+
+This reads every file given and interleaves their lines into a single stream
+in chronological order. Each file must already be sorted by its tags, e.g., by
+`bttf time sort`. This is the datetime analog of `sort -m`. Since only one line
+from each file is kept in memory at a time, this works on arbitrarily large
+inputs.
+
+Lines are compared in the same way as `bttf time sort`. That is, lines of
+tagged data are compared by their sequence of datetime tags, and datetimes are
+compared by the instant they represent. Lines that compare equal are printed
+in the order of the files given. Every line is printed exactly as it was read.
+
+By default, lines are not checked for being in order. If a file isn't sorted,
+then the output won't be either. Use `--check-sorted` to report an error
+instead.
+
+USAGE:
+    bttf tag merge <path>...
+    bttf tag merge < line delimited data
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Merge the lines of two log files in chronological order:
+
+        bttf tag merge <(bttf tag lines a.log) <(bttf tag lines b.log) \
+            | bttf untag
+
+    %snip-start%
+
+    Fail if any of the inputs aren't sorted:
+
+        bttf tag merge --check-sorted a.tagged b.tagged > merged.tagged
+
+    %snip-end%
+POSITIONAL ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    if config.inputs.is_empty() {
+        config.inputs.push(flags::FileOrStdin::stdin());
+    }
+    let mut sources = vec![];
+    for input in config.inputs.iter() {
+        let mut source = Source::new(input)?;
+        source.advance(config.check_sorted)?;
+        sources.push(source);
+    }
+
+    let mut wtr = std::io::BufWriter::new(std::io::stdout().lock());
+    loop {
+        // The number of inputs is usually small, so a linear scan for the
+        // smallest head is fine. Ties go to the input given first, which
+        // keeps the merge stable.
+        let mut min: Option<(usize, &Record)> = None;
+        for (i, source) in sources.iter().enumerate() {
+            let Some(ref head) = source.head else { continue };
+            if min.is_none_or(|(_, min)| head.key < min.key) {
+                min = Some((i, head));
+            }
+        }
+        let Some((i, head)) = min else { break };
+        wtr.write_all(&head.line)?;
+        wtr.write_all(b"\n")?;
+        sources[i].advance(config.check_sorted)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// A single input to merge, along with its next unmerged line.
+struct Source {
+    input: flags::FileOrStdin,
+    rdr: Box<dyn std::io::BufRead>,
+    /// The line number of `head`.
+    number: usize,
+    /// A buffer for reading lines, reused for each line.
+    buf: Vec<u8>,
+    /// The next line to merge, or `None` when the input is exhausted.
+    head: Option<Record>,
+}
+
+/// A single line read from an input.
+struct Record {
+    key: MaybeTagged<'static, DateTime>,
+    /// The line without its terminator.
+    line: BString,
+}
+
+impl Source {
+    fn new(input: &flags::FileOrStdin) -> anyhow::Result<Source> {
+        Ok(Source {
+            input: input.clone(),
+            rdr: input.reader()?,
+            number: 0,
+            buf: vec![],
+            head: None,
+        })
+    }
+
+    /// Reads the next line from this input into `head`.
+    ///
+    /// When `check_sorted` is true, this returns an error if the next line
+    /// is before the current one.
+    fn advance(&mut self, check_sorted: bool) -> anyhow::Result<()> {
+        self.buf.clear();
+        self.rdr
+            .read_until(b'\n', &mut self.buf)
+            .with_context(|| format!("{}", self.input.display()))?;
+        if self.buf.is_empty() {
+            self.head = None;
+            return Ok(());
+        }
+        self.number += 1;
+        let line = Line::new(self.number, &self.buf);
+        let key = line.content().parse().with_context(|| {
+            format!("{}: line {}", self.input.display(), self.number)
+        })?;
+        let record = Record { key, line: line.content().into() };
+        if check_sorted
+            && let Some(ref prev) = self.head
+            && record.key < prev.key
+        {
+            anyhow::bail!(
+                "{}: line {} is before line {}, but inputs must be sorted",
+                self.input.display(),
+                self.number,
+                self.number - 1,
+            );
+        }
+        self.head = Some(record);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    inputs: Vec<flags::FileOrStdin>,
+    /// Whether stdin was given as an input via `-`.
+    stdin: bool,
+    check_sorted: bool,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        _: &mut Parser,
+        arg: &mut Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            Arg::Long("check-sorted") => {
+                self.check_sorted = true;
+            }
+            Arg::Value(ref mut v) => {
+                let path = PathBuf::from(std::mem::take(v));
+                if path == Path::new("-") {
+                    anyhow::ensure!(
+                        !self.stdin,
+                        "stdin (`-`) can only be given once",
+                    );
+                    self.stdin = true;
+                }
+                self.inputs.push(flags::FileOrStdin::from(path));
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const PATH: Usage = Usage::arg(
+            "<path>...",
+            "One or more file paths to merge.",
+            r#"
+One or more file paths to merge.
+
+Each line of each file must be tagged data or a datetime, and each file must
+already be sorted. When no paths are given, or when a path is `-`, stdin is
+read instead.
+"#,
+        );
+
+        const CHECK_SORTED: Usage = Usage::flag(
+            "--check-sorted",
+            "Report an error if any input isn't sorted.",
+            r#"
+Report an error if any input isn't sorted.
+
+When given, each line is checked against the line before it in the same input.
+If it comes before that line, then an error is reported with the line numbers
+of both lines. Lines that are printed before the error is found are still
+written to stdout. Equal lines are allowed.
+"#,
+        );
+
+        &[PATH, CHECK_SORTED]
+    }
+}
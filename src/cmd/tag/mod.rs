@@ -1,9 +1,10 @@
 mod exec;
 mod files;
 mod lines;
+mod merge;
 mod stat;
 
-const USAGE: &'static str = "\
+const USAGE: &str = "\
 Tag arbitrary data with datetimes.
 
 USAGE:
@@ -13,6 +14,7 @@ COMMANDS:
     exec     Tag files by running arbitrary commands
     files    Tag file paths by running regexes over file contents
     lines    Extract datetimes from lines in a file
+    merge    Merge sorted tagged streams in chronological order
     stat     Extract datetimes from file metadata
 ";
 
@@ -22,6 +24,7 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
         "exec" => exec::run(p),
         "files" => files::run(p),
         "lines" => lines::run(p),
+        "merge" => merge::run(p),
         "stat" => stat::run(p),
         unk => anyhow::bail!("unrecognized command '{}'", unk),
     }
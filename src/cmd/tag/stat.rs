@@ -9,9 +9,9 @@ use crate::{
     tag::{Tag, Tagged},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Tag file paths with datetime metadata. The datetimes come from a file's
-last modified, last accessed or creation time.
+last modified, last accessed, last status change or creation time.
 
 File paths may be provided as positional arguments. Or, if there are no
 positional arguments, then file paths are read from stdin as line delimited
@@ -20,11 +20,24 @@ data.
 Note that it is not guaranteed that any particular metadata selection will
 return correct or even "sensible" values. This largely depends on platform,
 configuration and file system support. bttf just asks for the corresponding
-metadata and uses it as given.
+metadata and uses it as given. When a platform or file system doesn't expose
+the requested metadata at all (which is commonly the case for creation time),
+then bttf reports an error instead of guessing.
+
+Each tag is labeled with the kind of metadata it came from: one of `mtime`,
+`atime`, `ctime` or `btime`. The label can be referenced with `{label}` in
+`bttf untag -f`.
+
+With `--relative`, each file is instead tagged with its age: the span of time
+between the datetime from its metadata and the current time. Ages of files
+from the past are positive, while a file with a datetime in the future has a
+negative age. This makes it possible to filter files by age with commands that
+work on spans.
 
 USAGE:
     bttf tag stat <kinds> <path>...
     bttf tag stat <kinds> < line delimited <path>
+    bttf tag stat --time <kind> ... <path>...
 
 TIP:
     use -h for short docs and --help for long docs
@@ -34,6 +47,20 @@ EXAMPLES:
 
         find ./ | bttf tag stat created
 
+    Show both the last modified and creation time of each file, along with
+    which is which:
+
+        find ./ \
+            | bttf tag stat --time mtime --time btime \
+            | bttf untag -f '{label} {tag} {data}'
+
+    Print the age of each file in a directory tree, based on when it was last
+    modified, in units of days:
+
+        find ./ \
+            | bttf tag stat --relative -l days modified \
+            | bttf untag -f '{tag} {data}'
+
 REQUIRED ARGUMENTS:
 %args%
 OPTIONS:
@@ -43,9 +70,12 @@ OPTIONS:
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
     let mut args = positional::Arguments::default();
-    args::configure(p, USAGE, &mut [&mut config, &mut args])?;
+    let mut now = flags::Now;
+    args::configure(p, USAGE, &mut [&mut config, &mut now, &mut args])?;
 
     let kinds = config.metadata_kinds()?.to_vec();
+    let relative = config.relative()?;
+    let now = DateTime::now();
     let mut wtr = std::io::stdout();
     // It's questionable whether parallelism is that useful
     // here. It does seem to help when multiple datetimes are
@@ -57,13 +87,26 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
             let mut tagged = Tagged::new(data);
             let path = arg.into_path()?;
             for kind in kinds.iter() {
-                let tag = Tag::new(kind.get(&path)?);
+                let tag = Tag::new(kind.get(&path)?).with_label(kind.label());
                 tagged = tagged.tag(tag);
             }
             Ok(tagged.into_owned())
         },
         move |tagged: anyhow::Result<Tagged<DateTime>>| {
-            tagged?.write(&mut wtr)?;
+            let tagged = tagged?;
+            match relative {
+                None => tagged.write(&mut wtr)?,
+                Some(largest) => tagged
+                    .try_map(|dt| {
+                        // Metadata datetimes have an unknown time zone, but
+                        // computing spans with calendar units requires both
+                        // datetimes to be in the same time zone.
+                        let tz = now.get().time_zone().clone();
+                        let dt = DateTime::from(dt.get().with_time_zone(tz));
+                        now.since(largest, jiff::Unit::Nanosecond, &dt)
+                    })?
+                    .write(&mut wtr)?,
+            }
             writeln!(wtr)?;
             Ok(true)
         },
@@ -78,6 +121,7 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
 enum MetadataKind {
     Modified,
     Accessed,
+    Changed,
     Created,
 }
 
@@ -92,9 +136,14 @@ impl MetadataKind {
             MetadataKind::Accessed => {
                 md.accessed().context("failed to get last accessed time")
             }
-            MetadataKind::Created => {
-                md.created().context("failed to get created time")
+            MetadataKind::Changed => {
+                return changed(&md)
+                    .with_context(|| path.display().to_string());
             }
+            MetadataKind::Created => md.created().context(
+                "failed to get creation time (btime), which is not \
+                 available on all platforms and file systems",
+            ),
         };
         let systime = result.with_context(|| path.display().to_string())?;
         let ts = jiff::Timestamp::try_from(systime)
@@ -102,6 +151,37 @@ impl MetadataKind {
         let zdt = ts.to_zoned(jiff::tz::TimeZone::unknown());
         Ok(DateTime::from(zdt))
     }
+
+    /// Returns the name used to label tags with this kind of metadata.
+    fn label(&self) -> &'static str {
+        match *self {
+            MetadataKind::Modified => "mtime",
+            MetadataKind::Accessed => "atime",
+            MetadataKind::Changed => "ctime",
+            MetadataKind::Created => "btime",
+        }
+    }
+}
+
+/// Returns the last status change time from the given file metadata.
+#[cfg(unix)]
+fn changed(md: &std::fs::Metadata) -> anyhow::Result<DateTime> {
+    use std::os::unix::fs::MetadataExt;
+
+    let nanos = i32::try_from(md.ctime_nsec())
+        .context("invalid last status change time (ctime)")?;
+    let ts = jiff::Timestamp::new(md.ctime(), nanos)
+        .context("invalid last status change time (ctime)")?;
+    Ok(DateTime::from(ts.to_zoned(jiff::tz::TimeZone::unknown())))
+}
+
+/// Returns the last status change time from the given file metadata.
+#[cfg(not(unix))]
+fn changed(_: &std::fs::Metadata) -> anyhow::Result<DateTime> {
+    anyhow::bail!(
+        "failed to get last status change time (ctime), \
+         which is only available on Unix platforms",
+    )
 }
 
 impl std::str::FromStr for MetadataKind {
@@ -109,9 +189,10 @@ impl std::str::FromStr for MetadataKind {
 
     fn from_str(s: &str) -> anyhow::Result<MetadataKind> {
         Ok(match s {
-            "modify" | "modified" => MetadataKind::Modified,
-            "access" | "accessed" => MetadataKind::Accessed,
-            "create" | "created" | "creation" | "birth" => {
+            "modify" | "modified" | "mtime" => MetadataKind::Modified,
+            "access" | "accessed" | "atime" => MetadataKind::Accessed,
+            "change" | "changed" | "ctime" => MetadataKind::Changed,
+            "create" | "created" | "creation" | "birth" | "btime" => {
                 MetadataKind::Created
             }
             unk => anyhow::bail!("unknown file metadata kind: `{unk}`"),
@@ -122,17 +203,38 @@ impl std::str::FromStr for MetadataKind {
 #[derive(Debug, Default)]
 struct Config {
     metadata_kinds: Vec<MetadataKind>,
+    times: Vec<MetadataKind>,
     threads: flags::Threads,
+    relative: bool,
+    largest: Option<flags::Unit>,
 }
 
 impl Config {
     fn metadata_kinds(&self) -> anyhow::Result<&[MetadataKind]> {
+        if !self.times.is_empty() {
+            return Ok(&self.times);
+        }
         anyhow::ensure!(
             !self.metadata_kinds.is_empty(),
             "command requires at least one file metadata kind",
         );
         Ok(&self.metadata_kinds)
     }
+
+    /// Returns the largest unit of the ages to tag files with, or `None` if
+    /// files should be tagged with datetimes.
+    fn relative(&self) -> anyhow::Result<Option<jiff::Unit>> {
+        if !self.relative {
+            anyhow::ensure!(
+                self.largest.is_none(),
+                "-l/--largest can only be used with --relative",
+            );
+            return Ok(None);
+        }
+        let largest =
+            self.largest.as_ref().map_or(jiff::Unit::Hour, |u| u.get());
+        Ok(Some(largest))
+    }
 }
 
 impl args::Configurable for Config {
@@ -145,8 +247,23 @@ impl args::Configurable for Config {
             lexopt::Arg::Short('j') | lexopt::Arg::Long("threads") => {
                 self.threads = args::parse(p, "-j/--threads")?;
             }
+            lexopt::Arg::Long("relative") => {
+                self.relative = true;
+            }
+            lexopt::Arg::Short('l') | lexopt::Arg::Long("largest") => {
+                self.largest = Some(args::parse(p, "-l/--largest")?);
+            }
+            lexopt::Arg::Long("time") => {
+                anyhow::ensure!(
+                    self.metadata_kinds.is_empty(),
+                    "--time cannot be used with the <kinds> argument",
+                );
+                self.times.push(args::parse(p, "--time")?);
+            }
             lexopt::Arg::Value(ref mut v) => {
-                if !self.metadata_kinds.is_empty() {
+                // When `--time` is given, all positional arguments are file
+                // paths.
+                if !self.metadata_kinds.is_empty() || !self.times.is_empty() {
                     return Ok(false);
                 }
                 let v = std::mem::take(v)
@@ -169,15 +286,38 @@ impl args::Configurable for Config {
 The kind of metadata to extract. This may be multiple kinds via comma separated
 values of the following:
 
-`modify` or `modified` extracts the last modified datetime of the file.
+`modify`, `modified` or `mtime` extracts the last modified datetime of the
+file.
+
+`access`, `accessed` or `atime` extracts the last accessed datetime of the
+file.
 
-`access` or `accessed` extracts the last accessed datetime of the file.
+`change`, `changed` or `ctime` extracts the datetime that the file's metadata
+was last changed. This is only available on Unix platforms.
 
-`create`, `created`, `creation` or `birth` extracts the datetime that the file
-was created.
+`create`, `created`, `creation`, `birth` or `btime` extracts the datetime that
+the file was created. Not all platforms and file systems support this.
 
 When multiple kinds are requested, then they manifest as multiple tags for
-each file path.
+each file path, in the order given.
+
+This argument is not accepted when `--time` is used.
+"#,
+        );
+
+        const TIME: Usage = Usage::flag(
+            "--time <kind>",
+            "Add a kind of metadata to extract.",
+            r#"
+Add a kind of metadata to extract.
+
+This accepts the same values as <kinds>, but only one per flag. It may be
+repeated to extract multiple kinds of metadata, in which case each file path
+gets one tag per kind in the order given.
+
+When this flag is used, the <kinds> argument must not be given and every
+positional argument is treated as a file path. This flag must therefore come
+before any file paths given as positional arguments.
 "#,
         );
 
@@ -194,6 +334,29 @@ the file path is added to the end of the command invocation.
 "#,
         );
 
-        &[KINDS, PATH, flags::Threads::USAGE]
+        const RELATIVE: Usage = Usage::flag(
+            "--relative",
+            "Tag each file with its age instead of a datetime.",
+            r#"
+Tag each file with its age instead of a datetime.
+
+The age of a file is the span of time from the datetime in its metadata to the
+current time. So a file modified one day ago is tagged with `24h`. Ages are
+positive for datetimes in the past and negative (e.g., `1h ago`) for datetimes
+in the future. The current time may be overridden with `--now`.
+
+By default, ages use units of hours or smaller. Use `-l/--largest` to change
+this.
+"#,
+        );
+
+        &[
+            KINDS,
+            PATH,
+            TIME,
+            RELATIVE,
+            flags::Unit::LARGEST,
+            flags::Threads::USAGE,
+        ]
     }
 }
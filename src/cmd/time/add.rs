@@ -9,7 +9,7 @@ use crate::{
     span::TimeSpan,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Add a span to a datetime.
 
 This command accepts either one span first and then one or more datetimes, or
@@ -72,7 +72,7 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
                 arg.to_span()?.try_map(|span| dt.add(&span))?
             }
             flags::DateTimeOrSpan::TimeSpan(ref span) => {
-                arg.to_datetime()?.try_map(|dt| dt.add(&span))?
+                arg.to_datetime()?.try_map(|dt| dt.add(span))?
             }
         };
         sum.write(&mut wtr)?;
@@ -0,0 +1,333 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    jiff::{SignedDuration, Timestamp, ToSpan, Zoned, civil},
+    lexopt::ValueExt,
+};
+
+use crate::{
+    args::{self, Usage, flags},
+    datetime::{DateTime, DateTimeFlexible},
+    span::TimeSpan,
+    timezone::TimeZone,
+};
+
+const USAGE: &str = r#"
+Find instants when the local time is within a window in every time zone.
+
+This is useful for finding a meeting time that works for participants in
+different time zones. Given one or more time zones and a window of local time
+for each (which defaults to `9-17`), this prints every slot on the date given
+by `--date` during which the local time is inside the window in every time
+zone. A slot is `--step` long (which defaults to one hour), and the slots
+begin at the start of the date in the first time zone given. A slot only
+qualifies when it is entirely inside the window in every time zone.
+
+Each slot is printed as the datetime at which it starts, in the first time
+zone given. If there are no slots that work for every time zone, then an error
+is returned.
+
+Note that a window is always interpreted on the local date of the slot in each
+time zone. So slots on the date given may be on a different date in other time
+zones.
+
+USAGE:
+    bttf time align-zones <time-zone>...
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Find the working hours shared by New York and London:
+
+        $ bttf time align-zones America/New_York Europe/London \
+            --date 2024-07-20
+        2024-07-20T09:00:00-04:00[America/New_York]
+        2024-07-20T10:00:00-04:00[America/New_York]
+        2024-07-20T11:00:00-04:00[America/New_York]
+
+    %snip-start%
+
+    Give each time zone its own window, and use 30 minute slots:
+
+        $ bttf time align-zones America/New_York Asia/Tokyo \
+            --window 17-22 --window 7-9 --step 30m --date 2024-07-20
+        2024-07-20T18:00:00-04:00[America/New_York]
+        2024-07-20T18:30:00-04:00[America/New_York]
+        2024-07-20T19:00:00-04:00[America/New_York]
+        2024-07-20T19:30:00-04:00[America/New_York]
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut now = flags::Now;
+    args::configure(p, USAGE, &mut [&mut config, &mut now])?;
+
+    anyhow::ensure!(
+        !config.zones.is_empty(),
+        "at least one <time-zone> argument is required",
+    );
+    let windows = config.windows()?;
+    let step = config.step()?;
+    let first = config.zones[0].get();
+    let date = match config.date {
+        Some(ref dt) => dt.get().date(),
+        None => DateTime::now().get().with_time_zone(first.clone()).date(),
+    };
+    let start = date.to_zoned(first.clone())?.start_of_day()?.timestamp();
+    let end = date
+        .tomorrow()
+        .and_then(|tomorrow| tomorrow.to_zoned(first.clone()))
+        .and_then(|zdt| zdt.start_of_day())
+        .with_context(|| format!("end of date `{date}` is out of range"))?
+        .timestamp();
+
+    let mut wtr = std::io::stdout().lock();
+    let mut found = false;
+    let mut cur = start;
+    while cur < end {
+        let next = cur
+            .checked_add(step)
+            .with_context(|| format!("slot after `{cur}` is out of range"))?;
+        let fits = config
+            .zones
+            .iter()
+            .zip(windows.iter())
+            .map(|(tz, window)| window.contains(tz, cur, next))
+            .collect::<anyhow::Result<Vec<bool>>>()?;
+        if fits.iter().all(|&fit| fit) {
+            found = true;
+            writeln!(
+                wtr,
+                "{}",
+                DateTime::from(Zoned::new(cur, first.clone()))
+            )?;
+        }
+        cur = next;
+    }
+    anyhow::ensure!(
+        found,
+        "no common slot on `{date}` is within the window of every time zone",
+    );
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    zones: Vec<TimeZone>,
+    windows: Vec<Window>,
+    date: Option<DateTime>,
+    step: Option<TimeSpan>,
+}
+
+impl Config {
+    /// Returns the window for each time zone, in the same order as the time
+    /// zones.
+    ///
+    /// When no windows were given, every time zone gets the default window.
+    /// When one window was given, every time zone gets that window.
+    /// Otherwise, there must be exactly one window for each time zone.
+    fn windows(&self) -> anyhow::Result<Vec<Window>> {
+        match self.windows.len() {
+            0 => Ok(vec![Window::default(); self.zones.len()]),
+            1 => Ok(vec![self.windows[0]; self.zones.len()]),
+            n => {
+                anyhow::ensure!(
+                    n == self.zones.len(),
+                    "got {n} windows for {zones} time zones, but expected \
+                     either one window or exactly one window per time zone",
+                    zones = self.zones.len(),
+                );
+                Ok(self.windows.clone())
+            }
+        }
+    }
+
+    /// Returns the length of each slot as a fixed duration.
+    ///
+    /// This returns an error if the span has calendar units or if it isn't
+    /// positive.
+    fn step(&self) -> anyhow::Result<SignedDuration> {
+        let Some(ref step) = self.step else {
+            return Ok(SignedDuration::from_hours(1));
+        };
+        let dur =
+            SignedDuration::try_from(*step.get()).ok().with_context(|| {
+                format!("step `{step}` must not contain calendar units")
+            })?;
+        anyhow::ensure!(dur.is_positive(), "step `{step}` must be positive");
+        Ok(dur)
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Short('w') | lexopt::Arg::Long("window") => {
+                self.windows.push(args::parse(p, "-w/--window")?);
+            }
+            lexopt::Arg::Long("date") => {
+                let date: DateTimeFlexible = args::parse(p, "--date")?;
+                self.date = Some(date.into());
+            }
+            lexopt::Arg::Long("step") => {
+                self.step = Some(args::parse(p, "--step")?);
+            }
+            lexopt::Arg::Value(ref mut v) => {
+                self.zones.push(v.parse()?);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const TIME_ZONES: Usage = Usage::arg(
+            "<time-zone>...",
+            "One or more time zones, e.g., `America/New_York`.",
+            r#"
+One or more time zones, e.g., `America/New_York`.
+
+The slots are generated for the date given by `--date` in the first time zone,
+and every slot is printed in the first time zone.
+
+bttf accepts IANA time zone identifiers such as `Australia/Sydney`, specific
+offsets such as `+11:00`, POSIX time zone strings such as
+`EST5EDT,M3.2.0,M11.1.0` and the special string `system`.
+"#,
+        );
+
+        const WINDOW: Usage = Usage::flag(
+            "-w/--window <start>-<end>",
+            "The window of acceptable local time, e.g., `9-17`.",
+            r#"
+The window of acceptable local time, e.g., `9-17`.
+
+Each side of the window is an hour from `0` to `24`, optionally followed by
+minutes, e.g., `8:30-16:45`. The start must be before the end. The end is
+exclusive, so `9-17` accepts slots ending at 17:00 but not slots that go past
+it.
+
+When given once, the window applies to every time zone. When given more than
+once, it must be given once for each time zone, and each window applies to
+the time zone in the same position. This defaults to `9-17`.
+"#,
+        );
+
+        const DATE: Usage = Usage::flag(
+            "--date <datetime>",
+            "The date to search for common slots.",
+            r#"
+The date to search for common slots.
+
+Only the civil date of the datetime given is used. The slots cover this date
+in the first time zone given. This defaults to the current date in the first
+time zone.
+"#,
+        );
+
+        const STEP: Usage = Usage::flag(
+            "--step <span>",
+            "The length of each slot.",
+            r#"
+The length of each slot.
+
+The span must be positive and must not contain calendar units (years, months,
+weeks or days). This defaults to `1h`.
+"#,
+        );
+
+        &[TIME_ZONES, WINDOW, DATE, STEP]
+    }
+}
+
+/// A window of acceptable local time within a single day.
+#[derive(Clone, Copy, Debug)]
+struct Window {
+    /// The start of the window, in minutes since midnight.
+    start: i64,
+    /// The end of the window, in minutes since midnight. This may be `1440`
+    /// to indicate the end of the day.
+    end: i64,
+}
+
+impl Window {
+    /// Returns true when the slot from `start` up to `end` is entirely
+    /// inside this window in the given time zone.
+    ///
+    /// The window is interpreted on the local date of `start`.
+    fn contains(
+        &self,
+        tz: &TimeZone,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> anyhow::Result<bool> {
+        let start = start.to_zoned(tz.get().clone()).datetime();
+        let end = end.to_zoned(tz.get().clone()).datetime();
+        let midnight = start.date().to_datetime(civil::Time::midnight());
+        let lower = midnight.checked_add(self.start.minutes())?;
+        let upper = midnight.checked_add(self.end.minutes())?;
+        Ok(lower <= start && end <= upper)
+    }
+}
+
+impl Default for Window {
+    fn default() -> Window {
+        Window { start: 9 * 60, end: 17 * 60 }
+    }
+}
+
+impl std::str::FromStr for Window {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Window> {
+        let (start, end) = s.split_once('-').with_context(|| {
+            format!("window `{s}` must be written as `<start>-<end>`")
+        })?;
+        let start = minutes(start)
+            .with_context(|| format!("invalid start of window `{s}`"))?;
+        let end = minutes(end)
+            .with_context(|| format!("invalid end of window `{s}`"))?;
+        anyhow::ensure!(
+            start < end,
+            "start of window `{s}` must be before its end",
+        );
+        Ok(Window { start, end })
+    }
+}
+
+/// Parses a local time written as `H` or `H:MM` into minutes since midnight.
+///
+/// The hour may be `24` (with no minutes) to indicate the end of the day.
+fn minutes(s: &str) -> anyhow::Result<i64> {
+    let (hour, minute) = s.split_once(':').unwrap_or((s, "0"));
+    let hour: i64 = hour
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid hour `{hour}`"))?;
+    let minute: i64 = minute
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid minute `{minute}`"))?;
+    anyhow::ensure!(
+        (0..60).contains(&minute),
+        "minute `{minute}` must be in the range 0-59",
+    );
+    let total = hour * 60 + minute;
+    anyhow::ensure!(
+        (0..=24).contains(&hour) && total <= 24 * 60,
+        "time `{s}` must be between 0:00 and 24:00",
+    );
+    Ok(total)
+}
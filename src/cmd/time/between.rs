@@ -0,0 +1,241 @@
+use std::io::Write;
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::{DateTime, DateTimeFlexible},
+};
+
+const USAGE: &str = r#"
+Check whether datetimes fall within a range.
+
+This reads datetimes and checks whether each one falls between the datetimes
+given by `--start` and `--end`. By default, nothing is printed and bttf exits
+with an error if any datetime falls outside of the range. With `--print`, the
+result of each check is printed as `true` or `false` instead, and bttf exits
+successfully.
+
+Comparisons are made between the instants of each datetime. That is, the time
+zones of the datetimes are irrelevant, except when parsing a datetime without
+an offset. In that case, the datetime is interpreted in your system time zone.
+
+Both ends of the range are inclusive by default. Use `--exclude-start` or
+`--exclude-end` to make either end exclusive. When either `--start` or `--end`
+is omitted, the range is unbounded at that end.
+
+To filter datetimes by a single reference datetime, use `bttf time cmp`.
+
+USAGE:
+    bttf time between --start <datetime> --end <datetime> <datetime>...
+    bttf time between --start <datetime> --end <datetime> < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Fail if a datetime isn't in March 2025:
+
+        $ bttf time between --start 2025-03-01 --end 2025-04-01 --exclude-end \
+            2025-04-01T00:00:01
+        1 datetime(s) not between 2025-03-01T00:00:00-05:00[America/New_York] (inclusive) and 2025-04-01T00:00:00-04:00[America/New_York] (exclusive)
+
+    %snip-start%
+
+    Print whether each datetime is within the next week:
+
+        $ bttf time between --start now --end 1w --print tomorrow 2w
+        true
+        false
+
+    This also works on tagged data, where each tag is replaced with the
+    result of the check:
+
+        $ bttf tag lines access.log \
+            | bttf time between --start today --end tomorrow --print \
+            | bttf untag -f '{tag} {data}'
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut datetimes = positional::DateTimes::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+
+    anyhow::ensure!(
+        config.start.is_some() || config.end.is_some(),
+        "at least one of --start or --end is required",
+    );
+    let mut wtr = std::io::stdout().lock();
+    let mut outside = 0;
+    datetimes.try_map(|datum| {
+        let results = datum.try_map(|dt| {
+            let contained = config.contains(&dt);
+            if !contained {
+                outside += 1;
+            }
+            Ok(contained)
+        })?;
+        if config.print {
+            results.write(&mut wtr)?;
+            writeln!(wtr)?;
+        }
+        Ok(true)
+    })?;
+    if !config.print && outside > 0 {
+        anyhow::bail!("{outside} datetime(s) not {}", config.describe());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    start: Option<DateTime>,
+    end: Option<DateTime>,
+    exclude_start: bool,
+    exclude_end: bool,
+    print: bool,
+}
+
+impl Config {
+    /// Returns true when the given datetime is within the configured range.
+    fn contains(&self, dt: &DateTime) -> bool {
+        let after_start = match self.start {
+            None => true,
+            Some(ref start) if self.exclude_start => dt > start,
+            Some(ref start) => dt >= start,
+        };
+        let before_end = match self.end {
+            None => true,
+            Some(ref end) if self.exclude_end => dt < end,
+            Some(ref end) => dt <= end,
+        };
+        after_start && before_end
+    }
+
+    /// Returns a human readable description of the configured range.
+    fn describe(&self) -> String {
+        fn bound(dt: &DateTime, exclusive: bool) -> String {
+            let kind = if exclusive { "exclusive" } else { "inclusive" };
+            format!("{dt} ({kind})")
+        }
+
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) => format!(
+                "between {} and {}",
+                bound(start, self.exclude_start),
+                bound(end, self.exclude_end),
+            ),
+            (Some(start), None) => {
+                format!("after {}", bound(start, self.exclude_start))
+            }
+            (None, Some(end)) => {
+                format!("before {}", bound(end, self.exclude_end))
+            }
+            (None, None) => "in range".to_string(),
+        }
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("start") => {
+                let start: DateTimeFlexible = args::parse(p, "--start")?;
+                self.start = Some(start.into());
+            }
+            lexopt::Arg::Long("end") => {
+                let end: DateTimeFlexible = args::parse(p, "--end")?;
+                self.end = Some(end.into());
+            }
+            lexopt::Arg::Long("exclude-start") => {
+                self.exclude_start = true;
+            }
+            lexopt::Arg::Long("exclude-end") => {
+                self.exclude_end = true;
+            }
+            lexopt::Arg::Long("print") => {
+                self.print = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const START: Usage = Usage::flag(
+            "--start <datetime>",
+            "The start of the range.",
+            r#"
+The start of the range.
+
+This is inclusive by default. Use `--exclude-start` to make it exclusive. When
+omitted, the range has no lower bound. At least one of `--start` or `--end`
+must be given.
+"#,
+        );
+
+        const END: Usage = Usage::flag(
+            "--end <datetime>",
+            "The end of the range.",
+            r#"
+The end of the range.
+
+This is inclusive by default. Use `--exclude-end` to make it exclusive. When
+omitted, the range has no upper bound. At least one of `--start` or `--end`
+must be given.
+"#,
+        );
+
+        const EXCLUDE_START: Usage = Usage::flag(
+            "--exclude-start",
+            "Exclude datetimes equal to the start of the range.",
+            r#"
+Exclude datetimes equal to the start of the range.
+
+When given, a datetime must be strictly after `--start` to be in the range.
+"#,
+        );
+
+        const EXCLUDE_END: Usage = Usage::flag(
+            "--exclude-end",
+            "Exclude datetimes equal to the end of the range.",
+            r#"
+Exclude datetimes equal to the end of the range.
+
+When given, a datetime must be strictly before `--end` to be in the range.
+This is useful for half-open ranges, e.g., all datetimes in a particular
+month.
+"#,
+        );
+
+        const PRINT: Usage = Usage::flag(
+            "--print",
+            "Print `true` or `false` for each datetime instead of failing.",
+            r#"
+Print `true` or `false` for each datetime instead of failing.
+
+By default, nothing is printed and bttf exits with an error if any datetime is
+outside of the range. When this flag is given, the result for each datetime is
+printed and bttf exits successfully. For tagged data, each tag is replaced
+with its result.
+"#,
+        );
+
+        &[
+            DateTime::ARG_OR_STDIN,
+            START,
+            END,
+            EXCLUDE_START,
+            EXCLUDE_END,
+            PRINT,
+        ]
+    }
+}
@@ -0,0 +1,266 @@
+use std::{borrow::Cow, io::Write};
+
+use {
+    anyhow::Context,
+    bstr::{BStr, ByteSlice},
+    jiff::{Timestamp, civil, fmt::temporal::Pieces, tz},
+};
+
+use crate::{
+    args::{
+        self, Usage,
+        positional::{self, Argument},
+    },
+    datetime::{DateTime, DateTimeFlexible},
+    parse::{BytesExt, FromBytes},
+    tag::MaybeTagged,
+};
+
+const USAGE: &str = r#"
+Clamp datetimes to the range supported by bttf.
+
+bttf supports datetimes from `-009999-01-02T01:59:59Z` to
+`9999-12-30T22:00:00.999999999Z`. Other bttf commands report an error for
+datetimes outside of this range. This command instead replaces them with the
+nearest supported datetime, which is useful for processing data with sentinel
+"infinite" timestamps like `9999-12-31T23:59:59Z`. The time zone of each
+datetime is preserved. Datetimes within the supported range are printed
+unchanged (aside from normalizing them to RFC 9557 timestamps).
+
+Use `--on-overflow` to report an error or to skip datetimes outside of the
+supported range instead.
+
+This accepts one or more datetimes as positional arguments. When no positional
+arguments are given, then line delimited datetimes are read from stdin.
+
+USAGE:
+    bttf time clamp <datetime>...
+    bttf time clamp < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Clamp a sentinel "end of time" timestamp:
+
+        $ bttf time clamp 9999-12-31T23:59:59Z
+        9999-12-30T22:00:00.999999999Z[Etc/Unknown]
+
+    %snip-start%
+
+    The time zone of the original datetime is preserved:
+
+        $ bttf time clamp '9999-12-31T12:00[Asia/Tokyo]'
+        9999-12-31T07:00:00.999999999+09:00[Asia/Tokyo]
+
+    Drop datetimes outside of the supported range, so that later commands
+    don't fail:
+
+        $ printf '2025-03-15T12:00Z\n+010000-01-01T00:00Z\n' \
+            | bttf time clamp --on-overflow skip
+        2025-03-15T12:00:00Z[Etc/Unknown]
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut arguments = positional::Arguments::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut arguments])?;
+
+    let mut wtr = std::io::stdout().lock();
+    arguments.try_map(|arg| {
+        let datum = match arg {
+            Argument::Positional(ref raw) => {
+                MaybeTagged::Untagged(config.clamp(raw.as_bstr(), true)?)
+            }
+            Argument::StdinLine(_) | Argument::StdinLineBuf(_) => arg
+                .raw()
+                .parse::<MaybeTagged<'static, Cow<'static, BStr>>>()?
+                .try_map(|raw| config.clamp(&raw, false))?,
+        };
+        let datum = match datum {
+            MaybeTagged::Untagged(None) => return Ok(true),
+            MaybeTagged::Untagged(Some(dt)) => MaybeTagged::Untagged(dt),
+            MaybeTagged::Tagged(mut tagged) => {
+                tagged.retain(|dt| dt.is_some());
+                MaybeTagged::Tagged(tagged.map(Option::unwrap))
+            }
+        };
+        datum.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    on_overflow: OnOverflow,
+}
+
+impl Config {
+    /// Parses the given datetime and applies the overflow policy to it.
+    ///
+    /// When `flexible` is true, then relative datetimes like `now` or `-1d`
+    /// are also accepted. This is only appropriate for datetimes given as
+    /// positional arguments.
+    ///
+    /// When the datetime is outside the supported range and the policy is
+    /// to skip it, then `None` is returned.
+    fn clamp(
+        &self,
+        raw: &BStr,
+        flexible: bool,
+    ) -> anyhow::Result<Option<DateTime>> {
+        let parsed = if flexible {
+            raw.parse::<DateTimeFlexible>().map(DateTime::from)
+        } else {
+            raw.parse::<DateTime>()
+        };
+        let err = match parsed {
+            Ok(dt) => return Ok(Some(dt)),
+            Err(err) => err,
+        };
+        let Some(clamped) = clamp_overflow(raw) else {
+            return Err(err).context("invalid datetime");
+        };
+        match self.on_overflow {
+            OnOverflow::Clamp => Ok(Some(clamped)),
+            OnOverflow::Skip => Ok(None),
+            OnOverflow::Error => anyhow::bail!(
+                "datetime `{raw}` is outside the range supported by bttf \
+                 ({min} to {max})",
+                min = Timestamp::MIN,
+                max = Timestamp::MAX,
+            ),
+        }
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("on-overflow") => {
+                self.on_overflow = args::parse_bytes(p, "--on-overflow")?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        &[DateTime::ARG_OR_STDIN, OnOverflow::USAGE]
+    }
+}
+
+/// What to do with a datetime outside the range supported by bttf.
+#[derive(Clone, Copy, Debug, Default)]
+enum OnOverflow {
+    /// Replace it with the nearest supported datetime.
+    #[default]
+    Clamp,
+    /// Report an error.
+    Error,
+    /// Drop it from the output.
+    Skip,
+}
+
+impl OnOverflow {
+    const USAGE: Usage = Usage::flag(
+        "--on-overflow <policy>",
+        "What to do with datetimes outside the supported range.",
+        r#"
+What to do with datetimes outside the supported range.
+
+The default for this flag is `clamp`. The legal values are:
+
+`clamp`: replace the datetime with the earliest or latest supported datetime,
+in the time zone of the original datetime.
+
+`error`: report an error that includes the supported range.
+
+`skip`: drop the datetime. For tagged data, only the tag is dropped and the
+data is still printed.
+
+Inputs that aren't datetimes at all are always reported as errors.
+"#,
+    );
+}
+
+impl FromBytes for OnOverflow {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<OnOverflow> {
+        Ok(match s {
+            b"clamp" => OnOverflow::Clamp,
+            b"error" => OnOverflow::Error,
+            b"skip" => OnOverflow::Skip,
+            unk => anyhow::bail!(
+                "unknown overflow policy `{unk}`",
+                unk = unk.as_bstr()
+            ),
+        })
+    }
+}
+
+/// Returns the nearest supported datetime if the given string is a datetime
+/// outside the supported range.
+///
+/// This only recognizes datetimes in the RFC 9557 or RFC 3339 formats
+/// (including those with an expanded year like `+010000`). If the string
+/// can't be parsed as such a datetime, or if it's a datetime that is within
+/// the supported range, then this returns `None`.
+fn clamp_overflow(raw: &[u8]) -> Option<DateTime> {
+    // Civil dates with years outside of `-9999..=9999` can't be parsed at
+    // all. So we detect an expanded year ourselves and replace it with the
+    // biggest year that can be parsed. We still get the time zone from the
+    // rest of the string this way.
+    let mut rewritten;
+    let mut raw = raw;
+    let mut forced = None;
+    if let [sign @ (b'+' | b'-'), rest @ ..] = raw
+        && let Some(year) = rest.get(..6)
+        && rest.get(6) == Some(&b'-')
+        && year.iter().all(|b| b.is_ascii_digit())
+        && year.to_str().ok()?.parse::<u32>().ok()? > 9999
+    {
+        forced = Some(*sign == b'+');
+        rewritten = raw.to_vec();
+        rewritten[1..7].copy_from_slice(b"009999");
+        raw = &rewritten;
+    }
+
+    let pieces = Pieces::parse(raw).ok()?;
+    let tz = match pieces.to_time_zone().ok()? {
+        Some(tz) => tz,
+        None => match pieces.offset() {
+            None => crate::TZ.clone(),
+            Some(jiff::fmt::temporal::PiecesOffset::Zulu) => {
+                tz::TimeZone::unknown()
+            }
+            Some(jiff::fmt::temporal::PiecesOffset::Numeric(ref off)) => {
+                tz::TimeZone::fixed(off.offset())
+            }
+            Some(_) => return None,
+        },
+    };
+    let time = pieces.time().unwrap_or(civil::Time::midnight());
+    let dt = pieces.date().to_datetime(time);
+    let later = match forced {
+        Some(later) => later,
+        // If the civil datetime is representable in its time zone, then
+        // whatever went wrong wasn't an overflow.
+        None if dt.to_zoned(tz.clone()).is_ok() => return None,
+        None => dt.date().year() > 0,
+    };
+    let ts = if later { Timestamp::MAX } else { Timestamp::MIN };
+    Some(DateTime::from(ts.to_zoned(tz)))
+}
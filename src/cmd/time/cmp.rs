@@ -1,4 +1,4 @@
-use std::io::Write;
+use std::{cmp::Ordering, io::Write};
 
 use anyhow::Context;
 
@@ -6,15 +6,20 @@ use crate::{
     args::{self, Usage, flags::Op, positional},
     datetime::{DateTime, DateTimeFlexible},
     parse::OsStrExt,
+    span::TimeSpan,
     tag::MaybeTagged,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Print only datetimes that satisfy an inequality.
 
 This is useful for filtering datetimes according to whether they are older or
 newer than a reference time.
 
+Datetimes are compared by the instant in time they represent. Use `--within`
+to treat datetimes that are close to the reference time as equal to it, which
+is useful when comparing datetimes from systems with clock skew.
+
 USAGE:
     bttf time cmp <op> <datetime> <datetime>...
     bttf time cmp <op> <datetime> < line delimited <datetime>
@@ -37,6 +42,12 @@ EXAMPLES:
             | bttf time cmp lt 2025-03-10T11:01 \
             | bttf untag -s
 
+    Print only the datetimes within 5 seconds of a reference time:
+
+        $ bttf time cmp eq --within 5s 2025-03-01T12:00:00Z \
+            2025-03-01T11:59:57Z 2025-03-01T12:00:06Z
+        2025-03-01T11:59:57Z[Etc/Unknown]
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -52,14 +63,24 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let op = config.op.context("missing comparison operator")?;
     let base =
         config.base.context("missing initial datetime for comparison")?;
+    let tolerance = match config.within {
+        None => None,
+        Some(ref span) => Some(span.get().to_duration(base.get())?.abs()),
+    };
     let predicate = |dt: &DateTime| -> bool {
+        // Datetimes within the tolerance of the base are considered equal
+        // to it. Otherwise, they are ordered as usual.
+        let within = tolerance.is_some_and(|tolerance| {
+            dt.get().duration_since(base.get()).abs() <= tolerance
+        });
+        let ordering = if within { Ordering::Equal } else { dt.cmp(&base) };
         match op {
-            Op::Eq => dt == &base,
-            Op::Ne => dt != &base,
-            Op::Lt => dt < &base,
-            Op::Gt => dt > &base,
-            Op::Le => dt <= &base,
-            Op::Ge => dt >= &base,
+            Op::Eq => ordering.is_eq(),
+            Op::Ne => ordering.is_ne(),
+            Op::Lt => ordering.is_lt(),
+            Op::Gt => ordering.is_gt(),
+            Op::Le => ordering.is_le(),
+            Op::Ge => ordering.is_ge(),
         }
     };
 
@@ -91,18 +112,22 @@ struct Config {
     op: Option<Op>,
     base: Option<DateTime>,
     all: bool,
+    within: Option<TimeSpan>,
 }
 
 impl args::Configurable for Config {
     fn configure(
         &mut self,
-        _: &mut lexopt::Parser,
+        p: &mut lexopt::Parser,
         arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
         match *arg {
             lexopt::Arg::Long("all") => {
                 self.all = true;
             }
+            lexopt::Arg::Long("within") => {
+                self.within = Some(args::parse(p, "--within")?);
+            }
             lexopt::Arg::Value(ref mut v) => {
                 if self.base.is_some() {
                     return Ok(false);
@@ -140,6 +165,23 @@ positives but should never get false negatives.
 "#,
         );
 
-        &[Op::ARG, DateTime::ARG_OR_STDIN, ALL]
+        const WITHIN: Usage = Usage::flag(
+            "--within <span>",
+            "Treat datetimes within this span of the reference time as equal.",
+            r#"
+Treat datetimes within this span of the reference time as equal.
+
+When given, a datetime is considered equal to the reference time when the
+absolute difference between their instants is less than or equal to this span.
+Otherwise, datetimes are compared as usual. For example, with `--within 5s`,
+`eq` prints datetimes no more than 5 seconds before or after the reference
+time, while `lt` only prints datetimes more than 5 seconds before it.
+
+The sign of the span is ignored. Calendar units, like days, are interpreted
+relative to the reference time.
+"#,
+        );
+
+        &[Op::ARG, DateTime::ARG_OR_STDIN, ALL, WITHIN]
     }
 }
@@ -0,0 +1,415 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    jiff::{Zoned, civil::Weekday},
+    lexopt::ValueExt,
+};
+
+use crate::{
+    args::{self, Usage},
+    datetime::{DateTime, DateTimeFlexible},
+    ical::{Frequency, RecurrenceRule},
+};
+
+const USAGE: &str = r#"
+List upcoming fire times of a cron expression.
+
+This parses a standard 5 field cron expression and prints the datetimes at
+which it fires, in chronological order, starting from the current time. The
+fields are, in order: minute (0-59), hour (0-23), day of the month (1-31),
+month (1-12 or `jan`-`dec`) and day of the week (0-7 or `sun`-`sat`, where
+both 0 and 7 are Sunday).
+
+Each field may be `*` (every value), a single value, a range like `9-17`, or a
+comma separated list of any of those. A step may be added to `*` or a range,
+e.g., `*/15` or `9-17/2`, to select every Nth value in it. A step may also be
+added to a single value, e.g., `5/15`, to select every Nth value from it to
+the end of the field's range.
+
+As with traditional cron implementations, when both the day of the month and
+the day of the week are restricted (that is, neither begins with a `*`), the
+expression fires on days matching *either* field. Otherwise, it fires only on
+days matching both.
+
+The following shorthands are also supported: `@yearly` (or `@annually`),
+`@monthly`, `@weekly`, `@daily` (or `@midnight`) and `@hourly`. Other
+extensions, like `@reboot`, seconds or years fields, `L`, `W` and `#`, are not
+supported.
+
+Fire times are computed in the time zone of the starting datetime, which is
+your system's time zone by default. When a time zone transition skips over a
+fire time (for example, 02:30 on the day daylight saving time begins), it is
+skipped. When a transition repeats a fire time, it is printed once for each
+offset. This is the same behavior as `bttf time seq`.
+
+USAGE:
+    bttf time cron <expression>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the next 3 times a job runs that fires every 15 minutes during
+    business hours on weekdays:
+
+        $ bttf time cron -c 3 '*/15 9-17 * * 1-5'
+        2024-07-22T09:00:00-04:00[America/New_York]
+        2024-07-22T09:15:00-04:00[America/New_York]
+        2024-07-22T09:30:00-04:00[America/New_York]
+
+    %snip-start%
+
+    Print when a nightly job will run over the next few days, as seen from
+    Tokyo:
+
+        $ bttf time cron -c 3 --start '2025-03-15T12:00[Asia/Tokyo]' @daily
+        2025-03-16T00:00:00+09:00[Asia/Tokyo]
+        2025-03-17T00:00:00+09:00[Asia/Tokyo]
+        2025-03-18T00:00:00+09:00[Asia/Tokyo]
+
+    Fire on the 1st and 15th of each month, or on any Friday:
+
+        $ bttf time cron -c 4 --start 2025-03-01 '0 12 1,15 * fri'
+        2025-03-01T12:00:00-05:00[America/New_York]
+        2025-03-07T12:00:00-05:00[America/New_York]
+        2025-03-14T12:00:00-04:00[America/New_York]
+        2025-03-15T12:00:00-04:00[America/New_York]
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let schedule =
+        config.schedule.as_ref().context("missing required <expression>")?;
+    let start = config.start()?;
+    let mut wtr = std::io::stdout().lock();
+    let rules = schedule.recurrence_rules(&start)?;
+    let mut iters: Vec<_> =
+        rules.iter().map(|rule| rule.iter().peekable()).collect();
+    for _ in 0..config.count {
+        // When the day of the month and the day of the week are both
+        // restricted, there are two rules and a day matching either one is
+        // a match. So merge them and drop fire times produced by both.
+        let Some(next) =
+            iters.iter_mut().filter_map(|it| it.peek().cloned()).min()
+        else {
+            break;
+        };
+        for it in iters.iter_mut() {
+            it.next_if_eq(&next);
+        }
+        writeln!(wtr, "{}", DateTime::from(next))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Config {
+    schedule: Option<Schedule>,
+    start: Option<DateTime>,
+    count: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { schedule: None, start: None, count: 10 }
+    }
+}
+
+impl Config {
+    /// Returns the datetime to start looking for fire times from.
+    ///
+    /// Since fire times are always on a minute boundary, fractional seconds
+    /// are rounded up so that they don't leak into the output.
+    fn start(&self) -> anyhow::Result<Zoned> {
+        let start = self.start.clone().unwrap_or_else(DateTime::now);
+        let zdt = start.get();
+        if zdt.subsec_nanosecond() == 0 {
+            return Ok(zdt.clone());
+        }
+        let truncated = zdt.with().subsec_nanosecond(0).build()?;
+        Ok(truncated.checked_add(jiff::SignedDuration::from_secs(1))?)
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Short('c') | lexopt::Arg::Long("count") => {
+                self.count = args::parse(p, "-c/--count")?;
+            }
+            lexopt::Arg::Long("start") => {
+                let start: DateTimeFlexible = args::parse(p, "--start")?;
+                self.start = Some(start.into());
+            }
+            lexopt::Arg::Value(ref mut v) => {
+                if self.schedule.is_some() {
+                    return Ok(false);
+                }
+                let expr = std::mem::take(v)
+                    .string()
+                    .context("cron expression must be valid UTF-8")?;
+                self.schedule = Some(expr.parse()?);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const EXPRESSION: Usage = Usage::arg(
+            "<expression>",
+            "A cron expression, e.g., `*/15 9-17 * * 1-5`.",
+            r#"
+A cron expression, e.g., `*/15 9-17 * * 1-5`.
+
+This must contain exactly 5 fields separated by whitespace: minute, hour, day
+of the month, month and day of the week. Alternatively, this may be one of the
+shorthands `@yearly`, `@annually`, `@monthly`, `@weekly`, `@daily`,
+`@midnight` or `@hourly`.
+"#,
+        );
+
+        const COUNT: Usage = Usage::flag(
+            "-c/--count <number>",
+            "The number of fire times to print.",
+            r#"
+The number of fire times to print.
+
+This defaults to `10`. Zero is a legal value, but always results in no output.
+"#,
+        );
+
+        const START: Usage = Usage::flag(
+            "--start <datetime>",
+            "Print fire times at or after this datetime.",
+            r#"
+Print fire times at or after this datetime.
+
+This defaults to the current time. Fire times are computed in the time zone of
+this datetime.
+"#,
+        );
+
+        &[EXPRESSION, COUNT, START]
+    }
+}
+
+/// A parsed cron expression.
+#[derive(Clone, Debug)]
+struct Schedule {
+    minutes: Vec<i8>,
+    hours: Vec<i8>,
+    month_days: Field<i8>,
+    months: Vec<i8>,
+    week_days: Field<Weekday>,
+}
+
+/// The values of a day field, along with whether the field begins with `*`.
+///
+/// Traditional cron implementations use the latter to decide whether the
+/// day of the month and the day of the week are combined with "or" (when
+/// neither begins with `*`) or "and" (otherwise).
+#[derive(Clone, Debug)]
+struct Field<T> {
+    values: Vec<T>,
+    star: bool,
+}
+
+impl Schedule {
+    /// Returns the recurrence rules that together generate the fire times of
+    /// this schedule, starting at the datetime given.
+    ///
+    /// When more than one rule is returned, then the fire times are the
+    /// union of the datetimes generated by each rule.
+    fn recurrence_rules(
+        &self,
+        start: &Zoned,
+    ) -> anyhow::Result<Vec<RecurrenceRule>> {
+        let rule = |month_days: &[i8], week_days: &[Weekday]| {
+            let mut b =
+                RecurrenceRule::builder(Frequency::Daily, start.clone());
+            for &month in self.months.iter() {
+                b.by_month(month);
+            }
+            for &day in month_days.iter() {
+                b.by_month_day(day);
+            }
+            for &weekday in week_days.iter() {
+                b.by_week_day(weekday);
+            }
+            for &hour in self.hours.iter() {
+                b.by_hour(hour);
+            }
+            for &minute in self.minutes.iter() {
+                b.by_minute(minute);
+            }
+            b.by_second(0);
+            b.build()
+        };
+        let (days, weekdays) = (&self.month_days, &self.week_days);
+        if !days.star && !weekdays.star {
+            return Ok(vec![
+                rule(&days.values, &[])?,
+                rule(&[], &weekdays.values)?,
+            ]);
+        }
+        // When the day of the month is combined with "and," a day of the
+        // month that never occurs in any of the months given would result
+        // in searching forever.
+        let possible = self.months.iter().any(|&month| {
+            let max = match month {
+                2 => 29,
+                4 | 6 | 9 | 11 => 30,
+                _ => 31,
+            };
+            days.values.iter().any(|&day| day <= max)
+        });
+        anyhow::ensure!(
+            possible,
+            "cron expression never fires, since none of its days of the \
+             month occur in any of its months",
+        );
+        Ok(vec![rule(&days.values, &weekdays.values)?])
+    }
+}
+
+impl std::str::FromStr for Schedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Schedule> {
+        let expanded = match s.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            unk if unk.starts_with('@') => anyhow::bail!(
+                "unsupported cron shorthand `{unk}` (supported shorthands \
+                 are @yearly, @annually, @monthly, @weekly, @daily, \
+                 @midnight and @hourly)",
+            ),
+            expr => expr,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let &[minute, hour, month_day, month, week_day] = &*fields else {
+            anyhow::bail!(
+                "invalid cron expression `{s}`: expected 5 fields, \
+                 but found {len}",
+                len = fields.len(),
+            );
+        };
+        let parse = |name: &str, field: &str, min, max, names: &[&str]| {
+            parse_field(field, min, max, names).with_context(|| {
+                format!("invalid {name} field `{field}` in cron expression")
+            })
+        };
+        // Both `0` and `7` are Sunday.
+        let mut week_days: Vec<i8> =
+            parse("day of week", week_day, 0, 7, WEEKDAY_NAMES)?
+                .into_iter()
+                .map(|n| n % 7)
+                .collect();
+        week_days.sort();
+        week_days.dedup();
+        let week_days = week_days
+            .into_iter()
+            .map(Weekday::from_sunday_zero_offset)
+            .collect::<Result<Vec<Weekday>, _>>()?;
+        Ok(Schedule {
+            minutes: parse("minute", minute, 0, 59, &[])?,
+            hours: parse("hour", hour, 0, 23, &[])?,
+            month_days: Field {
+                values: parse("day of month", month_day, 1, 31, &[])?,
+                star: month_day.starts_with('*'),
+            },
+            months: parse("month", month, 1, 12, MONTH_NAMES)?,
+            week_days: Field {
+                values: week_days,
+                star: week_day.starts_with('*'),
+            },
+        })
+    }
+}
+
+/// Names for months, starting with `1` for January.
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct",
+    "nov", "dec",
+];
+
+/// Names for days of the week, starting with `0` for Sunday.
+const WEEKDAY_NAMES: &[&str] =
+    &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Parses a single cron field into a sorted list of distinct values.
+///
+/// Values must be in the range `min..=max`. The names given, if any, are
+/// case insensitive aliases for the values starting at `min`.
+fn parse_field(
+    field: &str,
+    min: i8,
+    max: i8,
+    names: &[&str],
+) -> anyhow::Result<Vec<i8>> {
+    let value = |s: &str| -> anyhow::Result<i8> {
+        if let Some(i) =
+            names.iter().position(|name| name.eq_ignore_ascii_case(s))
+        {
+            // There are never more than 12 names.
+            return Ok(min + i as i8);
+        }
+        let n: i8 =
+            s.parse().with_context(|| format!("invalid value `{s}`"))?;
+        anyhow::ensure!(
+            (min..=max).contains(&n),
+            "value `{n}` is not in the range {min}-{max}",
+        );
+        Ok(n)
+    };
+    let mut values = vec![];
+    for item in field.split(",") {
+        let (range, step) = match item.split_once("/") {
+            None => (item, None),
+            Some((range, step)) => {
+                let step: i8 = step
+                    .parse()
+                    .ok()
+                    .filter(|&step| step > 0)
+                    .with_context(|| format!("invalid step `{step}`"))?;
+                (range, Some(step))
+            }
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once("-") {
+            let (start, end) = (value(start)?, value(end)?);
+            anyhow::ensure!(
+                start <= end,
+                "range `{range}` has a start greater than its end",
+            );
+            (start, end)
+        } else {
+            let start = value(range)?;
+            // A step on a single value means "from this value to the end."
+            (start, if step.is_some() { max } else { start })
+        };
+        let step = usize::try_from(step.unwrap_or(1))?;
+        values.extend((start..=end).step_by(step));
+    }
+    values.sort();
+    values.dedup();
+    Ok(values)
+}
@@ -0,0 +1,213 @@
+use std::io::Write;
+
+use {anyhow::Context, bstr::ByteSlice, jiff::tz::TimeZone};
+
+use crate::{
+    args::{self, Usage, flags, positional},
+    datetime::DateTime,
+    parse::FromBytes,
+};
+
+const USAGE: &str = r#"
+Convert datetimes to and from integer Unix timestamps.
+
+By default, this converts each datetime given to the number of seconds since
+the Unix epoch, `1970-01-01T00:00:00Z`. Other units can be selected with
+`-u/--unit`. Any fractional part is truncated toward zero. This is like
+`bttf time fmt -f %s`, but supports more units than just seconds.
+
+With `--parse`, this does the reverse: each input must be an integer Unix
+timestamp, which is converted to a datetime. Since an integer timestamp has no
+time zone, the datetime printed has an unknown time zone. Use `bttf time in`
+to convert it to a specific time zone.
+
+This accepts one or more inputs as positional arguments. When no positional
+arguments are given, then line delimited inputs are read from stdin. When
+reading from stdin, tagged data is also accepted.
+
+USAGE:
+    bttf time epoch <datetime>...
+    bttf time epoch < line delimited <datetime>
+    bttf time epoch --parse <integer>...
+    bttf time epoch --parse < line delimited <integer>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the current time as a Unix timestamp in milliseconds:
+
+        $ bttf time epoch -u ms now
+        1721507455000
+
+    %snip-start%
+
+    Convert a Unix timestamp in seconds back to a datetime:
+
+        $ bttf time epoch --parse 1721507455
+        2024-07-20T20:30:55Z[Etc/Unknown]
+
+    Convert Unix timestamps of varying units, guessing each unit from the
+    magnitude of the timestamp, and print them in your time zone:
+
+        $ printf '1721507455\n1721507455123\n' \
+            | bttf time epoch --parse -u auto \
+            | bttf time in system
+        2024-07-20T16:30:55-04:00[America/New_York]
+        2024-07-20T16:30:55.123-04:00[America/New_York]
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut arguments = positional::Arguments::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut arguments])?;
+
+    let unit = config.unit.unwrap_or(Unit::Fixed(flags::EpochUnit::Second));
+    let mut wtr = std::io::stdout().lock();
+    if config.parse {
+        return arguments.try_map(|arg| {
+            let datum = arg.to_maybe_tagged()?.try_map(|raw| {
+                unit.to_datetime(&raw).with_context(|| {
+                    format!("invalid epoch timestamp `{raw}`")
+                })
+            })?;
+            datum.write(&mut wtr)?;
+            writeln!(wtr)?;
+            Ok(true)
+        });
+    }
+    let Unit::Fixed(unit) = unit else {
+        anyhow::bail!("-u/--unit auto can only be used with --parse");
+    };
+    arguments.try_map(|arg| {
+        let datum = arg
+            .to_datetime()?
+            .map(|dt| unit.timestamp_to_int(dt.get().timestamp()).to_string());
+        datum.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    parse: bool,
+    unit: Option<Unit>,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("parse") => {
+                self.parse = true;
+            }
+            lexopt::Arg::Short('u') | lexopt::Arg::Long("unit") => {
+                self.unit = Some(args::parse_bytes(p, "-u/--unit")?);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const INPUT: Usage = Usage::arg(
+            "<datetime> | <integer>",
+            "A datetime, or an integer Unix timestamp with `--parse`.",
+            r#"
+A datetime, or an integer Unix timestamp with `--parse`.
+
+Inputs can either be passed as positional arguments or as line delimited data
+on stdin, but not both. When `--parse` isn't given, inputs are datetimes and
+accept the same formats as other bttf commands. For example, `now`,
+`2025-03-15T12:00Z` or `-1d`. When `--parse` is given, inputs must be integers
+in the unit given by `-u/--unit`. Negative integers correspond to datetimes
+before the Unix epoch.
+"#,
+        );
+
+        const PARSE: Usage = Usage::flag(
+            "--parse",
+            "Convert integer Unix timestamps to datetimes.",
+            r#"
+Convert integer Unix timestamps to datetimes.
+
+Instead of converting datetimes to integer Unix timestamps, this converts
+integer Unix timestamps to datetimes. Each datetime printed is in an unknown
+time zone, which is printed with a `Z` offset and an `Etc/Unknown` annotation.
+Timestamps outside of the range of datetimes supported by bttf are rejected.
+"#,
+        );
+
+        &[INPUT, PARSE, Unit::USAGE]
+    }
+}
+
+/// The unit of the integer Unix timestamps to print or parse.
+#[derive(Clone, Copy, Debug)]
+enum Unit {
+    /// Always use this unit.
+    Fixed(flags::EpochUnit),
+    /// Guess the unit of each timestamp from its magnitude.
+    Auto,
+}
+
+impl Unit {
+    const USAGE: Usage = Usage::flag(
+        "-u/--unit <unit>",
+        "The unit of integer Unix timestamps, e.g., `s` or `ms`.",
+        r#"
+The unit of integer Unix timestamps, e.g., `s` or `ms`.
+
+The legal values are `s` (the default), `ms`, `us` and `ns`, corresponding to
+seconds, milliseconds, microseconds and nanoseconds since the Unix epoch,
+respectively.
+
+With `--parse`, the special value `auto` may also be used. This guesses the
+unit of each timestamp from its number of digits, assuming that it refers to
+a datetime within a few thousand years of today. Specifically, timestamps
+with an absolute value less than 10^11 are seconds, less than 10^14 are
+milliseconds, less than 10^17 are microseconds and everything else is
+nanoseconds. Note that this guesses wrong for timestamps close to the Unix
+epoch in units smaller than seconds. For example, `1000` is always interpreted
+as seconds, even if it's intended to be milliseconds.
+"#,
+    );
+
+    /// Converts the given integer Unix timestamp to a datetime.
+    fn to_datetime(self, raw: &[u8]) -> anyhow::Result<DateTime> {
+        let unit = match self {
+            Unit::Fixed(unit) => unit,
+            Unit::Auto => {
+                let number: i64 = raw
+                    .to_str()
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .context("expected an integer")?;
+                flags::EpochUnit::detect(number)
+            }
+        };
+        let ts = unit.to_timestamp(unit.parse(raw)?)?;
+        Ok(DateTime::from(ts.to_zoned(TimeZone::unknown())))
+    }
+}
+
+impl FromBytes for Unit {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<Unit> {
+        if s == b"auto" {
+            return Ok(Unit::Auto);
+        }
+        Ok(Unit::Fixed(flags::EpochUnit::from_bytes(s)?))
+    }
+}
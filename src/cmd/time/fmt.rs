@@ -1,11 +1,17 @@
 use std::io::Write;
 
+use bstr::ByteSlice;
+
 use crate::{
     args::{self, Usage, flags, positional},
-    datetime::DateTime,
+    datetime::{DateTime, DateTimeJson},
+    locale::Calendar,
+    parse::FromBytes,
+    strtime::{self, Token},
+    timezone::TimeZone,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Print a datetime in a particular format.
 
 This accepts one or more datetimes as positional arguments. When no positional
@@ -28,17 +34,80 @@ EXAMPLES:
     Format the current time as an RFC 2822 timestamp using the correct local
     offset for that instant in your time zone:
 
-        $ bttf time fmt -f rfc2822 now
+        $ bttf time fmt --rfc2822 now
 
     Do the same as above, but for one month ago:
 
         $ bttf time fmt -f rfc2822 -1mo
 
+    Format a datetime for use in an HTTP header, which is always in GMT:
+
+        $ bttf time fmt --http-date '2025-03-15T10:23[America/New_York]'
+        Sat, 15 Mar 2025 14:23:00 GMT
+
     Format the first instant of the given date, in your local time, as an
     RFC 9557 timestamp:
 
         $ bttf time fmt -f rfc9557 2025-03-15
 
+    Drop the time zone annotation for systems that don't support it:
+
+        $ bttf time fmt --no-zone-suffix '2025-03-15T10:23[America/New_York]'
+        2025-03-15T10:23:00-04:00
+
+    Print a formatted datetime along with its components as JSON:
+
+        $ bttf time fmt --output json -f '%A' 2024-07-20T16:30:55-04
+        {"formatted":"Saturday","timestamp":"2024-07-20T20:30:55Z","zoned":"2024-07-20T16:30:55-04:00[-04:00]","offset":"-04:00","epoch_seconds":1721507455,"epoch_nanoseconds":1721507455000000000}
+
+    Format the current time as it would appear on a wall clock in Tokyo,
+    without needing to pipe through `bttf time in` first:
+
+        $ bttf time fmt --tz Asia/Tokyo -f '%Y-%m-%d %H:%M %Z' now
+        2025-03-16 01:30 JST
+
+    Print the IANA time zone identifier instead of its abbreviation. When a
+    datetime has only a fixed offset, the offset is printed instead:
+
+        $ bttf time fmt -f '%H:%M %Q' \
+            '2025-03-15T10:23[America/New_York]' 2025-03-15T10:23-04:00
+        10:23 America/New_York
+        10:23 -0400
+
+    Remove padding from all numeric directives at once, instead of writing
+    `%-m/%-d %-H:%M`:
+
+        $ bttf time fmt --pad none -f '%m/%d %H:%M' 2025-03-05T09:05
+        3/5 9:5
+
+    Format datetimes from messy data, printing an empty line for each one
+    that doesn't parse so that output lines up with input. Errors are still
+    printed to stderr:
+
+        $ printf '2025-03-15T10:23-04\nfoo\n2025-03-16T10:23-04\n' \
+            | bttf time fmt --on-error empty -f %F 2>/dev/null
+        2025-03-15
+
+        2025-03-16
+
+    Make it obvious when a datetime has no known time zone, instead of
+    printing a misleading UTC offset:
+
+        $ bttf time fmt --if-unknown-zone '??' -f '%H:%M %Z' 2025-03-05T09:05Z
+        09:05 ??
+
+    Line up the output of variable width directives, like weekday names:
+
+        $ bttf time fmt --align auto -f '%A %-d %B' 2025-03-01 2025-03-12
+        Saturday   1 March
+        Wednesday 12 March
+
+    Write the year in terms of a Japanese era, while keeping the rest of an
+    English locale:
+
+        $ BTTF_LOCALE=en-US bttf time fmt --calendar japanese -f %x 2025-03-15
+        Mar 15, 7 Reiwa
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -48,25 +117,187 @@ OPTIONS:
 
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
-    let mut datetimes = positional::DateTimes::default();
-    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+    let mut args = positional::Arguments::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut args])?;
 
-    let jiff_strtime_config = crate::locale::jiff_strtime_config()?;
+    let tz = config.time_zone()?;
+    let format = config.format()?;
+    let unknown_zone_format = config.unknown_zone_format(&format)?;
+    let utc: TimeZone = "UTC".parse()?;
+    let jiff_strtime_config =
+        crate::locale::jiff_strtime_config_with_calendar(
+            config.calendar.as_ref(),
+        )?;
+    // This returns the datetime that was formatted (which may be in a
+    // different time zone than the one given) along with its formatting.
+    let format_with = |format: &flags::Format,
+                       unknown_zone_format: Option<&flags::Format>,
+                       mut datetime: DateTime| {
+        if let Some(ref tz) = tz {
+            datetime = datetime.in_tz(tz);
+        } else if datetime.get().time_zone().is_unknown() {
+            if let Some(format) = unknown_zone_format {
+                let formatted =
+                    format.format(&jiff_strtime_config, &datetime)?;
+                return Ok((datetime, formatted));
+            }
+            match config.if_unknown_zone {
+                None | Some(IfUnknownZone::Text(_)) => {}
+                Some(IfUnknownZone::Error) => anyhow::bail!(
+                    "datetime `{datetime}` has an unknown time zone, \
+                     which usually means it was parsed from a \
+                     timestamp with a `Z` offset",
+                ),
+                Some(IfUnknownZone::Utc) => {
+                    datetime = datetime.in_tz(&utc);
+                }
+            }
+        }
+        let formatted = format.format(&jiff_strtime_config, &datetime)?;
+        Ok((datetime, formatted))
+    };
     let mut wtr = std::io::stdout().lock();
-    datetimes.try_map(|datum| {
-        let formatted = datum.try_map(|datetime| {
-            config.format.format(&jiff_strtime_config, &datetime)
+    let mut failed = 0;
+    if let Some(align) = config.align {
+        anyhow::ensure!(
+            !config.output.is_json(),
+            "--align cannot be used with --output json",
+        );
+        let columns = Columns::new(&format, align, &config.if_unknown_zone)?;
+        let mut widths = vec![0; columns.fields.len()];
+        let mut rows = vec![];
+        args.try_map(|arg| {
+            let row = arg.to_datetime().and_then(|datum| {
+                datum.try_map(|datetime| {
+                    let mut cells = vec![];
+                    for field in columns.fields.iter() {
+                        let (_, cell) = format_with(
+                            &field.format,
+                            field.unknown_zone_format.as_ref(),
+                            datetime.clone(),
+                        )?;
+                        cells.push(cell);
+                    }
+                    Ok(cells)
+                })
+            });
+            let Some(row) = config.on_error.handle(&arg, row)? else {
+                failed += 1;
+                if let OnError::Empty = config.on_error {
+                    rows.push(None);
+                }
+                return Ok(true);
+            };
+            let row = row.map(|cells| {
+                for (i, cell) in cells.iter().enumerate() {
+                    widths[i] = widths[i].max(cell.chars().count());
+                }
+                cells
+            });
+            rows.push(Some(row));
+            Ok(true)
         })?;
-        formatted.write(&mut wtr)?;
+        for row in rows {
+            if let Some(row) = row {
+                row.map(|cells| columns.join(&cells, &widths))
+                    .write(&mut wtr)?;
+            }
+            writeln!(wtr)?;
+        }
+        config.on_error.summarize(&mut wtr, failed)?;
+        return Ok(());
+    }
+    args.try_map(|arg| {
+        let formatted = arg.to_datetime().and_then(|datum| {
+            datum.try_map(|datetime| {
+                format_with(&format, unknown_zone_format.as_ref(), datetime)
+            })
+        });
+        let Some(formatted) = config.on_error.handle(&arg, formatted)? else {
+            failed += 1;
+            if let OnError::Empty = config.on_error {
+                writeln!(wtr)?;
+            }
+            return Ok(true);
+        };
+        config.output.check(&formatted)?;
+        if config.output.is_json() {
+            formatted
+                .map(|(dt, s)| DateTimeJson::new(dt).formatted(s))
+                .write(&mut wtr)?;
+        } else {
+            formatted.map(|(_, s)| s).write(&mut wtr)?;
+        }
         writeln!(wtr)?;
         Ok(true)
     })?;
+    config.on_error.summarize(&mut wtr, failed)?;
     Ok(())
 }
 
 #[derive(Debug, Default)]
 struct Config {
     format: flags::Format,
+    tz: Option<TimeZone>,
+    utc: bool,
+    pad: Option<Pad>,
+    if_unknown_zone: Option<IfUnknownZone>,
+    align: Option<Align>,
+    calendar: Option<Calendar>,
+    on_error: OnError,
+    output: flags::Output,
+}
+
+impl Config {
+    /// Returns the format to use, taking `--pad` into account.
+    fn format(&self) -> anyhow::Result<flags::Format> {
+        let Some(pad) = self.pad else { return Ok(self.format.clone()) };
+        let flags::Format::Strtime(ref fmt) = self.format else {
+            anyhow::bail!(
+                "--pad can only be used with strftime formats, \
+                 but got {}",
+                self.format,
+            );
+        };
+        Ok(flags::Format::Strtime(pad.rewrite(fmt).into()))
+    }
+
+    /// Returns the format to use for datetimes with an unknown time zone.
+    ///
+    /// This is only `Some` when `--if-unknown-zone` was given literal text.
+    /// In which case, the format returned is the one given with every time
+    /// zone dependent directive replaced by that text.
+    fn unknown_zone_format(
+        &self,
+        format: &flags::Format,
+    ) -> anyhow::Result<Option<flags::Format>> {
+        let Some(IfUnknownZone::Text(ref text)) = self.if_unknown_zone else {
+            return Ok(None);
+        };
+        let flags::Format::Strtime(ref fmt) = *format else {
+            anyhow::bail!(
+                "--if-unknown-zone can only substitute text into strftime \
+                 formats, but got {format}",
+            );
+        };
+        let fmt = IfUnknownZone::substitute(fmt, text);
+        Ok(Some(flags::Format::Strtime(fmt.into())))
+    }
+
+    /// Returns the time zone to convert datetimes into before formatting.
+    ///
+    /// When `None` is returned, datetimes are formatted in whatever time zone
+    /// they already have.
+    fn time_zone(&self) -> anyhow::Result<Option<TimeZone>> {
+        anyhow::ensure!(
+            !(self.utc && self.tz.is_some()),
+            "--tz and --utc cannot be used together",
+        );
+        if self.utc {
+            return Ok(Some("UTC".parse()?));
+        }
+        Ok(self.tz.clone())
+    }
 }
 
 impl args::Configurable for Config {
@@ -79,12 +310,646 @@ impl args::Configurable for Config {
             lexopt::Arg::Short('f') | lexopt::Arg::Long("format") => {
                 self.format = args::parse(p, "-f/--format")?;
             }
+            lexopt::Arg::Long("rfc2822") => {
+                self.format = flags::Format::Rfc2822;
+            }
+            lexopt::Arg::Long("rfc3339" | "no-zone-suffix") => {
+                self.format = flags::Format::Rfc3339;
+            }
+            lexopt::Arg::Long("http-date") => {
+                self.format = flags::Format::Rfc9110;
+            }
+            lexopt::Arg::Long("tz") => {
+                self.tz = Some(args::parse(p, "--tz")?);
+            }
+            lexopt::Arg::Long("utc") => {
+                self.utc = true;
+            }
+            lexopt::Arg::Long("pad") => {
+                self.pad = Some(args::parse_bytes(p, "--pad")?);
+            }
+            lexopt::Arg::Long("if-unknown-zone") => {
+                self.if_unknown_zone =
+                    Some(args::parse_bytes(p, "--if-unknown-zone")?);
+            }
+            lexopt::Arg::Long("output") => {
+                self.output = args::parse(p, "--output")?;
+            }
+            lexopt::Arg::Long("align") => {
+                self.align = Some(args::parse_bytes(p, "--align")?);
+            }
+            lexopt::Arg::Long("calendar") => {
+                self.calendar = Some(args::parse(p, "--calendar")?);
+            }
+            lexopt::Arg::Long("on-error") => {
+                self.on_error = args::parse_bytes(p, "--on-error")?;
+            }
             _ => return Ok(false),
         }
         Ok(true)
     }
 
     fn usage(&self) -> &[Usage] {
-        &[DateTime::ARG_OR_STDIN, flags::Format::USAGE_PRINT]
+        const RFC2822: Usage = Usage::flag(
+            "--rfc2822",
+            "Print datetimes as RFC 2822 timestamps.",
+            r#"
+Print datetimes as RFC 2822 timestamps.
+
+This is equivalent to `-f rfc2822`. For example,
+`Sat, 15 Mar 2025 10:23:00 -0400`. The offset is always written as a signed
+four digit number, so datetimes in UTC are written with `+0000`. Datetimes
+with an unknown time zone (usually from parsing `Z` in RFC 3339 or `-0000` in
+RFC 2822) are written with `-0000`.
+"#,
+        );
+
+        const RFC3339: Usage = Usage::flag(
+            "--rfc3339",
+            "Print datetimes as RFC 3339 timestamps.",
+            r#"
+Print datetimes as RFC 3339 timestamps.
+
+This is equivalent to `-f rfc3339`. For example,
+`2025-03-15T10:23:00-04:00`. Datetimes with an unknown time zone (usually
+because they were parsed from a timestamp with a `Z` offset) are written with
+a `Z` offset.
+"#,
+        );
+
+        const NO_ZONE_SUFFIX: Usage = Usage::flag(
+            "--no-zone-suffix",
+            "Print datetimes without a `[time-zone]` suffix.",
+            r#"
+Print datetimes without a `[time-zone]` suffix.
+
+This is an alias for `--rfc3339`. By default, datetimes are printed as RFC
+9557 timestamps, which end with a time zone annotation, e.g.,
+`2025-03-15T10:23:00-04:00[America/New_York]`. Some systems reject these
+annotations. With this flag, the annotation is dropped and only the instant
+and its numeric offset are printed, e.g., `2025-03-15T10:23:00-04:00`. Since
+the time zone isn't printed, it can't be recovered when parsing the output.
+"#,
+        );
+
+        const HTTP_DATE: Usage = Usage::flag(
+            "--http-date",
+            "Print datetimes as HTTP dates.",
+            r#"
+Print datetimes as HTTP dates.
+
+This is equivalent to `-f rfc9110`. For example,
+`Sat, 15 Mar 2025 14:23:00 GMT`. This is the format used by HTTP headers like
+`Date` and `Last-Modified`, as defined by RFC 9110 (which obsoletes RFC 7231).
+HTTP dates are always written in GMT, regardless of the time zone of the
+datetime or any time zone given by `--tz`.
+"#,
+        );
+
+        const TZ: Usage = Usage::flag(
+            "--tz <time-zone>",
+            "Convert datetimes to this time zone before formatting.",
+            r#"
+Convert datetimes to this time zone before formatting.
+
+This is a convenience for `bttf time in <time-zone> | bttf time fmt`. The
+instant in time represented by each datetime is unchanged, but its civil
+(wall clock) time is computed in the time zone given. For example, this will
+change what is printed by directives like `%H` or `%Z`.
+
+This accepts the same time zone strings as `bttf time in`, including the
+special string `system`.
+
+This cannot be combined with `--utc`.
+"#,
+        );
+
+        const UTC: Usage = Usage::flag(
+            "--utc",
+            "Convert datetimes to UTC before formatting.",
+            r#"
+Convert datetimes to UTC before formatting.
+
+This is equivalent to `--tz UTC`. This cannot be combined with `--tz`.
+"#,
+        );
+
+        const CALENDAR: Usage = Usage::flag(
+            "--calendar <name>",
+            "Use this calendar system for locale dependent directives.",
+            r#"
+Use this calendar system for locale dependent directives.
+
+This overrides the calendar system of the locale set by the `BTTF_LOCALE`
+environment variable, while keeping everything else about that locale. For
+example, with `BTTF_LOCALE=en-US`, using `--calendar japanese` will write
+years in terms of Japanese eras in English. This is equivalent to adding a
+`-u-ca-<name>` extension to the locale.
+
+This only impacts directives that format via the locale: `%c`, `%r`, `%X` and
+`%x`. All other directives, like `%Y` or `%B`, always use the Gregorian
+calendar.
+
+Supported calendars are `gregorian`, `buddhist`, `chinese`, `coptic`, `dangi`,
+`ethioaa`, `ethiopic`, `hebrew`, `indian`, `islamic`, `iso8601`, `japanese`,
+`persian` and `roc`. When bttf is compiled without the `locale` feature, only
+`gregorian` is supported.
+"#,
+        );
+
+        &[
+            DateTime::ARG_OR_STDIN,
+            flags::Format::USAGE_PRINT,
+            RFC2822,
+            RFC3339,
+            NO_ZONE_SUFFIX,
+            HTTP_DATE,
+            TZ,
+            UTC,
+            Pad::USAGE,
+            IfUnknownZone::USAGE,
+            Align::USAGE,
+            CALENDAR,
+            OnError::USAGE,
+            flags::Output::USAGE,
+        ]
+    }
+}
+
+/// The default padding to apply to numeric strftime directives.
+#[derive(Clone, Copy, Debug)]
+enum Pad {
+    None,
+    Zero,
+    Space,
+}
+
+impl Pad {
+    const USAGE: Usage = Usage::flag(
+        "--pad <kind>",
+        "Set the padding for all numeric strftime directives.",
+        r#"
+Set the padding for all numeric strftime directives.
+
+The possible values are `none`, `zero` and `space`. This is equivalent to
+adding the `-`, `0` or `_` flag, respectively, to every numeric directive in
+the strftime format string given to `-f/--format`. For example, with
+`--pad none`, the format `%H:%M` is equivalent to `%-H:%-M`.
+
+This also applies to numeric directives implied by `%D`, `%F`, `%R` and `%T`.
+
+When a directive has its own explicit flag, like `%_d` or `%^b`, then that
+flag takes precedence and the directive is left as is.
+
+This can only be used with strftime format strings.
+"#,
+    );
+
+    /// Returns the flag character corresponding to this padding setting.
+    fn flag(&self) -> char {
+        match *self {
+            Pad::None => '-',
+            Pad::Zero => '0',
+            Pad::Space => '_',
+        }
+    }
+
+    /// Rewrite the given strftime format such that every numeric directive
+    /// without an explicit flag gets this padding setting's flag.
+    fn rewrite(&self, fmt: &str) -> String {
+        // The directives that emit numbers and thus respect padding flags.
+        const NUMERIC: &str = "CdeGgHIjklMmqSUuVWwYy";
+        // Directives that are equivalent to a sequence of numeric directives.
+        // These are expanded so that the flag applies to each of them.
+        const COMPOSITE: &[(char, &str)] = &[
+            ('D', "%m/%d/%y"),
+            ('F', "%Y-%m-%d"),
+            ('R', "%H:%M"),
+            ('T', "%H:%M:%S"),
+        ];
+
+        let mut out = String::with_capacity(fmt.len());
+        for token in strtime::tokens(fmt) {
+            let directive = match token {
+                Token::Literal(literal) => {
+                    out.push_str(literal);
+                    continue;
+                }
+                Token::Percent => {
+                    out.push_str("%%");
+                    continue;
+                }
+                Token::Directive(directive) => directive,
+            };
+            // A directive with an explicit flag, or without a specifier, is
+            // copied through verbatim.
+            let specifier = match directive.specifier {
+                Some(specifier) if !directive.has_flag() => specifier,
+                _ => {
+                    out.push_str(&directive.to_string());
+                    continue;
+                }
+            };
+            let composite = COMPOSITE.iter().find(|&&(d, _)| d == specifier);
+            if let Some(&(_, expansion)) = composite
+                && directive.modifiers.is_empty()
+            {
+                out.push_str(&self.rewrite(expansion));
+            } else if NUMERIC.contains(specifier) {
+                out.push('%');
+                out.push(self.flag());
+                out.push_str(directive.modifiers);
+                out.push(specifier);
+            } else {
+                out.push_str(&directive.to_string());
+            }
+        }
+        out
+    }
+}
+
+impl FromBytes for Pad {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<Pad> {
+        Ok(match s {
+            b"none" => Pad::None,
+            b"zero" => Pad::Zero,
+            b"space" => Pad::Space,
+            unk => anyhow::bail!(
+                "unknown padding option `{unk}`, \
+                 expected one of `none`, `zero` or `space`",
+                unk = unk.as_bstr(),
+            ),
+        })
+    }
+}
+
+/// What to do when a datetime fails to parse or format.
+#[derive(Clone, Copy, Debug, Default)]
+enum OnError {
+    /// Stop and report the error.
+    #[default]
+    Stop,
+    /// Report the error to stderr and print nothing for the datetime.
+    Skip,
+    /// Report the error to stderr and print an empty line for the datetime.
+    Empty,
+}
+
+impl OnError {
+    const USAGE: Usage = Usage::flag(
+        "--on-error <mode>",
+        "What to do when a datetime fails to parse or format.",
+        r#"
+What to do when a datetime fails to parse or format.
+
+The legal values for this flag are:
+
+`stop` (the default): stop and report the error.
+
+`skip`: print the error to stderr and continue on to the next datetime without
+printing anything for the datetime that failed. At the end, a count of every
+datetime that was skipped is printed to stderr.
+
+`empty`: print the error to stderr and print an empty line in place of the
+datetime that failed. This preserves the correspondence between lines of input
+and lines of output, which is useful when pasting output alongside its input.
+
+Errors writing output are never ignored.
+"#,
+    );
+
+    /// Returns the value in the given result, or `None` when the result is an
+    /// error that shouldn't stop execution.
+    ///
+    /// Errors that don't stop execution are printed to stderr.
+    fn handle<T>(
+        &self,
+        arg: &positional::Argument<'_>,
+        result: anyhow::Result<T>,
+    ) -> anyhow::Result<Option<T>> {
+        let err = match result {
+            Ok(value) => return Ok(Some(value)),
+            Err(err) => err,
+        };
+        if let OnError::Stop = *self {
+            return Err(err);
+        }
+        match arg.line_number() {
+            Some(number) => eprintln!("line {number} of <stdin>: {err:#}"),
+            None => eprintln!("{err:#}"),
+        }
+        Ok(None)
+    }
+
+    /// Prints a summary of the datetimes that were skipped to stderr.
+    ///
+    /// The writer given is flushed first, so that the summary comes after
+    /// all output.
+    fn summarize(
+        &self,
+        mut wtr: impl Write,
+        failed: usize,
+    ) -> anyhow::Result<()> {
+        if !matches!(*self, OnError::Skip) || failed == 0 {
+            return Ok(());
+        }
+        wtr.flush()?;
+        eprintln!(
+            "skipped {failed} datetime(s) that failed to parse or format"
+        );
+        Ok(())
+    }
+}
+
+impl FromBytes for OnError {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<OnError> {
+        Ok(match s {
+            b"stop" => OnError::Stop,
+            b"skip" => OnError::Skip,
+            b"empty" => OnError::Empty,
+            unk => anyhow::bail!(
+                "unknown mode `{unk}`, \
+                 expected one of `stop`, `skip` or `empty`",
+                unk = unk.as_bstr(),
+            ),
+        })
+    }
+}
+
+/// What to do when formatting a datetime whose time zone is unknown.
+///
+/// This happens when a datetime is parsed from an RFC 3339 timestamp with a
+/// `Z` offset, which means the offset is known to be UTC but the local offset
+/// isn't known. Jiff uses the special `Etc/Unknown` time zone in this case.
+#[derive(Clone, Debug)]
+enum IfUnknownZone {
+    /// Report an error.
+    Error,
+    /// Format the datetime as if it were in UTC.
+    Utc,
+    /// Replace every time zone dependent directive with this text.
+    Text(Box<str>),
+}
+
+impl IfUnknownZone {
+    const USAGE: Usage = Usage::flag(
+        "--if-unknown-zone <mode>",
+        "What to do with datetimes in the `Etc/Unknown` time zone.",
+        r#"
+What to do with datetimes in the `Etc/Unknown` time zone.
+
+A datetime is in the `Etc/Unknown` time zone when it was parsed from a
+timestamp with a `Z` offset, e.g., `2025-03-15T12:00Z`. This indicates that
+the instant is known, but the local offset is not. By default, such datetimes
+are formatted with a UTC offset, which can be misleading. The legal values for
+this flag are:
+
+`error`: report an error for any datetime in an unknown time zone.
+
+`utc`: format the datetime as if it were in the `UTC` time zone. For example,
+`%Z` is printed as `UTC` and RFC 9557 timestamps get a `[UTC]` annotation.
+
+Any other value is treated as literal text. Every time zone dependent strftime
+directive (that is, `%z`, `%Z` and `%Q` and their variants) is replaced with
+that text. This requires a strftime format. Note that `error` and `utc` are
+always interpreted as the modes above, and never as literal text.
+
+This has no effect when `--tz` or `--utc` is given, since those always convert
+datetimes to a known time zone.
+"#,
+    );
+
+    /// Returns the given strftime format with every time zone dependent
+    /// directive replaced by the given literal text.
+    fn substitute(fmt: &str, text: &str) -> String {
+        // The directives whose output depends on the time zone or offset.
+        const ZONED: &str = "zZQ";
+
+        let text = text.replace('%', "%%");
+        let mut out = String::with_capacity(fmt.len());
+        for token in strtime::tokens(fmt) {
+            match token {
+                Token::Literal(literal) => out.push_str(literal),
+                Token::Percent => out.push_str("%%"),
+                Token::Directive(directive) => match directive.specifier {
+                    Some(ch) if ZONED.contains(ch) => out.push_str(&text),
+                    _ => out.push_str(&directive.to_string()),
+                },
+            }
+        }
+        out
+    }
+}
+
+impl FromBytes for IfUnknownZone {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<IfUnknownZone> {
+        Ok(match s {
+            b"error" => IfUnknownZone::Error,
+            b"utc" => IfUnknownZone::Utc,
+            text => IfUnknownZone::Text(text.to_str()?.into()),
+        })
+    }
+}
+
+/// How to justify the fields of a strftime format when aligning them with
+/// `--align`.
+#[derive(Clone, Copy, Debug)]
+enum Align {
+    /// Right-justify numeric fields and left-justify everything else.
+    Auto,
+    /// Left-justify every field.
+    Left,
+    /// Right-justify every field.
+    Right,
+}
+
+impl Align {
+    const USAGE: Usage = Usage::flag(
+        "--align <mode>",
+        "Pad each field to a common width across all datetimes.",
+        r#"
+Pad each field to a common width across all datetimes.
+
+Some strftime directives, like `%A` or `%-d`, produce output of varying width.
+When this flag is given, the output of each directive in the format string is
+padded with spaces to the width of the widest output for that directive among
+all datetimes given. This makes the output line up in columns. The legal
+values for this flag are:
+
+`auto`: right-justify numeric fields, like `%d` or `%Y`, and left-justify all
+other fields, like `%A` or `%Z`.
+
+`left`: left-justify every field.
+
+`right`: right-justify every field.
+
+Since the width of each field isn't known until every datetime has been
+formatted, this flag causes all input to be read into memory before anything
+is printed. The last field is never padded on the right, so lines don't end
+with trailing whitespace. This can only be used with strftime format strings.
+"#,
+    );
+
+    /// Returns true when a field for the given directive should be
+    /// right-justified.
+    fn is_right(&self, directive: char) -> bool {
+        // The directives that emit numbers. Composite directives like `%F`
+        // are written with fixed width numbers, so they're treated the same.
+        const NUMERIC: &str = "CDdeFfGgHIjklMmNqRSsTUuVWwYy";
+        match *self {
+            Align::Auto => NUMERIC.contains(directive),
+            Align::Left => false,
+            Align::Right => true,
+        }
+    }
+}
+
+impl FromBytes for Align {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<Align> {
+        Ok(match s {
+            b"auto" => Align::Auto,
+            b"left" => Align::Left,
+            b"right" => Align::Right,
+            unk => anyhow::bail!(
+                "unknown alignment mode `{unk}`, \
+                 expected one of `auto`, `left` or `right`",
+                unk = unk.as_bstr(),
+            ),
+        })
+    }
+}
+
+/// A strftime format broken up into its directives, for use with `--align`.
+///
+/// Each directive is formatted on its own so that its output can be padded
+/// independently of the other directives.
+#[derive(Debug)]
+struct Columns {
+    /// The literal text and fields of the format, in order.
+    pieces: Vec<Piece>,
+    /// Every field in the format, in order.
+    fields: Vec<Field>,
+}
+
+/// A piece of a strftime format.
+#[derive(Debug)]
+enum Piece {
+    /// Literal text, printed as is.
+    Literal(String),
+    /// An index into `Columns::fields`.
+    Field(usize),
+}
+
+/// A single directive in a strftime format.
+#[derive(Debug)]
+struct Field {
+    /// A format containing only this directive.
+    format: flags::Format,
+    /// The format to use for datetimes in an unknown time zone, when
+    /// `--if-unknown-zone` was given literal text.
+    unknown_zone_format: Option<flags::Format>,
+    /// Whether to pad this field on the left.
+    right: bool,
+}
+
+impl Columns {
+    fn new(
+        format: &flags::Format,
+        align: Align,
+        if_unknown_zone: &Option<IfUnknownZone>,
+    ) -> anyhow::Result<Columns> {
+        let flags::Format::Strtime(ref fmt) = *format else {
+            anyhow::bail!(
+                "--align can only be used with strftime formats, but got {}",
+                format,
+            );
+        };
+        let mut columns = Columns { pieces: vec![], fields: vec![] };
+        let mut literal = String::new();
+        for token in strtime::tokens(fmt) {
+            let directive = match token {
+                Token::Literal(text) => {
+                    literal.push_str(text);
+                    continue;
+                }
+                Token::Percent => {
+                    literal.push('%');
+                    continue;
+                }
+                Token::Directive(directive) => directive,
+            };
+            if !literal.is_empty() {
+                columns
+                    .pieces
+                    .push(Piece::Literal(std::mem::take(&mut literal)));
+            }
+            // An incomplete directive at the end of the format is kept as
+            // its own field, so that formatting reports an error for it.
+            let right =
+                directive.specifier.is_some_and(|ch| align.is_right(ch));
+            columns.push_field(directive.to_string(), right, if_unknown_zone);
+        }
+        if !literal.is_empty() {
+            columns.pieces.push(Piece::Literal(literal));
+        }
+        Ok(columns)
+    }
+
+    /// Adds a field for the given directive.
+    fn push_field(
+        &mut self,
+        directive: String,
+        right: bool,
+        if_unknown_zone: &Option<IfUnknownZone>,
+    ) {
+        let unknown_zone_format = match *if_unknown_zone {
+            Some(IfUnknownZone::Text(ref text)) => {
+                Some(flags::Format::Strtime(
+                    IfUnknownZone::substitute(&directive, text).into(),
+                ))
+            }
+            _ => None,
+        };
+        self.pieces.push(Piece::Field(self.fields.len()));
+        self.fields.push(Field {
+            format: flags::Format::Strtime(directive.into()),
+            unknown_zone_format,
+            right,
+        });
+    }
+
+    /// Joins the formatted fields of a single datetime, padding each to the
+    /// given width.
+    fn join(&self, cells: &[String], widths: &[usize]) -> String {
+        let mut out = String::new();
+        for (i, piece) in self.pieces.iter().enumerate() {
+            let index = match *piece {
+                Piece::Literal(ref literal) => {
+                    out.push_str(literal);
+                    continue;
+                }
+                Piece::Field(index) => index,
+            };
+            let (cell, field) = (&cells[index], &self.fields[index]);
+            let padding = widths[index] - cell.chars().count();
+            if field.right {
+                out.extend(std::iter::repeat_n(' ', padding));
+                out.push_str(cell);
+            } else {
+                out.push_str(cell);
+                if i + 1 < self.pieces.len() {
+                    out.extend(std::iter::repeat_n(' ', padding));
+                }
+            }
+        }
+        out
     }
 }
@@ -0,0 +1,204 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    bstr::ByteSlice,
+    jiff::{civil, tz::AmbiguousOffset},
+};
+
+use crate::{
+    args::{self, Usage, positional},
+    parse::FromBytes,
+    timezone::TimeZone,
+};
+
+const USAGE: &str = r#"
+List every instant corresponding to a wall clock time in a time zone.
+
+Most wall clock times (i.e., civil datetimes without an offset) correspond to
+exactly one instant in a particular time zone. But around transitions, like
+those for daylight saving time, this isn't always the case:
+
+When clocks are set back (a "fold"), some wall clock times happen twice. In
+this case, both instants are printed, with the earlier one first.
+
+When clocks are set forward (a "gap"), some wall clock times never happen. In
+this case, no instants are printed and an error is reported by default. Use
+`--on-gap skip` to silently print nothing instead.
+
+Each instant is printed as an RFC 9557 timestamp, which includes the offset
+in effect for that instant. This is useful for understanding how bttf
+resolves an ambiguous wall clock time, since bttf usually picks the earlier
+instant in a fold and the later instant in a gap without saying so.
+
+This accepts one or more civil datetimes as positional arguments. When no
+positional arguments are given, then line delimited civil datetimes are read
+from stdin.
+
+USAGE:
+    bttf time fold <civil-datetime>...
+    bttf time fold < line delimited <civil-datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    List both instants for a wall clock time when clocks are set back:
+
+        $ bttf time fold --tz America/New_York 2024-11-03T01:30
+        2024-11-03T01:30:00-04:00[America/New_York]
+        2024-11-03T01:30:00-05:00[America/New_York]
+
+    %snip-start%
+
+    An unambiguous wall clock time has exactly one instant:
+
+        $ bttf time fold --tz America/New_York 2024-11-03T03:00
+        2024-11-03T03:00:00-05:00[America/New_York]
+
+    A wall clock time in a gap has no instants. This is reported as an error
+    unless `--on-gap skip` is given:
+
+        $ bttf time fold --tz America/New_York --on-gap skip 2024-03-10T02:30
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut arguments = positional::Arguments::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut arguments])?;
+
+    let tz = config.tz.unwrap_or_else(TimeZone::system);
+    let mut wtr = std::io::stdout().lock();
+    arguments.try_map(|arg| {
+        let raw = arg.raw();
+        let dt: civil::DateTime = raw
+            .to_str()
+            .ok()
+            .and_then(|raw| raw.parse().ok())
+            .with_context(|| format!("invalid civil datetime `{raw}`"))?;
+        let ambiguous = tz.get().to_ambiguous_zoned(dt);
+        match ambiguous.offset() {
+            AmbiguousOffset::Unambiguous { .. } => {
+                writeln!(wtr, "{}", ambiguous.unambiguous()?)?;
+            }
+            AmbiguousOffset::Fold { .. } => {
+                writeln!(wtr, "{}", ambiguous.clone().earlier()?)?;
+                writeln!(wtr, "{}", ambiguous.later()?)?;
+            }
+            AmbiguousOffset::Gap { before, after } => {
+                if let OnGap::Error = config.on_gap {
+                    anyhow::bail!(
+                        "wall clock time `{dt}` does not exist in time \
+                         zone `{tz}` (it falls in a gap between offsets \
+                         {before} and {after})",
+                    );
+                }
+            }
+        }
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    tz: Option<TimeZone>,
+    on_gap: OnGap,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("tz") => {
+                self.tz = Some(args::parse(p, "--tz")?);
+            }
+            lexopt::Arg::Long("on-gap") => {
+                self.on_gap = args::parse_bytes(p, "--on-gap")?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const CIVIL_DATETIME: Usage = Usage::arg(
+            "<civil-datetime>",
+            "A wall clock time, e.g., `2024-11-03T01:30`.",
+            r#"
+A wall clock time, e.g., `2024-11-03T01:30`.
+
+This is a datetime without an offset or time zone. When only a date is given,
+then midnight is used. Any offset or time zone annotation is ignored, so that
+the output of other bttf commands can be given as input to this command.
+
+Wall clock times can either be passed as positional arguments or as line
+delimited data on stdin, but not both.
+"#,
+        );
+
+        const TZ: Usage = Usage::flag(
+            "--tz <time-zone>",
+            "The time zone to interpret each wall clock time in.",
+            r#"
+The time zone to interpret each wall clock time in.
+
+This defaults to your system's time zone, which may be overridden by the `TZ`
+environment variable.
+"#,
+        );
+
+        &[CIVIL_DATETIME, TZ, OnGap::USAGE]
+    }
+}
+
+/// What to do with a wall clock time that falls in a gap.
+#[derive(Clone, Copy, Debug, Default)]
+enum OnGap {
+    /// Report an error.
+    #[default]
+    Error,
+    /// Print nothing.
+    Skip,
+}
+
+impl OnGap {
+    const USAGE: Usage = Usage::flag(
+        "--on-gap <policy>",
+        "What to do with wall clock times that fall in a gap.",
+        r#"
+What to do with wall clock times that fall in a gap.
+
+The default for this flag is `error`. The legal values are:
+
+`error`: report an error that includes the offsets on either side of the gap.
+This stops processing of any remaining inputs and exits with a non-zero
+status.
+
+`skip`: print nothing for the wall clock time and continue.
+"#,
+    );
+}
+
+impl FromBytes for OnGap {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<OnGap> {
+        Ok(match s {
+            b"error" => OnGap::Error,
+            b"skip" => OnGap::Skip,
+            unk => anyhow::bail!(
+                "unknown gap policy `{unk}`",
+                unk = unk.as_bstr()
+            ),
+        })
+    }
+}
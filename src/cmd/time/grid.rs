@@ -0,0 +1,263 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    jiff::{SignedDuration, SpanRelativeTo, Timestamp, Zoned, tz::TimeZone},
+};
+
+use crate::{
+    args::{self, Usage},
+    datetime::{DateTime, DateTimeFlexible},
+    span::TimeSpan,
+};
+
+use super::nearest::Epoch;
+
+const USAGE: &str = r#"
+Generate the boundaries of evenly spaced buckets covering a range.
+
+The buckets are aligned to a grid made up of every instant that is a multiple
+of `-b/--bucket` away from an origin. By default, the origin is the Unix
+epoch, but it can be set with `--origin`. The first boundary printed is the
+closest instant on the grid at or before `--start`, and the last boundary
+printed is the closest instant on the grid at or after `--end`. That is, the
+buckets always cover the entire range, even when neither end of the range is
+aligned to the grid. Each boundary is printed in the time zone of `--start`.
+
+This differs from `bttf time seq`, which starts its sequence at an arbitrary
+datetime and only ever generates datetimes up to the end of the range. This is
+useful for binning time series data, especially when combined with
+`bttf time nearest -m floor`.
+
+USAGE:
+    bttf time grid --start <datetime> --end <datetime> -b <span>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the boundaries of 15 minute buckets covering a range:
+
+        $ bttf time grid --start 2025-03-05T12:07 --end 2025-03-05T12:40 -b 15m
+        2025-03-05T12:00:00-05:00[America/New_York]
+        2025-03-05T12:15:00-05:00[America/New_York]
+        2025-03-05T12:30:00-05:00[America/New_York]
+        2025-03-05T12:45:00-05:00[America/New_York]
+
+    %snip-start%
+
+    Print each bucket as a tab separated pair of its start and end instead:
+
+        $ bttf time grid --start 2025-03-05T12:07 --end 2025-03-05T12:40 \
+            -b 15m --labels
+        2025-03-05T12:00:00-05:00[America/New_York]	2025-03-05T12:15:00-05:00[America/New_York]
+        2025-03-05T12:15:00-05:00[America/New_York]	2025-03-05T12:30:00-05:00[America/New_York]
+        2025-03-05T12:30:00-05:00[America/New_York]	2025-03-05T12:45:00-05:00[America/New_York]
+
+    Align hourly buckets to 7 minutes past the hour:
+
+        $ bttf time grid --start 2025-03-05T12:00 --end 2025-03-05T13:00 \
+            -b 1h --origin 2025-03-05T00:07
+        2025-03-05T11:07:00-05:00[America/New_York]
+        2025-03-05T12:07:00-05:00[America/New_York]
+        2025-03-05T13:07:00-05:00[America/New_York]
+
+    %snip-end%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let bucket = config.bucket()?;
+    let start = config.start.as_ref().context("--start is required")?;
+    let end = config.end.as_ref().context("--end is required")?;
+    anyhow::ensure!(
+        start.get().timestamp() < end.get().timestamp(),
+        "end of range `{end}` must be after its start `{start}`",
+    );
+    let tz = start.get().time_zone().clone();
+    let first = config.floor(start.get().timestamp(), bucket)?;
+    let mut last = config.floor(end.get().timestamp(), bucket)?;
+    // When the end isn't on the grid, include the partial bucket containing
+    // it so that the entire range is covered.
+    if last != end.get().timestamp() {
+        last = last.checked_add(bucket).with_context(|| {
+            format!("bucket boundary after `{end}` is out of range")
+        })?;
+    }
+
+    let mut wtr = std::io::stdout().lock();
+    let mut cur = first;
+    while cur < last {
+        let next = cur.checked_add(bucket).with_context(|| {
+            format!("bucket boundary after `{cur}` is out of range")
+        })?;
+        if config.labels {
+            writeln!(wtr, "{}\t{}", zoned(cur, &tz), zoned(next, &tz))?;
+        } else {
+            writeln!(wtr, "{}", zoned(cur, &tz))?;
+        }
+        cur = next;
+    }
+    if !config.labels {
+        writeln!(wtr, "{}", zoned(last, &tz))?;
+    }
+    Ok(())
+}
+
+/// Converts a grid boundary to a datetime in the given time zone.
+fn zoned(ts: Timestamp, tz: &TimeZone) -> DateTime {
+    DateTime::from(Zoned::new(ts, tz.clone()))
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    start: Option<DateTime>,
+    end: Option<DateTime>,
+    bucket: Option<TimeSpan>,
+    origin: Timestamp,
+    labels: bool,
+}
+
+impl Config {
+    /// Returns the bucket size as a fixed duration.
+    ///
+    /// This returns an error if no bucket size was given, if it has
+    /// non-uniform units or if it isn't positive. Days are always treated as
+    /// 24 hours.
+    fn bucket(&self) -> anyhow::Result<SignedDuration> {
+        let Some(ref bucket) = self.bucket else {
+            anyhow::bail!("-b/--bucket is required");
+        };
+        anyhow::ensure!(
+            bucket.get().get_years() == 0 && bucket.get().get_months() == 0,
+            "bucket span `{bucket}` must not contain units of months or years",
+        );
+        let dur =
+            bucket.get().to_duration(SpanRelativeTo::days_are_24_hours())?;
+        anyhow::ensure!(
+            dur.is_positive(),
+            "bucket span `{bucket}` must be positive",
+        );
+        Ok(dur)
+    }
+
+    /// Returns the closest instant on the grid at or before the given
+    /// timestamp.
+    ///
+    /// This returns an error if that instant is out of the range supported
+    /// by bttf.
+    fn floor(
+        &self,
+        ts: Timestamp,
+        bucket: SignedDuration,
+    ) -> anyhow::Result<Timestamp> {
+        let offset = ts.as_nanosecond() - self.origin.as_nanosecond();
+        let floor = offset - offset.rem_euclid(bucket.as_nanos());
+        Timestamp::from_nanosecond(self.origin.as_nanosecond() + floor)
+            .with_context(|| {
+                format!("bucket boundary before `{ts}` is out of range")
+            })
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("start") => {
+                let start: DateTimeFlexible = args::parse(p, "--start")?;
+                self.start = Some(start.into());
+            }
+            lexopt::Arg::Long("end") => {
+                let end: DateTimeFlexible = args::parse(p, "--end")?;
+                self.end = Some(end.into());
+            }
+            lexopt::Arg::Short('b') | lexopt::Arg::Long("bucket") => {
+                self.bucket = Some(args::parse(p, "-b/--bucket")?);
+            }
+            lexopt::Arg::Long("origin") => {
+                let origin: Epoch = args::parse_bytes(p, "--origin")?;
+                self.origin = origin.0;
+            }
+            lexopt::Arg::Long("labels") => {
+                self.labels = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const START: Usage = Usage::flag(
+            "--start <datetime>",
+            "The start of the range to cover.",
+            r#"
+The start of the range to cover.
+
+This flag is required. The first boundary printed is the closest instant on
+the grid at or before this datetime. All boundaries are printed in the time
+zone of this datetime.
+"#,
+        );
+
+        const END: Usage = Usage::flag(
+            "--end <datetime>",
+            "The end of the range to cover.",
+            r#"
+The end of the range to cover.
+
+This flag is required and must be after `--start`. The last boundary printed
+is the closest instant on the grid at or after this datetime.
+"#,
+        );
+
+        const BUCKET: Usage = Usage::flag(
+            "-b/--bucket <span>",
+            "The size of each bucket.",
+            r#"
+The size of each bucket.
+
+This flag is required. The span must be positive and must not contain units
+of months or years, since those don't have a fixed length. Weeks and days are
+always treated as 7 days and 24 hours, respectively.
+"#,
+        );
+
+        const ORIGIN: Usage = Usage::flag(
+            "--origin <datetime>",
+            "The origin of the grid that buckets are aligned to.",
+            r#"
+The origin of the grid that buckets are aligned to.
+
+Every bucket boundary is a multiple of `-b/--bucket` away from this datetime.
+It may be given as an integer number of seconds since the Unix epoch or as any
+datetime accepted elsewhere by bttf. The origin doesn't need to precede the
+range.
+
+This defaults to the Unix epoch, `1970-01-01T00:00:00Z`.
+"#,
+        );
+
+        const LABELS: Usage = Usage::flag(
+            "--labels",
+            "Print each bucket as a pair of its start and end.",
+            r#"
+Print each bucket as a pair of its start and end.
+
+Instead of printing each boundary on its own line, this prints one line per
+bucket containing the start and end of the bucket, separated by a tab. The
+start of each bucket is inclusive while its end is exclusive. This format is
+accepted by `bttf time overlap`.
+"#,
+        );
+
+        &[START, END, BUCKET, ORIGIN, LABELS]
+    }
+}
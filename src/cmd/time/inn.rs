@@ -4,23 +4,30 @@ use anyhow::Context;
 
 use crate::{
     args::{self, Usage, flags, positional},
-    datetime::DateTime,
+    datetime::{DateTime, DateTimeJson},
     parse::OsStrExt,
+    tag::MaybeTagged,
     timezone::TimeZone,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Convert a datetime to be in a particular time zone.
 
 This command accepts either one time zone first and then one or more datetimes
 to convert into that time zone, or one datetime first and then one or more time
 zones to convert that datetime into.
 
+Alternatively, time zones can be given with the `-z/--zone` flag, which may be
+repeated. In this case, every positional argument (or line on stdin) is a
+datetime, and each one is converted into every time zone given.
+
 USAGE:
     bttf time in <time-zone> <datetime>...
     bttf time in <time-zone> < line delimited <datetime>
     bttf time in <datetime> <time-zone>...
     bttf time in <datatime> < line delimited <time-zone>
+    bttf time in -z <time-zone> ... <datetime>...
+    bttf time in -z <time-zone> ... < line delimited <datetime>
 
 TIP:
     use -h for short docs and --help for long docs
@@ -73,6 +80,21 @@ EXAMPLES:
         2025-02-28T12:37:50.029853159-05:00[America/New_York]
         2024-03-29T12:37:50.029853159-04:00[America/New_York]
 
+    Print several datetimes in several time zones, with one line per time
+    zone for each datetime:
+
+        $ bttf time in -z America/New_York -z Asia/Tokyo 2025-03-01T12Z 2025-06-01T12Z
+        2025-03-01T07:00:00-05:00[America/New_York]
+        2025-03-01T21:00:00+09:00[Asia/Tokyo]
+        2025-06-01T08:00:00-04:00[America/New_York]
+        2025-06-01T21:00:00+09:00[Asia/Tokyo]
+
+    Print a datetime in several time zones as JSON, one object per line:
+
+        $ bttf time in --output json 2024-07-20T20:30:55Z Asia/Tokyo UTC
+        {"timestamp":"2024-07-20T20:30:55Z","zoned":"2024-07-21T05:30:55+09:00[Asia/Tokyo]","offset":"+09:00","epoch_seconds":1721507455,"epoch_nanoseconds":1721507455000000000}
+        {"timestamp":"2024-07-20T20:30:55Z","zoned":"2024-07-20T20:30:55+00:00[UTC]","offset":"+00:00","epoch_seconds":1721507455,"epoch_nanoseconds":1721507455000000000}
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -85,6 +107,9 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut args = positional::Arguments::default();
     args::configure(p, USAGE, &mut [&mut config, &mut args])?;
 
+    if !config.zones.is_empty() {
+        return run_zones(&config, args);
+    }
     let datetime_or_tz = config
         .datetime_or_tz
         .as_ref()
@@ -99,24 +124,84 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
                 arg.to_datetime()?.try_map(|dt| Ok(dt.in_tz(tz)))?
             }
         };
-        sum.write(&mut wtr)?;
+        config.output.check(&sum)?;
+        if config.output.is_json() {
+            sum.map(DateTimeJson::new).write(&mut wtr)?;
+        } else {
+            sum.write(&mut wtr)?;
+        }
         writeln!(wtr)?;
         Ok(true)
     })
 }
 
+/// Converts every datetime given into every time zone given by `-z/--zone`.
+///
+/// For each datetime, one line is written for each time zone, in the order
+/// in which the time zones were given.
+fn run_zones(
+    config: &Config,
+    args: positional::Arguments,
+) -> anyhow::Result<()> {
+    let mut wtr = std::io::stdout().lock();
+    let mut write = |dt: MaybeTagged<'_, DateTime>| -> anyhow::Result<()> {
+        config.output.check(&dt)?;
+        for tz in config.zones.iter() {
+            let dt = dt.clone().map(|dt| dt.in_tz(tz));
+            if config.output.is_json() {
+                dt.map(DateTimeJson::new).write(&mut wtr)?;
+            } else {
+                dt.write(&mut wtr)?;
+            }
+            writeln!(wtr)?;
+        }
+        Ok(())
+    };
+    // Since `-z/--zone` was given, the first positional argument (which is
+    // normally either a datetime or a time zone) must be a datetime.
+    match config.datetime_or_tz {
+        None => {}
+        Some(flags::DateTimeOrTimeZone::DateTime(ref dt)) => {
+            write(MaybeTagged::Untagged(dt.clone()))?;
+            // Don't fall back to reading stdin when the only datetime was
+            // the first positional argument.
+            if args.is_empty() {
+                return Ok(());
+            }
+        }
+        Some(flags::DateTimeOrTimeZone::TimeZone(ref tz)) => {
+            anyhow::bail!(
+                "when -z/--zone is given, all positional arguments must be \
+                 datetimes, but got time zone `{tz}`",
+            );
+        }
+    }
+    args.try_map(|arg| {
+        write(arg.to_datetime()?)?;
+        Ok(true)
+    })
+}
+
 #[derive(Debug, Default)]
 struct Config {
     datetime_or_tz: Option<flags::DateTimeOrTimeZone>,
+    zones: Vec<TimeZone>,
+    output: flags::Output,
 }
 
 impl args::Configurable for Config {
     fn configure(
         &mut self,
-        _: &mut lexopt::Parser,
+        p: &mut lexopt::Parser,
         arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
         match *arg {
+            lexopt::Arg::Short('z') | lexopt::Arg::Long("zone") => {
+                self.zones.push(args::parse(p, "-z/--zone")?);
+            }
+            lexopt::Arg::Long("output") => {
+                self.output = args::parse(p, "--output")?;
+            }
             lexopt::Arg::Value(ref mut v) => {
                 if self.datetime_or_tz.is_some() {
                     return Ok(false);
@@ -129,6 +214,33 @@ impl args::Configurable for Config {
     }
 
     fn usage(&self) -> &[Usage] {
-        &[TimeZone::ARG_OR_STDIN, DateTime::ARG_OR_STDIN]
+        const ZONE: Usage = Usage::flag(
+            "-z/--zone <time-zone>",
+            "Convert each datetime into this time zone. May be repeated.",
+            r#"
+Convert each datetime into this time zone. May be repeated.
+
+When this flag is given, every positional argument (or every line on stdin,
+when there are no positional arguments) is interpreted as a datetime. Each
+datetime is then converted into every time zone given, with one line written
+for each time zone. The lines for each datetime are written in the order that
+the time zones were given. For IANA time zone identifiers, the bracketed
+annotation of each line identifies the time zone it was converted into.
+
+For tagged data, each record is written once for each time zone, with its
+datetime tags converted into that time zone and its data preserved.
+
+Time zones may be given as IANA time zone identifiers, fixed offsets or POSIX
+time zone strings. Since POSIX time zone strings may contain commas, this flag
+does not accept comma separated lists. Instead, repeat the flag.
+"#,
+        );
+
+        &[
+            TimeZone::ARG_OR_STDIN,
+            DateTime::ARG_OR_STDIN,
+            ZONE,
+            flags::Output::USAGE,
+        ]
     }
 }
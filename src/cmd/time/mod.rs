@@ -1,48 +1,95 @@
 mod add;
+mod align_zones;
+mod between;
+mod clamp;
 mod cmp;
+mod cron;
+mod epoch;
 mod fmt;
+mod fold;
+mod grid;
 mod inn;
+mod monotonic_check;
+mod nearest;
 mod of;
+mod overlap;
 mod parse;
 mod relative;
 mod round;
+mod rrule;
 mod seq;
+mod skew;
 mod sort;
+mod sort_key;
+mod sun;
+mod weeknum_range;
 
-const USAGE: &'static str = "\
+const USAGE: &str = "\
 Commands for working with datetimes.
 
 USAGE:
     bttf time <command> ...
 
 COMMANDS:
-    add       Add a span to a datetime
-    cmp       Compare datetimes
-    end-of    Get the end of a year, month, week, etc
-    fmt       Format a datetime
-    in        Convert a datetime to a time zone
-    parse     Parse a datetime
-    relative  Parse a relative datetime
-    round     Round a datetime
-    seq       Generate a sequence of datetimes
-    sort      Sort datetimes
-    start-of  Get the start of a year, month, week, etc
+    add              Add a span to a datetime
+    align-zones      Find local time windows shared by time zones
+    between          Check whether datetimes fall within a range
+    clamp            Clamp datetimes to the supported range
+    cmp              Compare datetimes
+    cron             List upcoming fire times of a cron expression
+    end-of           Get the end of a year, month, week, etc
+    epoch            Convert datetimes to and from Unix timestamps
+    fmt              Format a datetime
+    fold             List every instant for a wall clock time
+    grid             Generate aligned buckets covering a range
+    in               Convert a datetime to a time zone
+    monotonic-check  Check that datetimes are in chronological order
+    nearest          Snap a datetime to a grid of evenly spaced instants
+    overlap          Report overlapping intervals
+    parse            Parse a datetime
+    relative         Parse a relative datetime
+    round            Round a datetime
+    rrule            Expand an RFC 5545 recurrence rule
+    seq              Generate a sequence of datetimes
+    skew             Report clock skew between pairs of datetimes
+    sort             Sort datetimes
+    sort-key         Print a key that sorts like the datetime's instant
+    start-of         Get the start of a year, month, week, etc
+    sunrise          Print the time of sunrise at a location
+    sunset           Print the time of sunset at a location
+    weeknum-range    List the week numbers of every week in a range
 ";
 
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let cmd = crate::args::next_as_command(USAGE, p)?;
     match &*cmd {
         "add" => add::run(p),
+        "align-zones" => align_zones::run(p),
+        "between" => between::run(p),
+        "clamp" => clamp::run(p),
         "cmp" => cmp::run(p),
+        "cron" => cron::run(p),
         "end-of" => of::end(p),
+        "epoch" => epoch::run(p),
         "fmt" => fmt::run(p),
+        "fold" => fold::run(p),
+        "grid" => grid::run(p),
         "in" => inn::run(p),
+        "monotonic-check" => monotonic_check::run(p),
+        "nearest" => nearest::run(p),
+        "overlap" => overlap::run(p),
         "parse" => parse::run(p),
         "relative" => relative::run(p),
         "round" => round::run(p),
+        "rrule" => rrule::run(p),
         "seq" => seq::run(p),
+        "skew" => skew::run(p),
         "sort" => sort::run(p),
+        "sort-key" => sort_key::run(p),
         "start-of" => of::start(p),
+        "sunrise" => sun::rise(p),
+        "sunset" => sun::set(p),
+        "weeknum-range" => weeknum_range::run(p),
         unk => anyhow::bail!("unrecognized command '{}'", unk),
     }
 }
@@ -0,0 +1,222 @@
+use std::io::Write;
+
+use bstr::BString;
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::DateTime,
+    tag::MaybeTagged,
+};
+
+const USAGE: &str = r#"
+Check that datetimes are in chronological order.
+
+This reads datetimes and exits with an error if they aren't in non-decreasing
+order. That is, each datetime must refer to the same instant as, or a later
+instant than, the datetime before it. Use `--strict` to also reject datetimes
+that are equal to the one before it. The error reports the first pair of
+datetimes that are out of order, along with their line numbers (or argument
+positions, when datetimes are given as positional arguments). Nothing is
+printed when the datetimes are in order.
+
+Datetimes are compared by the instant they represent, so datetimes in
+different time zones or with different offsets are compared correctly.
+
+Unlike `bttf time sort`, this doesn't reorder anything. It only checks the
+order. Although, with `--fix`, datetimes that are out of order are dropped
+instead of reported as an error.
+
+This accepts one or more datetimes as positional arguments. When no positional
+arguments are given, then line delimited datetimes are read from stdin. When
+reading from stdin, tagged data is also accepted. In that case, each line is
+compared by its sequence of datetime tags, in the same way as
+`bttf time sort`.
+
+USAGE:
+    bttf time monotonic-check <datetime>...
+    bttf time monotonic-check < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Check that datetimes in different time zones are in order:
+
+        $ bttf time monotonic-check \
+            '2025-03-15T10:00[America/New_York]' \
+            '2025-03-15T15:30[Europe/London]'
+        $ echo $?
+        0
+
+    %snip-start%
+
+    Report the first pair of datetimes that are out of order:
+
+        $ bttf time monotonic-check 2025-03-15T10:00Z 2025-03-15T09:00Z
+        argument 2 `2025-03-15T09:00Z` is before argument 1 `2025-03-15T10:00Z`
+
+    Check that the datetimes in a log file are in order:
+
+        $ bttf tag lines access.log | bttf time monotonic-check
+
+    Drop any datetimes that are out of order, instead of failing:
+
+        $ printf '2025-03-15T10:00Z\n2025-03-15T09:00Z\n2025-03-15T11:00Z\n' \
+            | bttf time monotonic-check --fix
+        2025-03-15T10:00Z
+        2025-03-15T11:00Z
+        dropped 1 out-of-order datetime(s)
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut args = positional::Arguments::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut args])?;
+
+    let mut wtr = std::io::stdout().lock();
+    let mut prev: Option<(Position, MaybeTagged<'static, DateTime>)> = None;
+    let mut position = 0;
+    let mut dropped = 0;
+    let mut violation = None;
+    args.try_map(|arg| {
+        position += 1;
+        let dt = arg.to_datetime()?;
+        let pos = Position {
+            line: arg.line_number(),
+            argument: position,
+            raw: arg.raw().into(),
+        };
+        if let Some((ref prev_pos, ref prev_dt)) = prev
+            && !config.in_order(prev_dt, &dt)
+        {
+            if config.fix {
+                dropped += 1;
+                return Ok(true);
+            }
+            let relation = if dt < *prev_dt { "before" } else { "equal to" };
+            violation = Some(format!("{pos} is {relation} {prev_pos}"));
+            return Ok(false);
+        }
+        if config.fix {
+            wtr.write_all(arg.raw())?;
+            writeln!(wtr)?;
+        }
+        prev = Some((pos, dt));
+        Ok(true)
+    })?;
+    if let Some(violation) = violation {
+        anyhow::bail!("{violation}");
+    }
+    if dropped > 0 {
+        wtr.flush()?;
+        eprintln!("dropped {dropped} out-of-order datetime(s)");
+    }
+    Ok(())
+}
+
+/// Where a datetime was found in the input, for use in error messages.
+#[derive(Debug)]
+struct Position {
+    /// The line number, when the datetime was read from stdin.
+    line: Option<usize>,
+    /// The 1-based position of the datetime among all datetimes given.
+    argument: usize,
+    /// The datetime as it was given.
+    raw: BString,
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line} `{}`", self.raw),
+            None => write!(f, "argument {} `{}`", self.argument, self.raw),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    strict: bool,
+    fix: bool,
+}
+
+impl Config {
+    /// Returns true when `cur` may follow `prev`.
+    fn in_order(
+        &self,
+        prev: &MaybeTagged<'static, DateTime>,
+        cur: &MaybeTagged<'static, DateTime>,
+    ) -> bool {
+        if self.strict { prev < cur } else { prev <= cur }
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        _: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("strict") => {
+                self.strict = true;
+            }
+            lexopt::Arg::Long("allow-equal") => {
+                self.strict = false;
+            }
+            lexopt::Arg::Long("fix") => {
+                self.fix = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const STRICT: Usage = Usage::flag(
+            "--strict",
+            "Require datetimes to be strictly increasing.",
+            r#"
+Require datetimes to be strictly increasing.
+
+By default, a datetime that refers to the same instant as the datetime before
+it is allowed. When this flag is given, such a datetime is treated as out of
+order. This can be undone with `--allow-equal`.
+"#,
+        );
+
+        const ALLOW_EQUAL: Usage = Usage::flag(
+            "--allow-equal",
+            "Allow datetimes equal to the one before it (the default).",
+            r#"
+Allow datetimes equal to the one before it (the default).
+
+This is the default behavior. That is, datetimes need only be non-decreasing.
+This flag exists to undo a previous `--strict` flag.
+"#,
+        );
+
+        const FIX: Usage = Usage::flag(
+            "--fix",
+            "Drop out-of-order datetimes instead of reporting an error.",
+            r#"
+Drop out-of-order datetimes instead of reporting an error.
+
+When enabled, every datetime that is in order is printed exactly as it was
+given, and every datetime that isn't is dropped. A datetime is out of order
+when it precedes (or, with `--strict`, is equal to) the last datetime that
+was printed. If any datetimes were dropped, then a count is printed to stderr
+at the end. This always exits successfully, unless the input couldn't be
+read.
+"#,
+        );
+
+        &[DateTime::ARG_OR_STDIN, STRICT, ALLOW_EQUAL, FIX]
+    }
+}
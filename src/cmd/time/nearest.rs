@@ -0,0 +1,260 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    bstr::ByteSlice,
+    jiff::{SignedDuration, SpanRelativeTo, Timestamp},
+};
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    parse::{BytesExt, FromBytes},
+    span::TimeSpan,
+};
+
+const USAGE: &str = r#"
+Snap datetimes to a grid of evenly spaced instants.
+
+The grid is made up of every instant that is a multiple of `-g/--grid` away
+from an origin. By default, the origin is the Unix epoch, but it can be set
+with `--epoch`. Each datetime is then moved to a nearby instant on the grid.
+The time zone of each datetime is preserved.
+
+This differs from `bttf time round`, which rounds to a calendar or clock unit
+in the datetime's time zone. In contrast, this command rounds to an arbitrary
+fixed interval measured from an arbitrary anchor. This is useful for bucketing
+telemetry, especially when the buckets aren't aligned with the hour.
+
+This accepts one or more datetimes as positional arguments. When no positional
+arguments are given, then line delimited datetimes are read from stdin.
+
+USAGE:
+    bttf time nearest -g <span> <datetime>...
+    bttf time nearest -g <span> < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Snap a datetime to the nearest 5 minute boundary:
+
+        $ bttf time nearest -g 5m 2025-03-05T12:03
+        2025-03-05T12:05:00-05:00[America/New_York]
+
+    %snip-start%
+
+    Put datetimes into 15 minute buckets starting at 7 minutes past the hour:
+
+        $ bttf time nearest -g 15m -m floor --epoch 2025-03-05T00:07 \
+            2025-03-05T12:03
+        2025-03-05T11:52:00-05:00[America/New_York]
+
+    The origin may also be given as a Unix timestamp in seconds:
+
+        $ bttf time nearest -g 90s --epoch 30 2025-03-05T12:02:30
+        2025-03-05T12:02:00-05:00[America/New_York]
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut datetimes = positional::DateTimes::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+
+    let grid = config.grid()?;
+    let mut wtr = std::io::stdout().lock();
+    datetimes.try_map(|datum| {
+        let snapped = datum.try_map(|dt| {
+            let ts =
+                config.mode.snap(dt.get().timestamp(), config.epoch, grid)?;
+            Ok(DateTime::from(ts.to_zoned(dt.get().time_zone().clone())))
+        })?;
+        snapped.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    grid: Option<TimeSpan>,
+    epoch: Timestamp,
+    mode: Mode,
+}
+
+impl Config {
+    /// Returns the grid period as a fixed duration.
+    ///
+    /// This returns an error if no grid was given, if it has non-uniform
+    /// units or if it isn't positive. Days are always treated as 24 hours.
+    fn grid(&self) -> anyhow::Result<SignedDuration> {
+        let Some(ref grid) = self.grid else {
+            anyhow::bail!("-g/--grid is required");
+        };
+        anyhow::ensure!(
+            grid.get().get_years() == 0 && grid.get().get_months() == 0,
+            "grid span `{grid}` must not contain units of months or years",
+        );
+        let dur =
+            grid.get().to_duration(SpanRelativeTo::days_are_24_hours())?;
+        anyhow::ensure!(
+            dur.is_positive(),
+            "grid span `{grid}` must be positive",
+        );
+        Ok(dur)
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Short('g') | lexopt::Arg::Long("grid") => {
+                self.grid = Some(args::parse(p, "-g/--grid")?);
+            }
+            lexopt::Arg::Long("epoch") => {
+                let epoch: Epoch = args::parse_bytes(p, "--epoch")?;
+                self.epoch = epoch.0;
+            }
+            lexopt::Arg::Short('m') | lexopt::Arg::Long("mode") => {
+                self.mode = args::parse_bytes(p, "-m/--mode")?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const GRID: Usage = Usage::flag(
+            "-g/--grid <span>",
+            "The distance between adjacent instants on the grid.",
+            r#"
+The distance between adjacent instants on the grid.
+
+This flag is required. The span must be positive and must not contain units
+of months or years, since those don't have a fixed length. Weeks and days are
+always treated as 7 days and 24 hours, respectively.
+"#,
+        );
+
+        const EPOCH: Usage = Usage::flag(
+            "--epoch <datetime>",
+            "The origin of the grid.",
+            r#"
+The origin of the grid.
+
+Every instant on the grid is a multiple of `-g/--grid` away from this datetime.
+It may be given as an integer number of seconds since the Unix epoch or as any
+datetime accepted elsewhere by bttf. The origin doesn't need to precede the
+datetimes being snapped.
+
+This defaults to the Unix epoch, `1970-01-01T00:00:00Z`.
+"#,
+        );
+
+        &[DateTime::ARG_OR_STDIN, GRID, EPOCH, Mode::USAGE]
+    }
+}
+
+/// The origin of a grid, parsed from the CLI.
+///
+/// This is also used by `bttf time grid`.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Epoch(pub(super) Timestamp);
+
+impl FromBytes for Epoch {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<Epoch> {
+        if let Ok(second) = s.to_str().unwrap_or("").parse::<i64>() {
+            return Ok(Epoch(Timestamp::from_second(second)?));
+        }
+        let dt: DateTimeFlexible = s.parse()?;
+        Ok(Epoch(DateTime::from(dt).get().timestamp()))
+    }
+}
+
+/// How to pick an instant on the grid for each datetime.
+#[derive(Clone, Copy, Debug, Default)]
+enum Mode {
+    /// Snap to the closest instant at or before the datetime.
+    Floor,
+    /// Snap to the closest instant at or after the datetime.
+    Ceil,
+    /// Snap to the closest instant, with ties going to the later one.
+    #[default]
+    Nearest,
+}
+
+impl Mode {
+    const USAGE: Usage = Usage::flag(
+        "-m/--mode <mode>",
+        "How to pick an instant on the grid, e.g., `floor` or `nearest`.",
+        r#"
+How to pick an instant on the grid.
+
+The default for this flag is `nearest`. The legal values are:
+
+`floor`: snaps to the closest instant on the grid that is at or before the
+datetime.
+
+`ceil`: snaps to the closest instant on the grid that is at or after the
+datetime.
+
+`nearest`: snaps to the closest instant on the grid. When a datetime is
+exactly halfway between two instants, it snaps to the later one.
+"#,
+    );
+
+    /// Snap the given timestamp to a grid with the given origin and period.
+    ///
+    /// This returns an error if the snapped instant is out of the range
+    /// supported by bttf.
+    fn snap(
+        &self,
+        ts: Timestamp,
+        epoch: Timestamp,
+        grid: SignedDuration,
+    ) -> anyhow::Result<Timestamp> {
+        let grid = grid.as_nanos();
+        let offset = ts.as_nanosecond() - epoch.as_nanosecond();
+        let floor = offset - offset.rem_euclid(grid);
+        let remainder = offset - floor;
+        let snapped = match *self {
+            Mode::Floor => floor,
+            Mode::Ceil if remainder == 0 => floor,
+            Mode::Ceil => floor + grid,
+            Mode::Nearest if remainder * 2 < grid => floor,
+            Mode::Nearest => floor + grid,
+        };
+        Timestamp::from_nanosecond(epoch.as_nanosecond() + snapped)
+            .with_context(|| {
+                format!("snapping `{ts}` to the grid is out of range")
+            })
+    }
+}
+
+impl FromBytes for Mode {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<Mode> {
+        Ok(match s {
+            b"floor" => Mode::Floor,
+            b"ceil" => Mode::Ceil,
+            b"nearest" => Mode::Nearest,
+            unk => anyhow::bail!(
+                "unknown snapping mode `{unk}`",
+                unk = unk.as_bstr()
+            ),
+        })
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     parse::OsStrExt,
 };
 
-const USAGE_START_OF: &'static str = r#"
+const USAGE_START_OF: &str = r#"
 Print the start of a particular calendar or time unit.
 
 This command makes it easy to "snap" datetimes to the beginning of a particular
@@ -44,6 +44,17 @@ EXAMPLES:
         $ bttf time start-of week-monday now
         2025-03-31T00:00:00-04:00[America/New_York]
 
+    Weeks start on the weekday given by `--week-start`, or Monday by default:
+
+        $ bttf time start-of week --week-start saturday now
+        2025-03-29T00:00:00-04:00[America/New_York]
+
+    Print the first instant of the current quarter, for a fiscal year that
+    starts in February:
+
+        $ bttf time start-of quarter --fiscal-start feb now
+        2025-02-01T00:00:00-05:00[America/New_York]
+
     This command is aware of time zone transitions. For example, on
     2015-10-18, Sao Paulo entered DST. Unlike most places, they set their
     clocks forward at midnight, which means the midnight hour never actually
@@ -60,7 +71,7 @@ OPTIONS:
 %flags%
 "#;
 
-const USAGE_END_OF: &'static str = r#"
+const USAGE_END_OF: &str = r#"
 Print the end of a particular calendar or time unit.
 
 This command makes it easy to "snap" datetimes to the end of a particular
@@ -96,6 +107,16 @@ EXAMPLES:
         $ bttf time end-of week-monday now
         2025-04-06T23:59:59.999999999-04:00[America/New_York]
 
+    Weeks start on the weekday given by `--week-start`, or Monday by default:
+
+        $ bttf time end-of week --week-start saturday now
+        2025-04-04T23:59:59.999999999-04:00[America/New_York]
+
+    Print the last instant of the current quarter:
+
+        $ bttf time end-of quarter now
+        2025-06-30T23:59:59.999999999-04:00[America/New_York]
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -103,6 +124,21 @@ OPTIONS:
 %flags%
 "#;
 
+const FISCAL_START: Usage = Usage::flag(
+    "--fiscal-start <month>",
+    "The month in which the first quarter starts (defaults to January).",
+    r#"
+The month in which the first quarter starts (defaults to January).
+
+This only impacts the `quarter` unit. Quarters are always three months long,
+so this determines which months quarters start in. For example, with
+`--fiscal-start april`, quarters start in April, July, October and January.
+
+The month may be given as an integer in the range `1..=12`, or as an English
+month name, e.g., `april` or `apr`.
+"#,
+);
+
 pub fn start(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = StartOf::default();
     let mut datetimes = positional::DateTimes::default();
@@ -111,7 +147,9 @@ pub fn start(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let of = config.of.context("missing required <start-of> argument")?;
     let mut wtr = std::io::stdout().lock();
     datetimes.try_map(|datum| {
-        let dt = datum.try_map(|dt| of.start(&dt))?;
+        let dt = datum.try_map(|dt| {
+            of.start(&dt, config.week_start.get(), config.fiscal_start.get())
+        })?;
         dt.write(&mut wtr)?;
         writeln!(wtr)?;
         Ok(true)
@@ -127,7 +165,9 @@ pub fn end(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let of = config.of.context("missing required <start-of> argument")?;
     let mut wtr = std::io::stdout().lock();
     datetimes.try_map(|datum| {
-        let dt = datum.try_map(|dt| of.end(&dt))?;
+        let dt = datum.try_map(|dt| {
+            of.end(&dt, config.week_start.get(), config.fiscal_start.get())
+        })?;
         dt.write(&mut wtr)?;
         writeln!(wtr)?;
         Ok(true)
@@ -138,15 +178,23 @@ pub fn end(p: &mut lexopt::Parser) -> anyhow::Result<()> {
 #[derive(Debug, Default)]
 struct StartOf {
     of: Option<flags::Of>,
+    week_start: flags::Weekday,
+    fiscal_start: flags::Month,
 }
 
 impl args::Configurable for EndOf {
     fn configure(
         &mut self,
-        _: &mut lexopt::Parser,
+        p: &mut lexopt::Parser,
         arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
         match *arg {
+            lexopt::Arg::Long("fiscal-start") => {
+                self.fiscal_start = args::parse(p, "--fiscal-start")?;
+            }
+            lexopt::Arg::Long("week-start") => {
+                self.week_start = args::parse(p, "--week-start")?;
+            }
             lexopt::Arg::Value(ref mut v) => {
                 if self.of.is_some() {
                     return Ok(false);
@@ -159,22 +207,35 @@ impl args::Configurable for EndOf {
     }
 
     fn usage(&self) -> &[Usage] {
-        &[DateTime::ARG_OR_STDIN, flags::Of::USAGE_ARG_START]
+        &[
+            DateTime::ARG_OR_STDIN,
+            flags::Of::USAGE_ARG_START,
+            FISCAL_START,
+            flags::Weekday::USAGE_WEEK_START,
+        ]
     }
 }
 
 #[derive(Debug, Default)]
 struct EndOf {
     of: Option<flags::Of>,
+    week_start: flags::Weekday,
+    fiscal_start: flags::Month,
 }
 
 impl args::Configurable for StartOf {
     fn configure(
         &mut self,
-        _: &mut lexopt::Parser,
+        p: &mut lexopt::Parser,
         arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
         match *arg {
+            lexopt::Arg::Long("fiscal-start") => {
+                self.fiscal_start = args::parse(p, "--fiscal-start")?;
+            }
+            lexopt::Arg::Long("week-start") => {
+                self.week_start = args::parse(p, "--week-start")?;
+            }
             lexopt::Arg::Value(ref mut v) => {
                 if self.of.is_some() {
                     return Ok(false);
@@ -187,6 +248,11 @@ impl args::Configurable for StartOf {
     }
 
     fn usage(&self) -> &[Usage] {
-        &[DateTime::ARG_OR_STDIN, flags::Of::USAGE_ARG_END]
+        &[
+            DateTime::ARG_OR_STDIN,
+            flags::Of::USAGE_ARG_END,
+            FISCAL_START,
+            flags::Weekday::USAGE_WEEK_START,
+        ]
     }
 }
@@ -0,0 +1,309 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    bstr::{BString, ByteSlice},
+    jiff::Timestamp,
+    lexopt::{Arg, Parser},
+};
+
+use crate::{
+    args::{self, Usage, flags},
+    datetime::DateTime,
+    parse::{BufReadExt, BytesExt},
+};
+
+const USAGE: &str = r#"
+Report overlapping intervals.
+
+This reads line delimited data from a file or stdin, where each line contains
+two datetimes separated by a tab: the start and end of an interval. For
+example, the start and end of a calendar event. For each pair of intervals that
+overlap, both lines are printed on a single line, separated by a tab. This is
+useful for detecting double-booked time slots.
+
+By default, only adjacent intervals (that is, each interval and the one on the
+line before it) are compared. This is fast and works well when the input is
+sorted by start time. Use `--all-pairs` to compare every interval with every
+other interval, regardless of their order.
+
+The end of each interval is exclusive by default. That is, an interval ending
+at 10:00 does not overlap with an interval starting at 10:00. Use `--closed`
+to treat the end of each interval as inclusive.
+
+USAGE:
+    bttf time overlap <path>
+    bttf time overlap < line delimited <datetime>\t<datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Find meetings that overlap with the meeting before them:
+
+        $ printf '%s\t%s\n' \
+            2025-03-15T09:00Z 2025-03-15T10:00Z \
+            2025-03-15T09:30Z 2025-03-15T10:30Z \
+            | bttf time overlap
+        2025-03-15T09:00Z	2025-03-15T10:00Z	2025-03-15T09:30Z	2025-03-15T10:30Z
+
+    %snip-start%
+
+    Intervals that merely touch don't overlap unless `--closed` is given:
+
+        $ printf '%s\t%s\n' \
+            2025-03-15T09:00Z 2025-03-15T10:00Z \
+            2025-03-15T10:00Z 2025-03-15T11:00Z \
+            | bttf time overlap --closed
+        2025-03-15T09:00Z	2025-03-15T10:00Z	2025-03-15T10:00Z	2025-03-15T11:00Z
+
+    Fail if any two intervals in a file overlap, without printing them:
+
+        $ bttf time overlap --all-pairs --quiet events.tsv
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let mut intervals = vec![];
+    let mut overlaps = 0;
+    let mut wtr = std::io::stdout().lock();
+    let result = config.input.reader()?.for_byte_line(|line| {
+        let interval = Interval::parse(line.number(), line.content())
+            .with_context(|| format!("line {}", line.number()))?;
+        if let Mode::Adjacent = config.mode {
+            // Only the previous interval is needed in this mode.
+            if let Some(prev) = intervals.pop()
+                && config.overlaps(&prev, &interval)
+            {
+                overlaps += 1;
+                config.write(&mut wtr, &prev, &interval)?;
+            }
+        }
+        intervals.push(interval);
+        Ok(true)
+    });
+    result.with_context(|| format!("{}", config.input.display()))?;
+    if let Mode::AllPairs = config.mode {
+        // Sort by start time so that, for each interval, we only need to
+        // look at the intervals after it up until the first one that starts
+        // after it ends. The sort is stable, so intervals with the same
+        // start time remain in input order.
+        let mut sorted: Vec<&Interval> = intervals.iter().collect();
+        sorted.sort_by_key(|interval| interval.start);
+        let mut pairs = vec![];
+        for (i, &first) in sorted.iter().enumerate() {
+            for &second in sorted[i + 1..].iter() {
+                if !config.overlaps(first, second) {
+                    break;
+                }
+                if first.number <= second.number {
+                    pairs.push((first, second));
+                } else {
+                    pairs.push((second, first));
+                }
+            }
+        }
+        // Report pairs in input order.
+        pairs.sort_by_key(|&(first, second)| (first.number, second.number));
+        for (first, second) in pairs {
+            overlaps += 1;
+            config.write(&mut wtr, first, second)?;
+        }
+    }
+    if config.quiet && overlaps > 0 {
+        anyhow::bail!("found {overlaps} pair(s) of overlapping intervals");
+    }
+    Ok(())
+}
+
+/// A single interval read from a line of input.
+#[derive(Debug)]
+struct Interval {
+    /// The line number this interval was read from.
+    number: usize,
+    /// The line's contents, which are printed verbatim.
+    raw: BString,
+    start: Timestamp,
+    end: Timestamp,
+}
+
+impl Interval {
+    /// Parses a pair of tab separated datetimes from the given line.
+    fn parse(number: usize, content: &[u8]) -> anyhow::Result<Interval> {
+        let (start, end) =
+            content.split_once_str("\t").with_context(|| {
+                format!(
+                    "expected two tab separated datetimes, but got `{}`",
+                    content.as_bstr(),
+                )
+            })?;
+        let start: DateTime = start.trim_ascii().parse()?;
+        let end: DateTime = end.trim_ascii().parse()?;
+        anyhow::ensure!(
+            start.get().timestamp() <= end.get().timestamp(),
+            "interval end `{end}` precedes its start `{start}`",
+        );
+        Ok(Interval {
+            number,
+            raw: BString::from(content),
+            start: start.get().timestamp(),
+            end: end.get().timestamp(),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    input: flags::FileOrStdin,
+    mode: Mode,
+    closed: bool,
+    quiet: bool,
+}
+
+impl Config {
+    /// Returns true when the given intervals overlap.
+    fn overlaps(&self, first: &Interval, second: &Interval) -> bool {
+        let (first, second) = if first.start <= second.start {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        if self.closed {
+            second.start <= first.end
+        } else {
+            second.start < first.end
+        }
+    }
+
+    /// Writes a pair of overlapping intervals, unless `--quiet` was given.
+    fn write(
+        &self,
+        mut wtr: impl Write,
+        first: &Interval,
+        second: &Interval,
+    ) -> anyhow::Result<()> {
+        if self.quiet {
+            return Ok(());
+        }
+        wtr.write_all(&first.raw)?;
+        wtr.write_all(b"\t")?;
+        wtr.write_all(&second.raw)?;
+        wtr.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        _: &mut Parser,
+        arg: &mut Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            Arg::Long("adjacent") => {
+                self.mode = Mode::Adjacent;
+            }
+            Arg::Long("all-pairs") => {
+                self.mode = Mode::AllPairs;
+            }
+            Arg::Long("closed") => {
+                self.closed = true;
+            }
+            Arg::Short('q') | Arg::Long("quiet") => {
+                self.quiet = true;
+            }
+            Arg::Value(ref mut v) => {
+                self.input.set(std::mem::take(v))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const PATH: Usage = Usage::arg(
+            "<path>",
+            "A file path to read intervals from.",
+            r#"
+A file path to read intervals from.
+
+Each line must contain two datetimes separated by a tab, with the start of the
+interval first. An interval whose end precedes its start is an error. In lieu
+of a specific file path, users may also pass line delimited data into stdin.
+"#,
+        );
+
+        const ADJACENT: Usage = Usage::flag(
+            "--adjacent",
+            "Compare each interval with the one before it (the default).",
+            r#"
+Compare each interval with the one before it (the default).
+
+This only finds all overlapping intervals when the input is sorted by start
+time and no interval overlaps with more than the one after it. For example, a
+long interval that overlaps with the next two intervals will only be reported
+once. Use `--all-pairs` to find every overlapping pair.
+
+This overrides `--all-pairs`.
+"#,
+        );
+
+        const ALL_PAIRS: Usage = Usage::flag(
+            "--all-pairs",
+            "Compare every interval with every other interval.",
+            r#"
+Compare every interval with every other interval.
+
+This reads all intervals into memory before reporting any overlaps. Each
+overlapping pair is printed once, with the interval that appears first in the
+input printed first. Pairs are printed in input order.
+
+This overrides `--adjacent`.
+"#,
+        );
+
+        const CLOSED: Usage = Usage::flag(
+            "--closed",
+            "Treat the end of each interval as inclusive.",
+            r#"
+Treat the end of each interval as inclusive.
+
+By default, the end of each interval is exclusive. This means that an interval
+ending at the same instant that another one starts does not overlap with it.
+When this flag is given, such intervals are considered overlapping.
+"#,
+        );
+
+        const QUIET: Usage = Usage::flag(
+            "-q/--quiet",
+            "Print nothing and exit with an error if any intervals overlap.",
+            r#"
+Print nothing and exit with an error if any intervals overlap.
+
+When this flag is given, overlapping pairs are not printed. Instead, if one or
+more pairs of intervals overlap, then an error is reported after all input has
+been read and bttf exits with a non-zero status.
+"#,
+        );
+
+        &[PATH, ADJACENT, ALL_PAIRS, CLOSED, QUIET]
+    }
+}
+
+/// How to choose which intervals to compare.
+#[derive(Clone, Copy, Debug, Default)]
+enum Mode {
+    /// Compare each interval with the one before it.
+    #[default]
+    Adjacent,
+    /// Compare every interval with every other interval.
+    AllPairs,
+}
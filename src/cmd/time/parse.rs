@@ -1,11 +1,21 @@
 use std::io::Write;
 
+use std::borrow::Cow;
+
+use {
+    anyhow::Context,
+    bstr::{BStr, ByteSlice},
+    jiff::civil,
+};
+
 use crate::{
-    args::{self, Usage, flags, positional},
-    datetime::{DateTime, DateTimeFlexible},
+    args::{self, Usage, flags, flags::OffsetMismatch, positional},
+    datetime::{DateTime, DateTimeFlexible, DateTimeJson},
+    locale::{Locale, NameKind, NameParser},
+    strtime::{self, Token},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Parse a datetime in a particular format.
 
 This accepts one or more strings to parse as positional arguments. When no
@@ -15,7 +25,13 @@ stdin.
 When `-f/--format` is used with an `strftime`-like format string and no
 offset or time zone is parsed, then the parsed time is assumed to be local
 relative to your system time zone. (Which can be overridden via the `TZ`
-environment variable.)
+environment variable.) Input parsed with an `strftime`-like format string may
+also end with an RFC 9557 time zone annotation, like `[Europe/London]`, that
+isn't part of the format. The annotation sets the time zone of the parsed
+datetime without changing its instant. Or, if no offset or time zone was
+parsed, then the parsed time is assumed to be local to the annotated time
+zone. This is especially useful for giving Unix timestamps, which have no time
+zone, a time zone to display them in.
 
 By default, this only parses RFC 9557 timestamps. For example,
 `2025-05-01T17:30-04[America/New_York]`. To accept a more flexible format
@@ -41,18 +57,85 @@ EXAMPLES:
 
         $ bttf time parse -f '%Y-%m-%d %Q' '2025-03-15 Australia/Tasmania'
 
+    Parse the `Date` header of an email:
+
+        $ bttf time parse --rfc2822 'Sat, 20 Jul 2024 16:30:55 -0400'
+        2024-07-20T16:30:55-04:00[-04:00]
+
+    Parse the `Last-Modified` header of an HTTP response:
+
+        $ bttf time parse --http-date 'Sat, 20 Jul 2024 20:30:55 GMT'
+        2024-07-20T20:30:55+00:00[UTC]
+
     Parse a Unix timestamp, in seconds:
 
         $ bttf time parse -f '%s' 999999999
 
+    Parse a Unix timestamp in nanoseconds, keeping its fractional seconds:
+
+        $ bttf time parse -f '%Ns' 1721492455123456789
+        2024-07-20T16:20:55.123456789Z[Etc/Unknown]
+
+    Parse a Unix timestamp and display it in a specific time zone:
+
+        $ bttf time parse -f '%s' '1721507455[Europe/London]'
+        2024-07-20T21:30:55+01:00[Europe/London]
+
     Parse an ISO 8601 week date in your local time:
 
         $ bttf time parse -f '%G-W%V-%u' 2025-W12-1
 
+    Parse an ISO 8601 week without a weekday as the Monday that starts it:
+
+        $ bttf time parse -f '%G-W%V' 2025-W01
+        2024-12-30T00:00:00-05:00[America/New_York]
+
+    Parse ISO 8601 ordinal dates and week dates from stdin:
+
+        $ printf '2024-202\n2024-W29-6\n' | bttf time parse -f flexible
+        2024-07-20T00:00:00-04:00[America/New_York]
+        2024-07-20T00:00:00-04:00[America/New_York]
+
+    Give dates without a time of day a time of noon instead of midnight:
+
+        $ bttf time parse -f flexible --default-time 12:00 2024-07-20
+        2024-07-20T12:00:00-04:00[America/New_York]
+
+    Parse a log with a mix of formats by trying each one in turn:
+
+        $ printf '2024-07-20T16:30:55Z\n1721507455\n' | bttf time parse \
+            --try rfc3339 --try %s --try '%Y-%m-%d %H:%M:%S'
+        2024-07-20T16:30:55Z[Etc/Unknown]
+        2024-07-20T20:30:55Z[Etc/Unknown]
+
+    Print the parsed datetime as JSON, e.g., for use in scripts:
+
+        $ bttf time parse --output json 2024-07-20T16:30:55-04[America/New_York]
+        {"timestamp":"2024-07-20T20:30:55Z","zoned":"2024-07-20T16:30:55-04:00[America/New_York]","offset":"-04:00","epoch_seconds":1721507455,"epoch_nanoseconds":1721507455000000000}
+
     Parse a relative datetime from stdin:
 
         $ echo '1 hour ago' | bttf time parse -f flexible
 
+    Show which kind of datetime was recognized by flexible parsing:
+
+        $ bttf time parse -f flexible --show-format 2025-03-15 now
+        2025-03-15: local
+        2025-03-15T00:00:00-04:00[America/New_York]
+        now: relative
+        2024-07-20T16:30:55-04:00[America/New_York]
+
+    Parse a date written in French, like the output of `%x` with
+    `BTTF_LOCALE=fr-FR`:
+
+        $ bttf time parse --locale fr-FR -f '%d %b %Y' '20 juil. 2024'
+        2024-07-20T00:00:00-04:00[America/New_York]
+
+    Reject a datetime whose offset is inconsistent with its time zone:
+
+        $ bttf time parse --strict '2024-07-20T12:00-08[America/New_York]'
+        datetime `2024-07-20T12:00-08[America/New_York]` has offset `-08`, but time zone `America/New_York` has offset `-04` at that instant
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -65,11 +148,55 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut args = positional::MaybeTaggedArguments::default();
     args::configure(p, USAGE, &mut [&mut config, &mut args])?;
 
+    let names = match config.locale {
+        None => None,
+        Some(ref locale) => locale.to_name_parser()?,
+    };
+    let formats = config
+        .formats()?
+        .into_iter()
+        .map(|given| {
+            let (format, kinds) = match (names.is_some(), &given) {
+                (false, format) => (format.clone(), vec![]),
+                (true, flags::Format::Strtime(fmt)) => {
+                    let format = full_name_directives(fmt).into();
+                    (flags::Format::Strtime(format), name_kinds(fmt))
+                }
+                (true, format) => anyhow::bail!(
+                    "--locale can only be used with strptime formats, \
+                     but got {format}",
+                ),
+            };
+            Ok(Candidate { given, format, kinds })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
     let mut wtr = std::io::stdout().lock();
+    let mut labels = vec![];
     args.try_map(|datum| {
-        let parsed = match datum
-            .try_map(|arg| config.format.parse(&config.relative, &arg))
-        {
+        labels.clear();
+        let parsed = match datum.try_map(|arg| {
+            let (i, input, (mut dt, mut label, mismatch)) =
+                parse_first(&config, names.as_ref(), &formats, arg.as_bstr())?;
+            let Candidate { ref given, ref format, .. } = formats[i];
+            if let flags::Format::Strtime(ref fmt) = *given {
+                label = Cow::Borrowed(&**fmt);
+            }
+            if let Some(time) = config.default_time
+                && format.is_date_only(&input)
+            {
+                dt = config.with_time(&dt, time)?;
+            }
+            if let Some(mismatch) = mismatch {
+                anyhow::ensure!(!config.strict, "{mismatch}");
+                eprintln!("warning: {mismatch}");
+            }
+            if let Some(ShowFormat::Stderr) = config.show_format {
+                eprintln!("{}: {label}", arg.as_bstr());
+            }
+            labels.push(label);
+            Ok(dt)
+        }) {
             Ok(parsed) => parsed,
             Err(err) => {
                 if !config.ignore_invalid {
@@ -80,18 +207,203 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
             }
         };
 
-        parsed.write(&mut wtr)?;
+        if let Some(ShowFormat::Column) = config.show_format {
+            write!(wtr, "{}\t", labels.join(","))?;
+        }
+        config.output.check(&parsed)?;
+        if config.output.is_json() {
+            parsed.map(DateTimeJson::new).write(&mut wtr)?;
+        } else {
+            parsed.write(&mut wtr)?;
+        }
         writeln!(wtr)?;
         Ok(true)
     })?;
     Ok(())
 }
 
+/// A format to try parsing each input with.
+#[derive(Debug)]
+struct Candidate {
+    /// The format as given by the user.
+    given: flags::Format,
+    /// The format actually used for parsing. This differs from `given` when
+    /// it was rewritten to parse names translated from a locale.
+    format: flags::Format,
+    /// The kinds of names, in order, parsed by the directives in `format`.
+    /// This is always empty when no locale was given.
+    kinds: Vec<NameKind>,
+}
+
+/// A parsed datetime, the label of the format that parsed it and whether its
+/// offset was inconsistent with its time zone.
+type Parsed<'f> = (DateTime, Cow<'f, str>, Option<OffsetMismatch>);
+
+/// Parses the given input with each format in turn, and returns the index of
+/// the first format that succeeds along with the input it parsed (after
+/// translating any localized names for that format) and what it parsed.
+///
+/// When there is only one format, its error is returned as-is. Otherwise, the
+/// error lists every format that was tried.
+fn parse_first<'a, 'f>(
+    config: &Config,
+    names: Option<&NameParser>,
+    formats: &'f [Candidate],
+    arg: &'a BStr,
+) -> anyhow::Result<(usize, Cow<'a, BStr>, Parsed<'f>)> {
+    let parse = |candidate: &'f Candidate| {
+        let input = translate(names, &candidate.kinds, arg)?;
+        let parsed =
+            candidate.format.parse_detect_lenient(&config.relative, &input);
+        let parsed = match config.locale {
+            Some(ref locale) if *input != *arg => parsed.with_context(|| {
+                format!("parsing `{arg}` in locale `{locale}` failed")
+            }),
+            _ => parsed,
+        };
+        parsed.map(|parsed| (input, parsed))
+    };
+    if let [ref candidate] = *formats {
+        let (input, parsed) = parse(candidate)?;
+        return Ok((0, input, parsed));
+    }
+    for (i, candidate) in formats.iter().enumerate() {
+        match parse(candidate) {
+            Ok((input, parsed)) => return Ok((i, input, parsed)),
+            Err(err) => {
+                log::debug!("{} did not match: {err:#}", candidate.given)
+            }
+        }
+    }
+    let tried = formats
+        .iter()
+        .map(|candidate| candidate.given.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    anyhow::bail!("`{arg}` did not match any of the formats tried: {tried}")
+}
+
+/// Translates localized month and weekday names in the given input into their
+/// full English names.
+///
+/// `kinds` is the sequence of names that the format expects. When no locale
+/// was given (or the input isn't valid UTF-8), the input is returned
+/// unchanged.
+fn translate<'a>(
+    names: Option<&NameParser>,
+    kinds: &[NameKind],
+    input: &'a BStr,
+) -> anyhow::Result<Cow<'a, BStr>> {
+    let (Some(names), Ok(s)) = (names, input.to_str()) else {
+        return Ok(Cow::Borrowed(input));
+    };
+    Ok(match names.translate(s, kinds)? {
+        Cow::Borrowed(s) => Cow::Borrowed(s.into()),
+        Cow::Owned(s) => Cow::Owned(s.into()),
+    })
+}
+
+/// Rewrites the given `strptime` format so that its month and weekday name
+/// directives accept full English names.
+///
+/// This is the counterpart to `translate`, which always writes full names.
+/// So `%b` and `%h` become `%B`, and `%a` becomes `%A`. Any flags or widths
+/// are kept as-is.
+fn full_name_directives(fmt: &str) -> String {
+    let mut rewritten = String::with_capacity(fmt.len());
+    for token in strtime::tokens(fmt) {
+        match token {
+            Token::Literal(literal) => rewritten.push_str(literal),
+            Token::Percent => rewritten.push_str("%%"),
+            Token::Directive(mut directive) => {
+                directive.specifier = directive.specifier.map(|ch| match ch {
+                    'b' | 'h' => 'B',
+                    'a' => 'A',
+                    ch => ch,
+                });
+                rewritten.push_str(&directive.to_string());
+            }
+        }
+    }
+    rewritten
+}
+
+/// Returns the kinds of names parsed by the directives in the given
+/// `strptime` format, in the order they appear.
+///
+/// `%a` and `%A` parse weekday names, while `%b`, `%B` and `%h` parse month
+/// names.
+fn name_kinds(fmt: &str) -> Vec<NameKind> {
+    strtime::tokens(fmt)
+        .filter_map(|token| {
+            let Token::Directive(directive) = token else { return None };
+            match directive.specifier? {
+                'a' | 'A' => Some(NameKind::Weekday),
+                'b' | 'B' | 'h' => Some(NameKind::Month),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Where to report the format detected for each parsed datetime.
+#[derive(Clone, Copy, Debug)]
+enum ShowFormat {
+    /// Print the input and its format to stderr.
+    Stderr,
+    /// Print the format as a tab separated column before each datetime.
+    Column,
+}
+
 #[derive(Debug, Default)]
 struct Config {
-    format: flags::Format,
+    default_time: Option<civil::Time>,
+    format: Option<flags::Format>,
     ignore_invalid: bool,
+    locale: Option<Locale>,
+    output: flags::Output,
     relative: DateTime,
+    show_format: Option<ShowFormat>,
+    strict: bool,
+    tries: Vec<flags::Format>,
+}
+
+impl Config {
+    /// Returns the formats to try for each input, in order.
+    fn formats(&self) -> anyhow::Result<Vec<flags::Format>> {
+        match self.format {
+            None if self.tries.is_empty() => {
+                Ok(vec![flags::Format::default()])
+            }
+            None => Ok(self.tries.clone()),
+            Some(ref format) if self.tries.is_empty() => {
+                Ok(vec![format.clone()])
+            }
+            Some(ref format) => anyhow::bail!(
+                "--try cannot be combined with {format} given to \
+                 -f/--format, instead give each format to --try",
+            ),
+        }
+    }
+
+    /// Returns the given date-only datetime with its time of day replaced.
+    ///
+    /// The date and time zone are kept. If the time doesn't exist on that
+    /// date in that time zone, e.g., because of a gap created by daylight
+    /// saving time, then the time after the gap is used.
+    fn with_time(
+        &self,
+        dt: &DateTime,
+        time: civil::Time,
+    ) -> anyhow::Result<DateTime> {
+        let zdt = dt.get();
+        let civil = zdt.date().to_datetime(time);
+        let filled =
+            civil.to_zoned(zdt.time_zone().clone()).with_context(|| {
+                format!("failed to set time of `{dt}` to `{time}`")
+            })?;
+        Ok(DateTime::from(filled))
+    }
 }
 
 impl args::Configurable for Config {
@@ -101,23 +413,133 @@ impl args::Configurable for Config {
         arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
         match *arg {
+            lexopt::Arg::Long("default-time") => {
+                self.default_time = Some(args::parse(p, "--default-time")?);
+            }
             lexopt::Arg::Short('f') | lexopt::Arg::Long("format") => {
-                self.format = args::parse(p, "-f/--format")?;
+                self.format = Some(args::parse(p, "-f/--format")?);
+            }
+            lexopt::Arg::Long("rfc2822") => {
+                self.format = Some(flags::Format::Rfc2822);
+            }
+            lexopt::Arg::Long("rfc3339") => {
+                self.format = Some(flags::Format::Rfc3339);
+            }
+            lexopt::Arg::Long("http-date") => {
+                self.format = Some(flags::Format::Rfc9110);
             }
             lexopt::Arg::Short('i') | lexopt::Arg::Long("ignore-invalid") => {
                 self.ignore_invalid = true;
             }
+            lexopt::Arg::Long("locale") => {
+                self.locale = Some(args::parse(p, "--locale")?);
+            }
+            lexopt::Arg::Long("output") => {
+                self.output = args::parse(p, "--output")?;
+            }
             lexopt::Arg::Short('r') | lexopt::Arg::Long("relative") => {
                 let relative: DateTimeFlexible =
                     args::parse(p, "-r/--relative")?;
                 self.relative = relative.into();
             }
+            lexopt::Arg::Long("strict") => {
+                self.strict = true;
+            }
+            lexopt::Arg::Long("show-format") => {
+                let Some(value) = p.optional_value() else {
+                    self.show_format = Some(ShowFormat::Stderr);
+                    return Ok(true);
+                };
+                self.show_format = Some(match value.to_str() {
+                    Some("stderr") => ShowFormat::Stderr,
+                    Some("column") => ShowFormat::Column,
+                    _ => anyhow::bail!(
+                        "unrecognized --show-format value `{}`, \
+                         expected `stderr` or `column`",
+                        value.to_string_lossy(),
+                    ),
+                });
+            }
+            lexopt::Arg::Long("try") => {
+                self.tries.push(args::parse(p, "--try")?);
+            }
             _ => return Ok(false),
         }
         Ok(true)
     }
 
     fn usage(&self) -> &[Usage] {
+        const RFC2822: Usage = Usage::flag(
+            "--rfc2822",
+            "Parse RFC 2822 timestamps, e.g., from email headers.",
+            r#"
+Parse RFC 2822 timestamps, e.g., from email headers.
+
+This is equivalent to `-f rfc2822`. For example,
+`Sat, 20 Jul 2024 16:30:55 -0400`. The day of the week and seconds are
+optional. When the day of the week is present, it must match the date.
+
+The obsolete two digit years are accepted: `00` through `49` are interpreted as
+`2000` through `2049`, and `50` through `99` are interpreted as `1950` through
+`1999`. The offset `-0000` indicates that the local offset is unknown, so it
+is treated like `Z` in RFC 3339.
+
+Besides numeric offsets, the time zone names `UT`, `UTC`, `GMT` and `Z` (all
+UTC), along with `EST`, `EDT`, `CST`, `CDT`, `MST`, `MDT`, `PST` and `PDT`
+(North American time zones) are accepted. Any other time zone name, like `IST`
+or the obsolete single letter military time zones, results in an error, since
+it's either ambiguous or commonly used incorrectly.
+"#,
+        );
+
+        const RFC3339: Usage = Usage::flag(
+            "--rfc3339",
+            "Parse RFC 3339 timestamps.",
+            r#"
+Parse RFC 3339 timestamps.
+
+This is equivalent to `-f rfc3339`. For example, `2024-07-20T16:30:55-04:00`.
+"#,
+        );
+
+        const HTTP_DATE: Usage = Usage::flag(
+            "--http-date",
+            "Parse HTTP dates, e.g., from HTTP response headers.",
+            r#"
+Parse HTTP dates, e.g., from HTTP response headers.
+
+This is equivalent to `-f rfc9110`. For example,
+`Sat, 20 Jul 2024 20:30:55 GMT`. This is the format used by HTTP headers like
+`Date` and `Last-Modified`, as defined by RFC 9110 (which obsoletes RFC 7231).
+
+Since HTTP dates are a subset of RFC 2822 timestamps, this accepts the same
+inputs as `--rfc2822`. The obsolete RFC 850 and ANSI C `asctime` formats for
+HTTP dates are not supported.
+"#,
+        );
+
+        const DEFAULT_TIME: Usage = Usage::flag(
+            "--default-time <time>",
+            "The time of day to use for inputs with only a date.",
+            r#"
+The time of day to use for inputs with only a date.
+
+By default, an input like `2024-07-20` with no time of day is given a time of
+midnight. When this flag is given, such inputs are given this time instead,
+e.g., `12:00` or `12:00:00`. The date and time zone of the input are kept.
+
+This is useful for avoiding surprises around midnight. In some time zones,
+daylight saving time transitions happen at midnight. When midnight doesn't
+exist on a date, it's moved forward past the gap (usually to 01:00). Noon is
+a common choice to avoid this. If the time given here falls in a gap, then it
+is also moved forward past the gap.
+
+Only the `rfc9557`, `rfc3339`, `flexible` and `strptime`-like formats can
+parse inputs with only a date. With `-f flexible`, relative datetimes like
+`tomorrow` are not affected by this flag.
+"#,
+        );
+
         const IGNORE_INVALID: Usage = Usage::flag(
             "-i/--ignore-invalid",
             "Ignore strings that don't parse in the requested format.",
@@ -127,13 +549,112 @@ Ignore strings that don't parse in the requested format.
 When enabled, these strings are dropped and parsing continues to the next
 input. To see error messages, enable logging with `BTTF_LOG=warn`. When
 disabled, if parsing fails, then execution stops and an error is printed.
+"#,
+        );
+        const LOCALE: Usage = Usage::flag(
+            "--locale <tag>",
+            "Parse month and weekday names written in the given locale.",
+            r#"
+Parse month and weekday names written in the given locale.
+
+The locale is given as a BCP 47 language tag, like `fr` or `de-CH`. When
+given, month and weekday names in each input are translated from the locale
+into English before parsing. This makes it possible to parse datetimes written
+by `bttf time fmt` with a locale set via `BTTF_LOCALE`. For example,
+`20 juil. 2024` is parsed by `-f '%d %b %Y' --locale fr`.
+
+Names are matched case insensitively, and abbreviated names match with or
+without a trailing period. A word that is a prefix of at least three letters
+of a full name is also accepted, e.g., `sept` for `septembre`. If a word could
+refer to more than one name, e.g., `jui` for either `juin` or `juillet` in
+French, then an error is returned.
+
+Names are matched in the order of the name directives in the format, and each
+name is only compared with names of the kind its directive parses. So in
+Spanish, `mar` is parsed as Tuesday by `%a` and as March by `%b`.
+
+Since names are always translated to their full English names, `%b`, `%h` and
+`%B` all accept abbreviated or full localized month names. Similarly for `%a`
+and `%A` with weekday names. This flag can only be used with `strptime`-like
+formats given to `-f/--format`.
+
+The special `und` locale is the same as not giving this flag at all. This
+flag requires that bttf was compiled with the `locale` feature.
+"#,
+        );
+        const SHOW_FORMAT: Usage = Usage::flag(
+            "--show-format[=<where>]",
+            "Report which format was recognized for each datetime.",
+            r#"
+Report which format was recognized for each datetime.
+
+This is useful for debugging why some inputs parse unexpectedly, especially
+with `-f flexible`, which tries several formats in turn. The label for a
+flexible datetime is one of `rfc9557`, `rfc3339`, `rfc2822`, `local` (a civil
+datetime without an offset, interpreted in your system time zone), `ordinal`
+(an ISO 8601 ordinal date like `2024-202`), `week-date` (an ISO 8601 week date
+like `2024-W29-6`) or `relative` (e.g., `now` or `1 hour ago`). For any other
+format, the label is the format given to `-f/--format` (or the one given to
+`--try` that succeeded).
+
+By default, or with `--show-format=stderr`, each input is printed to stderr
+along with its label. With `--show-format=column`, the label is printed as a
+tab separated column before each parsed datetime on stdout. When a line of
+tagged data has multiple tags, the labels are joined with a `,`.
+"#,
+        );
+        const STRICT: Usage = Usage::flag(
+            "--strict",
+            "Reject offsets that are inconsistent with their time zone.",
+            r#"
+Reject offsets that are inconsistent with their time zone.
+
+A datetime like `2024-07-20T12:00-08[America/New_York]` is inconsistent,
+since New York is at `-04`, not `-08`, at that instant. This usually indicates
+malformed data. By default, a warning with both the stated and expected
+offsets is printed to stderr, and the datetime is parsed at the instant given
+by its offset, in its time zone. When this flag is given, such datetimes are
+rejected with an error instead. (Which may be ignored with
+`-i/--ignore-invalid`.)
+
+Only formats that can parse both an offset and a time zone are checked. That
+is, `rfc9557`, `flexible` and `strptime`-like formats with both `%z` (or
+similar) and `%Q`. A critical time zone annotation, like
+`[!America/New_York]`, is always rejected when inconsistent.
+"#,
+        );
+        const TRY: Usage = Usage::flag(
+            "--try <format>",
+            "Try parsing with this format, may be given more than once.",
+            r#"
+Try parsing with this format, may be given more than once.
+
+When given, each input is parsed with each format in the order given, and the
+first format that succeeds is used. This is useful for inputs with a mix of
+formats, like a log where some lines are RFC 3339 timestamps and others are
+Unix timestamps. If no format succeeds, then the error lists every format that
+was tried. (The error for each format can be seen by enabling logging with
+`BTTF_LOG=debug`.)
+
+Each format accepts the same values as `-f/--format`. This flag cannot be
+combined with `-f/--format` or any of the flags that set it, like
+`--rfc2822`. Use `--show-format` to see which format was used for each input.
 "#,
         );
         &[
             DateTime::ARG_OR_STDIN,
             flags::Format::USAGE_PARSE,
+            DEFAULT_TIME,
+            RFC2822,
+            RFC3339,
+            HTTP_DATE,
             IGNORE_INVALID,
+            LOCALE,
+            flags::Output::USAGE,
             DateTime::RELATIVE_FLAG,
+            SHOW_FORMAT,
+            STRICT,
+            TRY,
         ]
     }
 }
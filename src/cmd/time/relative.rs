@@ -3,12 +3,12 @@ use std::io::Write;
 use anyhow::Context;
 
 use crate::{
-    args::{self, Usage, positional},
+    args::{self, Usage, flags, positional},
     datetime::{DateTime, DateTimeFlexible},
     parse::OsStrExt,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Interpret a relative description of a datetime with one or more datetimes given
 as reference points.
 
@@ -34,7 +34,8 @@ OPTIONS:
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
     let mut datetimes = positional::DateTimes::default();
-    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+    let mut now = flags::Now;
+    args::configure(p, USAGE, &mut [&mut config, &mut now, &mut datetimes])?;
 
     let relative = config
         .relative
@@ -93,8 +94,8 @@ allowed, for example, `1 year 1 second` or `1y1s`.
 Some special strings are supported as well:
 
 `now` refers to the current datetime to the highest precision supported by
-your system. The current datetime is computed once when bttf starts, or if the
-`BTTF_NOW` environment variable is set, that time is used instead.
+your system. The current datetime is computed once when bttf starts. It can be
+overridden by the `--now` flag or the `BTTF_NOW` environment variable.
 
 `today` refers to the first instant of the current day.
 
@@ -1,12 +1,16 @@
 use std::io::Write;
 
+use jiff::Timestamp;
+
 use crate::{
     args::{self, Usage, positional},
     round::DateTimeRound,
     span::TimeSpan,
 };
 
-const USAGE: &'static str = r#"
+use super::nearest::Epoch;
+
+const USAGE: &str = r#"
 Round a datetime.
 
 This accepts one or more datetimes as positional arguments. When no positional
@@ -14,6 +18,13 @@ arguments are given, then line delimited datetimes are read from stdin.
 
 Rounding only works for units of days or lower.
 
+By default, datetimes are rounded to boundaries of the smallest unit on the
+datetime's own clock. For example, rounding to 10 minute increments always
+produces times like `12:00` or `12:10`. With `--origin`, datetimes are instead
+rounded to the nearest instant that is a multiple of the increment away from
+the origin. This makes it possible to round to boundaries with any phase, like
+`12:03` and `12:13`.
+
 USAGE:
     bttf time round <datetime>...
     bttf time round < line delimited <datetime>
@@ -39,6 +50,20 @@ EXAMPLES:
         $ bttf time round -s minute -i 30 2025-03-05T12:15
         2025-03-05T12:30:00-05:00[America/New_York]
 
+    Snap datetimes to a 5 minute grid, e.g., for bucketing metrics:
+
+        $ bttf time round -s minute -i 5 2025-03-05T16:32 2025-03-05T16:33
+        2025-03-05T16:30:00-05:00[America/New_York]
+        2025-03-05T16:35:00-05:00[America/New_York]
+
+    Round down to 10 minute boundaries that are offset by 3 minutes, like the
+    schedule of a cron job that runs at 3, 13, 23 minutes past the hour and
+    so on:
+
+        $ bttf time round -s minute -i 10 -m floor --origin 2025-03-05T00:03 \
+            2025-03-05T12:15
+        2025-03-05T12:13:00-05:00[America/New_York]
+
     Rounding takes daylight saving time into account. For example, 2025-03-09
     was only 23 hours long. So a time of 12:15 on that day will round down,
     where as it would typically round up (using the default rounding mode):
@@ -76,7 +101,10 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
 
     let mut wtr = std::io::stdout().lock();
     datetimes.try_map(|datum| {
-        let rounded = datum.try_map(|dt| rounder.round(&dt))?;
+        let rounded = datum.try_map(|dt| match config.origin {
+            None => rounder.round(&dt),
+            Some(origin) => rounder.round_from(&dt, origin),
+        })?;
         rounded.write(&mut wtr)?;
         writeln!(wtr)?;
         Ok(true)
@@ -85,18 +113,46 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
 }
 
 #[derive(Debug, Default)]
-struct Config {}
+struct Config {
+    origin: Option<Timestamp>,
+}
 
 impl args::Configurable for Config {
     fn configure(
         &mut self,
-        _: &mut lexopt::Parser,
-        _: &mut lexopt::Arg,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
-        Ok(false)
+        match *arg {
+            lexopt::Arg::Long("origin") => {
+                let origin: Epoch = args::parse_bytes(p, "--origin")?;
+                self.origin = Some(origin.0);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
     }
 
     fn usage(&self) -> &[Usage] {
-        &[TimeSpan::ARG_OR_STDIN]
+        const ORIGIN: Usage = Usage::flag(
+            "--origin <datetime>",
+            "Round to multiples of the increment away from this datetime.",
+            r#"
+Round to multiples of the increment away from this datetime.
+
+When given, each datetime is rounded to an instant that is a multiple of the
+rounding increment (in units of `-s/--smallest`) away from this origin. It may
+be given as an integer number of seconds since the Unix epoch or as any
+datetime accepted elsewhere by bttf. The origin doesn't need to precede the
+datetimes being rounded.
+
+Rounding is done on the physical time elapsed since the origin, and so days
+are always treated as 24 hours, regardless of time zone transitions. Note that
+`trunc` and `expand` round toward and away from the origin, respectively. Use
+`floor` and `ceil` to always round to an earlier or later instant.
+"#,
+        );
+
+        &[TimeSpan::ARG_OR_STDIN, ORIGIN]
     }
 }
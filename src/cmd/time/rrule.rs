@@ -0,0 +1,204 @@
+use std::io::Write;
+
+use {anyhow::Context, lexopt::ValueExt};
+
+use crate::{
+    args::{self, Usage},
+    datetime::{DateTime, DateTimeFlexible},
+    ical::RecurrenceRule,
+};
+
+const USAGE: &str = r#"
+Expand an RFC 5545 recurrence rule into a sequence of datetimes.
+
+This parses the value of an iCalendar `RRULE` property, like
+`FREQ=MONTHLY;BYDAY=1FR;COUNT=10`, and prints each datetime it generates in
+chronological order. The starting point of the rule (what iCalendar calls
+`DTSTART`) is given by `--start` and defaults to the current time. Datetimes
+are generated in the time zone of the starting point.
+
+This uses the same recurrence rule engine as `bttf time seq`. Unlike
+`bttf time seq`, the rule is given in the standard iCalendar syntax, which
+makes it convenient for expanding rules copied out of calendar files.
+
+Unless the rule has a `COUNT` or `UNTIL` part, or the `-c/--count` or
+`-u/--until` flags are used, this command will generate datetimes until bttf's
+maximum is reached.
+
+USAGE:
+    bttf time rrule <rrule>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the first Friday of the next 3 months:
+
+        $ bttf time rrule --start 2025-03-01T09:00 'FREQ=MONTHLY;BYDAY=1FR;COUNT=3'
+        2025-03-07T09:00:00-05:00[America/New_York]
+        2025-04-04T09:00:00-04:00[America/New_York]
+        2025-05-02T09:00:00-04:00[America/New_York]
+
+    %snip-start%
+
+    Expand a rule straight out of an iCalendar file, with a starting point in
+    a particular time zone:
+
+        $ bttf time rrule --start '19970902T090000[America/New_York]' \
+            'RRULE:FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=TU,TH;COUNT=4'
+        1997-09-02T09:00:00-04:00[America/New_York]
+        1997-09-04T09:00:00-04:00[America/New_York]
+        1997-09-16T09:00:00-04:00[America/New_York]
+        1997-09-18T09:00:00-04:00[America/New_York]
+
+    Find the last work-day of each month for the rest of the year:
+
+        $ bttf time rrule --start 2025-09-01T17:00 -u 2026-01-01 \
+            'FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1'
+        2025-09-30T17:00:00-04:00[America/New_York]
+        2025-10-31T17:00:00-04:00[America/New_York]
+        2025-11-28T17:00:00-05:00[America/New_York]
+        2025-12-31T17:00:00-05:00[America/New_York]
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let rrule = config.rrule.as_deref().context("missing required <rrule>")?;
+    let start = config.start.clone().unwrap_or_else(DateTime::now);
+    let rule = RecurrenceRule::from_rrule_str(start.get().clone(), rrule)?;
+    // When both the rule and the command line give an "until" or a "count,"
+    // the one that stops the sequence sooner wins.
+    let until = config.until.as_ref().map(|until| until.get());
+    let mut wtr = std::io::stdout().lock();
+    for zdt in rule
+        .iter()
+        .take(config.count.unwrap_or(usize::MAX))
+        .take_while(|zdt| until.is_none_or(|until| zdt <= until))
+    {
+        writeln!(wtr, "{}", DateTime::from(zdt))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    rrule: Option<String>,
+    start: Option<DateTime>,
+    until: Option<DateTime>,
+    count: Option<usize>,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Short('c') | lexopt::Arg::Long("count") => {
+                self.count = Some(args::parse(p, "-c/--count")?);
+            }
+            lexopt::Arg::Short('u') | lexopt::Arg::Long("until") => {
+                let until: DateTimeFlexible = args::parse(p, "-u/--until")?;
+                self.until = Some(until.into());
+            }
+            lexopt::Arg::Long("start") => {
+                let start: DateTimeFlexible = args::parse(p, "--start")?;
+                self.start = Some(start.into());
+            }
+            lexopt::Arg::Value(ref mut v) => {
+                if self.rrule.is_some() {
+                    return Ok(false);
+                }
+                let rrule = std::mem::take(v)
+                    .string()
+                    .context("recurrence rule must be valid UTF-8")?;
+                self.rrule = Some(rrule);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const RRULE: Usage = Usage::arg(
+            "<rrule>",
+            "An RFC 5545 recurrence rule, e.g., `FREQ=DAILY;COUNT=10`.",
+            r#"
+An RFC 5545 recurrence rule, e.g., `FREQ=DAILY;COUNT=10`.
+
+This is a list of `NAME=VALUE` parts separated by semicolons. It may
+optionally start with `RRULE:`. Names are case insensitive. The `FREQ` part is
+required. Every other part is optional and may be given at most once. The
+supported parts are:
+
+`FREQ`: one of `YEARLY`, `MONTHLY`, `WEEKLY`, `DAILY`, `HOURLY`, `MINUTELY`
+or `SECONDLY`.
+
+`INTERVAL`: a positive integer.
+
+`COUNT`: the number of datetimes to generate.
+
+`UNTIL`: the last datetime to generate (inclusive). This may be a UTC
+datetime like `19971224T000000Z`, a local datetime like `19971224T000000`
+(interpreted in the time zone of the starting point) or a date like
+`19971224` (which includes every datetime on that date). This cannot be used
+with `COUNT`.
+
+`BYMONTH`, `BYWEEKNO`, `BYYEARDAY`, `BYMONTHDAY`, `BYHOUR`, `BYMINUTE`,
+`BYSECOND`, `BYSETPOS`: comma separated lists of integers.
+
+`BYDAY`: a comma separated list of weekdays, like `MO` or `TU`. Each may be
+preceded by a positive or negative integer to select the Nth weekday of the
+month or year, like `1FR` or `-1SU`.
+
+`WKST`: the weekday on which a week starts. The default is `MO`.
+"#,
+        );
+
+        const COUNT: Usage = Usage::flag(
+            "-c/--count <number>",
+            "Generate at most this number of datetimes.",
+            r#"
+Generate at most this number of datetimes.
+
+When the rule also has a `COUNT` part, the smaller of the two is used. Zero is
+a legal value, but always results in no output.
+"#,
+        );
+
+        const UNTIL: Usage = Usage::flag(
+            "-u/--until <datetime>",
+            "Generate datetimes up to this datetime (inclusive).",
+            r#"
+Generate datetimes up to this datetime (inclusive).
+
+When the rule also has an `UNTIL` part, the earlier of the two is used. Unlike
+the `UNTIL` part, this may be used with a `COUNT` part, in which case the
+sequence stops at whichever limit is reached first.
+"#,
+        );
+
+        const START: Usage = Usage::flag(
+            "--start <datetime>",
+            "The starting point of the rule.",
+            r#"
+The starting point of the rule.
+
+This corresponds to the `DTSTART` property in iCalendar. It defaults to the
+current time. Datetimes are generated in the time zone of this datetime. No
+datetime before this one is ever generated.
+"#,
+        );
+
+        &[RRULE, COUNT, UNTIL, START]
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::Write;
 
-use anyhow::Context;
+use jiff::{SignedDuration, Span, SpanRelativeTo, Zoned};
 
 use crate::{
     args::{
@@ -11,9 +11,10 @@ use crate::{
     datetime::{DateTime, DateTimeFlexible},
     ical::{Frequency, RecurrenceRule},
     parse::OsStrExt,
+    span::TimeSpan,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Generate a sequence of datetimes using RFC 5545 recurrence rules.
 
 Datetimes are generated in chronological order at a given frequency from the
@@ -24,8 +25,16 @@ Unless the `-c/--count` or `--until` flags are used, this command will
 generate datetimes until bttf's maximum is reached. In lieu of `-c/--count`,
 users may also choose to use programs like `head` to limit the output.
 
+The <frequency> may be omitted when `-w/--week-day` is given, in which case
+datetimes are generated daily on each of the given weekdays.
+
+Alternatively, `--step` may be used instead of a <frequency> to generate
+datetimes at a fixed span apart, like `1h30m` or `P2W`.
+
 USAGE:
     bttf time seq <frequency> <datetime>
+    bttf time seq -w <week-day-list> <datetime>
+    bttf time seq --step <span> <datetime>
 
 TIP:
     use -h for short docs and --help for long docs
@@ -42,6 +51,16 @@ EXAMPLES:
 
     %snip-start%
 
+    Generate a datetime every 90 minutes during a work day:
+
+        $ bttf time seq --step 1h30m --until 2025-03-10T17:00 2025-03-10T09:00
+        2025-03-10T09:00:00-04:00[America/New_York]
+        2025-03-10T10:30:00-04:00[America/New_York]
+        2025-03-10T12:00:00-04:00[America/New_York]
+        2025-03-10T13:30:00-04:00[America/New_York]
+        2025-03-10T15:00:00-04:00[America/New_York]
+        2025-03-10T16:30:00-04:00[America/New_York]
+
     Schedule my cat's medication, every Monday, Wednesday and Friday with
     breakfest, for the next two weeks, starting tomorrow:
 
@@ -53,6 +72,17 @@ EXAMPLES:
         2025-04-28T08:30:00-04:00[America/New_York]
         2025-04-30T08:30:00-04:00[America/New_York]
 
+    Generate every Monday, Wednesday and Friday in March 2025:
+
+        $ bttf time seq -w mon,wed,fri --until 2025-03-31 2025-03-01
+        2025-03-03T00:00:00-05:00[America/New_York]
+        2025-03-05T00:00:00-05:00[America/New_York]
+        2025-03-07T00:00:00-05:00[America/New_York]
+        2025-03-10T00:00:00-04:00[America/New_York]
+        2025-03-12T00:00:00-04:00[America/New_York]
+        2025-03-14T00:00:00-04:00[America/New_York]
+        ...
+
     Find the last work-day of the current month:
 
         $ bttf time seq monthly --count 1 -w mon..fri --set-position -1
@@ -102,6 +132,14 @@ EXAMPLES:
         2025-04-10T00:00:00-04:00[America/New_York]
         ...
 
+    Generate a reproducible load testing schedule, with requests every minute
+    give or take 20 seconds:
+
+        $ bttf time seq minutely -c 3 --jitter 20s --seed 42 2025-04-01T09:00
+        2025-04-01T09:00:18.411861607-04:00[America/New_York]
+        2025-04-01T09:00:54.198020974-04:00[America/New_York]
+        2025-04-01T09:01:56.050664346-04:00[America/New_York]
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -112,17 +150,93 @@ OPTIONS:
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
     let mut datetimes = positional::DateTimes::default();
-    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+    let mut now = flags::Now;
+    args::configure(p, USAGE, &mut [&mut config, &mut now, &mut datetimes])?;
 
     let mut wtr = std::io::stdout().lock();
     let terminates = &config.terminates;
-    let rrule = config.recurrence_rule()?;
-    for dt in rrule.iter().map(DateTime::from).take(terminates.count()) {
-        writeln!(wtr, "{dt}")?;
+    let rrule;
+    let datetimes: Box<dyn Iterator<Item = Zoned>> = match config.step()? {
+        Some(step) => Box::new(config.step_series(step)),
+        None => {
+            rrule = config.recurrence_rule()?;
+            Box::new(rrule.iter())
+        }
+    };
+    let Some(jitter) = config.jitter()? else {
+        for dt in datetimes.map(DateTime::from).take(terminates.count()) {
+            writeln!(wtr, "{dt}")?;
+        }
+        return Ok(());
+    };
+
+    let mut rng = Rng::new(config.seed);
+    let mut prev: Option<Zoned> = None;
+    let mut it = datetimes.take(terminates.count()).peekable();
+    while let Some(zdt) = it.next() {
+        let mut jittered = zdt.checked_add(rng.duration(jitter))?;
+        // Unless reordering is permitted, we clamp the jittered datetime
+        // such that it never precedes the previous (jittered) datetime and
+        // never follows the next (unjittered) datetime. Since the next
+        // datetime is itself clamped to be no earlier than this one, the
+        // output remains in chronological order.
+        if !config.allow_reorder {
+            if let Some(ref prev) = prev
+                && jittered < *prev
+            {
+                jittered = prev.clone();
+            }
+            if let Some(next) = it.peek()
+                && jittered > *next
+            {
+                jittered = next.clone();
+            }
+        }
+        writeln!(wtr, "{}", DateTime::from(jittered.clone()))?;
+        prev = Some(jittered);
     }
     Ok(())
 }
 
+/// A small seeded pseudo-random number generator.
+///
+/// This is an implementation of SplitMix64. It is not suitable for
+/// cryptographic purposes, but it is fast, simple and produces the same
+/// sequence of numbers for the same seed on every platform.
+#[derive(Clone, Debug)]
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a duration chosen uniformly from `-max..=max`.
+    ///
+    /// `max` must not be negative.
+    fn duration(&mut self, max: SignedDuration) -> SignedDuration {
+        let max = max.as_nanos();
+        // This can't overflow since the magnitude of a `SignedDuration`
+        // always fits in 96 bits. The modulo introduces a tiny bias, but
+        // that doesn't matter for our purposes.
+        let range = u128::try_from(2 * max + 1).unwrap();
+        let random =
+            (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64());
+        let offset = i128::try_from(random % range).unwrap() - max;
+        SignedDuration::from_nanos_i128(offset)
+    }
+}
+
 #[derive(Debug, Default)]
 struct Config {
     freq: Option<Frequency>,
@@ -139,6 +253,10 @@ struct Config {
     by_second: Vec<CommaSequence<NumberRange<i8>>>,
     by_set_pos: Vec<CommaSequence<NumberRange<i32>>>,
     week_start: flags::Weekday,
+    step: Option<TimeSpan>,
+    jitter: Option<TimeSpan>,
+    seed: u64,
+    allow_reorder: bool,
 }
 
 impl Config {
@@ -189,17 +307,95 @@ impl Config {
         b.build()
     }
 
+    /// Returns the frequency of the sequence.
+    ///
+    /// When no frequency was given but weekdays were, then this defaults to
+    /// a daily frequency. This makes the common case of generating datetimes
+    /// on particular days of the week a little simpler.
     fn freq(&self) -> anyhow::Result<Frequency> {
-        self.freq.context("missing required <frequency>")
+        if let Some(freq) = self.freq {
+            return Ok(freq);
+        }
+        anyhow::ensure!(
+            !self.by_week_day.is_empty(),
+            "missing required <frequency>",
+        );
+        Ok(Frequency::Daily)
+    }
+
+    /// Returns the span to step by between each datetime, if one was given.
+    ///
+    /// This returns an error if the step is zero or if it was combined with
+    /// any of the recurrence rule flags, since those only make sense when
+    /// generating datetimes at a frequency.
+    fn step(&self) -> anyhow::Result<Option<Span>> {
+        let Some(ref step) = self.step else { return Ok(None) };
+        anyhow::ensure!(
+            !step.get().is_zero(),
+            "--step span `{step}` must be non-zero",
+        );
+        anyhow::ensure!(
+            self.freq.is_none(),
+            "--step cannot be used with a <frequency>",
+        );
+        anyhow::ensure!(
+            self.interval.is_none(),
+            "--step cannot be used with -i/--interval",
+        );
+        let has_rule = !self.by_month.is_empty()
+            || !self.by_week.is_empty()
+            || !self.by_year_day.is_empty()
+            || !self.by_month_day.is_empty()
+            || !self.by_week_day.is_empty()
+            || !self.by_hour.is_empty()
+            || !self.by_minute.is_empty()
+            || !self.by_second.is_empty()
+            || !self.by_set_pos.is_empty();
+        anyhow::ensure!(
+            !has_rule,
+            "--step cannot be used with recurrence rule flags \
+             like -w/--week-day or -d/--day",
+        );
+        Ok(Some(*step.get()))
+    }
+
+    /// Returns the sequence of datetimes that are a multiple of `step` away
+    /// from the starting point.
+    ///
+    /// When `-u/--until` is given, the sequence stops at the first datetime
+    /// past it. For negative steps, "past" means "before."
+    fn step_series(&self, step: Span) -> impl Iterator<Item = Zoned> {
+        let until = match self.terminates {
+            Termination::Until(ref until) => Some(until.get().clone()),
+            _ => None,
+        };
+        let forward = step.is_positive();
+        self.start().get().series(step).take_while(move |zdt| {
+            let Some(ref until) = until else { return true };
+            if forward { zdt <= until } else { zdt >= until }
+        })
     }
 
     fn start(&self) -> DateTime {
-        self.start.clone().unwrap_or_else(|| DateTime::now())
+        self.start.clone().unwrap_or_else(DateTime::now)
     }
 
     fn interval(&self) -> i32 {
         self.interval.unwrap_or(1)
     }
+
+    /// Returns the maximum amount of jitter to apply to each datetime, if
+    /// any was requested.
+    fn jitter(&self) -> anyhow::Result<Option<SignedDuration>> {
+        let Some(ref jitter) = self.jitter else { return Ok(None) };
+        anyhow::ensure!(
+            jitter.get().get_years() == 0 && jitter.get().get_months() == 0,
+            "jitter span `{jitter}` must not contain units of months or years",
+        );
+        let dur =
+            jitter.get().to_duration(SpanRelativeTo::days_are_24_hours())?;
+        Ok(Some(dur.abs()))
+    }
 }
 
 impl args::Configurable for Config {
@@ -212,8 +408,23 @@ impl args::Configurable for Config {
 
         match *arg {
             Value(ref v) => {
-                if self.freq.is_none() {
-                    self.freq = Some(v.to_str()?.parse()?);
+                if self.freq.is_none() && self.start.is_none() {
+                    let err = match v.to_str()?.parse() {
+                        Ok(freq) => {
+                            self.freq = Some(freq);
+                            return Ok(true);
+                        }
+                        Err(err) => err,
+                    };
+                    // The frequency may be omitted when weekdays are given,
+                    // in which case the first positional argument is the
+                    // starting datetime. If it isn't a datetime either, then
+                    // report the frequency error, since that's what is
+                    // usually expected first.
+                    let Ok(dt) = v.parse::<DateTimeFlexible>() else {
+                        return Err(err);
+                    };
+                    self.start = Some(dt.into());
                     return Ok(true);
                 }
                 if self.start.is_none() {
@@ -254,7 +465,7 @@ impl args::Configurable for Config {
             Short('d') | Long("day") => {
                 self.by_month_day.push(args::parse(p, "-d/--day")?);
             }
-            Short('w') | Long("week-day") => {
+            Short('w') | Long("week-day") | Long("by-weekday") => {
                 self.by_week_day.push(args::parse(p, "-w/--week-day")?);
             }
             Short('H') | Long("hour") => {
@@ -272,6 +483,18 @@ impl args::Configurable for Config {
             Long("week-start") => {
                 self.week_start = args::parse(p, "--week-start")?;
             }
+            Long("step") => {
+                self.step = Some(args::parse(p, "--step")?);
+            }
+            Long("jitter") => {
+                self.jitter = Some(args::parse(p, "--jitter")?);
+            }
+            Long("seed") => {
+                self.seed = args::parse(p, "--seed")?;
+            }
+            Long("allow-reorder") => {
+                self.allow_reorder = true;
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -410,6 +633,10 @@ when a day of the year or month is set, and otherwise expands.
 
 Numbered weekdays can only be used at monthly or yearly frequencies. And when
 at a yearly frequency, this can't be used with week numbers.
+
+When this flag is given, the <frequency> may be omitted. In that case, a daily
+frequency is used, which generates every datetime on the given weekdays. This
+flag may also be spelled as `--by-weekday`.
 "#,
         );
         const BY_HOUR: Usage = Usage::flag(
@@ -499,6 +726,78 @@ This flag can only be used in conjunction with some other rule.
 "#,
         );
 
+        const STEP: Usage = Usage::flag(
+            "--step <span>",
+            "Generate datetimes a fixed span apart instead of at a frequency.",
+            r#"
+Generate datetimes a fixed span apart instead of at a frequency.
+
+The span may be in the ISO 8601 format (e.g., `PT1H30M`, `P2W`) or the
+"friendly" format (e.g., `1h30m`, `2 weeks`). When this flag is given, the
+<frequency> must be omitted and none of the recurrence rule flags (like
+`-w/--week-day`) may be used.
+
+The Nth datetime in the sequence is computed by adding N times this span to
+the starting point. The span is added to the datetime in its time zone, so
+calendar units like days are not always 24 hours long. For example, stepping
+by `1 day` across a daylight saving time transition keeps the same time on
+the clock, and stepping by `1 month` from January 31 yields the last day of
+each subsequent month.
+
+A negative span generates datetimes going backwards in time. In that case,
+`-u/--until` bounds the sequence from below. A zero span is an error.
+"#,
+        );
+
+        const JITTER: Usage = Usage::flag(
+            "--jitter <span>",
+            "Perturb each datetime by a pseudo-random amount within a span.",
+            r#"
+Perturb each datetime by a pseudo-random amount within a span.
+
+Each datetime in the sequence is moved forward or backward by an amount chosen
+uniformly at random between the negation of this span and the span itself.
+This is useful for generating realistic schedules for load testing. The
+pseudo-random amounts are determined entirely by `--seed`, so repeated runs
+with the same flags produce the same output.
+
+The span must not contain units of months or years. Weeks and days are always
+treated as 7 days and 24 hours, respectively. A negative span is treated the
+same as its absolute value.
+
+By default, jittered datetimes are clamped such that the sequence remains in
+chronological order. That is, no jittered datetime will precede the one before
+it or follow the next datetime in the sequence. Use `--allow-reorder` to
+disable this.
+
+Jitter is applied after the sequence is generated. So a jittered datetime may
+precede the starting point or follow the datetime given to `-u/--until`.
+"#,
+        );
+
+        const SEED: Usage = Usage::flag(
+            "--seed <number>",
+            "Set the seed used to generate jitter.",
+            r#"
+Set the seed used to generate jitter.
+
+This may be any non-negative integer that fits into 64 bits. The default is
+`0`. This flag has no effect unless `--jitter` is given.
+"#,
+        );
+
+        const ALLOW_REORDER: Usage = Usage::flag(
+            "--allow-reorder",
+            "Permit jitter to reorder datetimes in the sequence.",
+            r#"
+Permit jitter to reorder datetimes in the sequence.
+
+By default, `--jitter` is clamped such that the sequence stays in
+chronological order. When this flag is given, no clamping is done and the
+sequence may be printed out of order.
+"#,
+        );
+
         &[
             Frequency::USAGE,
             DateTime::ARG,
@@ -515,6 +814,10 @@ This flag can only be used in conjunction with some other rule.
             BY_MINUTE,
             BY_SECOND,
             BY_SET_POS,
+            STEP,
+            JITTER,
+            SEED,
+            ALLOW_REORDER,
         ]
     }
 }
@@ -0,0 +1,234 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    bstr::ByteSlice,
+    jiff::SignedDuration,
+    lexopt::{Arg, Parser},
+};
+
+use crate::{
+    args::{self, Usage, flags},
+    datetime::DateTime,
+    parse::{BufReadExt, BytesExt},
+    span::TimeSpan,
+};
+
+const USAGE: &str = r#"
+Report the clock skew between pairs of datetimes.
+
+This reads line delimited data from a file or stdin, where each line contains
+two datetimes separated by a tab. For example, the time a message was sent and
+the time it was received. For each line, the signed difference between the
+second datetime and the first is printed. A positive skew means the second
+datetime is later than the first.
+
+Unlike `bttf span since`, which computes spans relative to one reference
+datetime, this command computes a span for every pair independently. Skews
+are always reported in units of hours or smaller, since a skew is a physical
+duration.
+
+USAGE:
+    bttf time skew <path>
+    bttf time skew < line delimited <datetime>\t<datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Report the skew between sent and received timestamps:
+
+        $ printf '2025-03-15T10:00:00Z\t2025-03-15T10:00:02.5Z\n' \
+            | bttf time skew
+        2s 500ms
+
+    %snip-start%
+
+    Print the magnitude of each skew along with a summary at the end:
+
+        $ bttf time skew --abs --summary pairs.tsv
+        2s 500ms
+        1s 250ms
+        min: 1s 250ms
+        max: 2s 500ms
+        mean: 1s 875ms
+
+    Fail if any pair is skewed by more than one second in either direction:
+
+        $ bttf time skew --threshold 1s pairs.tsv
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let mut wtr = std::io::stdout().lock();
+    let mut summary = Summary::default();
+    let mut exceeded = 0;
+    let result = config.input.reader()?.for_byte_line(|line| {
+        let (sent, received) = parse_pair(line.content())
+            .with_context(|| format!("line {}", line.number()))?;
+        let mut skew = received.get().duration_since(sent.get());
+        if let Some(ref threshold) = config.threshold {
+            let limit = threshold.get().to_duration(sent.get())?;
+            if skew.abs() > limit.abs() {
+                exceeded += 1;
+            }
+        }
+        if config.abs {
+            skew = skew.abs();
+        }
+        summary.add(skew);
+        writeln!(wtr, "{skew:#}")?;
+        Ok(true)
+    });
+    result.with_context(|| format!("{}", config.input.display()))?;
+    if config.summary {
+        summary.write(&mut wtr)?;
+    }
+    if exceeded > 0 {
+        // This is always set when `exceeded > 0`.
+        let threshold = config.threshold.as_ref().unwrap();
+        anyhow::bail!(
+            "found {exceeded} pair(s) with a skew exceeding {threshold}"
+        );
+    }
+    Ok(())
+}
+
+/// Parses a pair of tab separated datetimes.
+fn parse_pair(content: &[u8]) -> anyhow::Result<(DateTime, DateTime)> {
+    let (sent, received) =
+        content.split_once_str("\t").with_context(|| {
+            format!(
+                "expected two tab separated datetimes, but got `{}`",
+                content.as_bstr(),
+            )
+        })?;
+    let sent: DateTime = sent.trim_ascii().parse()?;
+    let received: DateTime = received.trim_ascii().parse()?;
+    Ok((sent, received))
+}
+
+/// Tracks the minimum, maximum and mean skew seen so far.
+#[derive(Debug, Default)]
+struct Summary {
+    min: Option<SignedDuration>,
+    max: Option<SignedDuration>,
+    total: i128,
+    count: i128,
+}
+
+impl Summary {
+    fn add(&mut self, skew: SignedDuration) {
+        self.min = Some(self.min.map_or(skew, |min| min.min(skew)));
+        self.max = Some(self.max.map_or(skew, |max| max.max(skew)));
+        self.total += skew.as_nanos();
+        self.count += 1;
+    }
+
+    fn write(&self, mut wtr: impl Write) -> anyhow::Result<()> {
+        let (Some(min), Some(max)) = (self.min, self.max) else {
+            return Ok(());
+        };
+        // The mean of a set of durations is always between the minimum and
+        // maximum, so this can never overflow.
+        let mean = SignedDuration::from_nanos_i128(self.total / self.count);
+        writeln!(wtr, "min: {min:#}")?;
+        writeln!(wtr, "max: {max:#}")?;
+        writeln!(wtr, "mean: {mean:#}")?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    input: flags::FileOrStdin,
+    abs: bool,
+    threshold: Option<TimeSpan>,
+    summary: bool,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut Parser,
+        arg: &mut Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            Arg::Long("abs") => {
+                self.abs = true;
+            }
+            Arg::Short('t') | Arg::Long("threshold") => {
+                self.threshold = Some(args::parse(p, "-t/--threshold")?);
+            }
+            Arg::Long("summary") => {
+                self.summary = true;
+            }
+            Arg::Value(ref mut v) => {
+                self.input.set(std::mem::take(v))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const PATH: Usage = Usage::arg(
+            "<path>",
+            "A file path to read pairs of datetimes from.",
+            r#"
+A file path to read pairs of datetimes from.
+
+Each line must contain two datetimes separated by a tab. In lieu of a specific
+file path, users may also pass line delimited data into stdin.
+"#,
+        );
+
+        const ABS: Usage = Usage::flag(
+            "--abs",
+            "Print the magnitude of each skew.",
+            r#"
+Print the magnitude of each skew.
+
+By default, a skew is negative when the second datetime on a line precedes the
+first. When this flag is given, the sign is dropped. This also applies to the
+statistics printed by `--summary`.
+"#,
+        );
+
+        const THRESHOLD: Usage = Usage::flag(
+            "-t/--threshold <span>",
+            "Exit with an error if any skew exceeds this span.",
+            r#"
+Exit with an error if the magnitude of any skew exceeds this span.
+
+All pairs are still processed and printed. If one or more skews exceed the
+threshold, then an error is reported at the end and bttf exits with a non-zero
+status.
+
+When the span contains calendar units, it is interpreted relative to the
+first datetime on each line.
+"#,
+        );
+
+        const SUMMARY: Usage = Usage::flag(
+            "--summary",
+            "Print the minimum, maximum and mean skew at the end.",
+            r#"
+Print the minimum, maximum and mean skew at the end.
+
+The summary is printed after all pairs. Nothing is printed if the input is
+empty.
+"#,
+        );
+
+        &[PATH, ABS, THRESHOLD, SUMMARY]
+    }
+}
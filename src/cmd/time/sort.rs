@@ -1,11 +1,16 @@
-use std::io::Write;
+use std::{io::Write, ops::Range};
+
+use {
+    anyhow::Context,
+    bstr::{BString, ByteSlice},
+};
 
 use crate::{
-    args::{self, Usage, positional},
+    args::{self, Usage, flags, positional},
     datetime::DateTime,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Sort datetimes in ascending (the default) or descending order.
 
 This accepts one or more datetimes as positional arguments. When no positional
@@ -13,6 +18,11 @@ arguments are given, then line delimited datetimes are read from stdin. When
 reading from stdin, tagged data is also accepted. In the case of tagged data
 with multiple datetime tags, sorting is done lexicographically.
 
+Datetimes are compared by the instant in time they represent, regardless of
+their time zone. The sort is stable, so datetimes (or tagged records) that
+compare equal retain their relative order from the input. Use `-u/--unique` to
+keep only the first of each group of records that compare equal.
+
 USAGE:
     bttf time sort <datetime>...
     bttf time sort < line delimited <datetime>
@@ -29,6 +39,22 @@ EXAMPLES:
             | bttf time sort \
             | bttf untag -f '{tag} {data}'
 
+    %snip-start%
+
+    Sort datetimes from newest to oldest, dropping duplicate instants:
+
+        $ bttf time sort -r -u 2025-03-01T12Z 2025-03-01T07-05 2025-02-01T00Z
+        2025-03-01T12:00:00Z[Etc/Unknown]
+        2025-02-01T00:00:00Z[Etc/Unknown]
+
+    Quickly sort a large file of Unix timestamps in milliseconds:
+
+        $ bttf time sort --numeric-epoch --epoch ms < timestamps
+        1741186800000
+        1741190400000
+        1741194000000
+
+    %snip-end%
 REQUIRED ARGUMENTS:
 %args%
 OPTIONS:
@@ -37,12 +63,15 @@ OPTIONS:
 
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
-    let mut datetimes = positional::DateTimes::default();
-    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+    let mut arguments = positional::Arguments::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut arguments])?;
 
+    if let Some(unit) = config.numeric_epoch()? {
+        return sort_numeric_epoch(arguments, unit, &config);
+    }
     let mut dts = vec![];
-    datetimes.try_map(|dt| {
-        dts.push(dt);
+    arguments.try_map(|arg| {
+        dts.push(arg.to_datetime()?);
         Ok(true)
     })?;
 
@@ -51,6 +80,9 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     } else {
         dts.sort();
     }
+    if config.unique {
+        dts.dedup();
+    }
 
     let mut wtr = std::io::stdout().lock();
     for dt in dts {
@@ -60,21 +92,92 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Sorts integer Unix timestamps without parsing them into datetimes.
+///
+/// Each argument is printed exactly as it was given. Since the sort is
+/// stable, arguments with equal timestamps retain their relative order.
+fn sort_numeric_epoch(
+    arguments: positional::Arguments,
+    unit: flags::EpochUnit,
+    config: &Config,
+) -> anyhow::Result<()> {
+    // To avoid an allocation for each timestamp, the raw inputs are all
+    // stored in one buffer and each timestamp refers to its range in it.
+    let mut raw_inputs = BString::new(vec![]);
+    let mut timestamps: Vec<(i64, Range<usize>)> = vec![];
+    arguments.try_map(|arg| {
+        let raw = arg.raw();
+        let number = unit.parse(raw).with_context(|| {
+            format!("invalid epoch timestamp `{raw}`", raw = raw.as_bstr())
+        })?;
+        let start = raw_inputs.len();
+        raw_inputs.extend_from_slice(raw);
+        timestamps.push((number, start..raw_inputs.len()));
+        Ok(true)
+    })?;
+
+    if config.reverse {
+        timestamps.sort_by(|(n1, _), (n2, _)| n1.cmp(n2).reverse());
+    } else {
+        timestamps.sort_by_key(|&(n, _)| n);
+    }
+    if config.unique {
+        timestamps.dedup_by_key(|&mut (n, _)| n);
+    }
+
+    // Since stdout is line buffered, writing many short lines is dominated
+    // by syscalls without an explicit buffer.
+    let mut wtr = std::io::BufWriter::new(std::io::stdout().lock());
+    for (_, range) in timestamps {
+        wtr.write_all(&raw_inputs[range])?;
+        wtr.write_all(b"\n")?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
 #[derive(Debug, Default)]
 struct Config {
     reverse: bool,
+    unique: bool,
+    numeric_epoch: bool,
+    epoch: Option<flags::EpochUnit>,
+}
+
+impl Config {
+    /// Returns the unit of integer timestamps to sort, if the numeric fast
+    /// path was requested.
+    fn numeric_epoch(&self) -> anyhow::Result<Option<flags::EpochUnit>> {
+        if !self.numeric_epoch {
+            anyhow::ensure!(
+                self.epoch.is_none(),
+                "--epoch requires --numeric-epoch",
+            );
+            return Ok(None);
+        }
+        Ok(Some(self.epoch.unwrap_or(flags::EpochUnit::Second)))
+    }
 }
 
 impl args::Configurable for Config {
     fn configure(
         &mut self,
-        _: &mut lexopt::Parser,
+        p: &mut lexopt::Parser,
         arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
         match *arg {
             lexopt::Arg::Short('r') | lexopt::Arg::Long("reverse") => {
                 self.reverse = true;
             }
+            lexopt::Arg::Short('u') | lexopt::Arg::Long("unique") => {
+                self.unique = true;
+            }
+            lexopt::Arg::Long("numeric-epoch") => {
+                self.numeric_epoch = true;
+            }
+            lexopt::Arg::Long("epoch") => {
+                self.epoch = Some(args::parse_bytes(p, "--epoch")?);
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -87,10 +190,60 @@ impl args::Configurable for Config {
             r#"
 Sort datetimes in descending (newer to older) order.
 
-By default, datetimes are sorted in ascending (older to newer) order.
+By default, datetimes are sorted in ascending (older to newer) order. Either
+way, datetimes that compare equal retain their relative order from the input.
+"#,
+        );
+
+        const UNIQUE: Usage = Usage::flag(
+            "-u/--unique",
+            "Drop duplicate datetimes after sorting.",
+            r#"
+Drop duplicate datetimes after sorting.
+
+After sorting, only the first of each group of datetimes that refer to the same
+instant in time is kept. Since the sort is stable, this is the one that
+appeared first in the input. Datetimes in different time zones are equal when
+they refer to the same instant. For example, `2025-03-01T12Z` and
+`2025-03-01T07-05` are equal.
+
+For tagged data, records are equal when all of their datetime tags are equal.
+The data associated with records that are dropped is not printed.
+"#,
+        );
+
+        const NUMERIC_EPOCH: Usage = Usage::flag(
+            "--numeric-epoch",
+            "Sort integer Unix timestamps without parsing them as datetimes.",
+            r#"
+Sort integer Unix timestamps without parsing them as datetimes.
+
+This is a fast path for sorting large inputs made up entirely of integer Unix
+timestamps. Each input must be an integer number of seconds since the Unix
+epoch (or some other unit given by `--epoch`), and it is an error otherwise.
+Inputs are compared numerically without resolving a time zone for each one,
+and are printed exactly as they were given. Inputs with equal timestamps keep
+their original relative order.
+
+Tagged data is not supported with this flag.
+"#,
+        );
+
+        const EPOCH: Usage = Usage::flag(
+            "--epoch <unit>",
+            "The unit of timestamps sorted by `--numeric-epoch`.",
+            r#"
+The unit of timestamps sorted by `--numeric-epoch`.
+
+The legal values are `s` (the default), `ms`, `us` and `ns`, corresponding to
+seconds, milliseconds, microseconds and nanoseconds since the Unix epoch,
+respectively. Timestamps outside of the range of datetimes supported by bttf
+are rejected.
+
+This requires `--numeric-epoch`.
 "#,
         );
 
-        &[DateTime::ARG_OR_STDIN, REVERSE]
+        &[DateTime::ARG_OR_STDIN, REVERSE, UNIQUE, NUMERIC_EPOCH, EPOCH]
     }
 }
@@ -0,0 +1,106 @@
+use std::io::Write;
+
+use jiff::Timestamp;
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::DateTime,
+};
+
+const USAGE: &str = r#"
+Print a fixed width key for each datetime that sorts lexicographically in the
+same order as the instants the datetimes represent.
+
+This is useful for integrating with tools like `sort` that only know how to
+sort strings. Datetimes in different time zones, or with different offsets,
+do not generally sort correctly as strings. But their keys do.
+
+The key is the number of nanoseconds since the minimum datetime supported by
+bttf (`-009999-01-02T01:59:59Z`), padded with zeros to 21 digits. Since it is
+never negative and always has the same width, two keys compare the same way
+as their corresponding instants, even for datetimes before the Unix epoch.
+The key does not encode the time zone or offset of the original datetime.
+
+This accepts one or more datetimes as positional arguments. When no positional
+arguments are given, then line delimited datetimes are read from stdin. When
+reading from stdin, tagged data is also accepted. Each datetime tag is
+replaced with its key.
+
+USAGE:
+    bttf time sort-key <datetime>...
+    bttf time sort-key < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the sort key for the current time:
+
+        $ bttf time sort-key now
+        379426530656000000000
+
+    %snip-start%
+
+    Sort lines in a log file with datetimes in mixed time zones using `sort`.
+    Here, the key is put at the beginning of each line and removed after
+    sorting:
+
+        bttf tag lines access.log \
+            | bttf time sort-key \
+            | bttf untag -f '{tag}\t{data}' \
+            | sort \
+            | cut -f2-
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut datetimes = positional::DateTimes::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+
+    let mut wtr = std::io::stdout().lock();
+    datetimes.try_map(|datum| {
+        let key = datum.try_map(|dt| Ok(sort_key(&dt)))?;
+        key.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+/// The number of digits in the largest possible sort key.
+///
+/// This corresponds to the number of nanoseconds between the minimum and
+/// maximum timestamps supported by Jiff.
+const WIDTH: usize = 21;
+
+/// Returns a sort key for the given datetime.
+///
+/// The key is the number of nanoseconds since `Timestamp::MIN`, padded with
+/// zeros to a fixed width. It is never negative.
+fn sort_key(dt: &DateTime) -> String {
+    let nanos =
+        dt.get().timestamp().as_nanosecond() - Timestamp::MIN.as_nanosecond();
+    format!("{nanos:0WIDTH$}")
+}
+
+#[derive(Debug, Default)]
+struct Config {}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        _: &mut lexopt::Parser,
+        _: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        &[DateTime::ARG_OR_STDIN]
+    }
+}
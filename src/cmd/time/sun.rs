@@ -0,0 +1,291 @@
+use std::io::Write;
+
+use {
+    anyhow::Context,
+    jiff::{Timestamp, tz::TimeZone},
+};
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::DateTime,
+};
+
+const USAGE_SUNRISE: &str = r#"
+Print the time of sunrise at a location.
+
+For each datetime given, this computes the time at which the sun rises on that
+datetime's date at the location given by `--lat` and `--lon`. The result is
+printed in the time zone of the datetime given. Since the datetime `now` uses
+your system's time zone, asking for the sunrise `now` reports today's sunrise
+in your time zone.
+
+Sunrise is computed with the standard sunrise equation, which accounts for
+atmospheric refraction and the size of the sun's disc, but not for elevation
+or the local terrain. It's typically accurate to within a minute or two,
+although it becomes less accurate close to the poles. The time printed is
+rounded to the nearest second.
+
+When the sun doesn't rise at all on a particular date, which happens during
+polar night or midnight sun close to the poles, an error is reported.
+
+This accepts one or more datetimes as positional arguments. When no positional
+arguments are given, then line delimited datetimes are read from stdin.
+
+USAGE:
+    bttf time sunrise --lat <lat> --lon <lon> <datetime>...
+    bttf time sunrise --lat <lat> --lon <lon> < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the time of sunrise today in New York City:
+
+        $ bttf time sunrise --lat 40.71 --lon -74.01 now
+        2024-07-20T05:41:58-04:00[America/New_York]
+
+    %snip-start%
+
+    Print the time of sunrise in Sydney on the first day of each month, in
+    Sydney's time zone:
+
+        $ bttf time seq monthly -c 3 '2025-01-01[Australia/Sydney]' \
+            | bttf time sunrise --lat -33.87 --lon 151.21
+        2025-01-01T05:47:32+11:00[Australia/Sydney]
+        2025-02-01T06:16:25+11:00[Australia/Sydney]
+        2025-03-01T06:42:52+11:00[Australia/Sydney]
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+const USAGE_SUNSET: &str = r#"
+Print the time of sunset at a location.
+
+For each datetime given, this computes the time at which the sun sets on that
+datetime's date at the location given by `--lat` and `--lon`. The result is
+printed in the time zone of the datetime given. Since the datetime `now` uses
+your system's time zone, asking for the sunset `now` reports today's sunset in
+your time zone.
+
+Sunset is computed with the standard sunrise equation, which accounts for
+atmospheric refraction and the size of the sun's disc, but not for elevation
+or the local terrain. It's typically accurate to within a minute or two,
+although it becomes less accurate close to the poles. The time printed is
+rounded to the nearest second.
+
+When the sun doesn't set at all on a particular date, which happens during
+polar night or midnight sun close to the poles, an error is reported.
+
+This accepts one or more datetimes as positional arguments. When no positional
+arguments are given, then line delimited datetimes are read from stdin.
+
+USAGE:
+    bttf time sunset --lat <lat> --lon <lon> <datetime>...
+    bttf time sunset --lat <lat> --lon <lon> < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the time of sunset today in New York City:
+
+        $ bttf time sunset --lat 40.71 --lon -74.01 now
+        2024-07-20T20:22:30-04:00[America/New_York]
+
+    %snip-start%
+
+    Print how much daylight there is today in New York City:
+
+        $ bttf span since \
+            -r "$(bttf time sunset --lat 40.71 --lon -74.01 now)" \
+            "$(bttf time sunrise --lat 40.71 --lon -74.01 now)"
+        14h 40m 32s
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn rise(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    run(p, USAGE_SUNRISE, Event::Rise)
+}
+
+pub fn set(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    run(p, USAGE_SUNSET, Event::Set)
+}
+
+fn run(
+    p: &mut lexopt::Parser,
+    usage: &'static str,
+    event: Event,
+) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut datetimes = positional::DateTimes::default();
+    args::configure(p, usage, &mut [&mut config, &mut datetimes])?;
+
+    let location = config.location()?;
+    let mut wtr = std::io::stdout().lock();
+    datetimes.try_map(|datum| {
+        let dt = datum.try_map(|dt| location.event(event, &dt))?;
+        dt.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    lat: Option<f64>,
+    lon: Option<f64>,
+}
+
+impl Config {
+    /// Returns the location given, or an error if it's incomplete.
+    fn location(&self) -> anyhow::Result<Location> {
+        let lat = self.lat.context("missing required --lat flag")?;
+        let lon = self.lon.context("missing required --lon flag")?;
+        Ok(Location { lat, lon })
+    }
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("lat") => {
+                let lat: f64 = args::parse(p, "--lat")?;
+                anyhow::ensure!(
+                    (-90.0..=90.0).contains(&lat),
+                    "--lat: latitude `{lat}` must be between -90 and 90",
+                );
+                self.lat = Some(lat);
+            }
+            lexopt::Arg::Long("lon") => {
+                let lon: f64 = args::parse(p, "--lon")?;
+                anyhow::ensure!(
+                    (-180.0..=180.0).contains(&lon),
+                    "--lon: longitude `{lon}` must be between -180 and 180",
+                );
+                self.lon = Some(lon);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const LAT: Usage = Usage::flag(
+            "--lat <degrees>",
+            "The latitude of the location, in decimal degrees.",
+            r#"
+The latitude of the location, in decimal degrees.
+
+This is required. Positive values are north of the equator and negative values
+are south of it. For example, New York City is at about `40.71`.
+"#,
+        );
+
+        const LON: Usage = Usage::flag(
+            "--lon <degrees>",
+            "The longitude of the location, in decimal degrees.",
+            r#"
+The longitude of the location, in decimal degrees.
+
+This is required. Positive values are east of the prime meridian and negative
+values are west of it. For example, New York City is at about `-74.01`.
+"#,
+        );
+
+        &[DateTime::ARG_OR_STDIN, LAT, LON]
+    }
+}
+
+/// Which crossing of the horizon to compute.
+#[derive(Clone, Copy, Debug)]
+enum Event {
+    Rise,
+    Set,
+}
+
+/// A location on Earth, in decimal degrees.
+#[derive(Clone, Copy, Debug)]
+struct Location {
+    lat: f64,
+    lon: f64,
+}
+
+impl Location {
+    /// Returns the sunrise or sunset on the date of the given datetime, in
+    /// the datetime's time zone.
+    ///
+    /// This uses the sunrise equation as described at
+    /// <https://en.wikipedia.org/wiki/Sunrise_equation>.
+    fn event(&self, event: Event, dt: &DateTime) -> anyhow::Result<DateTime> {
+        // The Julian date of the J2000 epoch, 2000-01-01T12:00:00Z.
+        const J2000: f64 = 2451545.0;
+        // The Julian date of the Unix epoch, 1970-01-01T00:00:00Z.
+        const UNIX: f64 = 2440587.5;
+
+        let date = dt.get().date();
+        let days = date
+            .to_zoned(TimeZone::UTC)?
+            .timestamp()
+            .as_second()
+            .div_euclid(86_400);
+        // The number of days since J2000, adjusted to mean solar noon at the
+        // location's longitude.
+        let n = (days as f64) - (J2000 - UNIX - 0.5) - self.lon / 360.0;
+        let anomaly = (357.5291 + 0.98560028 * n).rem_euclid(360.0);
+        let m = anomaly.to_radians();
+        let center = 1.9148 * m.sin()
+            + 0.0200 * (2.0 * m).sin()
+            + 0.0003 * (3.0 * m).sin();
+        let ecliptic = (anomaly + center + 180.0 + 102.9372)
+            .rem_euclid(360.0)
+            .to_radians();
+        let transit =
+            J2000 + n + 0.0053 * m.sin() - 0.0069 * (2.0 * ecliptic).sin();
+        let declination =
+            (ecliptic.sin() * 23.4397f64.to_radians().sin()).asin();
+        // The sun's center is 0.833 degrees below the horizon at sunrise and
+        // sunset, due to refraction and the size of the sun's disc.
+        let lat = self.lat.to_radians();
+        let cos_hour_angle = ((-0.833f64).to_radians().sin()
+            - lat.sin() * declination.sin())
+            / (lat.cos() * declination.cos());
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            let verb = match event {
+                Event::Rise => "rise",
+                Event::Set => "set",
+            };
+            // Too far from the horizon means it's either polar night or
+            // midnight sun.
+            let position =
+                if cos_hour_angle > 1.0 { "below" } else { "above" };
+            anyhow::bail!(
+                "the sun never {verb}s on {date} at latitude {lat}, \
+                 longitude {lon} (it stays {position} the horizon all day)",
+                lat = self.lat,
+                lon = self.lon,
+            );
+        }
+        let hour_angle = cos_hour_angle.acos().to_degrees();
+        let julian = match event {
+            Event::Rise => transit - hour_angle / 360.0,
+            Event::Set => transit + hour_angle / 360.0,
+        };
+        let second = ((julian - UNIX) * 86_400.0).round() as i64;
+        let ts = Timestamp::from_second(second)
+            .context("sunrise or sunset is out of bounds")?;
+        Ok(ts.to_zoned(dt.get().time_zone().clone()).into())
+    }
+}
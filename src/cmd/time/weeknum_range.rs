@@ -0,0 +1,169 @@
+use std::io::Write;
+
+use {anyhow::Context, jiff::ToSpan};
+
+use crate::{
+    args::{self, Usage, flags},
+    datetime::{DateTime, DateTimeFlexible},
+    weekdate::{WeekDate, first_of_week},
+};
+
+const USAGE: &str = r#"
+List the week numbers of every week in a range of dates.
+
+This prints one week per line, written as `<year>-W<week>`, for every week that
+contains at least one date between `--start` and `--until` (inclusive). Only
+the civil date of each datetime is used.
+
+Weeks are numbered according to ISO 8601. That is, week `1` of a year is the
+first week with at least 4 days in that year, and a year has either 52 or 53
+weeks. This means the year of a week may differ from the year of the dates in
+it. For example, 2024-12-31 is in week `2025-W01`. When `--week-start` is
+given, weeks start on that weekday instead of Monday, but are otherwise
+numbered in the same way.
+
+USAGE:
+    bttf time weeknum-range --start <datetime> --until <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    List the weeks in the first month of 2025:
+
+        $ bttf time weeknum-range --start 2025-01-01 --until 2025-01-31
+        2025-W01
+        2025-W02
+        2025-W03
+        2025-W04
+        2025-W05
+
+    %snip-start%
+
+    Include the first date of each week:
+
+        $ bttf time weeknum-range --start 2025-01-01 --until 2025-01-31 \
+            --with-date
+        2025-W01	2024-12-30
+        2025-W02	2025-01-06
+        2025-W03	2025-01-13
+        2025-W04	2025-01-20
+        2025-W05	2025-01-27
+
+    Count the weeks in a week date year with 53 weeks:
+
+        $ bttf time weeknum-range --start 2020-W01-1 --until 2020-W53-7 | wc -l
+        53
+
+    %snip-end%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let start = config.start.as_ref().context("--start is required")?;
+    let until = config.until.as_ref().context("--until is required")?;
+    let (start, until) = (start.get().date(), until.get().date());
+    anyhow::ensure!(
+        start <= until,
+        "end of range `{until}` must not be before its start `{start}`",
+    );
+    let week_start = config.week_start.get();
+
+    let mut wtr = std::io::stdout().lock();
+    let mut first = first_of_week(week_start, start)?;
+    loop {
+        let wd = WeekDate::from_date(week_start, first)?;
+        write!(wtr, "{:04}-W{:02}", wd.year(), wd.week())?;
+        if config.with_date {
+            write!(wtr, "\t{first}")?;
+        }
+        writeln!(wtr)?;
+        // Stopping here avoids an error when the last week of the range is
+        // the last week supported by bttf.
+        if !first.checked_add(6.days()).is_ok_and(|last| last < until) {
+            break;
+        }
+        first = first.checked_add(1.week()).with_context(|| {
+            format!("week after `{first}` is out of range")
+        })?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    start: Option<DateTime>,
+    until: Option<DateTime>,
+    week_start: flags::Weekday,
+    with_date: bool,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("start") => {
+                let start: DateTimeFlexible = args::parse(p, "--start")?;
+                self.start = Some(start.into());
+            }
+            lexopt::Arg::Short('u') | lexopt::Arg::Long("until") => {
+                let until: DateTimeFlexible = args::parse(p, "-u/--until")?;
+                self.until = Some(until.into());
+            }
+            lexopt::Arg::Long("week-start") => {
+                self.week_start = args::parse(p, "--week-start")?;
+            }
+            lexopt::Arg::Long("with-date") => {
+                self.with_date = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const START: Usage = Usage::flag(
+            "--start <datetime>",
+            "The first date in the range.",
+            r#"
+The first date in the range.
+
+This flag is required. Only the civil date of the datetime given is used. The
+first week printed is the week containing this date.
+"#,
+        );
+
+        const UNTIL: Usage = Usage::flag(
+            "-u/--until <datetime>",
+            "The last date in the range.",
+            r#"
+The last date in the range.
+
+This flag is required and must not be before `--start`. Only the civil date of
+the datetime given is used. The last week printed is the week containing this
+date.
+"#,
+        );
+
+        const WITH_DATE: Usage = Usage::flag(
+            "--with-date",
+            "Print the first date of each week after its week number.",
+            r#"
+Print the first date of each week after its week number.
+
+When given, each line contains a week number, followed by a tab and the date
+of the first day of that week. This is a Monday unless `--week-start` is
+given. The first date of the first week may be before `--start`.
+"#,
+        );
+
+        &[START, UNTIL, flags::Weekday::USAGE_WEEK_START, WITH_DATE]
+    }
+}
@@ -0,0 +1,175 @@
+use std::io::Write;
+
+use anyhow::Context;
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::DateTime,
+    parse::OsStrExt,
+    timezone::{self, TimeZone},
+};
+
+const USAGE: &str = r#"
+Print the offset of a time zone at one or more datetimes.
+
+For each datetime, this prints the offset from UTC that the given time zone
+had at the instant of that datetime. The offset always includes minutes, e.g.,
+`-04:00`, so that output lines up. With `--abbrev`, the time zone abbreviation
+in effect at that instant is printed after the offset, separated by a space.
+With `--dst`, either `dst` (when daylight saving time is in effect) or `std`
+(when it isn't) is printed last.
+
+This is meant to be a simple building block for scripts. Only the instant of
+each datetime is used, so its own offset or time zone doesn't matter. To ask
+about a civil datetime in the given time zone, write the time zone as an
+annotation, e.g., `2025-03-09T02:30[America/New_York]`. When that civil
+datetime is in a gap (it never appeared on the clock) or a fold (it appeared
+twice), the `compatible` disambiguation strategy is used. That is, the instant
+after a gap and the first instant in a fold.
+
+USAGE:
+    bttf tz at <time-zone> <datetime>...
+    bttf tz at <time-zone> < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the offset in New York at the current time:
+
+        $ bttf tz at America/New_York now
+        -04:00
+
+    %snip-start%
+
+    Print the offset and abbreviation just before and at a transition to
+    daylight saving time:
+
+        $ bttf tz at --abbrev America/New_York \
+            2025-03-09T06:59:59Z 2025-03-09T07:00:00Z
+        -05:00 EST
+        -04:00 EDT
+
+    Look up offsets for many datetimes at once by passing them on stdin:
+
+        $ printf '2025-01-15T12:00Z\n2025-07-15T12:00Z\n' \
+            | bttf tz at --abbrev Europe/London
+        +00:00 GMT
+        +01:00 BST
+
+    Check whether DST is in effect at civil datetimes in a gap and a fold:
+
+        $ bttf tz at --dst America/New_York \
+            '2025-03-09T02:30[America/New_York]' \
+            '2025-11-02T01:30[America/New_York]'
+        -04:00 dst
+        -04:00 dst
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut datetimes = positional::DateTimes::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+
+    let tz =
+        config.tz.as_ref().context("missing required <time-zone> argument")?;
+    let mut wtr = std::io::stdout().lock();
+    datetimes.try_map(|datum| {
+        let datum =
+            datum.map(|dt| offset_at(tz, &dt, config.abbrev, config.dst));
+        datum.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    tz: Option<TimeZone>,
+    abbrev: bool,
+    dst: bool,
+}
+
+/// Returns the offset of the given time zone at the instant of the given
+/// datetime.
+///
+/// When `abbrev` is true, the time zone abbreviation is included. When `dst`
+/// is true, whether daylight saving time is in effect is included.
+pub(super) fn offset_at(
+    tz: &TimeZone,
+    dt: &DateTime,
+    abbrev: bool,
+    dst: bool,
+) -> String {
+    let zdt = dt.get().with_time_zone(tz.get().clone());
+    let mut out = timezone::format_offset(&zdt);
+    let info = tz.get().to_offset_info(zdt.timestamp());
+    if abbrev {
+        out.push(' ');
+        out.push_str(info.abbreviation());
+    }
+    if dst {
+        out.push_str(if info.dst().is_dst() { " dst" } else { " std" });
+    }
+    out
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        _: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("abbrev") => {
+                self.abbrev = true;
+            }
+            lexopt::Arg::Long("dst") => {
+                self.dst = true;
+            }
+            lexopt::Arg::Value(ref mut v) => {
+                if self.tz.is_some() {
+                    return Ok(false);
+                }
+                self.tz = Some(v.parse()?);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const ABBREV: Usage = Usage::flag(
+            "--abbrev",
+            "Also print the time zone abbreviation, e.g., `EDT`.",
+            r#"
+Also print the time zone abbreviation, e.g., `EDT`.
+
+The abbreviation is printed after the offset, separated by a space. Note that
+some time zones don't have abbreviations. In that case, the tzdb usually uses
+the offset itself, e.g., `-03`.
+"#,
+        );
+
+        const DST: Usage = Usage::flag(
+            "--dst",
+            "Also print whether daylight saving time is in effect.",
+            r#"
+Also print whether daylight saving time is in effect.
+
+This prints `dst` when daylight saving time is in effect and `std` otherwise,
+after the offset (and the abbreviation, if `--abbrev` is given). This comes
+from the time zone database. Note that a few time zones, like `Europe/Dublin`,
+consider their summer time to be standard time.
+"#,
+        );
+
+        &[TimeZone::ARG, DateTime::ARG_OR_STDIN, ABBREV, DST]
+    }
+}
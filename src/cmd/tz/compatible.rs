@@ -9,7 +9,7 @@ use crate::{
     timezone,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 List all compatible time zones for the given datetime.
 
 That is, for a given instant and every available time zone, if the instant
@@ -52,7 +52,7 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
 
     let mut wtr = std::io::stdout().lock();
     let dt = config.timestamp.with_context(|| {
-        format!("missing datetime to list compatible time zones for")
+        "missing datetime to list compatible time zones for".to_string()
     })?;
     let zdt = dt.get();
     let tz = zdt.time_zone();
@@ -0,0 +1,283 @@
+use std::io::Write;
+
+use jiff::{Timestamp, tz::Offset};
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::{DateTime, DateTimeFlexible},
+    parse::OsStrExt,
+    timezone::TimeZone,
+};
+
+const USAGE: &str = r#"
+Print the difference between the offsets of two time zones.
+
+This prints the offset of the second time zone minus the offset of the first
+time zone, at the instant given by `--at` (which defaults to the current time).
+In other words, it prints how far ahead the wall clock in the second time zone
+is compared to the wall clock in the first. The difference is always printed
+with a sign and minutes, e.g., `+13:00` or `-05:30`.
+
+Since time zones transition to and from daylight saving time at different
+instants, the difference between two time zones can change throughout the
+year. Use `--until` to list every change in the difference between `--at` and
+`--until`. In this mode, each line contains the instant at which a difference
+starts, followed by a tab and the difference itself. The first line is always
+for `--at`.
+
+Alternatively, one or more datetimes may be given after the time zones. In
+this case, the difference at the instant of each datetime is printed, one per
+line. When the only datetime given is `-`, then line delimited datetimes are
+read from stdin instead. Datetimes read from stdin may be tagged, in which case
+each tag is replaced with the difference at its instant.
+
+USAGE:
+    bttf tz diff <time-zone> <time-zone>
+    bttf tz diff <time-zone> <time-zone> <datetime>...
+    bttf tz diff <time-zone> <time-zone> - < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print how many hours ahead Tokyo is from New York right now:
+
+        $ bttf tz diff America/New_York Asia/Tokyo
+        +13:00
+
+    %snip-start%
+
+    The United States and Europe switch to daylight saving time on different
+    dates, so the difference between New York and London changes for a few
+    weeks each spring:
+
+        $ bttf tz diff America/New_York Europe/London --at 2025-03-15
+        +04:00
+        $ bttf tz diff America/New_York Europe/London --at 2025-04-15
+        +05:00
+
+    List each change in the difference over a year:
+
+        $ bttf tz diff America/New_York Europe/London \
+            --at 2025-01-01 --until 2026-01-01
+        2025-01-01T00:00:00-05:00[America/New_York]	+05:00
+        2025-03-09T03:00:00-04:00[America/New_York]	+04:00
+        2025-03-29T21:00:00-04:00[America/New_York]	+05:00
+        2025-10-25T21:00:00-04:00[America/New_York]	+04:00
+        2025-11-02T01:00:00-05:00[America/New_York]	+05:00
+
+    Print the difference at each of several datetimes read from stdin:
+
+        $ printf '2025-03-15T12Z\n2025-04-15T12Z\n' \
+            | bttf tz diff America/Los_Angeles Europe/Berlin -
+        +08:00
+        +09:00
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let (Some(from), Some(to)) = (&config.from, &config.to) else {
+        anyhow::bail!("missing required <time-zone> arguments");
+    };
+    let mut wtr = std::io::stdout().lock();
+    if config.stdin || !config.datetimes.is_empty() {
+        anyhow::ensure!(
+            config.at.is_none() && config.until.is_none(),
+            "--at and --until cannot be used with <datetime> arguments",
+        );
+        anyhow::ensure!(
+            !config.stdin || config.datetimes.is_empty(),
+            "stdin (`-`) cannot be combined with other <datetime> arguments",
+        );
+        let diff_at = |dt: DateTime| {
+            Difference(difference(from, to, dt.get().timestamp())).to_string()
+        };
+        if !config.stdin {
+            for dt in config.datetimes.iter() {
+                writeln!(wtr, "{}", diff_at(dt.clone()))?;
+            }
+            return Ok(());
+        }
+        // With no positional datetimes, this reads them from stdin.
+        return positional::DateTimes::default().try_map(|datum| {
+            datum.map(diff_at).write(&mut wtr)?;
+            writeln!(wtr)?;
+            Ok(true)
+        });
+    }
+    let at = config.at.clone().unwrap_or_else(DateTime::now);
+    let Some(ref until) = config.until else {
+        let diff = difference(from, to, at.get().timestamp());
+        writeln!(wtr, "{}", Difference(diff))?;
+        return Ok(());
+    };
+    let (start, end) = (at.get().timestamp(), until.get().timestamp());
+    anyhow::ensure!(
+        start < end,
+        "--until datetime `{until}` must be after --at datetime `{at}`",
+    );
+    // Collect every instant in the range at which either time zone
+    // transitions. The difference can only change at these instants.
+    let transitions = |tz: &TimeZone| {
+        tz.get()
+            .following(start)
+            .map(|t| t.timestamp())
+            .take_while(|&ts| ts <= end)
+            .collect::<Vec<Timestamp>>()
+    };
+    let mut changes = transitions(from);
+    changes.extend(transitions(to));
+    changes.sort();
+    changes.dedup();
+
+    let mut prev = difference(from, to, start);
+    writeln!(wtr, "{at}\t{}", Difference(prev))?;
+    for ts in changes {
+        let diff = difference(from, to, ts);
+        if diff == prev {
+            continue;
+        }
+        let dt = DateTime::from(ts.to_zoned(at.get().time_zone().clone()));
+        writeln!(wtr, "{dt}\t{}", Difference(diff))?;
+        prev = diff;
+    }
+    Ok(())
+}
+
+/// Returns the offset of `to` minus the offset of `from` at the given
+/// instant, in seconds.
+fn difference(from: &TimeZone, to: &TimeZone, ts: Timestamp) -> i32 {
+    let from: Offset = from.get().to_offset(ts);
+    let to: Offset = to.get().to_offset(ts);
+    to.seconds() - from.seconds()
+}
+
+/// A difference between offsets, in seconds.
+///
+/// This always prints a sign and minutes, and only prints seconds when they
+/// are non-zero.
+#[derive(Clone, Copy, Debug)]
+struct Difference(i32);
+
+impl std::fmt::Display for Difference {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let sign = if self.0 < 0 { '-' } else { '+' };
+        let secs = self.0.unsigned_abs();
+        let (hours, minutes, seconds) =
+            (secs / 3600, (secs / 60) % 60, secs % 60);
+        write!(f, "{sign}{hours:02}:{minutes:02}")?;
+        if seconds != 0 {
+            write!(f, ":{seconds:02}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    from: Option<TimeZone>,
+    to: Option<TimeZone>,
+    at: Option<DateTime>,
+    until: Option<DateTime>,
+    datetimes: Vec<DateTime>,
+    stdin: bool,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("at") => {
+                let at: DateTimeFlexible = args::parse(p, "--at")?;
+                self.at = Some(at.into());
+            }
+            lexopt::Arg::Long("until") => {
+                let until: DateTimeFlexible = args::parse(p, "--until")?;
+                self.until = Some(until.into());
+            }
+            lexopt::Arg::Value(ref mut v) => {
+                if self.from.is_none() {
+                    self.from = Some(v.parse()?);
+                } else if self.to.is_none() {
+                    self.to = Some(v.parse()?);
+                } else if v == "-" {
+                    anyhow::ensure!(
+                        !self.stdin,
+                        "stdin (`-`) can only be given once",
+                    );
+                    self.stdin = true;
+                } else {
+                    let dt: DateTimeFlexible = v.parse()?;
+                    self.datetimes.push(dt.into());
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const TIME_ZONES: Usage = Usage::arg(
+            "<time-zone>",
+            "Two time zones to compare, e.g., `America/New_York`.",
+            r#"
+Two time zones to compare, e.g., `America/New_York`.
+
+Exactly two time zones must be given. The difference printed is the offset of
+the second time zone minus the offset of the first. Each time zone may be an
+IANA time zone identifier, a fixed offset like `+05:30` or `system` for your
+system's time zone.
+"#,
+        );
+
+        const AT: Usage = Usage::flag(
+            "--at <datetime>",
+            "The instant at which to compare offsets.",
+            r#"
+The instant at which to compare offsets.
+
+This defaults to the current time. With `--until`, this is the start of the
+range in which to list changes. In that case, the instants printed are in the
+time zone of this datetime.
+"#,
+        );
+
+        const UNTIL: Usage = Usage::flag(
+            "--until <datetime>",
+            "List every change in the difference up to this datetime.",
+            r#"
+List every change in the difference up to this datetime.
+
+When given, every instant in the range from `--at` to this datetime (inclusive)
+at which the difference changes is printed, along with the new difference.
+This must be after `--at`.
+"#,
+        );
+
+        const DATETIMES: Usage = Usage::arg(
+            "<datetime>...",
+            "Datetimes at which to compare offsets, or `-` for stdin.",
+            r#"
+Datetimes at which to compare offsets, or `-` for stdin.
+
+When given, the difference at the instant of each datetime is printed, one per
+line. When the only datetime is `-`, then line delimited datetimes are read
+from stdin. Datetimes read from stdin may be tagged. These cannot be combined
+with `--at` or `--until`.
+"#,
+        );
+
+        &[TIME_ZONES, DATETIMES, AT, UNTIL]
+    }
+}
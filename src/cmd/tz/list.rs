@@ -1,11 +1,14 @@
 use std::io::Write;
 
+use {anyhow::Context, lexopt::ValueExt, regex::Regex};
+
 use crate::{
     args::{self, Usage},
+    datetime::{DateTime, DateTimeFlexible},
     timezone,
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 List all available time zones as IANA time zone identifiers.
 
 On Unix, this list will usually come from the time zones available in
@@ -15,14 +18,78 @@ database, the list will come from time zones bundled with bttf itself.
 Users may control where bttf looks for a time zone database via the `TZDIR`
 environment variable.
 
-The list is printed in lexicographic order.
+The list is printed in lexicographic order. When a pattern is given, only time
+zones whose name contains it (ignoring case) are printed. With `--regex`, the
+pattern is a regular expression instead.
+
+Use `--offset` to print each time zone's offset from UTC and abbreviation at
+the current time (or at the instant given by `--at`) beside its name.
+
+Many time zones are links to other time zones. For example, `US/Eastern` is a
+link to `America/New_York`. Links usually exist for backwards compatibility
+with names that have been deprecated, but a link always refers to the same
+rules as its canonical time zone. Use `--canonical-only` to hide links,
+`--links` to list each link along with its canonical time zone and
+`--resolve` to look up the canonical time zone for a particular name. The
+time zones bundled with bttf don't include link information, so it is read
+from the `tzdata.zi` file in the directory given by `TZDIR`, or in
+`/usr/share/zoneinfo` otherwise. These flags report an error when that file
+can't be read.
 
 USAGE:
-    bttf tz list
+    bttf tz list [<pattern>]
+    bttf tz list --resolve <time-zone>
 
 TIP:
     use -h for short docs and --help for long docs
 
+EXAMPLES:
+    List only canonical time zones:
+
+        $ bttf tz list --canonical-only
+
+    %snip-start%
+
+    List time zones in a particular region:
+
+        $ bttf tz list europe/l
+        Europe/Lisbon
+        Europe/Ljubljana
+        Europe/London
+        Europe/Luxembourg
+
+    Or use a regex for more control:
+
+        $ bttf tz list --regex '^America/(New_York|Chicago)$'
+        America/Chicago
+        America/New_York
+
+    Print the offset and abbreviation of each time zone in Australia at a
+    particular instant:
+
+        $ bttf tz list --offset --at 2025-01-15T12Z australia/
+        Australia/ACT	+11:00	AEDT
+        Australia/Adelaide	+10:30	ACDT
+        [... snip ...]
+        Australia/West	+08:00	AWST
+        Australia/Yancowinna	+10:30	ACDT
+
+    Find the canonical name for a deprecated time zone:
+
+        $ bttf tz list --resolve US/Eastern
+        America/New_York
+
+    List the `US/*` links along with their canonical time zones:
+
+        $ bttf tz list --links | grep '^US/'
+        US/Alaska	America/Anchorage
+        US/Aleutian	America/Adak
+        [... snip ...]
+        US/Pacific	America/Los_Angeles
+        US/Samoa	Pacific/Pago_Pago
+
+    %snip-end%
+
 REQUIRED ARGUMENTS:
 %args%
 OPTIONS:
@@ -34,25 +101,269 @@ pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     args::configure(p, USAGE, &mut [&mut config])?;
 
     let mut wtr = std::io::stdout().lock();
-    for id in timezone::available() {
-        writeln!(wtr, "{id}")?;
+    if let Some(ref name) = config.resolve {
+        writeln!(wtr, "{}", resolve(name)?)?;
+        return Ok(());
+    }
+    let filter = config.filter()?;
+    let at = config.at()?;
+    let available = timezone::available()
+        .iter()
+        .filter(|id| filter.as_ref().is_none_or(|filter| filter.is_match(id)));
+    if config.links {
+        let links = timezone::links()?;
+        for id in available {
+            if let Some(target) = links.get(id) {
+                writeln!(wtr, "{id}\t{target}")?;
+            }
+        }
+        return Ok(());
+    }
+    let links =
+        if config.canonical_only { Some(timezone::links()?) } else { None };
+    for id in available {
+        if links.is_some_and(|links| links.contains_key(id)) {
+            continue;
+        }
+        match at {
+            None => writeln!(wtr, "{id}")?,
+            Some(ref at) => {
+                let tz = jiff::tz::TimeZone::get(id)?;
+                let zdt = at.get().timestamp().to_zoned(tz);
+                writeln!(
+                    wtr,
+                    "{id}\t{offset}\t{abbrev}",
+                    offset = timezone::format_offset(&zdt),
+                    abbrev = zdt.strftime("%Z"),
+                )?;
+            }
+        }
     }
     Ok(())
 }
 
+/// Returns the canonical time zone for the given name.
+///
+/// The name is matched case insensitively against the available time zones.
+/// If the name isn't a link, then it is returned as-is (with the case used by
+/// the time zone database).
+fn resolve(name: &str) -> anyhow::Result<&'static str> {
+    let id = timezone::available()
+        .iter()
+        .find(|id| id.eq_ignore_ascii_case(name))
+        .with_context(|| format!("unknown time zone `{name}`"))?;
+    let links = timezone::links()?;
+    Ok(links.get(id).unwrap_or(id))
+}
+
+/// A filter on the names of time zones to list.
+#[derive(Debug)]
+enum Filter {
+    /// Matches names containing this string, ignoring case. This is stored
+    /// in lowercase.
+    Substring(String),
+    /// Matches names according to a regex.
+    Regex(Regex),
+}
+
+impl Filter {
+    fn is_match(&self, id: &str) -> bool {
+        match *self {
+            Filter::Substring(ref needle) => {
+                id.to_lowercase().contains(needle.as_str())
+            }
+            Filter::Regex(ref re) => re.is_match(id),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
-struct Config {}
+struct Config {
+    canonical_only: bool,
+    links: bool,
+    resolve: Option<String>,
+    pattern: Option<String>,
+    regex: bool,
+    offset: bool,
+    at: Option<DateTime>,
+}
+
+impl Config {
+    /// Returns the instant at which to print offsets, if `--offset` was
+    /// given.
+    fn at(&self) -> anyhow::Result<Option<DateTime>> {
+        if !self.offset {
+            anyhow::ensure!(self.at.is_none(), "--at requires --offset");
+            return Ok(None);
+        }
+        anyhow::ensure!(!self.links, "--offset cannot be used with --links",);
+        Ok(Some(self.at.clone().unwrap_or_else(DateTime::now)))
+    }
+
+    /// Returns the filter built from the pattern given, if any.
+    fn filter(&self) -> anyhow::Result<Option<Filter>> {
+        let Some(ref pattern) = self.pattern else {
+            anyhow::ensure!(!self.regex, "--regex requires a <pattern>");
+            return Ok(None);
+        };
+        if !self.regex {
+            return Ok(Some(Filter::Substring(pattern.to_lowercase())));
+        }
+        let re = regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .with_context(|| format!("invalid regex `{pattern}`"))?;
+        Ok(Some(Filter::Regex(re)))
+    }
+}
 
 impl args::Configurable for Config {
     fn configure(
         &mut self,
-        _: &mut lexopt::Parser,
-        _: &mut lexopt::Arg,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
     ) -> anyhow::Result<bool> {
-        Ok(false)
+        match *arg {
+            lexopt::Arg::Long("canonical-only") => {
+                self.canonical_only = true;
+            }
+            lexopt::Arg::Long("links") => {
+                self.links = true;
+            }
+            lexopt::Arg::Long("regex") => {
+                self.regex = true;
+            }
+            lexopt::Arg::Long("offset") => {
+                self.offset = true;
+            }
+            lexopt::Arg::Long("at") => {
+                let at: DateTimeFlexible = args::parse(p, "--at")?;
+                self.at = Some(at.into());
+            }
+            lexopt::Arg::Long("resolve") => {
+                let name = p
+                    .value()
+                    .context("--resolve")?
+                    .string()
+                    .context("--resolve: time zone must be valid UTF-8")?;
+                self.resolve = Some(name);
+            }
+            lexopt::Arg::Value(ref mut v) => {
+                if self.pattern.is_some() {
+                    return Ok(false);
+                }
+                let pattern = std::mem::take(v)
+                    .string()
+                    .context("<pattern> must be valid UTF-8")?;
+                self.pattern = Some(pattern);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
     }
 
     fn usage(&self) -> &[Usage] {
-        &[]
+        const CANONICAL_ONLY: Usage = Usage::flag(
+            "--canonical-only",
+            "Hide time zones that are links to other time zones.",
+            r#"
+Hide time zones that are links to other time zones.
+
+When given, only canonical time zones are printed. For example,
+`America/New_York` is printed but `US/Eastern`, which is a link to it, is not.
+"#,
+        );
+
+        const LINKS: Usage = Usage::flag(
+            "--links",
+            "List links along with the time zone each refers to.",
+            r#"
+List links along with the time zone each refers to.
+
+When given, only time zones that are links are printed. Each line contains a
+link, followed by a tab and the canonical time zone it refers to. When a link
+refers to another link, the canonical time zone at the end of the chain is
+printed.
+"#,
+        );
+
+        const RESOLVE: Usage = Usage::flag(
+            "--resolve <time-zone>",
+            "Print the canonical time zone for the given name.",
+            r#"
+Print the canonical time zone for the given name.
+
+The name must be an IANA time zone identifier, and is matched case
+insensitively. When it's a link, like `US/Eastern`, the canonical time zone it
+refers to (`America/New_York`) is printed. Otherwise, the name itself is
+printed. An error is returned when no time zone has the given name.
+
+When given, the list of time zones isn't printed.
+"#,
+        );
+
+        const PATTERN: Usage = Usage::arg(
+            "<pattern>",
+            "Only list time zones whose name contains this pattern.",
+            r#"
+Only list time zones whose name contains this pattern.
+
+The pattern is matched case insensitively against each IANA time zone
+identifier. For example, `europe/` lists every time zone in Europe and `york`
+lists `America/New_York`. With `--links`, the pattern is matched against the
+name of each link.
+
+This is optional. When it isn't given, every time zone is listed.
+"#,
+        );
+
+        const REGEX: Usage = Usage::flag(
+            "--regex",
+            "Interpret <pattern> as a regular expression.",
+            r#"
+Interpret <pattern> as a regular expression.
+
+When given, only time zones whose name matches the regex are listed. The regex
+is matched case insensitively and is not anchored, so use `^` and `$` to match
+whole names. For example, `^America/[^/]+$` lists time zones in the Americas,
+but not ones like `America/Indiana/Knox`.
+
+This requires a <pattern>.
+"#,
+        );
+
+        const OFFSET: Usage = Usage::flag(
+            "--offset",
+            "Print the offset and abbreviation beside each time zone.",
+            r#"
+Print the offset and abbreviation beside each time zone.
+
+When given, each line contains a time zone, followed by a tab, its offset from
+UTC, another tab and its abbreviation. Both are computed at the current time,
+or at the instant given by `--at`. For example, `America/New_York` is printed
+as `America/New_York<tab>-04:00<tab>EDT` during daylight saving time.
+
+The list is still sorted by time zone. To group time zones with the same
+offset, pipe the output through `sort -t $'\t' -k2,2 -s`.
+
+This cannot be used with `--links`.
+"#,
+        );
+
+        const AT: Usage = Usage::flag(
+            "--at <datetime>",
+            "The instant at which to compute offsets for `--offset`.",
+            r#"
+The instant at which to compute offsets for `--offset`.
+
+This defaults to the current time. Since many time zones change their offset
+and abbreviation over the course of a year, this is useful for finding out
+what they will be (or were) at some other time.
+
+This requires `--offset`.
+"#,
+        );
+
+        &[PATTERN, AT, CANONICAL_ONLY, LINKS, OFFSET, REGEX, RESOLVE]
     }
 }
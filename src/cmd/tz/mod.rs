@@ -1,28 +1,43 @@
+mod at;
 mod compatible;
+mod diff;
 mod list;
+mod now;
+mod offset;
+mod search;
 mod seq;
 
-const USAGE: &'static str = "\
+const USAGE: &str = "\
 Commands for working with time zones.
 
 USAGE:
     bttf tz <command> ...
 
 COMMANDS:
+    at          Print the offset of a time zone at datetimes
     compatible  List time zones compatible with an RFC 3339 timestamp
+    diff        Print the difference between the offsets of two time zones
     list        List available time zones
+    now         Show the system time zone and its current offset
+    offset      Print the offset of a time zone and whether DST is in effect
     prev        Find one time zone transition preceding datetimes
     next        Find one time zone transition following datetimes
+    search      List time zones using an abbreviation
     seq         List time zone transitions after (or before) a datetime
 ";
 
 pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
     let cmd = crate::args::next_as_command(USAGE, p)?;
     match &*cmd {
+        "at" => at::run(p),
         "compatible" => compatible::run(p),
+        "diff" => diff::run(p),
         "list" => list::run(p),
+        "now" => now::run(p),
+        "offset" => offset::run(p),
         "prev" => seq::prev(p),
         "next" => seq::next(p),
+        "search" => search::run(p),
         "seq" => seq::seq(p),
         unk => anyhow::bail!("unrecognized command '{}'", unk),
     }
@@ -0,0 +1,159 @@
+use std::io::Write;
+
+use jiff::fmt::temporal;
+
+use crate::{
+    args::{self, Usage},
+    datetime::{DateTime, DateTimeFlexible},
+    timezone::{self, TimeZone},
+};
+
+const USAGE: &str = r#"
+Print the system time zone along with its current offset and abbreviation.
+
+This is useful for checking which time zone bttf has detected from your
+system's configuration (or from the `TZ` environment variable). The time zone
+printed here is the one that bttf uses whenever it needs to interpret a
+datetime without an explicit offset or time zone.
+
+The output consists of the time zone name, its offset from UTC and its
+abbreviation, separated by a space. When the time zone has no IANA name, a
+POSIX time zone string or fixed offset is printed instead.
+
+By default, the offset and abbreviation are for the current time. To see the
+offset and abbreviation at a different instant, use the `--at` flag.
+
+USAGE:
+    bttf tz now
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the system time zone:
+
+        $ bttf tz now
+        America/New_York -04:00 EDT
+
+    %snip-start%
+
+    Print the offset that will be in effect in a few months instead:
+
+        $ bttf tz now --at 2025-12-25
+        America/New_York -05:00 EST
+
+    Or print the same information as JSON:
+
+        $ bttf tz now --json
+        {"abbreviation":"EDT","dst":true,"offset":"-04:00","time_zone":"America/New_York"}
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let tz = TimeZone::system();
+    let name = name(&tz);
+    let zdt = config.at.get().with_time_zone(tz.get().clone());
+    let info = tz.get().to_offset_info(zdt.timestamp());
+    let offset = timezone::format_offset(&zdt);
+    let mut wtr = std::io::stdout().lock();
+    if config.json {
+        let value = serde_json::json!({
+            "time_zone": name,
+            "offset": offset,
+            "abbreviation": info.abbreviation(),
+            "dst": info.dst().is_dst(),
+        });
+        serde_json::to_writer(&mut wtr, &value)?;
+        writeln!(wtr)?;
+    } else {
+        writeln!(
+            wtr,
+            "{name} {offset} {abbrev}",
+            abbrev = info.abbreviation(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns a human readable name for the given time zone.
+///
+/// This prefers the IANA time zone identifier. If there isn't one, then this
+/// falls back to how the time zone would be written in an RFC 9557 time zone
+/// annotation.
+fn name(tz: &TimeZone) -> String {
+    static PRINTER: temporal::DateTimePrinter =
+        temporal::DateTimePrinter::new();
+
+    if let Some(iana) = tz.get().iana_name() {
+        return iana.to_string();
+    }
+    let mut buf = String::new();
+    match PRINTER.print_time_zone(tz.get(), &mut buf) {
+        Ok(()) => buf,
+        // This happens when the system time zone was read from a TZif file
+        // without a corresponding IANA identifier. There isn't much else we
+        // can say about it.
+        Err(_) => "unknown".to_string(),
+    }
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    at: DateTime,
+    json: bool,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("at") => {
+                let at: DateTimeFlexible = args::parse(p, "--at")?;
+                self.at = at.into();
+            }
+            lexopt::Arg::Long("json") => {
+                self.json = true;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const AT: Usage = Usage::flag(
+            "--at <datetime>",
+            "Show the offset and abbreviation in effect at this datetime.",
+            r#"
+Show the offset and abbreviation in effect at this datetime.
+
+This defaults to the current time. The datetime may be written in any of the
+flexible formats supported by bttf, such as `2025-12-25` or `next sat`. Only
+the instant of the datetime given is used.
+"#,
+        );
+
+        const JSON: Usage = Usage::flag(
+            "--json",
+            "Print the time zone information as a JSON object.",
+            r#"
+Print the time zone information as a JSON object.
+
+The object has the keys `time_zone`, `offset`, `abbreviation` and `dst`. The
+last is a boolean indicating whether daylight saving time is in effect.
+"#,
+        );
+
+        &[AT, JSON]
+    }
+}
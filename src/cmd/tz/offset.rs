@@ -0,0 +1,108 @@
+use std::io::Write;
+
+use anyhow::Context;
+
+use crate::{
+    args::{self, Usage, positional},
+    datetime::DateTime,
+    parse::OsStrExt,
+    timezone::TimeZone,
+};
+
+const USAGE: &str = r#"
+Print the offset of a time zone, and whether DST is in effect, at datetimes.
+
+For each datetime, this prints the offset from UTC that the given time zone
+had at the instant of that datetime, followed by a space and either `dst`
+(when daylight saving time is in effect) or `std` (when it isn't). The offset
+always includes minutes, e.g., `-04:00`, so that output lines up. Whether DST
+is in effect comes from the time zone database. Note that a few time zones,
+like `Europe/Dublin`, consider their summer time to be standard time.
+
+This is the same as `bttf tz at --dst`. Only the instant of each datetime is
+used, so its own offset or time zone doesn't matter. To ask about a civil
+datetime in the given time zone, write the time zone as an annotation, e.g.,
+`2025-03-09T02:30[America/New_York]`. When that civil datetime is in a gap (it
+never appeared on the clock) or a fold (it appeared twice), the `compatible`
+disambiguation strategy is used. That is, the offset after a gap and the
+offset before a fold.
+
+USAGE:
+    bttf tz offset <time-zone> <datetime>...
+    bttf tz offset <time-zone> < line delimited <datetime>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Print the offset in New York at the current time:
+
+        $ bttf tz offset America/New_York now
+        -04:00 dst
+
+    %snip-start%
+
+    Print the offset at a civil time in a gap, which uses the offset after the
+    gap, and at a civil time in a fold, which uses the offset before the fold:
+
+        $ bttf tz offset America/New_York \
+            '2025-03-09T02:30[America/New_York]' \
+            '2025-11-02T01:30[America/New_York]'
+        -04:00 dst
+        -04:00 dst
+
+    Print the offset at an instant, regardless of the offset it's written in:
+
+        $ bttf tz offset Europe/London 2025-01-15T12:00-05
+        +00:00 std
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    let mut datetimes = positional::DateTimes::default();
+    args::configure(p, USAGE, &mut [&mut config, &mut datetimes])?;
+
+    let tz =
+        config.tz.as_ref().context("missing required <time-zone> argument")?;
+    let mut wtr = std::io::stdout().lock();
+    datetimes.try_map(|datum| {
+        let datum = datum.map(|dt| super::at::offset_at(tz, &dt, false, true));
+        datum.write(&mut wtr)?;
+        writeln!(wtr)?;
+        Ok(true)
+    })
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    tz: Option<TimeZone>,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        _: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Value(ref mut v) => {
+                if self.tz.is_some() {
+                    return Ok(false);
+                }
+                self.tz = Some(v.parse()?);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        &[TimeZone::ARG, DateTime::ARG_OR_STDIN]
+    }
+}
@@ -0,0 +1,155 @@
+use std::io::Write;
+
+use {anyhow::Context, lexopt::ValueExt};
+
+use crate::{
+    args::{self, Usage},
+    datetime::{DateTime, DateTimeFlexible},
+    timezone,
+};
+
+const USAGE: &str = r#"
+List time zones using an abbreviation at a particular instant.
+
+Time zone abbreviations are ambiguous. For example, `IST` is used for India
+Standard Time, Irish Standard Time and Israel Standard Time. They also depend
+on the time of year, since many time zones use a different abbreviation during
+daylight saving time. This command makes the ambiguity visible by listing
+every available time zone whose abbreviation at the instant given by `--at`
+(which defaults to the current time) matches the one given. Abbreviations are
+matched case insensitively.
+
+Each line contains a time zone, followed by a tab and its offset at that
+instant. The list is sorted by offset, and then by time zone. If no time zones
+use the abbreviation, then an error is returned.
+
+USAGE:
+    bttf tz search <abbreviation>
+
+TIP:
+    use -h for short docs and --help for long docs
+
+EXAMPLES:
+    Find out which time zones `CST` could refer to right now:
+
+        $ bttf tz search CST
+        America/Bahia_Banderas	-06:00
+        America/Belize	-06:00
+        [... snip ...]
+        Asia/Shanghai	+08:00
+        Asia/Taipei	+08:00
+        PRC	+08:00
+        ROC	+08:00
+
+    %snip-start%
+
+    In January, Ireland uses `GMT`, so `IST` refers to India and Israel:
+
+        $ bttf tz search IST --at 2025-01-15T12:00Z
+        Asia/Jerusalem	+02:00
+        Asia/Tel_Aviv	+02:00
+        Israel	+02:00
+        Asia/Calcutta	+05:30
+        Asia/Kolkata	+05:30
+
+    %snip-end%
+REQUIRED ARGUMENTS:
+%args%
+OPTIONS:
+%flags%
+"#;
+
+pub fn run(p: &mut lexopt::Parser) -> anyhow::Result<()> {
+    let mut config = Config::default();
+    args::configure(p, USAGE, &mut [&mut config])?;
+
+    let abbrev = config
+        .abbreviation
+        .as_deref()
+        .context("missing required <abbreviation> argument")?;
+    let at = config.at.clone().unwrap_or_else(DateTime::now);
+    let ts = at.get().timestamp();
+    let mut matches = vec![];
+    for id in timezone::available() {
+        let tz = jiff::tz::TimeZone::get(id)?;
+        let info = tz.to_offset_info(ts);
+        if !info.abbreviation().eq_ignore_ascii_case(abbrev) {
+            continue;
+        }
+        matches.push((info.offset(), id, ts.to_zoned(tz)));
+    }
+    anyhow::ensure!(
+        !matches.is_empty(),
+        "no time zones use the abbreviation `{abbrev}` at `{at}`",
+    );
+    matches.sort_by(|(off1, id1, _), (off2, id2, _)| {
+        (off1, id1).cmp(&(off2, id2))
+    });
+
+    let mut wtr = std::io::stdout().lock();
+    for (_, id, zdt) in matches {
+        writeln!(wtr, "{id}\t{}", timezone::format_offset(&zdt))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    abbreviation: Option<String>,
+    at: Option<DateTime>,
+}
+
+impl args::Configurable for Config {
+    fn configure(
+        &mut self,
+        p: &mut lexopt::Parser,
+        arg: &mut lexopt::Arg,
+    ) -> anyhow::Result<bool> {
+        match *arg {
+            lexopt::Arg::Long("at") => {
+                let at: DateTimeFlexible = args::parse(p, "--at")?;
+                self.at = Some(at.into());
+            }
+            lexopt::Arg::Value(ref mut v) => {
+                if self.abbreviation.is_some() {
+                    return Ok(false);
+                }
+                let abbrev = std::mem::take(v)
+                    .string()
+                    .context("time zone abbreviation must be valid UTF-8")?;
+                self.abbreviation = Some(abbrev);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn usage(&self) -> &[Usage] {
+        const ABBREVIATION: Usage = Usage::arg(
+            "<abbreviation>",
+            "A time zone abbreviation to search for, e.g., `IST`.",
+            r#"
+A time zone abbreviation to search for, e.g., `IST`.
+
+This is matched case insensitively against the abbreviation each time zone
+uses at the instant given by `--at`. Note that some time zones don't have
+abbreviations. In that case, the tzdb usually uses the offset itself, e.g.,
+`-03`, which can also be searched for.
+"#,
+        );
+
+        const AT: Usage = Usage::flag(
+            "--at <datetime>",
+            "The instant at which to look up abbreviations.",
+            r#"
+The instant at which to look up abbreviations.
+
+This defaults to the current time. Since many time zones use a different
+abbreviation during daylight saving time, the set of time zones using an
+abbreviation depends on the time of year.
+"#,
+        );
+
+        &[ABBREVIATION, AT]
+    }
+}
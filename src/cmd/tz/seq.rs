@@ -1,16 +1,16 @@
 use std::io::Write;
 
-use anyhow::Context;
+use {anyhow::Context, bstr::ByteSlice};
 
 use crate::{
     args::{self, Usage, positional},
     datetime::{DateTime, DateTimeFlexible},
-    parse::OsStrExt,
+    parse::{FromBytes, OsStrExt},
     tag::MaybeTagged,
     timezone::TimeZone,
 };
 
-const USAGE_SEQ: &'static str = r#"
+const USAGE_SEQ: &str = r#"
 Emit a sequence of time zone transitions following a datetime.
 
 The sequence emitted may be empty, for example, when there are no time zone
@@ -49,6 +49,18 @@ EXAMPLES:
         2027-04-03T12:00:00-04:00[America/New_York]
         2027-10-02T12:00:00-04:00[America/New_York]
 
+    Or group the transitions by year:
+
+        $ bttf tz seq -c5 --group-by year Australia/Sydney
+        2025
+          2025-10-05T03:00:00+11:00[Australia/Sydney]
+        2026
+          2026-04-05T02:00:00+10:00[Australia/Sydney]
+          2026-10-04T03:00:00+11:00[Australia/Sydney]
+        2027
+          2027-04-04T02:00:00+10:00[Australia/Sydney]
+          2027-10-03T03:00:00+11:00[Australia/Sydney]
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -56,7 +68,7 @@ OPTIONS:
 %flags%
 "#;
 
-const USAGE_NEXT: &'static str = r#"
+const USAGE_NEXT: &str = r#"
 Find next time zone transitions following one or more datetimes.
 
 If there is no next time zone transition, then no datetime is emitted. For
@@ -97,7 +109,7 @@ OPTIONS:
 %flags%
 "#;
 
-const USAGE_PREV: &'static str = r#"
+const USAGE_PREV: &str = r#"
 Find previous time zone transitions preceding one or more datetimes.
 
 If there is no previous time zone transition, then no datetime is emitted. For
@@ -195,17 +207,12 @@ pub fn seq(p: &mut lexopt::Parser) -> anyhow::Result<()> {
         config.tz.as_ref().context("missing required <time-zone> argument")?;
     let count = config.count.unwrap_or(usize::MAX);
     let relative = config.relative()?;
-    let mut wtr = std::io::stdout().lock();
+    let wtr = std::io::stdout().lock();
     if config.past {
-        for dt in relative.tz_preceding(tz).take(count) {
-            writeln!(wtr, "{dt}")?;
-        }
+        config.write(wtr, relative.tz_preceding(tz).take(count))
     } else {
-        for dt in relative.tz_following(tz).take(count) {
-            writeln!(wtr, "{dt}")?;
-        }
+        config.write(wtr, relative.tz_following(tz).take(count))
     }
-    Ok(())
 }
 
 pub fn next(p: &mut lexopt::Parser) -> anyhow::Result<()> {
@@ -291,9 +298,68 @@ struct Seq {
     inclusive: bool,
     count: Option<usize>,
     past: bool,
+    group_by: Option<GroupBy>,
+    json: bool,
+}
+
+/// A group of transitions printed by `--json`.
+#[derive(Debug, serde::Serialize)]
+struct Group {
+    year: i16,
+    transitions: Vec<DateTime>,
 }
 
 impl Seq {
+    /// Writes the given transitions, grouping them if requested.
+    fn write(
+        &self,
+        mut wtr: impl Write,
+        transitions: impl Iterator<Item = DateTime>,
+    ) -> anyhow::Result<()> {
+        let Some(GroupBy::Year) = self.group_by else {
+            anyhow::ensure!(!self.json, "--json requires --group-by");
+            for dt in transitions {
+                writeln!(wtr, "{dt}")?;
+            }
+            return Ok(());
+        };
+        // We write groups as we go instead of collecting them. Since the
+        // transitions are always in order (forwards or backwards), each
+        // year forms one contiguous group.
+        if self.json {
+            let mut group: Option<Group> = None;
+            for dt in transitions {
+                let year = dt.get().year();
+                if let Some(ref mut group) = group
+                    && group.year == year
+                {
+                    group.transitions.push(dt);
+                    continue;
+                }
+                let next = Group { year, transitions: vec![dt] };
+                if let Some(group) = group.replace(next) {
+                    serde_json::to_writer(&mut wtr, &group)?;
+                    writeln!(wtr)?;
+                }
+            }
+            if let Some(group) = group {
+                serde_json::to_writer(&mut wtr, &group)?;
+                writeln!(wtr)?;
+            }
+            return Ok(());
+        }
+        let mut year: Option<i16> = None;
+        for dt in transitions {
+            let this_year = dt.get().year();
+            if year != Some(this_year) {
+                writeln!(wtr, "{this_year}")?;
+            }
+            writeln!(wtr, "  {dt}")?;
+            year = Some(this_year);
+        }
+        Ok(())
+    }
+
     fn relative(&self) -> anyhow::Result<DateTime> {
         if !self.inclusive {
             Ok(self.relative.clone())
@@ -332,13 +398,74 @@ impl args::Configurable for Seq {
             lexopt::Arg::Short('p') | lexopt::Arg::Long("past") => {
                 self.past = true;
             }
+            lexopt::Arg::Long("group-by") => {
+                self.group_by = Some(args::parse_bytes(p, "--group-by")?);
+            }
+            lexopt::Arg::Long("json") => {
+                self.json = true;
+            }
             _ => return Ok(false),
         }
         Ok(true)
     }
 
     fn usage(&self) -> &[Usage] {
-        &[TimeZone::ARG, DateTime::RELATIVE_FLAG, INCLUSIVE, COUNT, PAST]
+        const JSON: Usage = Usage::flag(
+            "--json",
+            "Print each group of transitions as a JSON object.",
+            r#"
+Print each group of transitions as a JSON object.
+
+Each group is printed on its own line as an object with the group's `year` and
+a `transitions` array of the transitions in that group. Groups appear in the
+same order that transitions are emitted. This flag requires `--group-by`.
+"#,
+        );
+
+        &[
+            TimeZone::ARG,
+            DateTime::RELATIVE_FLAG,
+            INCLUSIVE,
+            COUNT,
+            PAST,
+            GroupBy::USAGE,
+            JSON,
+        ]
+    }
+}
+
+/// How to group the time zone transitions emitted by `bttf tz seq`.
+#[derive(Clone, Copy, Debug)]
+enum GroupBy {
+    Year,
+}
+
+impl GroupBy {
+    const USAGE: Usage = Usage::flag(
+        "--group-by <kind>",
+        "Group transitions under a header, e.g., by `year`.",
+        r#"
+Group transitions under a header.
+
+When given, a header is printed before each group of transitions, and each
+transition is indented beneath it. This is purely presentational and applies
+to whichever transitions would otherwise be shown, e.g., as limited by
+`-c/--count`. The year of a transition is determined in the requested time
+zone.
+
+The only legal value at present is `year`.
+"#,
+    );
+}
+
+impl FromBytes for GroupBy {
+    type Err = anyhow::Error;
+
+    fn from_bytes(s: &[u8]) -> anyhow::Result<GroupBy> {
+        match s {
+            b"year" => Ok(GroupBy::Year),
+            unk => anyhow::bail!("unknown grouping `{}`", unk.as_bstr()),
+        }
     }
 }
 
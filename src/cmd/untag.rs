@@ -1,4 +1,4 @@
-use std::{borrow::Cow, io::Write};
+use std::{borrow::Cow, io::Write, ops::Range};
 
 use {
     anyhow::Context,
@@ -8,18 +8,24 @@ use {
 
 use crate::{
     args::{self, Usage, flags},
+    datetime::DateTime,
+    locale::StrtimeConfig,
     parse::{BufReadExt, BytesExt},
     style::Theme,
     tag::{Tag, Tagged},
+    template::{Item, Template},
 };
 
-const USAGE: &'static str = r#"
+const USAGE: &str = r#"
 Untag tagged data.
 
 This effectively undoes any "tagging" done by the `bttf tag` commands. This
 is useful to get back the original data, or even to replace tagged values in
 the original data.
 
+Tagged data is processed one line at a time. Output for each line is written
+as soon as that line is read, regardless of which flags are given.
+
 USAGE:
     bttf untag <path>
     bttf untag < line delimited tagged data
@@ -72,6 +78,13 @@ EXAMPLES:
             | bttf time fmt -f '%Y-%m-%d %H:%M:%S' \
             | bttf untag -s
 
+    Print each tag and the file it came from as JSON, for processing with
+    `jq`:
+
+        $ bttf tag files *.log \
+            | bttf untag --output json \
+            | jq -r 'select(.tag > "2025") | .data'
+
     %snip-end%
 REQUIRED ARGUMENTS:
 %args%
@@ -82,14 +95,44 @@ OPTIONS:
 pub fn run(p: &mut Parser) -> anyhow::Result<()> {
     let mut config = Config::default();
     args::configure(p, USAGE, &mut [&mut config])?;
+    if config.output.is_json() {
+        anyhow::ensure!(
+            config.format.is_none(),
+            "--output json cannot be used with -f/--format",
+        );
+        anyhow::ensure!(
+            !config.substitute,
+            "--output json cannot be used with -s/--substitute",
+        );
+    }
 
     let mut wtr = std::io::stdout().lock();
     let mut buf = BString::new(vec![]);
+    // Every mode only needs the current line of tagged data, so we write
+    // output for each line as soon as it's read. This keeps memory usage
+    // proportional to the longest line instead of the entire input, and
+    // makes this command usable at the end of a pipeline fed by, e.g.,
+    // `tail -f`.
     let result = config.input.reader()?.for_byte_line(|line| {
         let tagged: Tagged<String> =
             line.content().parse().with_context(|| {
                 format!("line {}: failed to parse tagged data", line.number())
             })?;
+        if config.output.is_json() {
+            let data = tagged
+                .data()
+                .trim_end_with(|ch| ch == '\r' || ch == '\n')
+                .to_str_lossy();
+            for tag in tagged.tags() {
+                if config.drop_synthetic && tag.is_synthetic() {
+                    continue;
+                }
+                let record = Record { tag, data: &data };
+                serde_json::to_writer(&mut wtr, &record)?;
+                writeln!(wtr)?;
+            }
+            return Ok(true);
+        }
         let mut data = Cow::Borrowed(tagged.data());
         if config.substitute {
             data = substitute(data, tagged.tags());
@@ -102,12 +145,13 @@ pub fn run(p: &mut Parser) -> anyhow::Result<()> {
         };
         let data = data.trim_end_with(|ch| ch == '\r' || ch == '\n');
         for tag in tagged.tags() {
+            if config.drop_synthetic && tag.is_synthetic() {
+                continue;
+            }
             buf.clear();
-            format.interpolate(
-                tag.value().as_bytes().as_bstr(),
-                data.as_bstr(),
-                &mut buf,
-            );
+            format
+                .interpolate(tag, data.as_bstr(), &mut buf)
+                .with_context(|| format!("line {}", line.number()))?;
             wtr.write_all(&buf)?;
             writeln!(wtr)?;
         }
@@ -117,54 +161,91 @@ pub fn run(p: &mut Parser) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// A single tag and the data it was attached to, as printed by
+/// `--output json`.
+struct Record<'a> {
+    tag: &'a Tag<String>,
+    data: &'a str,
+}
+
+impl<'a> serde::Serialize for Record<'a> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let label = self.tag.label();
+        let len = 2 + if label.is_some() { 1 } else { 0 };
+        let mut state = s.serialize_struct("Record", len)?;
+        state.serialize_field("tag", self.tag.value())?;
+        if let Some(label) = label {
+            state.serialize_field("label", label)?;
+        } else {
+            state.skip_field("label")?;
+        }
+        state.serialize_field("data", self.data)?;
+        state.end()
+    }
+}
+
 /// Substitute each of the tags into the `data` given.
 ///
 /// This only applies for tags that have a corresponding range into the given
 /// data. e.g., They were extracted directly as literals from the data.
-fn substitute<'a>(
-    mut data: Cow<'a, BStr>,
-    tags: &[Tag<String>],
-) -> Cow<'a, BStr> {
-    // When we replace multiple tags, the ranges of tags that haven't
-    // been replaced yet are no longer correct unless the replacement
-    // is exactly the same length, in bytes, as the tagged value. To
-    // account for this, we "offset" our ranges based on the difference
-    // in length between the tag's range in the original data and the
-    // tag's length.
-    let mut offset: isize = 0;
-    for tag in tags {
-        let Some(range) = tag.range() else { continue };
-        let range = range.offset(offset);
-        let replacement = Theme::stdout().highlight(tag.value()).to_string();
-        offset += range.diff(replacement.len());
-        let replacement = replacement.as_bytes().iter().copied();
-
-        let mut new = data.into_owned();
-        drop(new.splice(range.range(), replacement));
-        data = Cow::Owned(new);
-    }
-    data
+fn substitute<'a>(data: Cow<'a, BStr>, tags: &[Tag<String>]) -> Cow<'a, BStr> {
+    replace_ranges(data, tags, |tag, _| {
+        Theme::stdout().highlight(tag.value()).to_string()
+    })
 }
 
 /// Like `substitute`, but just colorizes the ranges.
-fn stylize<'a>(
-    mut data: Cow<'a, BStr>,
+fn stylize<'a>(data: Cow<'a, BStr>, tags: &[Tag<String>]) -> Cow<'a, BStr> {
+    replace_ranges(data, tags, |_, original| {
+        Theme::stdout().highlight(original).to_string()
+    })
+}
+
+/// Replaces the range of each tag in `data` with the string returned by
+/// `replace`, which is given the tag and the original bytes in its range.
+///
+/// Everything outside of the tagged ranges is copied through byte-for-byte,
+/// including whitespace and line terminators. Tags are applied in the order
+/// in which their ranges appear in `data`, regardless of the order of the tags
+/// themselves. Tags without a range, with a range that is out of bounds or
+/// with a range that overlaps the range of a tag before it are ignored.
+fn replace_ranges<'a>(
+    data: Cow<'a, BStr>,
     tags: &[Tag<String>],
+    mut replace: impl FnMut(&Tag<String>, &BStr) -> String,
 ) -> Cow<'a, BStr> {
-    let mut offset: isize = 0;
-    for tag in tags {
-        let Some(range) = tag.range() else { continue };
-        let range = range.offset(offset);
-        let replacement =
-            Theme::stdout().highlight(&data[range.range()]).to_string();
-        offset += range.diff(replacement.len());
-        let replacement = replacement.as_bytes().iter().copied();
-
-        let mut new = data.into_owned();
-        drop(new.splice(range.range(), replacement));
-        data = Cow::Owned(new);
+    let mut ranged: Vec<(Range<usize>, &Tag<String>)> = tags
+        .iter()
+        .filter_map(|tag| {
+            let range = tag.range()?.range();
+            let valid = range.start <= range.end && range.end <= data.len();
+            valid.then_some((range, tag))
+        })
+        .collect();
+    if ranged.is_empty() {
+        return data;
+    }
+    // This is a stable sort, so when two tags have the same range, the one
+    // that comes first wins.
+    ranged.sort_by_key(|(range, _)| range.start);
+
+    let mut new = BString::new(Vec::with_capacity(data.len()));
+    let mut last = 0;
+    for (range, tag) in ranged {
+        if range.start < last {
+            continue;
+        }
+        new.push_str(&data[last..range.start]);
+        new.push_str(replace(tag, data[range.clone()].as_bstr()));
+        last = range.end;
     }
-    data
+    new.push_str(&data[last..]);
+    Cow::Owned(new)
 }
 
 /// A representation of a format string.
@@ -173,14 +254,20 @@ fn stylize<'a>(
 /// directives.
 #[derive(Debug)]
 struct Format {
-    items: Vec<FormatItem>,
+    template: Template<Directive>,
+    /// The configuration for formatting datetimes. This is only present when
+    /// the template contains a `{tag:<format>}` directive.
+    strtime_config: Option<StrtimeConfig>,
 }
 
-/// An individual item in a format string.
-#[derive(Debug)]
-enum FormatItem {
-    Literal(BString),
+/// A directive that can appear in a format string.
+#[derive(Clone, Debug)]
+enum Directive {
     Tag,
+    /// The tag parsed as a datetime and reformatted with a `strftime`-style
+    /// format string, e.g., `{tag:%Y-%m-%d}`.
+    TagFormat(flags::Format),
+    Label,
     Data,
 }
 
@@ -188,26 +275,58 @@ impl Format {
     /// Interpolate the formatting directives into `dst` using the given
     /// `tag` and `data`.
     ///
+    /// This returns an error if the format string reformats the tag as a
+    /// datetime, but the tag isn't a valid datetime.
+    ///
     /// Callers are responsible for clearing `dst`.
-    fn interpolate(&self, tag: &BStr, data: &BStr, dst: &mut BString) {
-        for item in self.items.iter() {
+    fn interpolate(
+        &self,
+        tag: &Tag<String>,
+        data: &BStr,
+        dst: &mut BString,
+    ) -> anyhow::Result<()> {
+        let label = tag.label();
+        let tag = tag.value().as_bytes().as_bstr();
+        for item in self.template.items() {
             match *item {
-                FormatItem::Literal(ref literal) => {
-                    dst.extend_from_slice(&literal);
+                Item::Literal(ref literal) => {
+                    dst.extend_from_slice(literal);
+                }
+                Item::Directive(Directive::Tag) => {
+                    push_tag(tag, dst);
                 }
-                FormatItem::Tag => {
-                    if Theme::stdout().is_none() {
-                        dst.extend_from_slice(tag);
-                    } else {
-                        let tag = Theme::stdout().highlight(tag).to_string();
-                        dst.extend_from_slice(tag.as_bytes());
-                    }
+                Item::Directive(Directive::TagFormat(ref format)) => {
+                    let dt: DateTime = tag.parse().with_context(|| {
+                        format!(
+                            "failed to reformat tag `{tag}` with format \
+                             {format}, since it is not a valid datetime",
+                        )
+                    })?;
+                    // This is always set when there is a `TagFormat`
+                    // directive.
+                    let config = self.strtime_config.as_ref().unwrap();
+                    let formatted = format.format(config, &dt)?;
+                    push_tag(formatted.as_bytes().as_bstr(), dst);
                 }
-                FormatItem::Data => {
+                Item::Directive(Directive::Label) => {
+                    dst.extend_from_slice(label.unwrap_or("").as_bytes());
+                }
+                Item::Directive(Directive::Data) => {
                     dst.extend_from_slice(data);
                 }
             }
         }
+        Ok(())
+    }
+}
+
+/// Writes the given tag to `dst`, highlighting it if appropriate.
+fn push_tag(tag: &BStr, dst: &mut BString) {
+    if Theme::stdout().is_none() {
+        dst.extend_from_slice(tag);
+    } else {
+        let tag = Theme::stdout().highlight(tag).to_string();
+        dst.extend_from_slice(tag.as_bytes());
     }
 }
 
@@ -215,76 +334,49 @@ impl std::str::FromStr for Format {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> anyhow::Result<Format> {
-        enum State {
-            Default,
-            InBrace,
-            Backslash,
-            BackslashInBrace,
-        }
-
-        let mut items = vec![];
-        let mut literal = BString::new(vec![]);
-        let mut name = BString::new(vec![]);
-        let mut state = State::Default;
-        for byte in Vec::unescape_bytes(s) {
-            state = match (state, byte) {
-                (State::Default, b'{') => {
-                    if !literal.is_empty() {
-                        let literal = std::mem::take(&mut literal);
-                        items.push(FormatItem::Literal(literal));
-                    }
-                    State::InBrace
-                }
-                (State::Default, b'\\') => State::Backslash,
-                (State::Default, _) => {
-                    literal.push(byte);
-                    State::Default
-                }
-                (State::InBrace, b'}') => {
-                    let item = match name.as_bytes() {
-                        b"tag" => FormatItem::Tag,
-                        b"data" => FormatItem::Data,
-                        _ => anyhow::bail!(
-                            "unrecognized format directive `{{{name}}}`, \
-                             allowed directives are `{{tag}}` and `{{data}}`",
-                        ),
-                    };
-                    name.clear();
-                    items.push(item);
-                    State::Default
-                }
-                (State::InBrace, b'\\') => State::BackslashInBrace,
-                (State::InBrace, _) => {
-                    name.push(byte);
-                    State::InBrace
-                }
-                (State::Backslash, _) => {
-                    literal.push(byte);
-                    State::Default
-                }
-                (State::BackslashInBrace, _) => {
-                    name.push(byte);
-                    State::InBrace
-                }
-            };
-        }
-        match state {
-            State::Default => {
-                if !literal.is_empty() {
-                    items.push(FormatItem::Literal(literal));
-                }
+        let template = Template::parse(&Vec::unescape_bytes(s), |name| {
+            // A `{` in the format string almost certainly means the
+            // directive is malformed, e.g., `{tag:{data}`. So we only treat
+            // this as a format directive when there isn't one, and otherwise
+            // fall through to reporting an unrecognized directive.
+            if let Some(fmt) = name.strip_prefix(b"tag:")
+                && fmt.find_byte(b'{').is_none()
+            {
+                let fmt = fmt.to_str().with_context(|| {
+                    format!(
+                        "format string in directive `{{{name}}}` \
+                         is not valid UTF-8",
+                    )
+                })?;
+                anyhow::ensure!(
+                    !fmt.is_empty(),
+                    "format directive `{{{name}}}` requires a non-empty \
+                     format string after the `:`",
+                );
+                return Ok(Directive::TagFormat(flags::Format::Strtime(
+                    fmt.into(),
+                )));
             }
-            State::InBrace => anyhow::bail!(
-                "found unclosed brace, which might be an invalid \
-                 format directive (to write a brace literally, escape \
-                 it with a backslash)",
-            ),
-            State::Backslash | State::BackslashInBrace => anyhow::bail!(
-                "found dangling backslash (to write a backslash \
-                 literally, escape it with a backslash)",
-            ),
-        }
-        Ok(Format { items })
+            Ok(match name.as_bytes() {
+                b"tag" => Directive::Tag,
+                b"label" => Directive::Label,
+                b"data" => Directive::Data,
+                _ => anyhow::bail!(
+                    "unrecognized format directive `{{{name}}}`, \
+                     allowed directives are `{{tag}}`, `{{tag:<format>}}`, \
+                     `{{label}}` and `{{data}}`",
+                ),
+            })
+        })?;
+        let has_tag_format = template.items().iter().any(|item| {
+            matches!(*item, Item::Directive(Directive::TagFormat(_)))
+        });
+        let strtime_config = if has_tag_format {
+            Some(crate::locale::jiff_strtime_config()?)
+        } else {
+            None
+        };
+        Ok(Format { template, strtime_config })
     }
 }
 
@@ -293,6 +385,8 @@ struct Config {
     input: flags::FileOrStdin,
     substitute: bool,
     format: Option<Format>,
+    drop_synthetic: bool,
+    output: flags::Output,
 }
 
 impl args::Configurable for Config {
@@ -308,6 +402,12 @@ impl args::Configurable for Config {
             Arg::Short('f') | Arg::Long("format") => {
                 self.format = Some(args::parse(p, "-f/--format")?);
             }
+            Arg::Long("drop-synthetic") => {
+                self.drop_synthetic = true;
+            }
+            Arg::Long("output") => {
+                self.output = args::parse(p, "--output")?;
+            }
             Arg::Value(ref mut v) => {
                 self.input.set(std::mem::take(v))?;
             }
@@ -342,6 +442,11 @@ This is useful when one extracts a datetime tag and turns it into a different
 format with, e.g., `bttf time fmt`. This flag will then substitute the original
 datetime in the source with the formatted datetime. For example, this can be
 used to localize RFC 3339 Zulu timestamps in arbitrary line oriented data.
+
+Everything outside of the tagged ranges, including whitespace, alignment and
+line terminators, is reproduced byte-for-byte. When a tag's range overlaps the
+range of an earlier tag in the data, or when it is out of bounds, then that
+tag is not substituted.
 "#,
         );
 
@@ -364,6 +469,18 @@ directives:
 `{tag}`: interpolate the tagged value. When there are multiple tags,
 interpolation occurs for each tag.
 
+`{tag:<format>}`: parse the tagged value as a datetime and interpolate it
+formatted with the given `strftime`-style format string. For example,
+`{tag:%Y-%m-%d}`. This is like using `bttf time fmt` before untagging. If the
+tagged value isn't a valid datetime, then an error is reported. To use a `}`
+in the format string, escape it with a backslash. The format string may not
+contain a `{`.
+
+`{label}`: interpolate the label of the tag, if it has one. Labels describe
+where a tag came from. For example, `bttf tag stat` labels each tag with the
+kind of file metadata it was read from, like `mtime` or `btime`. Tags without
+a label interpolate to an empty string.
+
 `{data}`: interpolate the original data. This is replaced with the original
 data for each tag.
 
@@ -372,6 +489,45 @@ then interpolation is skipped entirely for that data.
 "#,
         );
 
-        &[PATH, SUBSTITUTE, FORMAT]
+        const DROP_SYNTHETIC: Usage = Usage::flag(
+            "--drop-synthetic",
+            "Ignore synthetic tags when interpolating a format string.",
+            r#"
+Ignore synthetic tags when interpolating a format string.
+
+Synthetic tags are datetimes made up by bttf for data that didn't have any,
+e.g., via `bttf tag lines --synthetic`. By default, they are kept and treated
+like any other tag. When this flag is given, they are ignored by
+`-f/--format` and `--output json`. So data whose only tag is synthetic is
+skipped entirely.
+
+Synthetic tags are never substituted by `-s/--substitute`, since they don't
+correspond to any part of the original data.
+"#,
+        );
+
+        const OUTPUT: Usage = Usage::flag(
+            "--output <mode>",
+            "Print untagged data as `text` (default) or `json`.",
+            r#"
+Print untagged data as `text` (default) or `json`.
+
+With `json`, instead of printing the original data, each tag is printed as a
+JSON object on its own line (also known as NDJSON). Like `-f/--format`, data
+with multiple tags results in one object per tag, and data without any tags
+is skipped entirely. Each object has the following fields:
+
+`tag`: the tagged value.
+
+`label`: the label of the tag. This is omitted when the tag has no label.
+
+`data`: the original data, without its line terminator. Data that isn't valid
+UTF-8 has its invalid bytes replaced with the Unicode replacement codepoint.
+
+This cannot be used with `-f/--format` or `-s/--substitute`.
+"#,
+        );
+
+        &[PATH, SUBSTITUTE, FORMAT, DROP_SYNTHETIC, OUTPUT]
     }
 }
@@ -4,17 +4,20 @@ use {
     anyhow::Context,
     bstr::{BStr, ByteSlice},
     jiff::{
-        ToSpan, Unit, Zoned, civil, fmt,
+        RoundMode, ToSpan, Unit, Zoned, ZonedDifference, civil, fmt,
         tz::{self, Offset},
     },
 };
 
 use crate::{
     NOW, TZ,
-    args::{Usage, flags::Weekday},
+    args::{
+        Usage,
+        flags::{self, Weekday},
+    },
     parse::{BytesExt, FromBytes},
     span::TimeSpan,
-    timezone::TimeZone,
+    timezone::{self, TimeZone},
 };
 
 static TEMPORAL_PARSER: fmt::temporal::DateTimeParser =
@@ -86,6 +89,11 @@ in a datetime in the past. In both cases, the datetime is then interpreted as
 a local time in your system's configured time zone (which may be overridden
 by the `TZ` environment variable).
 
+ISO 8601 ordinal dates and week dates, e.g., `2024-202` (the 202nd day of 2024)
+or `2024-W29-6` (the 6th day of the 29th week of 2024). These may be followed
+by a time, offset and time zone annotation, just like `2025-03-15`. Week dates
+always use the ISO 8601 definition of a week, which starts on Monday.
+
 A relative datetime expressed as a duration from the current time. For example,
 to get 1 day from the current time, you can use `1 day`, or more succinctly,
 `1d`. To get 1 day in the past from the current time, you can use `1 day ago`,
@@ -95,8 +103,8 @@ example, `1 year 1 second` or `1y1s`.
 Some special strings are supported as well:
 
 `now` refers to the current datetime to the highest precision supported by
-your system. The current datetime is computed once when bttf starts, or if the
-`BTTF_NOW` environment variable is set, that time is used instead.
+your system. The current datetime is computed once when bttf starts. It can be
+overridden by the `--now` flag or the `BTTF_NOW` environment variable.
 
 `today` refers to the first instant of the current day.
 
@@ -158,8 +166,8 @@ year 1 second` or `1y1s`.
 Some special strings are supported as well:
 
 `now` refers to the current datetime to the highest precision supported by
-your system. The current datetime is computed once when bttf starts, or if the
-`BTTF_NOW` environment variable is set, that time is used instead.
+your system. The current datetime is computed once when bttf starts. It can be
+overridden by the `--now` flag or the `BTTF_NOW` environment variable.
 
 `today` refers to the first instant of the current day.
 
@@ -218,8 +226,8 @@ year 1 second` or `1y1s`.
 Some special strings are supported as well:
 
 `now` refers to the current datetime to the highest precision supported by
-your system. The current datetime is computed once when bttf starts, or if the
-`BTTF_NOW` environment variable is set, that time is used instead.
+your system. The current datetime is computed once when bttf starts. It can be
+overridden by the `--now` flag or the `BTTF_NOW` environment variable.
 
 `today` refers to the first instant of the current day.
 
@@ -256,13 +264,39 @@ the next Wednesday or 5pm in 1 week from today.
         &self.zdt
     }
 
+    /// Returns the largest and smallest units to use for computing spans
+    /// with `DateTime::since` or `DateTime::until`.
+    ///
+    /// When no largest unit is given, it defaults to hours (or the smallest
+    /// unit, if that is bigger).
+    pub fn span_units(
+        largest: Option<&flags::Unit>,
+        smallest: &flags::Unit,
+    ) -> anyhow::Result<(Unit, Unit)> {
+        let Some(largest) = largest else {
+            return Ok((smallest.get().max(Unit::Hour), smallest.get()));
+        };
+        if largest.get() < smallest.get() {
+            anyhow::bail!(
+                "-l/--largest unit ({largest}) must be at least as big as \
+                 -s/--smallest unit ({smallest})",
+            );
+        }
+        Ok((largest.get(), smallest.get()))
+    }
+
     pub fn since(
         &self,
         largest: Unit,
+        smallest: Unit,
         dt: &DateTime,
     ) -> anyhow::Result<TimeSpan> {
+        let diff = ZonedDifference::new(&dt.zdt)
+            .largest(largest)
+            .smallest(smallest)
+            .mode(RoundMode::HalfExpand);
         self.zdt
-            .since((largest, &dt.zdt))
+            .since(diff)
             .with_context(|| format!("failed to find span relative to {dt}"))
             .map(TimeSpan::from)
     }
@@ -270,10 +304,15 @@ the next Wednesday or 5pm in 1 week from today.
     pub fn until(
         &self,
         largest: Unit,
+        smallest: Unit,
         dt: &DateTime,
     ) -> anyhow::Result<TimeSpan> {
+        let diff = ZonedDifference::new(&dt.zdt)
+            .largest(largest)
+            .smallest(smallest)
+            .mode(RoundMode::HalfExpand);
         self.zdt
-            .until((largest, &dt.zdt))
+            .until(diff)
             .with_context(|| format!("failed to find span relative to {dt}"))
             .map(TimeSpan::from)
     }
@@ -419,6 +458,8 @@ impl FromBytes for DateTime {
         }
         // N.B. This also includes RFC 9110.
         if let Ok(zdt) = RFC2822_PARSER.parse_zoned(s) {
+            check_rfc2822_zone(s)?;
+            let zdt = rfc2822_unknown_offset(s, zdt);
             return Ok(DateTime::from(zdt));
         }
         anyhow::bail!("unrecognized datetime `{s}`", s = BStr::new(s))
@@ -434,6 +475,56 @@ impl serde::Serialize for DateTime {
     }
 }
 
+/// A datetime printed as a JSON object, for `--output json`.
+///
+/// Its `Display` impl writes the object on a single line. This is never used
+/// as the value of a tag, since tag values must be strings.
+#[derive(Clone, Debug)]
+pub struct DateTimeJson {
+    dt: DateTime,
+    formatted: Option<String>,
+}
+
+impl DateTimeJson {
+    pub fn new(dt: DateTime) -> DateTimeJson {
+        DateTimeJson { dt, formatted: None }
+    }
+
+    /// Include the given formatted version of this datetime in the object.
+    pub fn formatted(self, formatted: String) -> DateTimeJson {
+        DateTimeJson { formatted: Some(formatted), ..self }
+    }
+}
+
+impl std::fmt::Display for DateTimeJson {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl serde::Serialize for DateTimeJson {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let zdt = self.dt.get();
+        let ts = zdt.timestamp();
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(ref formatted) = self.formatted {
+            map.serialize_entry("formatted", formatted)?;
+        }
+        map.serialize_entry("timestamp", &ts.to_string())?;
+        map.serialize_entry("zoned", &self.dt.to_string())?;
+        map.serialize_entry("offset", &timezone::format_offset(zdt))?;
+        map.serialize_entry("epoch_seconds", &ts.as_second())?;
+        map.serialize_entry("epoch_nanoseconds", &ts.as_nanosecond())?;
+        map.end()
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for DateTime {
     #[inline]
     fn deserialize<D: serde::Deserializer<'de>>(
@@ -501,13 +592,30 @@ impl DateTimeFlexible {
         relative: &Zoned,
         s: &[u8],
     ) -> anyhow::Result<DateTimeFlexible> {
+        DateTimeFlexible::parse_relative_kind(relative, s).map(|(dt, _)| dt)
+    }
+
+    /// Parses a "flexible" datetime and reports which kind of datetime was
+    /// recognized.
+    ///
+    /// This is like `DateTimeFlexible::parse_relative`, but also returns the
+    /// kind of format that matched. This is useful for diagnostics.
+    pub fn parse_relative_kind(
+        relative: &Zoned,
+        s: &[u8],
+    ) -> anyhow::Result<(DateTimeFlexible, FlexibleKind)> {
         // First try to parse something that is definitive. If it fails,
         // keep the error and we'll report it below if everything else fails.
         // We specifically try parsing a zoned datetime since my guess is
         // that it's the common case in bttf shell pipelines.
         let temporal_parse_err = match TEMPORAL_PARSER.parse_zoned(s) {
             Err(err) => err,
-            Ok(zdt) => return Ok(DateTimeFlexible::from(zdt)),
+            Ok(zdt) => {
+                return Ok((
+                    DateTimeFlexible::from(zdt),
+                    FlexibleKind::Rfc9557,
+                ));
+            }
         };
         // This is somewhat similar to non-flexible parsing, except we'll
         // happily use `TZ` when no offset is found. The non-flexible case
@@ -524,6 +632,10 @@ impl DateTimeFlexible {
             let date = pieces.date();
             let time = pieces.time().unwrap_or(civil::Time::midnight());
             let dt = date.to_datetime(time);
+            let kind = match pieces.offset() {
+                None => FlexibleKind::Local,
+                Some(_) => FlexibleKind::Rfc3339,
+            };
             let zdt = match pieces.offset() {
                 None => dt.to_zoned(TZ.clone())?,
                 Some(fmt::temporal::PiecesOffset::Zulu) => {
@@ -540,15 +652,35 @@ impl DateTimeFlexible {
                     anyhow::bail!("unrecognized parsed offset: {unk:?}")
                 }
             };
-            return Ok(DateTimeFlexible { zdt });
+            return Ok((DateTimeFlexible { zdt }, kind));
+        }
+        // ISO 8601 ordinal dates and week dates aren't supported by Jiff's
+        // parser. So we rewrite them into a calendar date and try again. The
+        // kind reported is the kind of date, since that's the unusual part.
+        if let Some((rewritten, kind)) = rewrite_iso8601_date(s)? {
+            let (dt, _) =
+                DateTimeFlexible::parse_relative_kind(relative, &rewritten)
+                    .with_context(|| {
+                        format!(
+                            "failed to parse `{s}` as an ISO 8601 {what}",
+                            s = BStr::new(s),
+                            what = match kind {
+                                FlexibleKind::WeekDate => "week date",
+                                _ => "ordinal date",
+                            },
+                        )
+                    })?;
+            return Ok((dt, kind));
         }
         // N.B. This also includes RFC 9110.
         if let Ok(zdt) = RFC2822_PARSER.parse_zoned(s) {
-            return Ok(DateTimeFlexible::from(zdt));
+            check_rfc2822_zone(s)?;
+            let zdt = rfc2822_unknown_offset(s, zdt);
+            return Ok((DateTimeFlexible::from(zdt), FlexibleKind::Rfc2822));
         }
         // Now try parsing a relative datetime.
         if let Some(zdt) = parse_relative(relative, s.as_bstr())? {
-            return Ok(DateTimeFlexible::from(zdt));
+            return Ok((DateTimeFlexible::from(zdt), FlexibleKind::Relative));
         }
         // Not really sure how to do good error reporting here, since the
         // format is so flexible. We'd somehow need to invest more work into
@@ -574,6 +706,44 @@ impl DateTimeFlexible {
     }
 }
 
+/// The kind of datetime recognized by flexible datetime parsing.
+#[derive(Clone, Copy, Debug)]
+pub enum FlexibleKind {
+    /// An RFC 9557 timestamp with a time zone annotation.
+    Rfc9557,
+    /// An RFC 3339 timestamp (or something resembling it) with an offset.
+    Rfc3339,
+    /// A civil date or datetime without an offset, interpreted in the
+    /// system time zone.
+    Local,
+    /// An ISO 8601 ordinal date, e.g., `2024-202`, possibly followed by a
+    /// time, offset and time zone annotation.
+    Ordinal,
+    /// An ISO 8601 week date, e.g., `2024-W29-6`, possibly followed by a
+    /// time, offset and time zone annotation.
+    WeekDate,
+    /// An RFC 2822 (or RFC 9110) timestamp.
+    Rfc2822,
+    /// A datetime relative to some reference point, e.g., `-1d` or
+    /// `next thurs`.
+    Relative,
+}
+
+impl FlexibleKind {
+    /// Returns a short label describing this kind of datetime.
+    pub fn label(&self) -> &'static str {
+        match *self {
+            FlexibleKind::Rfc9557 => "rfc9557",
+            FlexibleKind::Rfc3339 => "rfc3339",
+            FlexibleKind::Local => "local",
+            FlexibleKind::Ordinal => "ordinal",
+            FlexibleKind::WeekDate => "week-date",
+            FlexibleKind::Rfc2822 => "rfc2822",
+            FlexibleKind::Relative => "relative",
+        }
+    }
+}
+
 impl From<Zoned> for DateTimeFlexible {
     fn from(zdt: Zoned) -> DateTimeFlexible {
         DateTimeFlexible { zdt }
@@ -602,6 +772,149 @@ impl FromBytes for DateTimeFlexible {
     }
 }
 
+/// Checks that the time zone of an RFC 2822 (or RFC 9110) datetime
+/// unambiguously identifies an offset.
+///
+/// This should be called after successfully parsing `s` with Jiff's RFC 2822
+/// parser. Jiff accepts any alphabetic time zone and treats ones it doesn't
+/// recognize (including the obsolete military time zones) as UTC, as RFC 2822
+/// recommends. In practice, this silently produces the wrong instant, since
+/// abbreviations like `IST` are used in multiple places with different
+/// offsets. So we reject all of them except for the ones RFC 2822 defines
+/// (and `UTC`).
+pub fn check_rfc2822_zone(s: &[u8]) -> anyhow::Result<()> {
+    const KNOWN: &[&str] = &[
+        "UT", "UTC", "GMT", "Z", "EST", "EDT", "CST", "CDT", "MST", "MDT",
+        "PST", "PDT",
+    ];
+
+    let s = s.trim_ascii_end();
+    let zone = s.rsplit(|b| b.is_ascii_whitespace()).next().unwrap_or(s);
+    if zone.is_empty()
+        || !zone.iter().all(|b| b.is_ascii_alphabetic())
+        || KNOWN
+            .iter()
+            .any(|known| zone.eq_ignore_ascii_case(known.as_bytes()))
+    {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "unrecognized or ambiguous RFC 2822 time zone `{zone}` \
+         (the supported time zone names are {known})",
+        zone = zone.as_bstr(),
+        known = KNOWN.join(", "),
+    )
+}
+
+/// Puts an RFC 2822 (or RFC 9110) datetime into the unknown time zone when
+/// its offset is `-0000`.
+///
+/// RFC 2822 says that `-0000` indicates that the datetime is in UTC, but that
+/// the local offset is unknown. Jiff parses it as a fixed offset of zero. This
+/// treats it the same way as `Z` or `-00:00` in RFC 3339, so that it round
+/// trips. `zdt` should be the result of parsing `s` with Jiff's RFC 2822
+/// parser.
+pub fn rfc2822_unknown_offset(s: &[u8], zdt: Zoned) -> Zoned {
+    let s = s.trim_ascii_end();
+    let zone = s.rsplit(|b| b.is_ascii_whitespace()).next().unwrap_or(s);
+    if zone != b"-0000" {
+        return zdt;
+    }
+    zdt.with_time_zone(tz::TimeZone::unknown())
+}
+
+/// Rewrites an ISO 8601 ordinal date or week date at the start of the given
+/// string into a calendar date.
+///
+/// Only the extended formats are recognized, e.g., `2024-202` or
+/// `2024-W29-6`. Anything following the date (like a time or an offset)
+/// is kept as-is. Week dates always use the ISO 8601 definition of a week,
+/// which starts on Monday.
+///
+/// If the string doesn't start with an ordinal date or week date, then
+/// `None` is returned. If it does but the date is invalid (e.g., day `366`
+/// in a year that isn't a leap year), then an error is returned.
+pub fn rewrite_iso8601_date(
+    s: &[u8],
+) -> anyhow::Result<Option<(Vec<u8>, FlexibleKind)>> {
+    fn number(bytes: &[u8]) -> Option<i16> {
+        if !bytes.iter().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        bytes.to_str().ok()?.parse().ok()
+    }
+
+    let Some(year) = s.get(..4).and_then(number) else { return Ok(None) };
+    let Some(rest) = s[4..].strip_prefix(b"-") else { return Ok(None) };
+    let (date, kind, rest) = if let Some(rest) = rest.strip_prefix(b"W") {
+        let Some(week) = rest.get(..2).and_then(number) else {
+            return Ok(None);
+        };
+        if rest.get(2) != Some(&b'-') {
+            return Ok(None);
+        }
+        let Some(weekday) = rest.get(3..4).and_then(number) else {
+            return Ok(None);
+        };
+        let rest = &rest[4..];
+        if !is_date_end(rest) {
+            return Ok(None);
+        }
+        let invalid = || {
+            format!(
+                "invalid ISO 8601 week date `{}`",
+                s[..s.len() - rest.len()].as_bstr(),
+            )
+        };
+        anyhow::ensure!(
+            (1..=53).contains(&week),
+            "{}: week number must be in the range 1..=53",
+            invalid(),
+        );
+        let weekday = jiff::civil::Weekday::from_monday_one_offset(
+            i8::try_from(weekday).unwrap(),
+        )
+        .with_context(invalid)?;
+        let week = i8::try_from(week).unwrap();
+        let wd = crate::weekdate::WeekDate::new(
+            jiff::civil::Weekday::Monday,
+            year,
+            week,
+            weekday,
+        )
+        .with_context(invalid)?;
+        (wd.date(), FlexibleKind::WeekDate, rest)
+    } else {
+        let Some(day) = rest.get(..3).and_then(number) else {
+            return Ok(None);
+        };
+        let rest = &rest[3..];
+        if !is_date_end(rest) {
+            return Ok(None);
+        }
+        let date = civil::date(year, 1, 1)
+            .with()
+            .day_of_year(day)
+            .build()
+            .with_context(|| {
+                format!(
+                    "invalid ISO 8601 ordinal date `{}`",
+                    s[..s.len() - rest.len()].as_bstr(),
+                )
+            })?;
+        (date, FlexibleKind::Ordinal, rest)
+    };
+    let mut rewritten = date.to_string().into_bytes();
+    rewritten.extend_from_slice(rest);
+    Ok(Some((rewritten, kind)))
+}
+
+/// Returns true when the given string, which follows a date, is either empty
+/// or starts with a time.
+fn is_date_end(rest: &[u8]) -> bool {
+    matches!(rest.first(), None | Some(b'T' | b't' | b' '))
+}
+
 /// Tries to parse a datetime in `s` relative to the one given.
 ///
 /// If one could not be found, then `None` is returned. If one is definitively
@@ -650,10 +963,8 @@ fn parse_relative(
         let Some((first, rest)) = rest.split_once_str(" ") else {
             return Ok(if let Ok(wd) = rest.parse::<Weekday>() {
                 Some(relative_weekday(&relative, 0, wd)?)
-            } else if let Some(zdt) = parse_day(&relative, rest.as_bstr())? {
-                Some(zdt)
             } else {
-                None
+                parse_day(&relative, rest.as_bstr())?
             });
         };
         if let Some(n) = parse_multiplier(first.as_bstr())? {
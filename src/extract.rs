@@ -1,13 +1,16 @@
 use std::{borrow::Cow, ops::Range, sync::LazyLock};
 
 use {
+    anyhow::Context,
+    bstr::ByteSlice,
     jiff::fmt::{rfc2822, temporal},
     regex_automata::{PatternID, meta::Regex},
     regex_syntax::hir::Hir,
 };
 
 use crate::{
-    args::{self, Configurable, Usage},
+    args::{self, Configurable, Usage, flags},
+    datetime::{self, DateTimeFlexible},
     timezone,
 };
 
@@ -50,6 +53,7 @@ impl Extractor {
 #[derive(Clone, Debug, Default)]
 pub struct ExtractorBuilder {
     auto: Option<Auto>,
+    formats: Vec<AutoFormat>,
     patterns: Vec<Pattern>,
     all: bool,
 }
@@ -71,15 +75,34 @@ impl ExtractorBuilder {
         // user provided patterns by virtue of regex's leftmost-first
         // match semantics.
         match self.auto() {
-            Auto::None => {}
+            Auto::None => {
+                anyhow::ensure!(
+                    self.formats.is_empty(),
+                    "--timestamp-format can only be used when extracting \
+                     datetimes, but `--auto none` was given",
+                );
+            }
             Auto::DateTime => {
-                for &(pattern, validator) in AUTO_DATE_TIME.iter() {
+                for &(format, pattern, validator) in AUTO_DATE_TIME.iter() {
+                    let enabled = if self.formats.is_empty() {
+                        format.is_default()
+                    } else {
+                        self.formats.contains(&format)
+                    };
+                    if !enabled {
+                        continue;
+                    }
                     let pattern: Pattern = pattern.parse()?;
                     patterns.push(Cow::Owned(pattern.hir));
                     validators.push(validator);
                 }
             }
             Auto::TimeZone => {
+                anyhow::ensure!(
+                    self.formats.is_empty(),
+                    "--timestamp-format can only be used when extracting \
+                     datetimes, but `--auto timezone` was given",
+                );
                 // For time zones, we don't bother with making each IANA
                 // identifier its own pattern. We could, but there's no real
                 // benefit to it. And I believe stick them all in one regex
@@ -102,7 +125,7 @@ impl ExtractorBuilder {
                     // I believe the escaping is not necessary here (since I
                     // don't think IANA ids can have regex meta characters in
                     // them), but we do so for robustness reasons.
-                    .map(|name| regex_syntax::escape(&name))
+                    .map(|name| regex_syntax::escape(name))
                     .collect::<Vec<String>>()
                     .join("|");
                 let pattern: Pattern = pattern.parse()?;
@@ -121,13 +144,26 @@ impl ExtractorBuilder {
             .configure(Regex::config().auto_prefilter(false))
             .build_many_from_hir(&patterns)?;
 
+        let groups = regex.group_info();
         let mut tag_group_indices = vec![];
         for pid in (0..regex.pattern_len()).map(PatternID::new_unchecked) {
-            if let Some(i) = regex.group_info().to_index(pid, "tag") {
+            // User provided patterns come first, so a pattern ID less than
+            // the number of user patterns always refers to one of them.
+            let capture =
+                self.patterns.get(pid.as_usize()).and_then(|p| p.capture());
+            let Some(name) = capture else {
+                let i = groups.to_index(pid, "tag").unwrap_or(0);
                 tag_group_indices.push(i);
-            } else {
-                tag_group_indices.push(0);
-            }
+                continue;
+            };
+            let pattern = &self.patterns[pid.as_usize()].original;
+            let i = groups.to_index(pid, name).with_context(|| {
+                format!(
+                    "capture group `{name}` given by --capture was not \
+                     found in pattern `{pattern}`",
+                )
+            })?;
+            tag_group_indices.push(i);
         }
         Ok(Extractor { regex, validators, tag_group_indices, all: self.all })
     }
@@ -137,11 +173,15 @@ impl ExtractorBuilder {
     /// When no patterns or given and no explicit value is given, then
     /// the automatic setting defaults to `DateTime`. Otherwise, if a
     /// pattern is given but `--auto` isn't given, then this defaults to
-    /// `None`.
+    /// `None`. Restricting the datetime formats with `--timestamp-format`
+    /// always implies `DateTime` when `--auto` isn't given.
     fn auto(&self) -> Auto {
-        self.auto.unwrap_or_else(|| {
-            if self.patterns.is_empty() { Auto::DateTime } else { Auto::None }
-        })
+        let default = if self.patterns.is_empty() || !self.formats.is_empty() {
+            Auto::DateTime
+        } else {
+            Auto::None
+        };
+        self.auto.unwrap_or(default)
     }
 }
 
@@ -158,11 +198,27 @@ impl Configurable for ExtractorBuilder {
             lexopt::Arg::Long("all") => {
                 self.all = true;
             }
+            lexopt::Arg::Long("timestamp-format") => {
+                let formats: String = args::parse(p, "--timestamp-format")?;
+                for format in formats.split(",") {
+                    let format =
+                        format.parse().context("--timestamp-format")?;
+                    self.formats.push(format);
+                }
+            }
             lexopt::Arg::Short('e')
             | lexopt::Arg::Long("regex")
             | lexopt::Arg::Long("regexp") => {
                 self.patterns.push(args::parse(p, "-e/--regex")?);
             }
+            lexopt::Arg::Long("capture") => {
+                let name: String = args::parse(p, "--capture")?;
+                let pattern = self.patterns.last_mut().context(
+                    "--capture must come after the -e/--regex flag \
+                     it applies to",
+                )?;
+                pattern.capture = Some(name);
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -177,6 +233,8 @@ Find all matching tags in each line given.
 
 By default, only the first tag found is extracted. Subsequent tags on a line
 are ignored. This flag overrides that behavior and finds all tags on each line.
+When multiple patterns are given, this reports every match of every pattern
+in the order in which they appear in the data.
 
 The downside of this option is that multiple tags for each datum can lead to
 some confusing behavior in other commands. For example, `bttf time cmp lt` will
@@ -185,7 +243,24 @@ datetime.
 "#,
         );
 
-        &[Auto::USAGE, Pattern::USAGE, ALL]
+        const CAPTURE: Usage = Usage::flag(
+            "--capture <name>",
+            "The capture group to extract tags from.",
+            r#"
+The name of the capture group to extract tags from.
+
+This applies to the `-e/--regex` flag given immediately before it. So when
+multiple patterns are given, each one may select its own capture group. For
+example, `-e '(?<d>[0-9]{4}-[0-9]{2}-[0-9]{2})' --capture d` uses the value of
+the capture group named `d` as the tag.
+
+When this isn't given, then a capture group named `tag` is used if it exists.
+Otherwise, the entire match is used. It is an error to name a capture group
+that doesn't exist in the corresponding pattern.
+"#,
+        );
+
+        &[Auto::USAGE, AutoFormat::USAGE, Pattern::USAGE, CAPTURE, ALL]
     }
 }
 
@@ -237,9 +312,102 @@ impl std::str::FromStr for Auto {
     }
 }
 
+/// A datetime format recognized by automatic datetime extraction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AutoFormat {
+    Rfc9557,
+    Rfc3339,
+    Rfc9110,
+    Rfc2822,
+    Iso8601,
+    Unix,
+    Friendly,
+}
+
+impl AutoFormat {
+    const USAGE: Usage = Usage::flag(
+        "--timestamp-format <format>",
+        "Only extract datetimes in these formats, e.g., `rfc3339`.",
+        r#"
+Only extract datetimes in these formats, e.g., `rfc3339`.
+
+By default, automatic datetime extraction recognizes all of the formats it
+supports. This restricts extraction to only the formats given, which can be
+useful for avoiding spurious tags when the data contains datetimes in more
+than one format and only some of them are of interest.
+
+The supported values are `rfc9557`, `rfc3339`, `rfc9110` and `rfc2822`, which
+are the formats recognized by default, along with the following formats that
+are only recognized when requested explicitly:
+
+`iso8601` is a civil date with an optional time and no offset, e.g.,
+`2025-03-15` or `2025-03-15T10:23:00`.
+
+`unix` is an integer Unix timestamp of 10, 13, 16 or 19 digits, i.e., seconds,
+milliseconds, microseconds or nanoseconds since the Unix epoch.
+
+`friendly` is a datetime relative to the current time, e.g., `3 days ago`,
+`1h30m ago`, `yesterday`, `today` or `tomorrow`.
+
+Since these tags don't refer to an instant on their own, they usually need to
+be parsed before other commands can use them. Use `bttf time parse -f flexible`
+for `iso8601` and `friendly` tags, and `bttf time epoch --parse -u auto` for
+`unix` tags.
+
+More than one format may be given by separating them with commas, or by
+repeating this flag.
+
+Note that an RFC 9557 timestamp begins with an RFC 3339 timestamp, which in
+turn begins with an ISO 8601 civil datetime. So when `rfc3339` is given
+without `rfc9557`, only the RFC 3339 prefix of an RFC 9557 timestamp is
+extracted. Similarly for `iso8601` without `rfc3339`.
+
+This implies `--auto datetime`, and it is an error to use this with any other
+`--auto` value.
+"#,
+    );
+
+    /// Returns true when this format is recognized when no formats are given
+    /// explicitly.
+    fn is_default(self) -> bool {
+        match self {
+            AutoFormat::Rfc9557
+            | AutoFormat::Rfc3339
+            | AutoFormat::Rfc9110
+            | AutoFormat::Rfc2822 => true,
+            AutoFormat::Iso8601 | AutoFormat::Unix | AutoFormat::Friendly => {
+                false
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for AutoFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<AutoFormat> {
+        match s {
+            "rfc9557" => Ok(AutoFormat::Rfc9557),
+            "rfc3339" => Ok(AutoFormat::Rfc3339),
+            "rfc9110" => Ok(AutoFormat::Rfc9110),
+            "rfc2822" => Ok(AutoFormat::Rfc2822),
+            "iso8601" => Ok(AutoFormat::Iso8601),
+            "unix" => Ok(AutoFormat::Unix),
+            "friendly" => Ok(AutoFormat::Friendly),
+            unk => anyhow::bail!(
+                "unknown timestamp format `{unk}` (supported formats are \
+                 rfc9557, rfc3339, rfc9110, rfc2822, iso8601, unix and \
+                 friendly)",
+            ),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 struct Pattern {
+    original: String,
     hir: Hir,
+    capture: Option<String>,
 }
 
 impl Pattern {
@@ -252,7 +420,10 @@ A pattern for extracting tags.
 Matches of this pattern are treated as tags. If the pattern has a capture
 group named `tag`, then the value of that group is used instead.
 
-Multiple patterns may be given.
+Multiple patterns may be given. When there are multiple patterns, the first
+tag is the leftmost match of any of them. If more than one pattern matches at
+the same position, then the pattern given first wins. Use `--all` to extract
+every match of every pattern.
 
 Note that matches are not validated. For example, if one uses a regex like
 `[0-9]{4}-[0-9]{2}-[0-9]{2}`, then this will match `2025-13-01`, which is
@@ -262,14 +433,22 @@ to validate tags as datetimes, and invalid datetimes can be dropped with the
 `-i/--ignore-invalid` flag.
 "#,
     );
+
+    /// Returns the name of the capture group to extract tags from, if one
+    /// was explicitly given.
+    fn capture(&self) -> Option<&str> {
+        self.capture.as_deref()
+    }
 }
 
 impl std::str::FromStr for Pattern {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> anyhow::Result<Pattern> {
-        let hir = regex_syntax::Parser::new().parse(s)?;
-        Ok(Pattern { hir })
+        let hir = regex_syntax::Parser::new().parse(s).map_err(|err| {
+            anyhow::anyhow!("failed to compile pattern `{s}`:\n{err}")
+        })?;
+        Ok(Pattern { original: s.to_string(), hir, capture: None })
     }
 }
 
@@ -289,9 +468,10 @@ static AUTO_TIME_ZONE: LazyLock<Vec<String>> = LazyLock::new(|| {
 
 /// The regexes to use when doing automatic detection for datetimes.
 ///
-/// We specifically only support well known and _specified_ datetime formats.
-/// That is, if we find one of these, it is very very likely to be an actual
-/// datetime.
+/// By default, we specifically only support well known and _specified_
+/// datetime formats. That is, if we find one of these, it is very very likely
+/// to be an actual datetime. The other formats are more prone to false
+/// positives, so they're only used when asked for via `--timestamp-format`.
 ///
 /// These regexes are permitted to have false positives. When a match is
 /// found, the matched text is parsed as the corresponding datetime, which
@@ -300,7 +480,7 @@ static AUTO_TIME_ZONE: LazyLock<Vec<String>> = LazyLock::new(|| {
 /// Ideally these regexes do not have any false negatives, but they probably
 /// do have some corner cases. We should probably try to fix those, but only
 /// if it's practical to do so in the regex.
-static AUTO_DATE_TIME: &[(&str, Validator)] = {
+static AUTO_DATE_TIME: &[(AutoFormat, &str, Validator)] = {
     // e.g., 2025-11-05T16:13:00.123456789-04:00[America/New_York]
     static RFC9557: &str = r#"(?x)
         [0-9]{4}-?[0-9]{2}-?[0-9]{2}
@@ -350,11 +530,60 @@ static AUTO_DATE_TIME: &[(&str, Validator)] = {
             (?:UT|GMT|EST|EDT|CST|CDT|MST|MDT|PST|PDT)
         )
     "#;
+
+    // e.g., 2025-11-05T16:13:00.123456789 or 2025-11-05
+    //
+    // We require separators (unlike RFC 3339 above) since otherwise this
+    // would match all sorts of integers.
+    static ISO8601: &str = r#"(?x)
+        \b
+        [0-9]{4}-[0-9]{2}-[0-9]{2}
+        (?:
+            (?:T|\x20)
+            [0-9]{2}:[0-9]{2}(?::[0-9]{2}(?:[.,][0-9]{1,9})?)?
+        )?
+    "#;
+
+    // e.g., 1762377180 or 1762377180123
+    static UNIX: &str = r#"(?x)
+        \b[0-9]{10}(?:[0-9]{3}){0,3}\b
+    "#;
+
+    // e.g., 3 days ago, 1h30m ago or yesterday
+    static FRIENDLY: &str = r#"(?x)
+        \b
+        (?:
+            (?:
+                [0-9]+
+                \x20?
+                (?:
+                    years?|yrs?|y
+                    |months?|mos?|mo
+                    |weeks?|wks?|w
+                    |days?|d
+                    |hours?|hrs?|h
+                    |minutes?|mins?|m
+                    |seconds?|secs?|s
+                    |milliseconds?|millis?|msecs?|ms
+                    |microseconds?|micros?|usecs?|us
+                    |nanoseconds?|nanos?|nsecs?|ns
+                )
+                ,?\x20?
+            )+
+            ago
+            |
+            yesterday|today|tomorrow
+        )
+        \b
+    "#;
     &[
-        (RFC9557, validate_rfc9557),
-        (RFC3339, validate_rfc3339),
-        (RFC9110, validate_rfc2822),
-        (RFC2822, validate_rfc2822),
+        (AutoFormat::Rfc9557, RFC9557, validate_rfc9557),
+        (AutoFormat::Rfc3339, RFC3339, validate_rfc3339),
+        (AutoFormat::Rfc9110, RFC9110, validate_rfc2822),
+        (AutoFormat::Rfc2822, RFC2822, validate_rfc2822),
+        (AutoFormat::Iso8601, ISO8601, validate_iso8601),
+        (AutoFormat::Unix, UNIX, validate_unix),
+        (AutoFormat::Friendly, FRIENDLY, validate_friendly),
     ]
 };
 
@@ -378,6 +607,22 @@ fn validate_rfc3339(bytes: &[u8]) -> bool {
 // Also used for RFC 9110. (Jiff doesn't have a separate RFC 9110 parser.)
 fn validate_rfc2822(bytes: &[u8]) -> bool {
     RFC2822_PARSER.parse_zoned(bytes).is_ok()
+        && datetime::check_rfc2822_zone(bytes).is_ok()
+}
+
+fn validate_iso8601(bytes: &[u8]) -> bool {
+    TEMPORAL_PARSER.parse_datetime(bytes).is_ok()
+}
+
+fn validate_unix(bytes: &[u8]) -> bool {
+    let Some(number) = bytes.to_str().ok().and_then(|s| s.parse().ok()) else {
+        return false;
+    };
+    flags::EpochUnit::detect(number).to_timestamp(number).is_ok()
+}
+
+fn validate_friendly(bytes: &[u8]) -> bool {
+    DateTimeFlexible::parse_relative(&crate::NOW, bytes).is_ok()
 }
 
 // We don't do any extra validation since every match is a true positive.
@@ -7,7 +7,7 @@ use std::{
 use {
     anyhow::Context,
     jiff::{
-        Span, ToSpan, Zoned,
+        Span, Timestamp, ToSpan, Zoned,
         civil::{DateTime, Weekday},
         tz::{AmbiguousOffset, TimeZone},
     },
@@ -43,6 +43,7 @@ struct RecurrenceRuleInner {
     zoned_start: Zoned,
     civil_start: DateTime,
     zoned_until: Option<Zoned>,
+    count: Option<usize>,
     interval: Span,
     by_month: Box<[i8]>,
     // can be negative
@@ -59,6 +60,10 @@ struct RecurrenceRuleInner {
     // can be negative
     by_set_pos: Box<[i32]>,
     week_start: Weekday,
+    // sorted, for binary search
+    exclusions: Box<[Timestamp]>,
+    // sorted chronologically, without duplicate instants
+    inclusions: Box<[Zoned]>,
 }
 
 impl RecurrenceRule {
@@ -70,6 +75,127 @@ impl RecurrenceRule {
         RecurrenceRuleBuilder::new(freq, start)
     }
 
+    /// Parses the value of an RFC 5545 RRULE property into a recurrence rule
+    /// starting at the datetime given.
+    ///
+    /// The value is a list of `NAME=VALUE` parts separated by semicolons, e.g.,
+    /// `FREQ=MONTHLY;BYDAY=1FR;COUNT=10`. It may optionally be prefixed by
+    /// `RRULE:`. Names are case insensitive. Unknown and repeated parts are
+    /// rejected. Otherwise, each part is mapped to the corresponding builder
+    /// method, so the values themselves are validated by
+    /// `RecurrenceRuleBuilder::build`.
+    ///
+    /// An `UNTIL` value that is a UTC datetime (with a `Z` suffix) refers to
+    /// a precise instant. A local datetime is interpreted in the time zone of
+    /// `start`. And a date refers to the last instant of that date in the
+    /// time zone of `start`.
+    pub fn from_rrule_str(
+        start: Zoned,
+        rrule: &str,
+    ) -> anyhow::Result<RecurrenceRule> {
+        let trimmed = rrule.trim();
+        let body = match trimmed.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => {
+                &trimmed[6..]
+            }
+            _ => trimmed,
+        };
+
+        let mut parts: Vec<(String, &str)> = vec![];
+        for part in body.split(';').filter(|part| !part.is_empty()) {
+            let Some((name, value)) = part.split_once('=') else {
+                anyhow::bail!(
+                    "invalid RRULE part `{part}` (expected `NAME=VALUE`)"
+                );
+            };
+            let name = name.to_ascii_uppercase();
+            anyhow::ensure!(
+                !parts.iter().any(|(seen, _)| *seen == name),
+                "RRULE part `{name}` was given more than once",
+            );
+            parts.push((name, value));
+        }
+
+        let &(_, freq) = parts
+            .iter()
+            .find(|(name, _)| name == "FREQ")
+            .context("RRULE is missing required `FREQ` part")?;
+        let freq: Frequency = freq
+            .parse()
+            .with_context(|| format!("invalid RRULE part `FREQ={freq}`"))?;
+        let mut b = RecurrenceRule::builder(freq, start.clone());
+        for &(ref name, value) in parts.iter() {
+            let mut apply = || -> anyhow::Result<()> {
+                match name.as_str() {
+                    "FREQ" => {}
+                    "INTERVAL" => {
+                        b.interval(value.parse()?);
+                    }
+                    "COUNT" => {
+                        b.count(value.parse()?);
+                    }
+                    "UNTIL" => {
+                        b.until(parse_rrule_until(&start, value)?);
+                    }
+                    "BYMONTH" => {
+                        for v in parse_rrule_list::<i8>(value) {
+                            b.by_month(v?);
+                        }
+                    }
+                    "BYWEEKNO" => {
+                        for v in parse_rrule_list::<i8>(value) {
+                            b.by_week(v?);
+                        }
+                    }
+                    "BYYEARDAY" => {
+                        for v in parse_rrule_list::<i16>(value) {
+                            b.by_year_day(v?);
+                        }
+                    }
+                    "BYMONTHDAY" => {
+                        for v in parse_rrule_list::<i8>(value) {
+                            b.by_month_day(v?);
+                        }
+                    }
+                    "BYDAY" => {
+                        for v in value.split(',') {
+                            b.by_week_day(parse_rrule_by_day(v)?);
+                        }
+                    }
+                    "BYHOUR" => {
+                        for v in parse_rrule_list::<i8>(value) {
+                            b.by_hour(v?);
+                        }
+                    }
+                    "BYMINUTE" => {
+                        for v in parse_rrule_list::<i8>(value) {
+                            b.by_minute(v?);
+                        }
+                    }
+                    "BYSECOND" => {
+                        for v in parse_rrule_list::<i8>(value) {
+                            b.by_second(v?);
+                        }
+                    }
+                    "BYSETPOS" => {
+                        for v in parse_rrule_list::<i32>(value) {
+                            b.by_set_position(v?);
+                        }
+                    }
+                    "WKST" => {
+                        b.week_start(value.parse::<flags::Weekday>()?.get());
+                    }
+                    unk => anyhow::bail!("unsupported RRULE part `{unk}`"),
+                }
+                Ok(())
+            };
+            apply().with_context(|| {
+                format!("invalid RRULE part `{name}={value}`")
+            })?;
+        }
+        b.build()
+    }
+
     /// Returns an iterator over all datetimes in this recurrence rule.
     ///
     /// Note that the iterator may be "infinite," in the sense that it returns
@@ -81,6 +207,9 @@ impl RecurrenceRule {
             rule: self,
             set: RecurrenceSet::new(),
             cur: Some((0, self.inner.civil_start)),
+            generated: 0,
+            peeked: None,
+            included: 0,
         }
     }
 
@@ -88,6 +217,11 @@ impl RecurrenceRule {
     fn time_zone(&self) -> &TimeZone {
         self.inner.zoned_start.time_zone()
     }
+
+    /// Returns true when the given datetime has been excluded from this rule.
+    fn is_excluded(&self, zdt: &Zoned) -> bool {
+        self.inner.exclusions.binary_search(&zdt.timestamp()).is_ok()
+    }
 }
 
 impl<'r> IntoIterator for &'r RecurrenceRule {
@@ -141,7 +275,7 @@ impl<'a> Expander<'a> {
             while let Some(zdt) = set.pop_civil(self.rule) {
                 set.zoned.push(zdt);
             }
-            set.zoned.sort_by(|zdt1, zdt2| zdt1.cmp(zdt2));
+            set.zoned.sort();
             set.zoned.dedup();
 
             let mut position = 0;
@@ -900,9 +1034,54 @@ pub struct RecurrenceIter<'r> {
     ///
     /// When this is `None`, iteration has ceased.
     cur: Option<(i64, DateTime)>,
+    /// The number of datetimes generated so far. This is used to stop
+    /// iteration when the rule has a "count."
+    ///
+    /// Following RFC 5545, this includes datetimes that were generated but
+    /// then excluded, and does not include datetimes that were included
+    /// explicitly.
+    generated: usize,
+    /// The next datetime generated by the rule, when it has been generated
+    /// but not yet emitted. This is used to merge inclusions into the
+    /// datetimes generated by the rule in chronological order.
+    peeked: Option<Zoned>,
+    /// The index of the next inclusion to emit.
+    included: usize,
 }
 
 impl<'r> RecurrenceIter<'r> {
+    /// Returns the next datetime generated by the rule without consuming it.
+    ///
+    /// This respects the "count" of the rule, but not its exclusions or
+    /// inclusions.
+    fn peek_generated(&mut self) -> Option<&Zoned> {
+        if self.peeked.is_none() {
+            if let Some(count) = self.rule.inner.count
+                && self.generated >= count
+            {
+                return None;
+            }
+            self.peeked = self.generate();
+            if self.peeked.is_some() {
+                self.generated += 1;
+            }
+        }
+        self.peeked.as_ref()
+    }
+
+    /// Returns the next datetime generated by the rule, without regard to
+    /// its "count," exclusions or inclusions.
+    fn generate(&mut self) -> Option<Zoned> {
+        while !self.is_done() {
+            if let Some(zdt) = self.set.pop(self.rule) {
+                return Some(zdt);
+            }
+            self.expand();
+            self.cur = self.increment();
+        }
+        None
+    }
+
     fn expand(&mut self) {
         let Some((_, cur)) = self.cur else { return };
         Expander { rule: self.rule, cur }.expand(&mut self.set);
@@ -912,6 +1091,35 @@ impl<'r> RecurrenceIter<'r> {
         self.cur.is_none() && self.set.is_empty()
     }
 
+    /// Returns the earliest datetime that expanding the interval starting
+    /// at `dt` could produce.
+    ///
+    /// This is the start of the period (year, month, week, etc.)
+    /// containing `dt` for the frequency of this rule. For a yearly
+    /// frequency, this also includes the last week of the previous year,
+    /// since week numbers can refer to dates in it.
+    fn earliest_in_interval(&self, dt: DateTime) -> Option<DateTime> {
+        let rule = &self.rule.inner;
+        match rule.freq {
+            Frequency::Yearly => {
+                dt.first_of_year().start_of_day().checked_sub(1.week()).ok()
+            }
+            Frequency::Monthly => Some(dt.first_of_month().start_of_day()),
+            Frequency::Weekly => {
+                let start = first_of_week(rule.week_start, dt.date()).ok()?;
+                Some(start.to_datetime(jiff::civil::Time::midnight()))
+            }
+            Frequency::Daily => Some(dt.start_of_day()),
+            Frequency::Hourly => {
+                dt.with().minute(0).second(0).subsec_nanosecond(0).build().ok()
+            }
+            Frequency::Minutely => {
+                dt.with().second(0).subsec_nanosecond(0).build().ok()
+            }
+            Frequency::Secondly => dt.with().subsec_nanosecond(0).build().ok(),
+        }
+    }
+
     fn increment(&self) -> Option<(i64, DateTime)> {
         let (mut attempt, orig) = self.cur?;
         let interval = self.rule.inner.interval;
@@ -938,12 +1146,18 @@ impl<'r> RecurrenceIter<'r> {
             // set is empty after expansion for a single interval, that doesn't
             // mean iteration can stop. Because a subsequent interval might
             // generate a non-empty recurrence set.
+            //
+            // Also, expansion can produce datetimes *before* `next`. For
+            // example, a weekly rule starting on a Saturday with BYDAY=MO
+            // expands to the Monday of the same week. So we compare `until`
+            // with the earliest datetime that this interval could expand to.
+            // Otherwise, we'd stop one interval too early.
             if let Some(ref until) = self.rule.inner.zoned_until {
                 let zoned_next = self
                     .rule
                     .time_zone()
-                    .to_ambiguous_zoned(next)
-                    .later()
+                    .to_ambiguous_zoned(self.earliest_in_interval(next)?)
+                    .earlier()
                     .ok()?;
                 if &zoned_next > until {
                     return None;
@@ -979,20 +1193,22 @@ impl<'r> RecurrenceIter<'r> {
             // unless the rule would have otherwise selected it.
             let r = &self.rule.inner;
             match r.freq {
-                Frequency::Yearly if next.day() != orig.day() => {
-                    if r.by_month.is_empty()
+                Frequency::Yearly
+                    if next.day() != orig.day()
+                        && r.by_month.is_empty()
                         && r.by_week.is_empty()
                         && r.by_year_day.is_empty()
                         && r.by_month_day.is_empty()
-                        && r.by_week_day.is_empty()
-                    {
-                        continue;
-                    }
+                        && r.by_week_day.is_empty() =>
+                {
+                    continue;
                 }
-                Frequency::Monthly if next.day() != orig.day() => {
-                    if r.by_week_day.is_empty() && r.by_month_day.is_empty() {
-                        continue;
-                    }
+                Frequency::Monthly
+                    if next.day() != orig.day()
+                        && r.by_week_day.is_empty()
+                        && r.by_month_day.is_empty() =>
+                {
+                    continue;
                 }
                 _ => {}
             }
@@ -1005,14 +1221,32 @@ impl<'r> Iterator for RecurrenceIter<'r> {
     type Item = Zoned;
 
     fn next(&mut self) -> Option<Zoned> {
-        while !self.is_done() {
-            while let Some(zdt) = self.set.pop(&self.rule) {
+        let inclusions = &self.rule.inner.inclusions;
+        loop {
+            let generated = self.peek_generated().map(|zdt| zdt.timestamp());
+            let included = inclusions.get(self.included);
+            let zdt = match (generated, included) {
+                (None, None) => return None,
+                (Some(generated), Some(included))
+                    if included.timestamp() > generated =>
+                {
+                    self.peeked.take()?
+                }
+                (Some(_), None) => self.peeked.take()?,
+                (generated, Some(included)) => {
+                    self.included += 1;
+                    // When an inclusion is the same instant as a generated
+                    // datetime, it is only emitted once.
+                    if generated == Some(included.timestamp()) {
+                        self.peeked = None;
+                    }
+                    included.clone()
+                }
+            };
+            if !self.rule.is_excluded(&zdt) {
                 return Some(zdt);
             }
-            self.expand();
-            self.cur = self.increment();
         }
-        None
     }
 }
 
@@ -1024,6 +1258,7 @@ pub struct RecurrenceRuleBuilder {
     freq: Frequency,
     start: Zoned,
     until: Option<Zoned>,
+    count: Option<usize>,
     interval: i32,
     by_month: Vec<i8>,
     by_week: Vec<i8>,
@@ -1035,6 +1270,8 @@ pub struct RecurrenceRuleBuilder {
     by_second: Vec<i8>,
     by_set_pos: Vec<i32>,
     week_start: Weekday,
+    exclusions: Vec<Timestamp>,
+    inclusions: Vec<Zoned>,
 }
 
 impl RecurrenceRuleBuilder {
@@ -1043,6 +1280,7 @@ impl RecurrenceRuleBuilder {
             freq,
             start,
             until: None,
+            count: None,
             interval: 1,
             by_month: vec![],
             by_week: vec![],
@@ -1054,6 +1292,8 @@ impl RecurrenceRuleBuilder {
             by_second: vec![],
             by_set_pos: vec![],
             week_start: Weekday::Monday,
+            exclusions: vec![],
+            inclusions: vec![],
         }
     }
 
@@ -1073,28 +1313,28 @@ impl RecurrenceRuleBuilder {
         );
         for &v in self.by_month.iter() {
             anyhow::ensure!(
-                1 <= v && v <= 12,
+                (1..=12).contains(&v),
                 "invalid 'by month' value `{v}` \
                  (values must be in range 1..=12)",
             );
         }
         for &v in self.by_week.iter() {
             anyhow::ensure!(
-                (-53 <= v && v <= -1) || (1 <= v && v <= 53),
+                (-53..=-1).contains(&v) || (1..=53).contains(&v),
                 "invalid 'by week' value `{v}` \
                  (values must be in range 1..=53 or -53..=-1)",
             );
         }
         for &v in self.by_year_day.iter() {
             anyhow::ensure!(
-                (-366 <= v && v <= -1) || (1 <= v && v <= 366),
+                (-366..=-1).contains(&v) || (1..=366).contains(&v),
                 "invalid 'by day of the year' value `{v}` \
                  (values must be in range 1..=366 or -366..=-1)",
             );
         }
         for &v in self.by_month_day.iter() {
             anyhow::ensure!(
-                (-31 <= v && v <= -1) || (1 <= v && v <= 31),
+                (-31..=-1).contains(&v) || (1..=31).contains(&v),
                 "invalid 'by day of the month' value `{v}` \
                  (values must be in range 1..=31 or -31..=-1)",
             );
@@ -1125,13 +1365,13 @@ impl RecurrenceRuleBuilder {
                 && self.by_month.is_empty()
             {
                 anyhow::ensure!(
-                    (-53 <= nth && nth <= -1) || (1 <= nth && nth <= 53),
+                    (-53..=-1).contains(&nth) || (1..=53).contains(&nth),
                     "invalid numbered 'by week day' value `{v}` \
                      (values must be in range 1..=53 or -53..=-1)",
                 );
             } else {
                 anyhow::ensure!(
-                    (-5 <= nth && nth <= -1) || (1 <= nth && nth <= 5),
+                    (-5..=-1).contains(&nth) || (1..=5).contains(&nth),
                     "invalid numbered 'by week day' value `{v}` \
                      (values must be in range 1..=5 or -5..=-1)",
                 );
@@ -1139,14 +1379,14 @@ impl RecurrenceRuleBuilder {
         }
         for &v in self.by_hour.iter() {
             anyhow::ensure!(
-                0 <= v && v <= 23,
+                (0..=23).contains(&v),
                 "invalid 'by hour' value `{v}` \
                  (values must be in range 0..=23)",
             );
         }
         for &v in self.by_minute.iter() {
             anyhow::ensure!(
-                0 <= v && v <= 59,
+                (0..=59).contains(&v),
                 "invalid 'by minute' value `{v}` \
                  (values must be in range 0..=59)",
             );
@@ -1159,14 +1399,14 @@ impl RecurrenceRuleBuilder {
             // doesn't really make sense here, so just reject
             // it. This is also what `python-dateutil` does.
             anyhow::ensure!(
-                0 <= v && v <= 59,
+                (0..=59).contains(&v),
                 "invalid 'by second' value `{v}` \
                  (values must be in range 0..=59)",
             );
         }
         for &v in self.by_set_pos.iter() {
             anyhow::ensure!(
-                (-366 <= v && v <= -1) || (1 <= v && v <= 366),
+                (-366..=-1).contains(&v) || (1..=366).contains(&v),
                 "invalid 'by set position' value `{v}` \
                  (values must be in range 1..=366 or -366..=-1)",
             );
@@ -1213,6 +1453,24 @@ impl RecurrenceRuleBuilder {
             );
         }
 
+        // A rule whose "until" precedes its start never produces any
+        // datetimes. This is almost certainly a mistake, so report it instead
+        // of silently producing nothing. Since both are zoned datetimes, this
+        // compares their instants, regardless of their time zones.
+        if let Some(ref until) = self.until {
+            anyhow::ensure!(
+                until.timestamp() >= self.start.timestamp(),
+                "'until' datetime `{until}` is before \
+                 start datetime `{start}`",
+                start = self.start,
+            );
+            // RFC 5545 says these MUST NOT occur in the same rule.
+            anyhow::ensure!(
+                self.count.is_none(),
+                "'until' and 'count' cannot both be used in the same rule",
+            );
+        }
+
         let interval =
             self.freq.to_span(self.interval).with_context(|| {
                 format!(
@@ -1222,11 +1480,21 @@ impl RecurrenceRuleBuilder {
                     interval = self.interval,
                 )
             })?;
+        // Inclusions are emitted in the same time zone as every other
+        // datetime generated by this rule.
+        let mut inclusions: Vec<Zoned> = self
+            .inclusions
+            .iter()
+            .map(|zdt| zdt.with_time_zone(self.start.time_zone().clone()))
+            .collect();
+        inclusions.sort_by_key(|zdt| zdt.timestamp());
+        inclusions.dedup_by_key(|zdt| zdt.timestamp());
         let inner = Arc::new(RecurrenceRuleInner {
             freq: self.freq,
             zoned_start: self.start.clone(),
             civil_start: self.start.datetime(),
             zoned_until: self.until.clone(),
+            count: self.count,
             interval,
             by_month: sort_and_dedup(&self.by_month),
             by_week: sort_and_dedup(&self.by_week),
@@ -1238,6 +1506,8 @@ impl RecurrenceRuleBuilder {
             by_second: sort_and_dedup(&self.by_second),
             by_set_pos: sort_and_dedup(&self.by_set_pos),
             week_start: self.week_start,
+            exclusions: sort_and_dedup(&self.exclusions),
+            inclusions: inclusions.into_boxed_slice(),
         });
         Ok(RecurrenceRule { inner })
     }
@@ -1247,6 +1517,12 @@ impl RecurrenceRuleBuilder {
         self
     }
 
+    /// Limits the rule to generating at most this many datetimes.
+    pub fn count(&mut self, count: usize) -> &mut RecurrenceRuleBuilder {
+        self.count = Some(count);
+        self
+    }
+
     pub fn interval(&mut self, increment: i32) -> &mut RecurrenceRuleBuilder {
         self.interval = increment;
         self
@@ -1331,6 +1607,39 @@ impl RecurrenceRuleBuilder {
         self.week_start = weekday;
         self
     }
+
+    /// Excludes the given datetimes from the rule, like `EXDATE` in RFC 5545.
+    ///
+    /// A generated datetime is skipped when it refers to the same instant as
+    /// an exclusion, regardless of time zone. This means that only one of the
+    /// two datetimes generated for a wall clock time in a fold is excluded,
+    /// depending on the exclusion's offset. Exclusions that don't correspond
+    /// to any generated datetime are ignored.
+    pub fn exclude<I: IntoIterator<Item = Zoned>>(
+        &mut self,
+        datetimes: I,
+    ) -> &mut RecurrenceRuleBuilder {
+        self.exclusions
+            .extend(datetimes.into_iter().map(|zdt| zdt.timestamp()));
+        self
+    }
+
+    /// Includes the given datetimes in the rule, like `RDATE` in RFC 5545.
+    ///
+    /// Included datetimes are merged with the datetimes generated by the
+    /// rule in chronological order. An included datetime that is the same
+    /// instant as a generated datetime is only emitted once. Included
+    /// datetimes are converted to the time zone of the starting point.
+    ///
+    /// Following RFC 5545, included datetimes are not subject to the "until"
+    /// or "count" of the rule, but exclusions still apply to them.
+    pub fn include<I: IntoIterator<Item = Zoned>>(
+        &mut self,
+        datetimes: I,
+    ) -> &mut RecurrenceRuleBuilder {
+        self.inclusions.extend(datetimes);
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1696,7 +2005,7 @@ impl<const N: usize> IntoI32Iter for [RangeInclusive<i32>; N] {
 /// * As a range of weekdays via `Weekday::Monday..=Weekday::Wednesday`.
 /// * As an array of weekdays via `[Weekday::Monday, Weekday::Friday]`.
 /// * As an array of numbered weekdays via
-/// `[(2, Weekday::Monday), (1, Weekday::Friday)]`.
+///   `[(2, Weekday::Monday), (1, Weekday::Friday)]`.
 pub trait IntoByWeekdayIter {
     fn into_by_weekday_iter(self) -> impl Iterator<Item = ByWeekday>;
 }
@@ -1790,8 +2099,8 @@ secondly, second, sec, s
 "#,
     );
 
-    fn to_span(&self, interval: i32) -> anyhow::Result<Span> {
-        let base = match *self {
+    fn to_span(self, interval: i32) -> anyhow::Result<Span> {
+        let base = match self {
             Frequency::Yearly => 1.year(),
             Frequency::Monthly => 1.month(),
             Frequency::Weekly => 1.week(),
@@ -1800,7 +2109,7 @@ secondly, second, sec, s
             Frequency::Minutely => 1.minute(),
             Frequency::Secondly => 1.second(),
         };
-        Ok(base.checked_mul(i64::try_from(interval)?)?)
+        Ok(base.checked_mul(i64::from(interval))?)
     }
 
     fn as_str(&self) -> &'static str {
@@ -2060,6 +2369,52 @@ where
     }
 }
 
+/// Parses a comma separated list of integers from an RRULE part.
+fn parse_rrule_list<'a, T>(
+    value: &'a str,
+) -> impl Iterator<Item = anyhow::Result<T>> + 'a
+where
+    T: std::str::FromStr<Err = std::num::ParseIntError> + 'a,
+{
+    value
+        .split(',')
+        .map(|v| v.parse().with_context(|| format!("invalid integer `{v}`")))
+}
+
+/// Parses a single RRULE `BYDAY` value, like `MO`, `1FR` or `-1SU`.
+///
+/// This differs from the `FromStr` impl for `ByWeekday`, which uses a `-` to
+/// separate the number from the weekday (e.g., `1-fri`).
+fn parse_rrule_by_day(value: &str) -> anyhow::Result<ByWeekday> {
+    let split = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .with_context(|| format!("missing weekday in `{value}`"))?;
+    let (nth, weekday) = value.split_at(split);
+    let weekday = weekday.parse::<flags::Weekday>()?.get();
+    if nth.is_empty() {
+        return Ok(ByWeekday::Any(weekday));
+    }
+    let nth = nth.parse().with_context(|| {
+        format!("failed to parse `{nth}` as integer weekday number")
+    })?;
+    Ok(ByWeekday::Numbered { nth, weekday })
+}
+
+/// Parses the value of an RRULE `UNTIL` part in the time zone of `start`.
+fn parse_rrule_until(start: &Zoned, value: &str) -> anyhow::Result<Zoned> {
+    let tz = start.time_zone().clone();
+    if let Some(utc) = value.strip_suffix(['Z', 'z']) {
+        let dt: DateTime = utc.parse()?;
+        return Ok(dt.to_zoned(TimeZone::UTC)?.with_time_zone(tz));
+    }
+    if !value.contains(['T', 't']) {
+        let date: jiff::civil::Date = value.parse()?;
+        return Ok(date.to_zoned(tz)?.end_of_day()?);
+    }
+    let dt: DateTime = value.parse()?;
+    Ok(dt.to_zoned(tz)?)
+}
+
 /// Returns an iterator for every weekday between `start` and `end` (inclusive).
 fn iter_weekdays_between(
     weekday: Weekday,
@@ -2361,6 +2716,33 @@ mod tests {
         );
     }
 
+    // DTSTART;TZID=America/New_York:20250301T000000
+    // RRULE:FREQ=WEEKLY;UNTIL=20250331T000000;BYDAY=MO
+    //
+    // This is a regression test where the last Monday was dropped because
+    // iteration stopped once the start of an interval (a Saturday) was after
+    // UNTIL, even though expanding that interval yields an earlier Monday.
+    #[test]
+    fn weekly_until_before_interval_start() {
+        let start = zoned("20250301T000000[America/New_York]");
+        let until = zoned("20250331T000000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Weekly, start)
+            .until(until)
+            .by_week_day([Weekday::Monday])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        2025-03-03T00:00:00-05:00[America/New_York]
+        2025-03-10T00:00:00-04:00[America/New_York]
+        2025-03-17T00:00:00-04:00[America/New_York]
+        2025-03-24T00:00:00-04:00[America/New_York]
+        2025-03-31T00:00:00-04:00[America/New_York]
+        ",
+        );
+    }
+
     // DTSTART;TZID=America/New_York:19970901T090000
     // RRULE:FREQ=WEEKLY;INTERVAL=2;UNTIL=19971224T000000Z;WKST=SU;
     //  BYDAY=MO,WE,FR
@@ -2871,6 +3253,32 @@ mod tests {
         );
     }
 
+    // This is the same as above, but with a DTSTART that is itself a Friday
+    // the 13th. Since the EXDATE excludes it, it is not yielded.
+    //
+    // DTSTART;TZID=America/New_York:19980213T090000
+    // EXDATE;TZID=America/New_York:19980213T090000
+    // RRULE:FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13
+    #[test]
+    fn monthly_every_friday_the_13th_excluding_start() {
+        let start = zoned("19980213T090000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Monthly, start.clone())
+            .by_week_day(Weekday::Friday)
+            .by_month_day(13)
+            .exclude([start])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(rrule.iter().take(4)),
+            @r"
+        1998-03-13T09:00:00-05:00[America/New_York]
+        1998-11-13T09:00:00-05:00[America/New_York]
+        1999-08-13T09:00:00-04:00[America/New_York]
+        2000-10-13T09:00:00-04:00[America/New_York]
+        ",
+        );
+    }
+
     // DTSTART;TZID=America/New_York:19970913T090000
     // RRULE:FREQ=MONTHLY;BYDAY=SA;BYMONTHDAY=7,8,9,10,11,12,13
     #[test]
@@ -5333,6 +5741,33 @@ mod tests {
 
     // The tests below check the error cases for rule construction.
 
+    /// Checks that "until" can't precede the start.
+    #[test]
+    fn until_errors() {
+        let start = zoned("20250513T000000[America/New_York]");
+        let until = zoned("20250512T235959[America/New_York]");
+        let err = expect_err(
+            RecurrenceRule::builder(Frequency::Daily, start.clone())
+                .until(until),
+        );
+        insta::assert_snapshot!(
+            err,
+            @"'until' datetime `2025-05-12T23:59:59-04:00[America/New_York]` is before start datetime `2025-05-13T00:00:00-04:00[America/New_York]`",
+        );
+
+        // This is the same instant as the start, but in a different time
+        // zone, so it's fine.
+        let until = zoned("20250513T040000[UTC]");
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start)
+            .until(until)
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @"2025-05-13T00:00:00-04:00[America/New_York]",
+        );
+    }
+
     /// Checks that interval values are legal.
     #[test]
     fn interval_errors() {
@@ -5752,6 +6187,467 @@ mod tests {
         );
     }
 
+    // The tests below check that parsing RRULE strings produces the same
+    // rules as the corresponding builder calls in the RFC 5545 tests above.
+
+    #[test]
+    fn rrule_str_rfc5545_examples() {
+        let ny = |s: &str| zoned(&format!("{s}[America/New_York]"));
+
+        let parsed = from_rrule_str("19970902T090000", "FREQ=DAILY;COUNT=10");
+        let built =
+            RecurrenceRule::builder(Frequency::Daily, ny("19970902T090000"))
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(built.iter().take(10)));
+
+        let parsed = from_rrule_str(
+            "19970902T090000",
+            "FREQ=DAILY;UNTIL=19971224T000000Z",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Daily, ny("19970902T090000"))
+                .until(zoned("19971224T000000Z[America/New_York]"))
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(&built));
+
+        let parsed = from_rrule_str(
+            "19970902T090000",
+            "FREQ=WEEKLY;UNTIL=19971007T000000Z;WKST=SU;BYDAY=TU,TH",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Weekly, ny("19970902T090000"))
+                .until(zoned("19971007T000000Z[America/New_York]"))
+                .by_week_day([Weekday::Tuesday, Weekday::Thursday])
+                .week_start(Weekday::Sunday)
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(&built));
+
+        let parsed = from_rrule_str(
+            "19970902T090000",
+            "FREQ=WEEKLY;INTERVAL=2;COUNT=8;WKST=SU;BYDAY=TU,TH",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Weekly, ny("19970902T090000"))
+                .interval(2)
+                .by_week_day([Weekday::Tuesday, Weekday::Thursday])
+                .week_start(Weekday::Sunday)
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(built.iter().take(8)));
+
+        let parsed = from_rrule_str(
+            "19970905T090000",
+            "FREQ=MONTHLY;COUNT=10;BYDAY=1FR",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Monthly, ny("19970905T090000"))
+                .by_week_day((1, Weekday::Friday))
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(built.iter().take(10)));
+
+        let parsed = from_rrule_str(
+            "19970907T090000",
+            "FREQ=MONTHLY;INTERVAL=2;COUNT=10;BYDAY=1SU,-1SU",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Monthly, ny("19970907T090000"))
+                .interval(2)
+                .by_week_day([(1, Weekday::Sunday), (-1, Weekday::Sunday)])
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(built.iter().take(10)));
+
+        let parsed = from_rrule_str(
+            "19970930T090000",
+            "FREQ=MONTHLY;COUNT=10;BYMONTHDAY=1,-1",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Monthly, ny("19970930T090000"))
+                .by_month_day([1, -1])
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(built.iter().take(10)));
+
+        let parsed = from_rrule_str(
+            "19970101T090000",
+            "FREQ=YEARLY;INTERVAL=3;COUNT=10;BYYEARDAY=1,100,200",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Yearly, ny("19970101T090000"))
+                .interval(3)
+                .by_year_day([1, 100, 200])
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(built.iter().take(10)));
+
+        let parsed = from_rrule_str(
+            "19970512T090000",
+            "FREQ=YEARLY;BYWEEKNO=20;BYDAY=MO",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Yearly, ny("19970512T090000"))
+                .by_week(20)
+                .by_week_day(Weekday::Monday)
+                .build()
+                .unwrap();
+        assert_eq!(
+            collect(parsed.iter().take(5)),
+            collect(built.iter().take(5))
+        );
+
+        let parsed = from_rrule_str(
+            "19970904T090000",
+            "FREQ=MONTHLY;COUNT=3;BYDAY=TU,WE,TH;BYSETPOS=3",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Monthly, ny("19970904T090000"))
+                .by_week_day(Weekday::Tuesday..=Weekday::Thursday)
+                .by_set_position(3)
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(built.iter().take(3)));
+
+        let parsed = from_rrule_str(
+            "19970902T090000",
+            "FREQ=DAILY;BYHOUR=9,10,11,12,13,14,15,16;BYMINUTE=0,20,40",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Daily, ny("19970902T090000"))
+                .by_hour(9..=16)
+                .by_minute([0, 20, 40])
+                .build()
+                .unwrap();
+        assert_eq!(
+            collect(parsed.iter().take(50)),
+            collect(built.iter().take(50)),
+        );
+
+        let parsed = from_rrule_str(
+            "19970805T090000",
+            "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=SU",
+        );
+        let built =
+            RecurrenceRule::builder(Frequency::Weekly, ny("19970805T090000"))
+                .interval(2)
+                .by_week_day([Weekday::Tuesday, Weekday::Sunday])
+                .week_start(Weekday::Sunday)
+                .build()
+                .unwrap();
+        assert_eq!(collect(&parsed), collect(built.iter().take(4)));
+    }
+
+    /// Checks the syntactic flexibility of RRULE strings.
+    #[test]
+    fn rrule_str_syntax() {
+        // An optional `RRULE:` prefix, case insensitive names and a
+        // trailing semicolon (which appears in a few RFC 5545 examples).
+        let rrule = from_rrule_str(
+            "19970902T090000",
+            "rrule:freq=monthly;count=3;byday=-1fr;",
+        );
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        1997-09-26T09:00:00-04:00[America/New_York]
+        1997-10-31T09:00:00-05:00[America/New_York]
+        1997-11-28T09:00:00-05:00[America/New_York]
+        ",
+        );
+
+        // A local `UNTIL` is in the time zone of the start, and a date
+        // `UNTIL` includes every datetime on that date.
+        let rrule = from_rrule_str(
+            "19970902T090000",
+            "FREQ=DAILY;UNTIL=19970903T090000",
+        );
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        1997-09-02T09:00:00-04:00[America/New_York]
+        1997-09-03T09:00:00-04:00[America/New_York]
+        ",
+        );
+        let rrule =
+            from_rrule_str("19970902T090000", "FREQ=DAILY;UNTIL=19970903");
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        1997-09-02T09:00:00-04:00[America/New_York]
+        1997-09-03T09:00:00-04:00[America/New_York]
+        ",
+        );
+    }
+
+    /// Checks that invalid RRULE strings are rejected.
+    #[test]
+    fn rrule_str_errors() {
+        let err = |rrule: &str| {
+            let start = zoned("19970902T090000[America/New_York]");
+            match RecurrenceRule::from_rrule_str(start, rrule) {
+                Err(err) => format!("{err:#}"),
+                Ok(ok) => {
+                    panic!("expected RRULE error, but got:\n{ok:?}")
+                }
+            }
+        };
+
+        insta::assert_snapshot!(
+            err("BYDAY=MO"),
+            @"RRULE is missing required `FREQ` part",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;FREQ=WEEKLY"),
+            @"RRULE part `FREQ` was given more than once",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;COUNT=1;count=2"),
+            @"RRULE part `COUNT` was given more than once",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;RDATE=19970903"),
+            @"invalid RRULE part `RDATE=19970903`: unsupported RRULE part `RDATE`",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;COUNT"),
+            @"invalid RRULE part `COUNT` (expected `NAME=VALUE`)",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=FORTNIGHTLY"),
+            @"invalid RRULE part `FREQ=FORTNIGHTLY`: unrecognized frequency: `fortnightly`",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=MONTHLY;BYDAY=1XX"),
+            @"invalid RRULE part `BYDAY=1XX`: unrecognized weekday: `xx`",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=MONTHLY;BYDAY=1"),
+            @"invalid RRULE part `BYDAY=1`: missing weekday in `1`",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;BYHOUR=9-17"),
+            @"invalid RRULE part `BYHOUR=9-17`: invalid integer `9-17`: invalid digit found in string",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;UNTIL=tomorrow"),
+            @"invalid RRULE part `UNTIL=tomorrow`: failed to parse year in date: failed to parse four digit integer as year: invalid digit, expected 0-9 but got t",
+        );
+
+        // Conflicting and invalid rules produce the same errors as the
+        // builder.
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;COUNT=2;UNTIL=19971224T000000Z"),
+            @"'until' and 'count' cannot both be used in the same rule",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;UNTIL=19970901"),
+            @"'until' datetime `1997-09-01T23:59:59.999999999-04:00[America/New_York]` is before start datetime `1997-09-02T09:00:00-04:00[America/New_York]`",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;BYDAY=1FR"),
+            @"numbered weekday `1-Fri` is only allowed at yearly or monthly frequencies",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=WEEKLY;BYMONTHDAY=1"),
+            @"'by day of the month' cannot be used with weekly frequency",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=DAILY;BYSETPOS=1"),
+            @"when 'by set position' is used, at least one other 'by' rule must be specified, but all are empty",
+        );
+        insta::assert_snapshot!(
+            err("FREQ=YEARLY;INTERVAL=0"),
+            @"interval value of `0` is invalid (interval must be greater than or equal to 1)",
+        );
+    }
+
+    /// Checks that a "count" limits the number of datetimes generated.
+    #[test]
+    fn count() {
+        let start = zoned("20250513T000000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start.clone())
+            .count(2)
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        2025-05-13T00:00:00-04:00[America/New_York]
+        2025-05-14T00:00:00-04:00[America/New_York]
+        ",
+        );
+
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start)
+            .count(0)
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(snapshot(&rrule), @"");
+    }
+
+    /// Checks that exclusions match on the exact instant, and so only
+    /// exclude one of the two datetimes generated in a fold.
+    #[test]
+    fn exclude_in_fold() {
+        let start = zoned("20251031T013000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start)
+            .exclude([zoned("2025-11-02T01:30-05:00[America/New_York]")])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(rrule.iter().take(4)),
+            @r"
+        2025-10-31T01:30:00-04:00[America/New_York]
+        2025-11-01T01:30:00-04:00[America/New_York]
+        2025-11-02T01:30:00-04:00[America/New_York]
+        2025-11-03T01:30:00-05:00[America/New_York]
+        ",
+        );
+
+        // The same instant in a different time zone is also excluded.
+        let start = zoned("20251031T013000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start)
+            .exclude([zoned("2025-11-02T05:30Z[UTC]")])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(rrule.iter().take(4)),
+            @r"
+        2025-10-31T01:30:00-04:00[America/New_York]
+        2025-11-01T01:30:00-04:00[America/New_York]
+        2025-11-02T01:30:00-05:00[America/New_York]
+        2025-11-03T01:30:00-05:00[America/New_York]
+        ",
+        );
+
+        // But a wall clock time that isn't exactly a generated instant
+        // doesn't exclude anything.
+        let start = zoned("20251031T013000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start)
+            .exclude([zoned("2025-11-01T01:30:01[America/New_York]")])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(rrule.iter().take(3)),
+            @r"
+        2025-10-31T01:30:00-04:00[America/New_York]
+        2025-11-01T01:30:00-04:00[America/New_York]
+        2025-11-02T01:30:00-04:00[America/New_York]
+        ",
+        );
+    }
+
+    /// Checks that, as per RFC 5545, excluded datetimes still count towards
+    /// the "count" of a rule.
+    #[test]
+    fn exclude_with_count() {
+        let start = zoned("20250513T000000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start)
+            .count(3)
+            .exclude([zoned("20250514T000000[America/New_York]")])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        2025-05-13T00:00:00-04:00[America/New_York]
+        2025-05-15T00:00:00-04:00[America/New_York]
+        ",
+        );
+    }
+
+    /// Checks that inclusions are merged in chronological order, and that
+    /// those that duplicate a generated datetime are only emitted once.
+    #[test]
+    fn include() {
+        let start = zoned("20250505T090000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Weekly, start)
+            .include([
+                // Falls between two generated datetimes.
+                zoned("20250514T120000[America/New_York]"),
+                // The same instant as a generated datetime.
+                zoned("2025-05-19T13:00Z[UTC]"),
+            ])
+            .build()
+            .unwrap();
+        // The rule is infinite, so this also checks that merging is lazy.
+        insta::assert_snapshot!(
+            snapshot(rrule.iter().take(5)),
+            @r"
+        2025-05-05T09:00:00-04:00[America/New_York]
+        2025-05-12T09:00:00-04:00[America/New_York]
+        2025-05-14T12:00:00-04:00[America/New_York]
+        2025-05-19T09:00:00-04:00[America/New_York]
+        2025-05-26T09:00:00-04:00[America/New_York]
+        ",
+        );
+    }
+
+    /// Checks that inclusions aren't limited by "until" or "count," but are
+    /// subject to exclusions.
+    #[test]
+    fn include_with_limits() {
+        let start = zoned("20250513T000000[America/New_York]");
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start.clone())
+            .until(zoned("20250514T000000[America/New_York]"))
+            .include([
+                zoned("20250512T120000[America/New_York]"),
+                zoned("20250520T120000[America/New_York]"),
+            ])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        2025-05-12T12:00:00-04:00[America/New_York]
+        2025-05-13T00:00:00-04:00[America/New_York]
+        2025-05-14T00:00:00-04:00[America/New_York]
+        2025-05-20T12:00:00-04:00[America/New_York]
+        ",
+        );
+
+        // This also checks that inclusions are converted to the time zone
+        // of the start.
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start.clone())
+            .count(2)
+            .include([zoned("20250513T120000[Asia/Tokyo]")])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        2025-05-12T23:00:00-04:00[America/New_York]
+        2025-05-13T00:00:00-04:00[America/New_York]
+        2025-05-14T00:00:00-04:00[America/New_York]
+        ",
+        );
+
+        let rrule = RecurrenceRule::builder(Frequency::Daily, start)
+            .count(2)
+            .include([zoned("20250520T120000[America/New_York]")])
+            .exclude([zoned("20250520T120000[America/New_York]")])
+            .build()
+            .unwrap();
+        insta::assert_snapshot!(
+            snapshot(&rrule),
+            @r"
+        2025-05-13T00:00:00-04:00[America/New_York]
+        2025-05-14T00:00:00-04:00[America/New_York]
+        ",
+        );
+    }
+
+    fn from_rrule_str(start: &str, rrule: &str) -> RecurrenceRule {
+        let start = zoned(&format!("{start}[America/New_York]"));
+        RecurrenceRule::from_rrule_str(start, rrule).unwrap()
+    }
+
+    fn collect(it: impl IntoIterator<Item = Zoned>) -> Vec<Zoned> {
+        it.into_iter().collect()
+    }
+
     fn now() -> Zoned {
         crate::NOW.clone()
     }
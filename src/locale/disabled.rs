@@ -6,18 +6,35 @@ impl Locale {
         Locale(())
     }
 
+    pub fn with_calendar(&self, _calendar: &Calendar) -> Locale {
+        self.clone()
+    }
+
     pub fn to_formatter(&self) -> anyhow::Result<StrtimeLocaleFormatter> {
         Ok(jiff::fmt::strtime::DefaultCustom::new())
     }
+
+    pub fn to_span_formatter(
+        &self,
+    ) -> anyhow::Result<Option<SpanLocaleFormatter>> {
+        Ok(None)
+    }
+
+    pub fn to_name_parser(&self) -> anyhow::Result<Option<NameParser>> {
+        Ok(None)
+    }
 }
 
 impl std::str::FromStr for Locale {
     type Err = anyhow::Error;
 
-    fn from_str(_: &str) -> anyhow::Result<Locale> {
+    fn from_str(s: &str) -> anyhow::Result<Locale> {
+        if s == "und" {
+            return Ok(Locale::unknown());
+        }
         anyhow::bail!(
             "bttf must be compiled with the `locale` feature to \
-             format datetimes in a particular locale",
+             use a particular locale",
         )
     }
 }
@@ -28,4 +45,59 @@ impl std::fmt::Display for Locale {
     }
 }
 
+/// A calendar system used when formatting datetimes via a locale.
+///
+/// Only the Gregorian calendar is available when the `locale` feature is
+/// disabled.
+#[derive(Clone, Debug)]
+pub struct Calendar(());
+
+impl std::str::FromStr for Calendar {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Calendar> {
+        if s == "gregorian" || s == "gregory" {
+            return Ok(Calendar(()));
+        }
+        anyhow::bail!(
+            "bttf must be compiled with the `locale` feature to \
+             format datetimes with the `{s}` calendar",
+        )
+    }
+}
+
 pub type StrtimeLocaleFormatter = jiff::fmt::strtime::DefaultCustom;
+
+/// A formatter for writing spans with localized unit names.
+///
+/// This can never be created when the `locale` feature is disabled.
+#[derive(Debug)]
+pub enum SpanLocaleFormatter {}
+
+impl SpanLocaleFormatter {
+    pub fn format(
+        &self,
+        _span: &jiff::Span,
+        _zero_unit: jiff::Unit,
+        _comma: bool,
+    ) -> String {
+        match *self {}
+    }
+}
+
+/// A parser for translating localized month and weekday names into their
+/// English names.
+///
+/// This can never be created when the `locale` feature is disabled.
+#[derive(Debug)]
+pub enum NameParser {}
+
+impl NameParser {
+    pub fn translate<'a>(
+        &self,
+        _s: &'a str,
+        _kinds: &[super::NameKind],
+    ) -> anyhow::Result<std::borrow::Cow<'a, str>> {
+        match *self {}
+    }
+}
@@ -1,11 +1,13 @@
+use std::borrow::Cow;
+
 use jiff::fmt::{
     Write,
     strtime::{BrokenDownTime, Custom, Extension},
 };
 use jiff_icu::ConvertInto;
-use writeable::Writeable;
+use writeable::{TryWriteable, Writeable};
 use {
-    icu_calendar::{Date, Iso},
+    icu_calendar::{Date, Gregorian, Iso},
     icu_datetime::{
         DateTimeFormatter as IcuDateTimeFormatter,
         DateTimeFormatterPreferences,
@@ -17,12 +19,25 @@ use {
             },
             zone::SpecificShort,
         },
+        pattern::{
+            DateTimePattern, FixedCalendarDateTimeNames, MonthNameLength,
+            WeekdayNameLength,
+        },
         preferences::HourCycle,
     },
-    icu_locale::Locale as IcuLocale,
+    icu_locale::{
+        Locale as IcuLocale,
+        extensions::unicode::{Value, key},
+        preferences::extensions::unicode::keywords::CalendarAlgorithm,
+    },
+    icu_plurals::{PluralCategory, PluralRules},
     icu_time::{Time, TimeZoneInfo, ZonedDateTime, zone::models::AtTime},
 };
 
+use crate::span::{UNITS, unit_value};
+
+use super::NameKind;
+
 /// A wrapper around an ICU4X locale to create a locale formatter.
 #[derive(Clone, Debug)]
 pub struct Locale(IcuLocale);
@@ -33,6 +48,16 @@ impl Locale {
         Locale(IcuLocale::UNKNOWN)
     }
 
+    /// Returns a copy of this locale that uses the given calendar system.
+    ///
+    /// This overrides any calendar set in the locale itself via the `-u-ca-`
+    /// extension, but leaves everything else about the locale unchanged.
+    pub fn with_calendar(&self, calendar: &Calendar) -> Locale {
+        let mut locale = self.0.clone();
+        locale.extensions.unicode.keywords.set(key!("ca"), calendar.0.clone());
+        Locale(locale)
+    }
+
     /// Create a formatter that implements Jiff's `strtime::Locale` trait.
     pub fn to_formatter(&self) -> anyhow::Result<StrtimeLocaleFormatter> {
         let zone = ZoneFieldSet::SpecificShort(SpecificShort);
@@ -63,6 +88,108 @@ impl Locale {
 
         Ok(StrtimeLocaleFormatter { datetime, date, time, time12 })
     }
+
+    /// Create a formatter for writing spans with localized unit names.
+    ///
+    /// When this is the unknown locale, then `None` is returned. Callers
+    /// should fall back to the "friendly" format in that case.
+    pub fn to_span_formatter(
+        &self,
+    ) -> anyhow::Result<Option<SpanLocaleFormatter>> {
+        let language = self.0.id.language;
+        if language.is_unknown() {
+            return Ok(None);
+        }
+        let Some(&(_, names)) = SPAN_UNIT_NAMES
+            .iter()
+            .find(|&&(lang, _)| language.as_str() == lang)
+        else {
+            let supported: Vec<&str> =
+                SPAN_UNIT_NAMES.iter().map(|&(lang, _)| lang).collect();
+            anyhow::bail!(
+                "localized unit names are not available for locale `{self}` \
+                 (supported languages are: {supported})",
+                supported = supported.join(", "),
+            );
+        };
+        let plurals = PluralRules::try_new_cardinal((&self.0).into())?;
+        Ok(Some(SpanLocaleFormatter { plurals, names }))
+    }
+
+    /// Create a parser for translating localized month and weekday names
+    /// into their English names.
+    ///
+    /// When this is the unknown locale, then `None` is returned. Callers
+    /// should parse their input as-is in that case.
+    pub fn to_name_parser(&self) -> anyhow::Result<Option<NameParser>> {
+        if self.0.id.language.is_unknown() {
+            return Ok(None);
+        }
+        let prefs = DateTimeFormatterPreferences::from(&self.0);
+        let mut names = vec![];
+        for (length, pattern, wide) in [
+            (MonthNameLength::Abbreviated, "MMM", false),
+            (MonthNameLength::Wide, "MMMM", true),
+        ] {
+            let mut loaded = FixedCalendarDateTimeNames::<
+                Gregorian,
+                DateFieldSet,
+            >::try_new(prefs)?;
+            loaded.include_month_names(length)?;
+            let pattern: DateTimePattern = pattern.parse()?;
+            let fmt = loaded.with_pattern_unchecked(&pattern);
+            for (month, english) in (1..=12).zip(MONTH_NAMES) {
+                let date = Date::try_new_gregorian(2024, month, 1)?;
+                let name = fmt
+                    .format(&date)
+                    .try_write_to_string()
+                    .map_err(|_| {
+                        anyhow::anyhow!(
+                            "failed to format month name using ICU4X locale",
+                        )
+                    })?
+                    .into_owned();
+                names.push(LocalizedName::new(
+                    &name,
+                    english,
+                    NameKind::Month,
+                    wide,
+                ));
+            }
+        }
+        for (length, pattern, wide) in [
+            (WeekdayNameLength::Abbreviated, "EEE", false),
+            (WeekdayNameLength::Wide, "EEEE", true),
+        ] {
+            let mut loaded = FixedCalendarDateTimeNames::<
+                Gregorian,
+                DateFieldSet,
+            >::try_new(prefs)?;
+            loaded.include_weekday_names(length)?;
+            let pattern: DateTimePattern = pattern.parse()?;
+            let fmt = loaded.with_pattern_unchecked(&pattern);
+            // 2024-01-01 is a Monday.
+            for (day, english) in (1..=7).zip(WEEKDAY_NAMES) {
+                let date = Date::try_new_gregorian(2024, 1, day)?;
+                let name = fmt
+                    .format(&date)
+                    .try_write_to_string()
+                    .map_err(|_| {
+                        anyhow::anyhow!(
+                            "failed to format weekday name using ICU4X locale",
+                        )
+                    })?
+                    .into_owned();
+                names.push(LocalizedName::new(
+                    &name,
+                    english,
+                    NameKind::Weekday,
+                    wide,
+                ));
+            }
+        }
+        Ok(Some(NameParser { locale: self.clone(), names }))
+    }
 }
 
 impl std::str::FromStr for Locale {
@@ -79,6 +206,33 @@ impl std::fmt::Display for Locale {
     }
 }
 
+/// A calendar system used when formatting datetimes via a locale.
+#[derive(Clone, Debug)]
+pub struct Calendar(Value);
+
+impl std::str::FromStr for Calendar {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Calendar> {
+        // CLDR calls the Gregorian calendar `gregory`, but it seems nice to
+        // accept the full name too.
+        let name = if s == "gregorian" { "gregory" } else { s };
+        let value = name
+            .parse::<Value>()
+            .ok()
+            .filter(|value| CalendarAlgorithm::try_from(value).is_ok());
+        let Some(value) = value else {
+            anyhow::bail!(
+                "unrecognized calendar `{s}` (supported calendars are: \
+                 gregorian, buddhist, chinese, coptic, dangi, ethioaa, \
+                 ethiopic, hebrew, indian, islamic, iso8601, japanese, \
+                 persian and roc)",
+            );
+        };
+        Ok(Calendar(value))
+    }
+}
+
 /// A collection of ICU4X datetime formatters for `strftime` formatting.
 #[derive(Debug)]
 pub struct StrtimeLocaleFormatter {
@@ -171,6 +325,297 @@ impl Custom for StrtimeLocaleFormatter {
     }
 }
 
+/// A formatter for writing spans with localized unit names.
+#[derive(Debug)]
+pub struct SpanLocaleFormatter {
+    plurals: PluralRules,
+    names: &'static [[&'static str; 2]; 10],
+}
+
+impl SpanLocaleFormatter {
+    /// Format the given span by writing out each non-zero unit with its
+    /// localized name, from largest to smallest.
+    ///
+    /// A zero span is written as zero of the given unit. When `comma` is
+    /// true, units are separated by a comma in addition to a space.
+    pub fn format(
+        &self,
+        span: &jiff::Span,
+        zero_unit: jiff::Unit,
+        comma: bool,
+    ) -> String {
+        let values = UNITS.iter().map(|&unit| unit_value(span, unit));
+        let mut units = vec![];
+        for (value, names) in values.zip(self.names) {
+            if value != 0 {
+                units.push(self.format_unit(value.unsigned_abs(), names));
+            }
+        }
+        if units.is_empty() {
+            // Units are numbered from nanoseconds (0) to years (9), which is
+            // the reverse of the order of the names table.
+            let names = &self.names[9 - (zero_unit as usize)];
+            units.push(self.format_unit(0, names));
+        }
+        let sign = if span.is_negative() { "-" } else { "" };
+        format!("{sign}{}", units.join(if comma { ", " } else { " " }))
+    }
+
+    fn format_unit(&self, value: u64, names: &[&str; 2]) -> String {
+        let name = match self.plurals.category_for(value) {
+            PluralCategory::One => names[0],
+            _ => names[1],
+        };
+        format!("{value} {name}")
+    }
+}
+
+/// A parser for translating localized month and weekday names into their
+/// English names.
+///
+/// This is used to parse datetimes written in a particular locale with
+/// `strptime`, which only knows about English names.
+#[derive(Debug)]
+pub struct NameParser {
+    locale: Locale,
+    names: Vec<LocalizedName>,
+}
+
+impl NameParser {
+    /// Replace localized month or weekday names in the given string with
+    /// their full English names.
+    ///
+    /// `kinds` is the sequence of names expected in the string, in the order
+    /// that the corresponding directives appear in the format string. Each
+    /// word is only matched against names of the next expected kind. So in a
+    /// locale where, e.g., `mar` is both a month and a weekday, it's resolved
+    /// by the directive it appears under. Once every expected name has been
+    /// found, the rest of the string is left as-is.
+    ///
+    /// Names are matched case insensitively. An abbreviated name matches
+    /// with or without its trailing period. A word that isn't a name, but
+    /// is a prefix of at least three letters of a full name, matches that
+    /// name too. Any other text is left as-is.
+    ///
+    /// This returns an error when a word matches more than one name.
+    pub fn translate<'a>(
+        &self,
+        s: &'a str,
+        kinds: &[NameKind],
+    ) -> anyhow::Result<Cow<'a, str>> {
+        let mut translated = String::new();
+        let mut kinds = kinds.iter().copied();
+        let Some(mut kind) = kinds.next() else {
+            return Ok(Cow::Borrowed(s));
+        };
+        let (mut last, mut at) = (0, 0);
+        while let Some(ch) = s[at..].chars().next() {
+            if !ch.is_alphabetic() {
+                at += ch.len_utf8();
+                continue;
+            }
+            let start = at;
+            let end = s[start..]
+                .find(|ch: char| !ch.is_alphabetic())
+                .map_or(s.len(), |i| start + i);
+            at = end;
+            let Some((english, period)) = self.find(kind, &s[start..end])?
+            else {
+                continue;
+            };
+            if period && s[end..].starts_with('.') {
+                at += 1;
+            }
+            translated.push_str(&s[last..start]);
+            translated.push_str(english);
+            last = at;
+            match kinds.next() {
+                None => break,
+                Some(next) => kind = next,
+            }
+        }
+        if last == 0 {
+            return Ok(Cow::Borrowed(s));
+        }
+        translated.push_str(&s[last..]);
+        Ok(Cow::Owned(translated))
+    }
+
+    /// Returns the English name of the given kind matching the given word,
+    /// if any, along with whether a period following the word is part of the
+    /// name.
+    fn find(
+        &self,
+        kind: NameKind,
+        word: &str,
+    ) -> anyhow::Result<Option<(&'static str, bool)>> {
+        let word = word.to_lowercase();
+        let names = self.names.iter().filter(|name| name.kind == kind);
+        let mut matches: Vec<&LocalizedName> =
+            names.clone().filter(|name| name.key == word).collect();
+        let prefix = matches.is_empty();
+        if prefix && word.chars().count() >= 3 {
+            matches = names
+                .filter(|name| name.wide && name.key.starts_with(&word))
+                .collect();
+        }
+        let Some(first) = matches.first() else { return Ok(None) };
+        let mut englishes: Vec<&str> =
+            matches.iter().map(|name| name.english).collect();
+        englishes.dedup();
+        if englishes.len() > 1 {
+            let mut localized: Vec<&str> =
+                matches.iter().map(|name| &*name.name).collect();
+            localized.dedup();
+            anyhow::bail!(
+                "`{word}` is ambiguous in locale `{locale}`, since it could \
+                 be any of: {localized} ({englishes})",
+                locale = self.locale,
+                localized = localized.join(", "),
+                englishes = englishes.join(", "),
+            );
+        }
+        let period = prefix || matches.iter().any(|name| name.period);
+        Ok(Some((first.english, period)))
+    }
+}
+
+/// A single localized month or weekday name.
+#[derive(Debug)]
+struct LocalizedName {
+    /// The name as written in the locale.
+    name: String,
+    /// The lowercase name without any trailing period, used for matching.
+    key: String,
+    /// Whether the name as written in the locale ends with a period.
+    period: bool,
+    /// Whether this is a full name, as opposed to an abbreviation.
+    wide: bool,
+    /// The full English name.
+    english: &'static str,
+    /// Whether this is a month or a weekday name.
+    kind: NameKind,
+}
+
+impl LocalizedName {
+    fn new(
+        name: &str,
+        english: &'static str,
+        kind: NameKind,
+        wide: bool,
+    ) -> LocalizedName {
+        let period = name.ends_with('.');
+        let key = name.trim_end_matches('.').to_lowercase();
+        LocalizedName {
+            name: name.to_string(),
+            key,
+            period,
+            wide,
+            english,
+            kind,
+        }
+    }
+}
+
+/// The English month names, starting with January.
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The English weekday names, starting with Monday.
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Localized unit names for spans, keyed by language.
+///
+/// Each entry has the singular and plural names for years, months, weeks,
+/// days, hours, minutes, seconds, milliseconds, microseconds and nanoseconds,
+/// in that order. The singular name is used when a locale's plural rules
+/// select the `one` category, and the plural name is used otherwise.
+///
+/// ICU4X doesn't (yet) have a stable API for unit names, so we keep a small
+/// table of our own for a handful of languages.
+const SPAN_UNIT_NAMES: &[(&str, &[[&str; 2]; 10])] = &[
+    (
+        "de",
+        &[
+            ["Jahr", "Jahre"],
+            ["Monat", "Monate"],
+            ["Woche", "Wochen"],
+            ["Tag", "Tage"],
+            ["Stunde", "Stunden"],
+            ["Minute", "Minuten"],
+            ["Sekunde", "Sekunden"],
+            ["Millisekunde", "Millisekunden"],
+            ["Mikrosekunde", "Mikrosekunden"],
+            ["Nanosekunde", "Nanosekunden"],
+        ],
+    ),
+    (
+        "en",
+        &[
+            ["year", "years"],
+            ["month", "months"],
+            ["week", "weeks"],
+            ["day", "days"],
+            ["hour", "hours"],
+            ["minute", "minutes"],
+            ["second", "seconds"],
+            ["millisecond", "milliseconds"],
+            ["microsecond", "microseconds"],
+            ["nanosecond", "nanoseconds"],
+        ],
+    ),
+    (
+        "es",
+        &[
+            ["año", "años"],
+            ["mes", "meses"],
+            ["semana", "semanas"],
+            ["día", "días"],
+            ["hora", "horas"],
+            ["minuto", "minutos"],
+            ["segundo", "segundos"],
+            ["milisegundo", "milisegundos"],
+            ["microsegundo", "microsegundos"],
+            ["nanosegundo", "nanosegundos"],
+        ],
+    ),
+    (
+        "fr",
+        &[
+            ["an", "ans"],
+            ["mois", "mois"],
+            ["semaine", "semaines"],
+            ["jour", "jours"],
+            ["heure", "heures"],
+            ["minute", "minutes"],
+            ["seconde", "secondes"],
+            ["milliseconde", "millisecondes"],
+            ["microseconde", "microsecondes"],
+            ["nanoseconde", "nanosecondes"],
+        ],
+    ),
+];
+
 // This was the original more generic version that I came up with. Writing
 // down the trait bounds was QUITE the effort, so I am keeping this around
 // for now. But the above is a more concrete version that works for bttf's
@@ -5,7 +5,28 @@ pub use self::inner::*;
 pub type StrtimeConfig = Config<StrtimeLocaleFormatter>;
 
 pub fn jiff_strtime_config() -> anyhow::Result<StrtimeConfig> {
-    Ok(Config::new().custom(crate::LOCALE.to_formatter()?))
+    jiff_strtime_config_with_calendar(None)
+}
+
+/// Returns a `strftime` configuration for the current locale, but with its
+/// calendar system overridden by the one given (if present).
+pub fn jiff_strtime_config_with_calendar(
+    calendar: Option<&Calendar>,
+) -> anyhow::Result<StrtimeConfig> {
+    let formatter = match calendar {
+        None => crate::LOCALE.to_formatter()?,
+        Some(calendar) => {
+            crate::LOCALE.with_calendar(calendar).to_formatter()?
+        }
+    };
+    Ok(Config::new().custom(formatter))
+}
+
+/// The kind of name that a `strptime` directive like `%b` or `%A` parses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NameKind {
+    Month,
+    Weekday,
 }
 
 #[cfg(feature = "locale")]
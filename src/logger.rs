@@ -92,7 +92,7 @@ impl Log for Logger {
     }
 }
 
-fn relative<'p>(path: &'p str) -> &'p str {
+fn relative(path: &str) -> &str {
     let Some(cwd) = cwd() else { return path };
     let Ok(relative) = Path::new(path).strip_prefix(cwd) else { return path };
     let Some(relative) = relative.to_str() else { return path };
@@ -1,4 +1,12 @@
-use std::{env, io::Write, process::ExitCode, sync::LazyLock};
+use std::{
+    env,
+    io::Write,
+    process::ExitCode,
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
 use {
     anyhow::Context,
@@ -18,14 +26,31 @@ mod parallel;
 mod parse;
 mod round;
 mod span;
+mod strtime;
 mod style;
 mod tag;
+mod template;
 mod timezone;
 mod weekdate;
 
-static TZ: LazyLock<TimeZone> = LazyLock::new(|| TimeZone::system());
+static TZ: LazyLock<TimeZone> = LazyLock::new(TimeZone::system);
+
+/// The current time given by the `--now` flag, if any.
+///
+/// This takes precedence over the `BTTF_NOW` environment variable when
+/// initializing `NOW`. Setting it has no effect once `NOW` is initialized.
+static NOW_OVERRIDE: Mutex<Option<Timestamp>> = Mutex::new(None);
+
+/// Whether `NOW` has been initialized, and thus whether it's too late to set
+/// `NOW_OVERRIDE`.
+static NOW_INITIALIZED: AtomicBool = AtomicBool::new(false);
 
 static NOW: LazyLock<Zoned> = LazyLock::new(|| {
+    NOW_INITIALIZED.store(true, Ordering::SeqCst);
+    if let Some(ts) = *NOW_OVERRIDE.lock().unwrap() {
+        log::trace!("setting current time to `{ts}` from `--now` flag");
+        return ts.to_zoned(TZ.clone());
+    }
     let ts = match read_env_bttf_now() {
         Ok(Some(ts)) => {
             log::trace!(
@@ -54,8 +79,8 @@ static NOW: LazyLock<Zoned> = LazyLock::new(|| {
     ts.to_zoned(TZ.clone())
 });
 
-static LOCALE: LazyLock<Locale> = LazyLock::new(|| {
-    let locale = match read_env_bttf_locale() {
+static LOCALE: LazyLock<Locale> =
+    LazyLock::new(|| match read_env_bttf_locale() {
         Ok(Some(locale)) => {
             log::trace!(
                 "setting locale to `{locale}` from `BTTF_LOCALE` \
@@ -79,9 +104,7 @@ static LOCALE: LazyLock<Locale> = LazyLock::new(|| {
             );
             locale
         }
-    };
-    locale
-});
+    });
 
 /// Then, as it was, then again it will be.
 fn main() -> ExitCode {
@@ -122,7 +145,7 @@ fn main() -> ExitCode {
             return ExitCode::from(0);
         }
     }
-    if std::env::var("RUST_BACKTRACE").map_or(false, |v| v == "1")
+    if std::env::var("RUST_BACKTRACE").is_ok_and(|v| v == "1")
         && std::env::var("RUST_LIB_BACKTRACE").map_or(true, |v| v == "1")
     {
         writeln!(&mut std::io::stderr(), "{:?}", err).unwrap();
@@ -152,7 +175,11 @@ fn run() -> anyhow::Result<ExitCode> {
     // But after that, we can set the time zone and things become local.
     let logger = logger::Logger::init()?;
     logger.set_time_zone(TZ.clone());
-    cmd::run(&mut lexopt::Parser::from_env())?;
+    let (now, args) = args::flags::Now::extract(env::args_os().skip(1))?;
+    if let Some(now) = now {
+        args::flags::Now::set(now)?;
+    }
+    cmd::run(&mut lexopt::Parser::from_args(args))?;
     Ok(ExitCode::SUCCESS)
 }
 
@@ -178,7 +205,7 @@ fn read_env_bttf_locale() -> anyhow::Result<Option<Locale>> {
         )
     };
     let locale = val.parse().with_context(|| {
-        format!("failed to parse `BTTF_LOCALE` environment variable")
+        "failed to parse `BTTF_LOCALE` environment variable".to_string()
     })?;
     Ok(Some(locale))
 }
@@ -201,7 +201,7 @@ impl Done {
             let mut queue: BinaryHeap<Output<O>> = BinaryHeap::new();
             for output in outputs {
                 queue.push(output);
-                while queue.peek().map_or(false, |o| o.sequence == sequence) {
+                while queue.peek().is_some_and(|o| o.sequence == sequence) {
                     let o = queue.pop().unwrap();
                     done(o.value)?;
                     sequence += 1;
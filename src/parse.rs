@@ -468,6 +468,24 @@ impl<'a, 'de> serde::Deserialize<'de> for TextBytes<'a> {
     }
 }
 
+/// Splits a trailing RFC 9557 time zone annotation from the given bytes.
+///
+/// For example, `1721507455[Europe/London]` is split into `1721507455` and
+/// `Europe/London`. The critical flag, as in `[!Europe/London]`, is dropped.
+/// If the bytes don't end with a time zone annotation, then this returns
+/// `None`. Other kinds of annotations, like `[u-ca=iso8601]`, aren't time zone
+/// annotations.
+pub fn split_time_zone_annotation(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let inner = bytes.strip_suffix(b"]")?;
+    let start = inner.rfind_byte(b'[')?;
+    let (rest, name) = (&inner[..start], &inner[start + 1..]);
+    let name = name.strip_prefix(b"!").unwrap_or(name);
+    if name.is_empty() || name.contains(&b'=') {
+        return None;
+    }
+    Some((rest, name))
+}
+
 fn split_line_terminator(line: &[u8]) -> (&[u8], &[u8]) {
     let mut terminator_at = line.len();
     if line.last_byte() == Some(b'\n') {
@@ -17,6 +17,13 @@ nearest integer increment specified. For example, if the smallest unit is
 set to `minute`, then a rounding increment of 30 would result in rounding in
 increments of a half hour. That is, the only minute value that could result
 would be 0 or 30.
+
+When rounding datetimes without `--origin`, the increment must evenly divide
+the next larger unit. For example, an increment for minutes must divide 60, so
+`15` is allowed but `7` is not. The increment for days must be 1. With
+`--origin`, any positive increment is allowed, since datetimes are rounded to
+multiples of the increment away from the origin instead of to boundaries of
+the next larger unit.
 "#,
 );
 
@@ -30,9 +37,74 @@ pub struct DateTimeRound {
 
 impl DateTimeRound {
     pub fn round(&self, dt: &DateTime) -> anyhow::Result<DateTime> {
+        self.check_increment()?;
         Ok(dt.get().round(self.options())?.into())
     }
 
+    /// Rounds the given datetime to the nearest instant that is a multiple
+    /// of the rounding increment away from `origin`.
+    ///
+    /// Unlike `DateTimeRound::round`, this rounds the physical time elapsed
+    /// since `origin`. That means days are always treated as 24 hours.
+    pub fn round_from(
+        &self,
+        dt: &DateTime,
+        origin: jiff::Timestamp,
+    ) -> anyhow::Result<DateTime> {
+        let (smallest, increment) = match self.smallest.get() {
+            jiff::Unit::Day => (jiff::Unit::Hour, self.increment * 24),
+            unit if unit > jiff::Unit::Day => anyhow::bail!(
+                "rounding relative to an origin only works for units of \
+                 days or lower",
+            ),
+            unit => (unit, self.increment),
+        };
+        let options = jiff::SignedDurationRound::new()
+            .smallest(smallest)
+            .mode(self.mode.get())
+            .increment(increment);
+        let elapsed = dt.get().timestamp().duration_since(origin);
+        let rounded = elapsed.round(options)?;
+        let ts = origin.checked_add(rounded).with_context(|| {
+            format!("rounding `{dt}` relative to `{origin}` is out of range")
+        })?;
+        Ok(ts.to_zoned(dt.get().time_zone().clone()).into())
+    }
+
+    /// Returns an error when the rounding increment doesn't evenly divide
+    /// the next larger unit.
+    ///
+    /// Jiff checks this too, but its error doesn't say which increments are
+    /// allowed.
+    fn check_increment(&self) -> anyhow::Result<()> {
+        use jiff::Unit::*;
+
+        let (max, larger) = match self.smallest.get() {
+            Hour => (24, "a day"),
+            Minute => (60, "an hour"),
+            Second => (60, "a minute"),
+            Millisecond => (1000, "a second"),
+            Microsecond => (1000, "a millisecond"),
+            Nanosecond => (1000, "a microsecond"),
+            _ => return Ok(()),
+        };
+        let increment = self.increment;
+        if increment < 1 || (increment < max && max % increment == 0) {
+            return Ok(());
+        }
+        let allowed = (1..max)
+            .filter(|n| max % n == 0)
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        anyhow::bail!(
+            "increment `{increment}` for rounding to {smallest} must evenly \
+             divide the {max} {smallest} in {larger}, so it must be one of: \
+             {allowed}",
+            smallest = self.smallest,
+        )
+    }
+
     fn options(&self) -> jiff::ZonedRound {
         jiff::ZonedRound::new()
             .smallest(self.smallest.get())
@@ -175,14 +247,22 @@ pub struct TimeSpanBalance {
 
 impl TimeSpanBalance {
     pub fn balance(&self, span: &TimeSpan) -> anyhow::Result<TimeSpan> {
-        let balanced =
-            span.get().round(self.options()).with_context(|| {
-                format!(
-                    "failed to balance span relative to `{relative}`",
-                    relative = self.relative
-                )
-            })?;
-        Ok(balanced.into())
+        self.round(span, self.options())
+    }
+
+    /// Balances the given span and then rounds it to the given smallest
+    /// unit.
+    pub fn balance_to(
+        &self,
+        span: &TimeSpan,
+        smallest: jiff::Unit,
+    ) -> anyhow::Result<TimeSpan> {
+        self.round(span, self.options().smallest(smallest))
+    }
+
+    /// Returns the largest unit that spans are balanced up to.
+    pub fn largest(&self) -> jiff::Unit {
+        self.largest.get()
     }
 
     fn options(&self) -> jiff::SpanRound<'_> {
@@ -190,6 +270,20 @@ impl TimeSpanBalance {
             .largest(self.largest.get())
             .relative(self.relative.get())
     }
+
+    fn round(
+        &self,
+        span: &TimeSpan,
+        options: jiff::SpanRound<'_>,
+    ) -> anyhow::Result<TimeSpan> {
+        let balanced = span.get().round(options).with_context(|| {
+            format!(
+                "failed to balance span relative to `{relative}`",
+                relative = self.relative
+            )
+        })?;
+        Ok(balanced.into())
+    }
 }
 
 impl Default for TimeSpanBalance {
@@ -1,7 +1,12 @@
-use {bstr::BStr, jiff::Span};
+use {
+    anyhow::Context,
+    bstr::BStr,
+    jiff::{SignedDuration, Span, Unit},
+};
 
 use crate::{
     args::Usage,
+    datetime::DateTime,
     parse::{BytesExt, FromBytes},
 };
 
@@ -60,6 +65,22 @@ to read.
 "#,
     );
 
+    pub const RELATIVE_FLAG: Usage = Usage::flag(
+        "-r/--relative <datetime>",
+        "The datetime that calendar units are relative to.",
+        r#"
+The datetime that calendar units are relative to.
+
+Calendar units (years, months, weeks or days) vary in length depending on when
+they occur. When a span with calendar units needs to be converted to an
+absolute duration, the conversion starts from this datetime. For example,
+relative to `2025-02-01`, `1mo` is 28 days, but relative to `2025-03-01`, it's
+31 days.
+
+This has no effect on spans with only time units.
+"#,
+    );
+
     /// Get the underlying Jiff span.
     ///
     /// If possible, prefer defining an operation on `TimeSpan` instead of
@@ -68,6 +89,37 @@ to read.
     pub fn get(&self) -> &Span {
         &self.span
     }
+
+    /// Converts this span to an absolute duration.
+    ///
+    /// When a relative datetime is given, calendar units are resolved
+    /// relative to it. Otherwise, this returns an error if this span has any
+    /// calendar units.
+    pub fn to_duration(
+        &self,
+        relative: Option<&DateTime>,
+    ) -> anyhow::Result<SignedDuration> {
+        match relative {
+            Some(relative) => {
+                self.span.to_duration(relative.get()).with_context(|| {
+                    format!(
+                        "failed to compute duration of span `{self}` \
+                         relative to `{relative}`",
+                    )
+                })
+            }
+            None => {
+                // This only fails when the span has calendar units.
+                let duration = SignedDuration::try_from(self.span).ok();
+                duration.with_context(|| {
+                    format!(
+                        "span `{self}` has calendar units, which require a \
+                         relative datetime given by -r/--relative",
+                    )
+                })
+            }
+        }
+    }
 }
 
 impl std::fmt::Display for TimeSpan {
@@ -149,6 +201,36 @@ impl<'de> serde::Deserialize<'de> for TimeSpan {
     }
 }
 
+/// All units that may appear in a span, from largest to smallest.
+pub const UNITS: &[Unit] = &[
+    Unit::Year,
+    Unit::Month,
+    Unit::Week,
+    Unit::Day,
+    Unit::Hour,
+    Unit::Minute,
+    Unit::Second,
+    Unit::Millisecond,
+    Unit::Microsecond,
+    Unit::Nanosecond,
+];
+
+/// Returns the value of the given unit in the given span.
+pub fn unit_value(span: &Span, unit: Unit) -> i64 {
+    match unit {
+        Unit::Year => i64::from(span.get_years()),
+        Unit::Month => i64::from(span.get_months()),
+        Unit::Week => i64::from(span.get_weeks()),
+        Unit::Day => i64::from(span.get_days()),
+        Unit::Hour => i64::from(span.get_hours()),
+        Unit::Minute => span.get_minutes(),
+        Unit::Second => span.get_seconds(),
+        Unit::Millisecond => span.get_milliseconds(),
+        Unit::Microsecond => span.get_microseconds(),
+        Unit::Nanosecond => span.get_nanoseconds(),
+    }
+}
+
 /// A common parsing function that works in bytes.
 ///
 /// Specifically, this parses either an ISO 8601 duration into a `Span` or
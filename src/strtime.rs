@@ -0,0 +1,75 @@
+/// A single piece of a `strftime` or `strptime` format string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Token<'a> {
+    /// A run of literal text. This never contains a `%`.
+    Literal(&'a str),
+    /// An escaped `%`, written as `%%`.
+    Percent,
+    /// A directive, like `%Y` or `%-d`.
+    Directive(Directive<'a>),
+}
+
+/// A directive in a `strftime` or `strptime` format string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Directive<'a> {
+    /// Everything between the `%` and the conversion specifier. That is, any
+    /// flags, width, colons or precision. For example, `-` in `%-d` or `:` in
+    /// `%:z`.
+    pub modifiers: &'a str,
+    /// The conversion specifier, e.g., `d` in `%-d`. This is `None` when the
+    /// format ends before a specifier is found.
+    pub specifier: Option<char>,
+}
+
+impl Directive<'_> {
+    /// Returns true when the modifiers of this directive begin with an
+    /// explicit flag, like `%_d` or `%^b`.
+    pub fn has_flag(&self) -> bool {
+        self.modifiers.starts_with(['_', '0', '-', '^', '#'])
+    }
+}
+
+impl std::fmt::Display for Directive<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "%{}", self.modifiers)?;
+        if let Some(specifier) = self.specifier {
+            write!(f, "{specifier}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns an iterator over the literals and directives in the given
+/// `strftime` or `strptime` format string.
+///
+/// This doesn't check whether any of the directives are valid. Writing every
+/// token back out, with `Token::Percent` as `%%`, reproduces the format
+/// string given.
+pub fn tokens(fmt: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut rest = fmt;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let Some(after) = rest.strip_prefix('%') else {
+            let end = rest.find('%').unwrap_or(rest.len());
+            let (literal, remaining) = rest.split_at(end);
+            rest = remaining;
+            return Some(Token::Literal(literal));
+        };
+        if let Some(remaining) = after.strip_prefix('%') {
+            rest = remaining;
+            return Some(Token::Percent);
+        }
+        let end = after
+            .find(|ch| {
+                !matches!(ch, '_' | '-' | '^' | '#' | ':' | '.' | '0'..='9')
+            })
+            .unwrap_or(after.len());
+        let (modifiers, remaining) = after.split_at(end);
+        let mut chars = remaining.chars();
+        let specifier = chars.next();
+        rest = chars.as_str();
+        Some(Token::Directive(Directive { modifiers, specifier }))
+    })
+}
@@ -29,7 +29,7 @@ impl Theme {
             let inner = Some(Arc::new(ThemeInner::default()));
             Theme { inner }
         });
-        &*THEME
+        &THEME
     }
 
     /// Returns a theme for stderr.
@@ -41,7 +41,7 @@ impl Theme {
             let inner = Some(Arc::new(ThemeInner::default()));
             Theme { inner }
         });
-        &*THEME
+        &THEME
     }
 
     /// Returns a theme that never does any styling.
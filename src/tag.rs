@@ -15,7 +15,6 @@ pub enum MaybeTagged<'a, T> {
 }
 
 impl<'a, T> MaybeTagged<'a, T> {
-    #[expect(dead_code)]
     pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> MaybeTagged<'a, U> {
         match self {
             MaybeTagged::Untagged(t) => MaybeTagged::Untagged(f(t)),
@@ -54,7 +53,7 @@ where
     type Err = anyhow::Error;
 
     fn from_bytes(s: &[u8]) -> anyhow::Result<MaybeTagged<'static, T>> {
-        let probably_json = s.first().map_or(false, |&byte| byte == b'{');
+        let probably_json = s.first().is_some_and(|&byte| byte == b'{');
         let mut json_decoding_err = None;
         if probably_json {
             json_decoding_err = Some(match s.parse() {
@@ -109,7 +108,7 @@ impl<'a, T> Tagged<'a, T> {
     }
 
     pub fn data(&self) -> &BStr {
-        &*self.data
+        &self.data
     }
 
     pub fn into_owned(self) -> Tagged<'static, T> {
@@ -358,17 +357,31 @@ impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tags<T> {
 pub struct Tag<T> {
     value: T,
     range: Option<TagRange>,
+    /// Whether this tag was made up by bttf instead of being found in (or
+    /// derived from) the data. e.g., via `bttf tag lines --synthetic`.
+    synthetic: bool,
+    /// An optional short name describing where this tag came from. e.g.,
+    /// `bttf tag stat` labels each tag with the file time it was read from.
+    label: Option<String>,
 }
 
 impl<T> Tag<T> {
     pub fn new(value: T) -> Tag<T> {
-        Tag { value, range: None }
+        Tag { value, range: None, synthetic: false, label: None }
     }
 
     pub fn with_range(self, range: impl Into<TagRange>) -> Tag<T> {
         Tag { range: Some(range.into()), ..self }
     }
 
+    pub fn with_synthetic(self, synthetic: bool) -> Tag<T> {
+        Tag { synthetic, ..self }
+    }
+
+    pub fn with_label(self, label: impl Into<String>) -> Tag<T> {
+        Tag { label: Some(label.into()), ..self }
+    }
+
     pub fn value(&self) -> &T {
         &self.value
     }
@@ -381,15 +394,33 @@ impl<T> Tag<T> {
         self.range.as_ref().copied()
     }
 
+    pub fn is_synthetic(&self) -> bool {
+        self.synthetic
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
     pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Tag<U> {
-        Tag { value: f(self.value), range: self.range }
+        Tag {
+            value: f(self.value),
+            range: self.range,
+            synthetic: self.synthetic,
+            label: self.label,
+        }
     }
 
     pub fn try_map<U>(
         self,
         mut f: impl FnMut(T) -> anyhow::Result<U>,
     ) -> anyhow::Result<Tag<U>> {
-        Ok(Tag { value: f(self.value)?, range: self.range })
+        Ok(Tag {
+            value: f(self.value)?,
+            range: self.range,
+            synthetic: self.synthetic,
+            label: self.label,
+        })
     }
 }
 
@@ -426,7 +457,10 @@ impl<T: serde::Serialize> serde::Serialize for Tag<T> {
     ) -> Result<S::Ok, S::Error> {
         use serde::ser::SerializeStruct;
 
-        let len = 1 + if self.range.is_some() { 1 } else { 0 };
+        let len = 1
+            + if self.range.is_some() { 1 } else { 0 }
+            + if self.synthetic { 1 } else { 0 }
+            + if self.label.is_some() { 1 } else { 0 };
         let mut state = s.serialize_struct("Tag", len)?;
         state.serialize_field("value", &self.value)?;
         if let Some(ref range) = self.range {
@@ -434,6 +468,16 @@ impl<T: serde::Serialize> serde::Serialize for Tag<T> {
         } else {
             state.skip_field("range")?;
         }
+        if self.synthetic {
+            state.serialize_field("synthetic", &true)?;
+        } else {
+            state.skip_field("synthetic")?;
+        }
+        if let Some(ref label) = self.label {
+            state.serialize_field("label", label)?;
+        } else {
+            state.skip_field("label")?;
+        }
         state.end()
     }
 }
@@ -448,6 +492,8 @@ impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tag<T> {
         enum Field {
             Value,
             Range,
+            Synthetic,
+            Label,
         }
 
         impl<'de> serde::Deserialize<'de> for Field {
@@ -464,7 +510,7 @@ impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tag<T> {
                         &self,
                         f: &mut std::fmt::Formatter,
                     ) -> std::fmt::Result {
-                        f.write_str("`value` or `range`")
+                        f.write_str("`value`, `range`, `synthetic` or `label`")
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -474,6 +520,8 @@ impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tag<T> {
                         match value {
                             "value" => Ok(Field::Value),
                             "range" => Ok(Field::Range),
+                            "synthetic" => Ok(Field::Synthetic),
+                            "label" => Ok(Field::Label),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -493,7 +541,8 @@ impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tag<T> {
                 formatter: &mut std::fmt::Formatter,
             ) -> std::fmt::Result {
                 formatter.write_str(
-                    "a map with a `value` key and an optional `range` key",
+                    "a map with a `value` key and optional `range`, \
+                     `synthetic` and `label` keys",
                 )
             }
 
@@ -503,6 +552,8 @@ impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tag<T> {
             {
                 let mut value = None;
                 let mut range = None;
+                let mut synthetic = None;
+                let mut label = None;
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::Value => {
@@ -521,15 +572,32 @@ impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tag<T> {
                             }
                             range = Some(map.next_value()?);
                         }
+                        Field::Synthetic => {
+                            if synthetic.is_some() {
+                                return Err(de::Error::duplicate_field(
+                                    "synthetic",
+                                ));
+                            }
+                            synthetic = Some(map.next_value()?);
+                        }
+                        Field::Label => {
+                            if label.is_some() {
+                                return Err(de::Error::duplicate_field(
+                                    "label",
+                                ));
+                            }
+                            label = Some(map.next_value()?);
+                        }
                     }
                 }
                 let value =
                     value.ok_or_else(|| de::Error::missing_field("value"))?;
-                Ok(Tag { value, range })
+                let synthetic = synthetic.unwrap_or(false);
+                Ok(Tag { value, range, synthetic, label })
             }
         }
 
-        const FIELDS: &[&str] = &["text", "bytes"];
+        const FIELDS: &[&str] = &["value", "range", "synthetic", "label"];
         deserializer.deserialize_struct(
             "Tag",
             FIELDS,
@@ -548,16 +616,6 @@ impl TagRange {
     pub fn range(&self) -> Range<usize> {
         self.start..self.end
     }
-
-    pub fn diff(&self, len: usize) -> isize {
-        len as isize - (self.end.saturating_sub(self.start) as isize)
-    }
-
-    pub fn offset(self, offset: isize) -> TagRange {
-        let start = (self.start as isize) + offset;
-        let end = (self.end as isize) + offset;
-        TagRange { start: start as usize, end: end as usize }
-    }
 }
 
 impl From<Range<usize>> for TagRange {
@@ -0,0 +1,106 @@
+use bstr::{BStr, BString, ByteSlice};
+
+/// A template string made up of literals and `{name}` directives.
+///
+/// Any byte may be escaped with a backslash, including `{`, `}` and `\`.
+/// This permits writing braces literally. The directive names permitted,
+/// and what they map to, are determined by the caller.
+#[derive(Clone, Debug)]
+pub struct Template<D> {
+    items: Vec<Item<D>>,
+}
+
+/// An individual item in a template.
+#[derive(Clone, Debug)]
+pub enum Item<D> {
+    Literal(BString),
+    Directive(D),
+}
+
+impl<D> Template<D> {
+    /// Parses a template from the given bytes.
+    ///
+    /// The closure given is called with the name of each directive found,
+    /// i.e., the bytes between `{` and `}`. It should return an error if the
+    /// name isn't recognized.
+    ///
+    /// This returns an error if there is an unclosed brace or a dangling
+    /// backslash.
+    pub fn parse(
+        s: &[u8],
+        mut directive: impl FnMut(&BStr) -> anyhow::Result<D>,
+    ) -> anyhow::Result<Template<D>> {
+        enum State {
+            Default,
+            InBrace,
+            Backslash,
+            BackslashInBrace,
+        }
+
+        let mut items = vec![];
+        let mut literal = BString::new(vec![]);
+        let mut name = BString::new(vec![]);
+        let mut state = State::Default;
+        for &byte in s {
+            state = match (state, byte) {
+                (State::Default, b'{') => {
+                    if !literal.is_empty() {
+                        let literal = std::mem::take(&mut literal);
+                        items.push(Item::Literal(literal));
+                    }
+                    State::InBrace
+                }
+                (State::Default, b'\\') => State::Backslash,
+                (State::Default, _) => {
+                    literal.push(byte);
+                    State::Default
+                }
+                (State::InBrace, b'}') => {
+                    items.push(Item::Directive(directive(name.as_bstr())?));
+                    name.clear();
+                    State::Default
+                }
+                (State::InBrace, b'\\') => State::BackslashInBrace,
+                (State::InBrace, _) => {
+                    name.push(byte);
+                    State::InBrace
+                }
+                (State::Backslash, _) => {
+                    literal.push(byte);
+                    State::Default
+                }
+                (State::BackslashInBrace, _) => {
+                    name.push(byte);
+                    State::InBrace
+                }
+            };
+        }
+        match state {
+            State::Default => {
+                if !literal.is_empty() {
+                    items.push(Item::Literal(literal));
+                }
+            }
+            State::InBrace => anyhow::bail!(
+                "found unclosed brace, which might be an invalid \
+                 format directive (to write a brace literally, escape \
+                 it with a backslash)",
+            ),
+            State::Backslash | State::BackslashInBrace => anyhow::bail!(
+                "found dangling backslash (to write a backslash \
+                 literally, escape it with a backslash)",
+            ),
+        }
+        Ok(Template { items })
+    }
+
+    /// Returns the sequence of items in this template.
+    pub fn items(&self) -> &[Item<D>] {
+        &self.items
+    }
+
+    /// Returns true when this template contains at least one directive.
+    pub fn has_directive(&self) -> bool {
+        self.items.iter().any(|item| matches!(*item, Item::Directive(_)))
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::LazyLock;
+use std::{collections::BTreeMap, path::PathBuf, sync::LazyLock};
 
 use jiff::fmt::{StdFmtWrite, temporal};
 
@@ -28,7 +28,73 @@ pub fn available() -> &'static [String] {
         names.sort();
         names
     });
-    &**IDS
+    &IDS
+}
+
+/// Returns a map from every link in the time zone database to the canonical
+/// time zone it refers to.
+///
+/// For example, `US/Eastern` maps to `America/New_York`. The time zones
+/// bundled with bttf don't include any link information, so links are read
+/// from the system's `tzdata.zi` file instead. This file is found in the
+/// directory given by `TZDIR`, or in `/usr/share/zoneinfo` otherwise. This
+/// returns an error when that file can't be read.
+pub fn links() -> anyhow::Result<&'static BTreeMap<String, String>> {
+    static LINKS: LazyLock<Option<BTreeMap<String, String>>> =
+        LazyLock::new(|| {
+            let dir = std::env::var_os("TZDIR").map_or_else(
+                || PathBuf::from("/usr/share/zoneinfo"),
+                PathBuf::from,
+            );
+            let path = dir.join("tzdata.zi");
+            let data = match std::fs::read_to_string(&path) {
+                Ok(data) => data,
+                Err(err) => {
+                    log::debug!("failed to read {}: {err}", path.display());
+                    return None;
+                }
+            };
+            let mut links = BTreeMap::new();
+            for line in data.lines() {
+                let mut fields = line.split_whitespace();
+                let (Some("L"), Some(target), Some(link)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+                links.insert(link.to_string(), target.to_string());
+            }
+            // A link may refer to another link. This is rare, but we always
+            // want to map to a canonical time zone. The bound protects
+            // against cycles in malformed data.
+            let resolved = links
+                .keys()
+                .map(|link| {
+                    let mut target = &links[link];
+                    for _ in 0..links.len() {
+                        let Some(next) = links.get(target) else { break };
+                        target = next;
+                    }
+                    (link.clone(), target.clone())
+                })
+                .collect();
+            Some(resolved)
+        });
+    LINKS.as_ref().ok_or_else(|| {
+        anyhow::anyhow!(
+            "could not read `tzdata.zi` from `TZDIR` or \
+             `/usr/share/zoneinfo`, which is required for link \
+             information (set `TZDIR` to the directory containing it)",
+        )
+    })
+}
+
+/// Formats the offset of the given zoned datetime as `[+-]HH:MM[:SS]`.
+///
+/// The `Display` impl for `Offset` omits the minutes when they're zero, but
+/// this always includes them to make output easier to line up.
+pub fn format_offset(zdt: &jiff::Zoned) -> String {
+    zdt.strftime("%:z").to_string()
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
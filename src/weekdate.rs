@@ -108,6 +108,19 @@ impl WeekDate {
         start_of_year.checked_add(days.days()).unwrap()
     }
 
+    /// Returns the week date year.
+    ///
+    /// This may differ from the Gregorian year of the corresponding date
+    /// for dates near the start or end of a year.
+    pub fn year(self) -> i16 {
+        self.year
+    }
+
+    /// Returns the week number, in the range `1..=53`.
+    pub fn week(self) -> i8 {
+        self.week
+    }
+
     /// Returns the number of weeks in the year containing this week date.
     pub fn weeks_in_year(self) -> i8 {
         if is_long_year(self.start, self.year) { 53 } else { 52 }
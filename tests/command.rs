@@ -183,10 +183,7 @@ impl Pipeline {
         last.stdout(process::Stdio::piped());
         last.stderr(process::Stdio::piped());
         let output = match first_stdin.take() {
-            None => {
-                let output = last.output().unwrap();
-                output
-            }
+            None => last.output().unwrap(),
             Some(first_stdin) => {
                 let mut child = last.spawn().unwrap();
                 let mut child_stdin = child.stdin.take().unwrap();
@@ -421,7 +418,7 @@ impl CommandInfo {
             bin
         };
         CommandInfo {
-            bin: bytes_to_string(&bin),
+            bin: bytes_to_string(bin),
             args: cmd.get_args().map(os_str_to_string).collect(),
             env: cmd
                 .get_envs()
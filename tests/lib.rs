@@ -51,6 +51,14 @@ impl TempDir {
         let path = self.0.path().join(relative_path.as_ref());
         std::fs::write(&path, contents).unwrap();
     }
+
+    /// Set the last modified time of the file at the given relative path in
+    /// this temporary directory.
+    fn set_modified(&self, relative_path: impl AsRef<Path>, when: &Zoned) {
+        let path = self.0.path().join(relative_path.as_ref());
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(when.timestamp().into()).unwrap();
+    }
 }
 
 /// Return a command for the `bttf` binary and no argument.
@@ -101,7 +109,7 @@ fn help() {
     A simple utility for doing datetime arithmetic, parsing and formatting.
 
     USAGE:
-        bttf <command> ...
+        bttf [--now <datetime>] <command> ...
 
     COMMANDS:
         span   Tools for manipulating time spans/durations
@@ -110,6 +118,9 @@ fn help() {
         tz     Commands for working directly with time zones
         untag  Remove tags from previously tagged data
 
+    OPTIONS:
+        --now <datetime>  Override the current time for this invocation
+
     ----- stderr -----
     ",
     );
@@ -122,7 +133,7 @@ fn help() {
     A simple utility for doing datetime arithmetic, parsing and formatting.
 
     USAGE:
-        bttf <command> ...
+        bttf [--now <datetime>] <command> ...
 
     COMMANDS:
         span   Tools for manipulating time spans/durations
@@ -131,6 +142,9 @@ fn help() {
         tz     Commands for working directly with time zones
         untag  Remove tags from previously tagged data
 
+    OPTIONS:
+        --now <datetime>  Override the current time for this invocation
+
     ----- stderr -----
     ",
     );
@@ -146,17 +160,33 @@ fn help() {
         bttf time <command> ...
 
     COMMANDS:
-        add       Add a span to a datetime
-        cmp       Compare datetimes
-        end-of    Get the end of a year, month, week, etc
-        fmt       Format a datetime
-        in        Convert a datetime to a time zone
-        parse     Parse a datetime
-        relative  Parse a relative datetime
-        round     Round a datetime
-        seq       Generate a sequence of datetimes
-        sort      Sort datetimes
-        start-of  Get the start of a year, month, week, etc
+        add              Add a span to a datetime
+        align-zones      Find local time windows shared by time zones
+        between          Check whether datetimes fall within a range
+        clamp            Clamp datetimes to the supported range
+        cmp              Compare datetimes
+        cron             List upcoming fire times of a cron expression
+        end-of           Get the end of a year, month, week, etc
+        epoch            Convert datetimes to and from Unix timestamps
+        fmt              Format a datetime
+        fold             List every instant for a wall clock time
+        grid             Generate aligned buckets covering a range
+        in               Convert a datetime to a time zone
+        monotonic-check  Check that datetimes are in chronological order
+        nearest          Snap a datetime to a grid of evenly spaced instants
+        overlap          Report overlapping intervals
+        parse            Parse a datetime
+        relative         Parse a relative datetime
+        round            Round a datetime
+        rrule            Expand an RFC 5545 recurrence rule
+        seq              Generate a sequence of datetimes
+        skew             Report clock skew between pairs of datetimes
+        sort             Sort datetimes
+        sort-key         Print a key that sorts like the datetime's instant
+        start-of         Get the start of a year, month, week, etc
+        sunrise          Print the time of sunrise at a location
+        sunset           Print the time of sunset at a location
+        weeknum-range    List the week numbers of every week in a range
 
     ----- stderr -----
     ",
@@ -0,0 +1,214 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn add() -> crate::command::Command {
+    bttf(["span", "add"])
+}
+
+fn subtract() -> crate::command::Command {
+    bttf(["span", "subtract"])
+}
+
+fn sum() -> crate::command::Command {
+    bttf(["span", "sum"])
+}
+
+/// Tests that time units are balanced up to the largest time unit given,
+/// while calendar units are summed on their own.
+#[test]
+fn mixed_units() {
+    assert_cmd_snapshot!(
+        add().args(["1h30m", "45m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2h 15m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        add().args(["90m", "30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    120m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        add().args(["1y2mo", "10mo", "5d", "12h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1y 12mo 5d 12h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        add().stdin("1m30s\n2m15s\n45s\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    4m 30s
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn negative() {
+    assert_cmd_snapshot!(
+        subtract().args(["2h", "30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h 30m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        subtract().args(["1h", "90m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    30m ago
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        subtract().args(["1d", "2d", "3h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1d 3h ago
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        add().args(["-1w", "-2d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1w 2d ago
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that units with mixed signs require `--balance`.
+#[test]
+fn balance() {
+    assert_cmd_snapshot!(
+        subtract().args(["1mo", "1d"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    result has units with mixed signs, which can only be combined with --balance (and a relative datetime given by -r/--relative for calendar units)
+    ",
+    );
+    assert_cmd_snapshot!(
+        subtract().args(["--balance", "1mo", "1d"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    failed to add span `1mo`, which may require a relative datetime given by -r/--relative: using unit 'month' in a span or configuration requires that a relative reference time be given, but none was provided
+    ",
+    );
+    assert_cmd_snapshot!(
+        subtract().args(["--balance", "-r", "2025-02-01", "1mo", "1d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    27d
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        add().args(["--balance", "-r", "2025-01-01", "1y2mo", "10mo", "5d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2y 5d
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `sum` is the same as `add`, including for tagged data and ISO
+/// 8601 durations, and that overflow is reported as an error.
+#[test]
+fn sum_alias() {
+    assert_cmd_snapshot!(
+        sum().stdin("PT1M30S\n2m15s\n45s\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    4m 30s
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        sum().stdin(concat!(
+            r#"{"tags":[{"value":"1h","range":[0,2]},{"value":"PT30M","range":[3,8]}],"data":{"text":"1h PT30M\n"}}"#,
+            "\n",
+            r#"{"tags":[{"value":"45m","range":[5,8]}],"data":{"text":"task 45m\n"}}"#,
+        )),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2h 15m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        sum().args(["19998y", "1y"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    sum of spans is out of range: parameter 'years' is not in the required range of -19998..=19998
+    ",
+    );
+    assert_cmd_snapshot!(
+        sum().args(["--balance", "175307616h", "1h"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    failed to add span `1h`, which may require a relative datetime given by -r/--relative: parameter 'hours' is not in the required range of -175307616..=175307616
+    ",
+    );
+}
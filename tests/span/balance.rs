@@ -734,3 +734,146 @@ fn balance_down() {
     ",
     );
 }
+
+#[test]
+fn breakdown() {
+    assert_cmd_snapshot!(
+        balance().args(["--breakdown", "1mo15d2h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    years=0 months=1 days=15 hours=2 minutes=0 seconds=0 milliseconds=0 microseconds=0 nanoseconds=0
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        balance().args(["--breakdown", "--omit-zero", "1mo15d2h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    months=1 days=15 hours=2
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        balance().args(["--breakdown", "-l", "week", "-r", "2025-01-01", "15d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    weeks=2 days=1 hours=0 minutes=0 seconds=0 milliseconds=0 microseconds=0 nanoseconds=0
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        balance().args(["--breakdown", "--omit-zero", "-l", "hours", "-90m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hours=-1 minutes=-30
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn breakdown_json() {
+    assert_cmd_snapshot!(
+        balance().args(["--breakdown", "--json", "-l", "hours", "90m"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"hours":1,"minutes":30,"seconds":0,"milliseconds":0,"microseconds":0,"nanoseconds":0}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        balance().args(["--breakdown", "--json", "--omit-zero", "1y2mo"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"years":1,"months":2}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        balance().args(["--breakdown", "--json", "--omit-zero", "0s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {}
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        balance().args(["--json", "1y2mo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --json and --omit-zero can only be used with --breakdown
+    ",
+    );
+}
+
+#[test]
+fn breakdown_smallest() {
+    assert_cmd_snapshot!(
+        balance().args(["--breakdown", "-l", "hours", "-s", "min", "1h30m29s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    hours=1 minutes=30
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        balance().args([
+            "--breakdown",
+            "--json",
+            "-l",
+            "month",
+            "-s",
+            "day",
+            "-r",
+            "2025-01-01",
+            "1mo15d13h",
+        ]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"months":1,"days":16}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        balance().args(["-s", "day", "1d"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -s/--smallest can only be used with --breakdown (use `bttf span round` to round spans)
+    ",
+    );
+}
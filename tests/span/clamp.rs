@@ -0,0 +1,202 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn clamp() -> crate::command::Command {
+    bttf(["span", "clamp"])
+}
+
+/// Tests spans below, at, within, at and above each bound.
+#[test]
+fn bounds() {
+    let input = "\
+99ms
+100ms
+0.1s
+2s
+30s
+30001ms
+1m
+";
+    assert_cmd_snapshot!(
+        clamp().args(["--min", "100ms", "--max", "30s"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    100ms
+    100ms
+    100ms
+    2s
+    30s
+    30s
+    30s
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["--min", "100ms"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    100ms
+    100ms
+    100ms
+    2s
+    30s
+    30001ms
+    1m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["--max", "30s"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    99ms
+    100ms
+    100ms
+    2s
+    30s
+    30s
+    30s
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn negative() {
+    assert_cmd_snapshot!(
+        clamp().args(["--min", "-1h", "--max", "1h", "-2h", "-30m", "2h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h ago
+    30m ago
+    1h
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that calendar units are compared relative to a datetime.
+#[test]
+fn relative() {
+    assert_cmd_snapshot!(
+        clamp().args([
+            "-r",
+            "2025-03-09[America/New_York]",
+            "--min",
+            "24h",
+            "1d",
+            "2d",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    24h
+    2d
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args([
+            "-r",
+            "2025-03-10[America/New_York]",
+            "--min",
+            "24h",
+            "1d",
+            "2d",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1d
+    2d
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["-r", "2025-02-01", "--max", "28d", "1mo", "27d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1mo
+    27d
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["-r", "2025-03-01", "--max", "28d", "1mo", "27d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    28d
+    27d
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        clamp().args(["1h"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    at least one of --min or --max is required
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["--min", "2h", "--max", "1h", "1h"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --min span `2h` must not be greater than --max span `1h`
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["--min", "1h", "1d"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1d` has calendar units, which require a relative datetime given by -r/--relative
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["--max", "1mo", "1h"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1mo` has calendar units, which require a relative datetime given by -r/--relative
+    ",
+    );
+}
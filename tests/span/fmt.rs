@@ -109,6 +109,71 @@ fn spacing() {
     );
 }
 
+/// Tests that `--style` sets designators and spacing, and that explicit
+/// `-d/--designator` and `-s/--spacing` flags override it.
+#[test]
+fn style() {
+    assert_cmd_snapshot!(
+        fmt().args(["--style", "compact", "1h30m", "2y1mo1d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h30m
+    2y1mo1d
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--style", "short", "1h30m", "2y1mo1d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1hr 30mins
+    2yrs 1mo 1day
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--style", "long", "1h1m", "1h30m", "2y1mo1d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1 hour 1 minute
+    1 hour 30 minutes
+    2 years 1 month 1 day
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["-s", "units", "--style", "long", "1h30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1hour 30minutes
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--style", "verbose", "1h30m"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --style: unknown style `verbose`
+    ",
+    );
+}
+
 #[test]
 fn sign() {
     assert_cmd_snapshot!(
@@ -210,6 +275,78 @@ fn sign() {
     );
 }
 
+#[test]
+fn sign_always_never() {
+    assert_cmd_snapshot!(
+        fmt().args(["--sign", "always", "1h2m", "-1h2m", "0s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +1h 2m
+    -1h 2m
+    +0s
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        fmt().args(["--sign", "never", "1h2m", "-1h2m", "0s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h 2m
+    1h 2m
+    0s
+
+    ----- stderr -----
+    ",
+    );
+
+    // The sign precedes the entire span, even with `--hms`.
+    assert_cmd_snapshot!(
+        fmt().args(["--sign", "always", "--hms", "5d2h", "-5d2h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +5d 02:00:00
+    -5d 02:00:00
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        fmt().args(["--sign", "never", "--hms", "-5d2h", "-1h2m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    5d 02:00:00
+    01:02:00
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        fmt().args(["--sign", "auto", "1h2m", "-1h2m", "0s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h 2m
+    1h 2m ago
+    0s
+
+    ----- stderr -----
+    ",
+    );
+}
+
 #[test]
 fn fractional() {
     assert_cmd_snapshot!(
@@ -393,6 +530,151 @@ fn hms() {
     );
 }
 
+#[test]
+fn compact() {
+    // Without `--hms`, zero units are already omitted, so `--compact` has
+    // no effect.
+    assert_cmd_snapshot!(
+        fmt().arg("--compact").arg("0y0mo2d0h30m0s"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2d 30m
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        fmt().args(["--hms", "--compact"]).stdin("2d\n-1y2mo\n2d30m\n0s\n30m\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2d
+    1y 2mo ago
+    2d 00:30:00
+    00:00:00
+    00:30:00
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--hms", "--compact", "-d", "verbose"])
+            .args(["-s", "units-and-designators", "--comma"])
+            .arg("1w2d"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1 week, 2 days
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn max_units() {
+    // By default, the dropped units are truncated.
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--max-units", "2"])
+            .stdin("2d3h45m40s\n1h59m40s\n2d13h ago\n1d0h0m40s\n45m\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2d 3h
+    1h 59m
+    2d 13h ago
+    1d 40s
+    45m
+
+    ----- stderr -----
+    ",
+    );
+
+    // With `--round`, they are rounded into the smallest unit kept, which
+    // may carry into larger units.
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--max-units", "2", "--round"])
+            .stdin("2d3h45m40s\n1h59m40s\n2d13h ago\n1d0h0m40s\n45m\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2d 4h
+    2h
+    2d 13h ago
+    1d 40s
+    45m
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        fmt().args(["--max-units", "1", "--round"]).arg("1y5mo20d"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1y
+
+    ----- stderr -----
+    ",
+    );
+
+    // Rounding calendar units depends on the relative datetime. In New
+    // York, 2024-03-10 is only 23 hours long.
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--max-units", "1", "--round"])
+            .args(["-r", "2024-03-09T00:00-05[America/New_York]"])
+            .arg("1d11h45m"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2d
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--max-units", "1", "--round"])
+            .args(["-r", "2024-07-09T00:00-04[America/New_York]"])
+            .arg("1d11h45m"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1d
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        fmt().args(["--max-units", "0"]).arg("1h"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --max-units must be greater than zero
+    ",
+    );
+}
+
 #[test]
 fn padding() {
     assert_cmd_snapshot!(
@@ -569,3 +851,134 @@ fn zero_unit() {
     ",
     );
 }
+
+/// Tests that unit names are written out in the requested locale.
+#[cfg(feature = "locale")]
+#[test]
+fn locale() {
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "fr", "2h30m", "1y1s", "0s"]).arg("-1d"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2 heures 30 minutes
+    1 an 1 seconde
+    0 seconde
+    -1 jour
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "de", "--comma", "2h30m", "1y1s", "0s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2 Stunden, 30 Minuten
+    1 Jahr, 1 Sekunde
+    0 Sekunden
+
+    ----- stderr -----
+    ",
+    );
+    // The unknown locale uses the friendly format.
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "und", "2h30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2h 30m
+
+    ----- stderr -----
+    ",
+    );
+    // Flags that only apply to the friendly format are rejected.
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "fr", "--hms", "2h30m"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --hms cannot be used with --locale
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "fr", "--sign", "always", "2h30m"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --sign cannot be used with --locale
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "fr", "--sign", "never", "--", "-2h30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2 heures 30 minutes
+
+    ----- stderr -----
+    ",
+    );
+    // But they're fine with the unknown locale, which uses the friendly
+    // format.
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "und", "--hms", "2h30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    02:30:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "ja", "2h30m"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    localized unit names are not available for locale `ja` (supported languages are: de, en, es, fr)
+    ",
+    );
+}
+
+/// Tests that a locale can't be requested without the `locale` feature.
+#[cfg(not(feature = "locale"))]
+#[test]
+fn locale_disabled() {
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "und", "2h30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2h 30m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--locale", "fr", "2h30m"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --locale: bttf must be compiled with the `locale` feature to use a particular locale
+    ",
+    );
+}
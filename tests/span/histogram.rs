@@ -0,0 +1,174 @@
+use crate::command::assert_cmd_snapshot;
+
+fn histogram() -> crate::command::Command {
+    crate::bttf(["span", "histogram"])
+}
+
+const LATENCIES: &str = "\
+500us
+1ms
+2ms
+9ms 999us
+10ms
+40ms
+1s
+2s
+";
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        histogram().args(["--buckets", "1ms,10ms,100ms,1s"]).stdin(LATENCIES),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    < 1ms          1
+    [1ms, 10ms)    3
+    [10ms, 100ms)  2
+    [100ms, 1s)    0
+    >= 1s          2
+
+    ----- stderr -----
+    ",
+    );
+    // Every bucket is printed, even empty ones.
+    assert_cmd_snapshot!(
+        histogram().args(["--buckets", "1h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    < 1h   0
+    >= 1h  0
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn cumulative() {
+    assert_cmd_snapshot!(
+        histogram()
+            .args(["--buckets", "1ms,10ms,100ms,1s", "--cumulative"])
+            .stdin(LATENCIES),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    < 1ms          1
+    [1ms, 10ms)    4
+    [10ms, 100ms)  6
+    [100ms, 1s)    6
+    >= 1s          8
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn percentiles() {
+    assert_cmd_snapshot!(
+        histogram()
+            .args(["--buckets", "10ms", "--percentiles", "0,50,87.5,99,100"])
+            .stdin(LATENCIES),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    < 10ms   4
+    >= 10ms  4
+    p0: 500µs
+    p50: 9ms 999µs
+    p87.5: 1s
+    p99: 2s
+    p100: 2s
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        histogram().args(["--buckets", "10ms", "--percentiles", "101"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --percentiles: failed to parse `101` within sequence `101`: percentile must be a number from 0 to 100
+    ",
+    );
+}
+
+#[test]
+fn json() {
+    assert_cmd_snapshot!(
+        histogram()
+            .args(["--buckets", "10ms,1s", "--percentiles", "50", "--json"])
+            .stdin(LATENCIES),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"buckets":[{"count":4,"end":"10ms","start":null},{"count":2,"end":"1s","start":"10ms"},{"count":2,"end":null,"start":"1s"}],"percentiles":[{"percentile":50.0,"span":"9ms 999µs"}]}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn calendar_units() {
+    assert_cmd_snapshot!(
+        histogram().args(["--buckets", "1d", "23h", "25h"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1d` has calendar units, which require a relative datetime given by -r/--relative
+    ",
+    );
+    // In New York, 2025-03-09 is only 23 hours long.
+    assert_cmd_snapshot!(
+        histogram().args(["--buckets", "1d", "-r", "2025-03-09", "23h", "25h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    < 1d   0
+    >= 1d  2
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn invalid_buckets() {
+    assert_cmd_snapshot!(
+        histogram().arg("1s"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --buckets is required
+    ",
+    );
+    assert_cmd_snapshot!(
+        histogram().args(["--buckets", "1s,1000ms", "1s"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    bucket edges must be strictly increasing, but `1000ms` is not greater than `1s`
+    ",
+    );
+}
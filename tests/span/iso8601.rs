@@ -60,3 +60,23 @@ fn fractional() {
     ",
     );
 }
+
+#[test]
+fn weeks() {
+    assert_cmd_snapshot!(
+        iso8601().args(["-w", "14d", "15d", "1w7d", "-3w", "1w12h", "0s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    P2W
+    P15D
+    P2W
+    -P3W
+    P1WT12H
+    PT0S
+
+    ----- stderr -----
+    ",
+    );
+}
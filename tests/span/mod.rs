@@ -1,5 +1,14 @@
+mod add;
 mod balance;
+mod clamp;
 mod fmt;
+mod histogram;
 mod iso8601;
+mod overlap;
+mod parse;
 mod round;
+mod scale;
 mod since;
+mod stats;
+mod total;
+mod until;
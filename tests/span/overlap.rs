@@ -0,0 +1,145 @@
+use crate::command::assert_cmd_snapshot;
+
+fn overlap() -> crate::command::Command {
+    crate::bttf(["span", "overlap"])
+}
+
+#[test]
+fn flags() {
+    // Partially overlapping.
+    assert_cmd_snapshot!(
+        overlap()
+            .args(["--a", "2025-03-15T09:00,2025-03-15T10:00"])
+            .args(["--b", "2025-03-15T09:30,2025-03-15T11:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    30m
+
+    ----- stderr -----
+    ",
+    );
+    // One interval contains the other.
+    assert_cmd_snapshot!(
+        overlap()
+            .args(["--a", "2025-03-15T09:00,2025-03-15T17:00"])
+            .args(["--b", "2025-03-15T12:15,2025-03-15T13:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    45m
+
+    ----- stderr -----
+    ",
+    );
+    // Touching.
+    assert_cmd_snapshot!(
+        overlap()
+            .args(["--a", "2025-03-15T09:00,2025-03-15T10:00"])
+            .args(["--b", "2025-03-15T10:00,2025-03-15T11:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    0s
+
+    ----- stderr -----
+    ",
+    );
+    // Disjoint.
+    assert_cmd_snapshot!(
+        overlap()
+            .args(["--a", "2025-03-15T09:00,2025-03-15T10:00"])
+            .args(["--b", "2025-03-16T09:00,2025-03-16T10:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    0s
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// The overlap is the actual time elapsed, so it's one hour shorter than
+/// the wall clock difference across a DST gap.
+#[test]
+fn dst() {
+    assert_cmd_snapshot!(
+        overlap()
+            .args(["--a", "2025-03-09T00:00,2025-03-10T00:00"])
+            .args(["--b", "2025-03-08T12:00,2025-03-09T12:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    11h
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn stdin() {
+    let stdin = "\
+2025-03-15T09:00Z\t2025-03-15T10:00Z\t2025-03-15T09:30Z\t2025-03-15T10:30Z
+2025-03-15T09:00Z\t2025-03-15T10:00Z\t2025-03-15T10:00Z\t2025-03-15T11:00Z
+2025-03-15T09:00-04[America/New_York]\t2025-03-15T10:00-04[America/New_York]\t2025-03-15T13:15Z\t2025-03-15T15:00Z
+";
+    assert_cmd_snapshot!(
+        overlap().stdin(stdin),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    30m
+    0s
+    45m
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        overlap().args(["--a", "2025-03-15T09:00,2025-03-15T10:00"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --a and --b must be given together
+    ",
+    );
+    assert_cmd_snapshot!(
+        overlap()
+            .args(["--a", "2025-03-15T10:00,2025-03-15T09:00"])
+            .args(["--b", "2025-03-15T09:30,2025-03-15T11:00"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --a: interval end `2025-03-15T09:00:00-04:00[America/New_York]` precedes its start `2025-03-15T10:00:00-04:00[America/New_York]`
+    ",
+    );
+    assert_cmd_snapshot!(
+        overlap().stdin("2025-03-15T09:00Z\t2025-03-15T10:00Z\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    <stdin>: line 1: expected four tab separated datetimes, but got `2025-03-15T09:00Z	2025-03-15T10:00Z`
+    ",
+    );
+}
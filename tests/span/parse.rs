@@ -0,0 +1,269 @@
+use crate::command::assert_cmd_snapshot;
+
+fn parse() -> crate::command::Command {
+    crate::bttf(["span", "parse"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        parse()
+            .arg("PT1H30M")
+            .arg("2 hours, 5 minutes ago")
+            .arg("1hr30mins")
+            .arg("P1dT1h")
+            .arg("-1w"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h 30m
+    2h 5m ago
+    1h 30m
+    1d 1h
+    1w ago
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that the first invalid span stops processing and is reported with
+/// its line number.
+#[test]
+fn invalid() {
+    assert_cmd_snapshot!(
+        parse().stdin("1d\n1h1h\n2d\n"),
+        @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    1d
+
+    ----- stderr -----
+    line 2 of <stdin>: invalid time span `1h1h`: failed to parse input in the "friendly" duration format: found value with unit hour after unit hour, but units must be written from largest to smallest (and they can't be repeated)
+    "#,
+    );
+    assert_cmd_snapshot!(
+        parse().arg("1m1h"),
+        @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid time span `1m1h`: failed to parse input in the "friendly" duration format: found value with unit hour after unit minute, but units must be written from largest to smallest (and they can't be repeated)
+    "#,
+    );
+    assert_cmd_snapshot!(
+        parse().arg("1.5h30m"),
+        @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid time span `1.5h30m`: failed to parse input in the "friendly" duration format: parsed value 'PT1H30M', but unparsed input "30m" remains (expected no unparsed input)
+    "#,
+    );
+    assert_cmd_snapshot!(
+        parse().arg("5 parsecs"),
+        @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid time span `5 parsecs`: failed to parse input in the "friendly" duration format: expected to find unit designator suffix (e.g., `years` or `secs`) after parsing integer
+    "#,
+    );
+    assert_cmd_snapshot!(
+        parse().stdin("1d\n\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    1d
+
+    ----- stderr -----
+    line 2 of <stdin>: invalid time span ``: an empty string is not a valid `Span`, expected either a ISO 8601 or Jiff's 'friendly' format
+    ",
+    );
+}
+
+#[test]
+fn strict() {
+    // Everything here is valid, even in strict mode.
+    assert_cmd_snapshot!(
+        parse()
+            .arg("--strict")
+            .stdin("PT1H30M\nP1dT1h\n1 hour, 30 minutes\n01:30:00\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h 30m
+    1d 1h
+    1h 30m
+    1h 30m
+
+    ----- stderr -----
+    ",
+    );
+    // But without strict mode, all of these are accepted.
+    let lenient = "1h \np1d\nP1Dt1h\nPT1H30m\n1h  30m\n1h\t30m\n";
+    assert_cmd_snapshot!(
+        parse().stdin(lenient),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h
+    1d
+    1d 1h
+    1h 30m
+    1h 30m
+    1h 30m
+
+    ----- stderr -----
+    ",
+    );
+    // In strict mode, each of them is rejected.
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("1h "),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1h ` has trailing whitespace
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("p1d"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    ISO 8601 span `p1d` must use uppercase `P` and `T` designators
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("P1Dt1h"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    ISO 8601 span `P1Dt1h` must use uppercase `P` and `T` designators
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("PT1H30m"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    ISO 8601 span `PT1H30m` mixes uppercase and lowercase unit designators
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("1h  30m"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1h  30m` has consecutive spaces
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("1h\t30m"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1h	30m` contains whitespace other than a space: `\t`
+    ",
+    );
+}
+
+/// Tests that strict mode requires spans to round trip through one of the
+/// styles that bttf prints.
+#[test]
+fn strict_canonical() {
+    assert_cmd_snapshot!(
+        parse().arg("--strict").stdin(
+            "1h30m\n1 h 30 m\n-1h\n+1h\n1.5h\n2 hours, 5 minutes ago\n\
+             1d 02:00:00\nP1DT1H\n",
+        ),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h 30m
+    1h 30m
+    1h ago
+    1h
+    1h 30m
+    2h 5m ago
+    1d 2h
+    1d 1h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("1hr"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1hr` is not in canonical form, expected, e.g., `1h` or `1 hour`
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("1 hour, 30 mins"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1 hour, 30 mins` is not in canonical form, expected, e.g., `1h 30m` or `1 hour 30 minutes`
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("1h 0m"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1h 0m` is not in canonical form, expected, e.g., `1h` or `1 hour`
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").arg("PT90M0S"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    ISO 8601 span `PT90M0S` is not in canonical form, expected `PT90M`
+    ",
+    );
+}
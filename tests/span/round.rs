@@ -148,3 +148,239 @@ fn since_then_round() {
     ",
     );
 }
+
+/// Tests how each rounding mode breaks ties, i.e., spans that are exactly
+/// half of the rounding increment away from two allowed values.
+#[test]
+fn ties() {
+    let input = "\
+1m30s
+-1m30s
+2m30s
+-2m30s
+1m29s
+";
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mhalf-expand"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2m
+    2m ago
+    3m
+    3m ago
+    1m
+
+    ----- stderr -----
+    ",
+    );
+    // `half-up` is another name for `half-expand`.
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mhalf-up"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2m
+    2m ago
+    3m
+    3m ago
+    1m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mhalf-trunc"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1m
+    1m ago
+    2m
+    2m ago
+    1m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mhalf-ceil"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2m
+    1m ago
+    3m
+    2m ago
+    1m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mhalf-floor"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1m
+    2m ago
+    2m
+    3m ago
+    1m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mhalf-even"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2m
+    2m ago
+    2m
+    2m ago
+    1m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mexpand"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2m
+    2m ago
+    3m
+    3m ago
+    2m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mtrunc"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1m
+    1m ago
+    2m
+    2m ago
+    1m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mceil"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2m
+    1m ago
+    3m
+    2m ago
+    2m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-mfloor"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1m
+    2m ago
+    2m
+    3m ago
+    1m
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests the directed rounding modes with a rounding increment, e.g., for
+/// billing in 15 minute blocks.
+#[test]
+fn mode_with_increment() {
+    let input = "1h7m\n7m30s\n1h52m30s\n";
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-i15", "-mceil"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h 15m
+    15m
+    2h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-i15", "-mfloor"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h
+    0s
+    1h 45m
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-i15", "-mhalf-up"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h
+    15m
+    2h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-i15", "-mhalf-even"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h
+    0s
+    2h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-shour", "-mfloor"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h
+    0s
+    1h
+
+    ----- stderr -----
+    ",
+    );
+}
@@ -0,0 +1,176 @@
+use crate::command::assert_cmd_snapshot;
+
+fn scale() -> crate::command::Command {
+    crate::bttf(["span", "scale"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        scale().args(["--by", "0.5", "2h"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        scale().args(["--by", "3", "1h20m", "90s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    4h
+    270s
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        scale().args(["--by", "1/3", "1h", "1s"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    20m
+    333ms 333µs 333ns
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        scale().args(["--by", "-2", "1h30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    3h ago
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn round() {
+    assert_cmd_snapshot!(
+        scale().args(["--by", "0.3", "5m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1m 30s
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        scale().args(["--by", "0.3", "--round", "5m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2m
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that calendar spans require a relative datetime, and that the
+/// result depends on it.
+#[test]
+fn calendar() {
+    assert_cmd_snapshot!(
+        scale().args(["--by", "0.5", "1mo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1mo` has calendar units, which require a relative datetime given by -r/--relative
+    ",
+    );
+    assert_cmd_snapshot!(
+        scale().args(["--by", "0.5", "-r", "2025-02-01", "1mo"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    14d
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        scale().args(["--by", "0.5", "-r", "2025-03-09", "1d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    11h 30m
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn invalid_factor() {
+    assert_cmd_snapshot!(
+        scale().arg("1h"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --by is required
+    ",
+    );
+    assert_cmd_snapshot!(
+        scale().args(["--by", "half", "1h"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --by: expected an integer, decimal number or fraction
+    ",
+    );
+    assert_cmd_snapshot!(
+        scale().args(["--by", "1/0", "1h"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --by: factor has a zero denominator
+    ",
+    );
+}
+
+/// Tests that rounding doesn't overflow with a very large denominator.
+#[test]
+fn huge_fraction() {
+    let by = "170141183460469231731687303715884105726/\
+              170141183460469231731687303715884105727";
+    assert_cmd_snapshot!(
+        scale().args(["--by", by, "1ns"]).arg("-1ns"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1ns
+    1ns ago
+
+    ----- stderr -----
+    ",
+    );
+}
@@ -60,3 +60,191 @@ fn relative() {
     ",
     );
 }
+
+/// Tests that `--now` overrides `BTTF_NOW` for a single invocation, both
+/// before and after the command name.
+#[test]
+fn now_flag() {
+    let bttf = |args: &[&str]| {
+        crate::bttf(args).env("BTTF_NOW", "2030-01-01T00:00:00Z")
+    };
+    let since = || bttf(&["span", "since"]);
+    assert_cmd_snapshot!(
+        since().arg("2023-12-31"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    52627h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(&["--now", "2024-01-01", "span", "since", "2023-12-31"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    24h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(&["--now=2024-01-02T00Z", "span", "since", "2023-12-31"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    43h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(&["--now", "2024-01-01", "span", "since", "-r", "now"])
+            .arg("2023-12-31"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    24h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(&["--now", "foo", "span", "since", "2023-12-31"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --now: unrecognized datetime `foo`
+    ",
+    );
+    // The flag is also accepted after the command name.
+    assert_cmd_snapshot!(
+        since().args(["--now", "2024-01-01", "2023-12-31"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    24h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        since().args(["--now", "2024-01-01", "-r", "now", "2023-12-31"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    24h
+
+    ----- stderr -----
+    ",
+    );
+    // But it has to come before anything that uses the current time.
+    assert_cmd_snapshot!(
+        since().args(["-r", "now", "--now", "2024-01-01", "2023-12-31"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --now must come before any other flag that takes a datetime
+    ",
+    );
+}
+
+/// Tests that the endpoints of each span are printed as they were
+/// interpreted, including the time zone they were put in.
+#[test]
+fn with_endpoints() {
+    assert_cmd_snapshot!(
+        since()
+            .args(["--with-endpoints", "-r", "2024-05-31"])
+            .args(["2024-04-30T12Z", "2024-05-30T12-04[America/New_York]"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-04-30T12:00:00Z[Etc/Unknown] → 2024-05-31T00:00:00-04:00[America/New_York] = 736h
+    2024-05-30T12:00:00-04:00[America/New_York] → 2024-05-31T00:00:00-04:00[America/New_York] = 12h
+
+    ----- stderr -----
+    ",
+    );
+    // `now` is resolved from `BTTF_NOW` and the relative datetime defaults
+    // to it.
+    assert_cmd_snapshot!(
+        since().args(["--with-endpoints", "-l", "day", "now", "2024-07-01"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55-04:00[America/New_York] → 2024-07-20T16:30:55-04:00[America/New_York] = 0s
+    2024-07-01T00:00:00-04:00[America/New_York] → 2024-07-20T16:30:55-04:00[America/New_York] = 19d 16h 30m 55s
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `-s/--smallest` rounds spans and pairs with `-l/--largest`,
+/// both for datetimes given as arguments and on tagged stdin.
+#[test]
+fn smallest() {
+    assert_cmd_snapshot!(
+        since().args(["-l", "year", "-s", "day", "2019-02-03T22:30"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    5y 5mo 17d
+
+    ----- stderr -----
+    ",
+    );
+    // When no largest unit is given, it is bumped up to the smallest unit.
+    assert_cmd_snapshot!(
+        since().args(["-s", "day", "2023-01-01"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    567d
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        since().args(["-l", "week", "-s", "hour"]).stdin(
+            r#"{"tags":[{"value":"2023-01-01T00:00:00-05:00[America/New_York]","range":[7,32]}],"data":{"text":"launch 2023-01-01 party\n"}}"#,
+        ),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"80w 6d 17h","range":[7,32]}],"data":{"text":"launch 2023-01-01 party\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        since().args(["-l", "hour", "-s", "day", "2023-01-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -l/--largest unit (hours) must be at least as big as -s/--smallest unit (days)
+    ",
+    );
+}
@@ -0,0 +1,141 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn stats() -> crate::command::Command {
+    bttf(["span", "stats"])
+}
+
+/// Tests each statistic on a small known set of spans.
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        stats().args(["250ms", "2ms", "40ms", "5ms"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    count: 4
+    sum: 297ms
+    mean: 74ms 250µs
+    median: 22ms 500µs
+    min: 2ms
+    max: 250ms
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        stats().stdin("1s\n3s\n2s\n-1s\n5s\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    count: 5
+    sum: 10s
+    mean: 2s
+    median: 2s
+    min: 1s ago
+    max: 5s
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that no spans prints only the count and sum.
+#[test]
+fn empty() {
+    assert_cmd_snapshot!(
+        stats().stdin(""),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    count: 0
+    sum: 0s
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        stats().arg("--json").stdin(""),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"count":0,"max":null,"mean":null,"median":null,"min":null,"sum":"0s"}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn json() {
+    assert_cmd_snapshot!(
+        stats().args(["--json", "2ms", "5ms", "40ms", "250ms"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"count":4,"max":"250ms","mean":"74ms 250µs","median":"22ms 500µs","min":"2ms","sum":"297ms"}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+/// Tests that calendar units require a relative datetime.
+#[test]
+fn relative() {
+    assert_cmd_snapshot!(
+        stats().args(["1mo", "1w"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1mo` has calendar units, which require a relative datetime given by -r/--relative
+    ",
+    );
+    assert_cmd_snapshot!(
+        stats().args(["-r", "2025-03-01", "1mo", "1w"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    count: 2
+    sum: 911h
+    mean: 455h 30m
+    median: 455h 30m
+    min: 168h
+    max: 743h
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that every tag in tagged data is counted.
+#[test]
+fn tagged() {
+    let input = r#"{"tags":[{"value":"1h","range":[0,2]},{"value":"30m","range":[3,6]}],"data":{"text":"1h 30m\n"}}
+{"tags":[{"value":"2h","range":[0,2]}],"data":{"text":"2h\n"}}
+"#;
+    assert_cmd_snapshot!(
+        stats().stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    count: 3
+    sum: 3h 30m
+    mean: 1h 10m
+    median: 1h
+    min: 30m
+    max: 2h
+
+    ----- stderr -----
+    ",
+    );
+}
@@ -0,0 +1,99 @@
+use crate::command::assert_cmd_snapshot;
+
+fn total() -> crate::command::Command {
+    crate::bttf(["span", "total"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        total().args(["-u", "hours", "P1DT3H", "90m", "-2h30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    27
+    1.5
+    -2.5
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        total().args(["-u", "seconds"]).stdin("1m\n1.5s\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    60
+    1.5
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that calendar units require a relative datetime.
+#[test]
+fn calendar_units() {
+    assert_cmd_snapshot!(
+        total().args(["-u", "days", "-r", "2025-02-01", "1mo", "1w"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    28
+    7
+
+    ----- stderr -----
+    ",
+    );
+    // A day is 23 hours when it contains a transition to daylight saving
+    // time.
+    assert_cmd_snapshot!(
+        total()
+            .args(["-u", "hours", "-r", "2025-03-09T00-05[America/New_York]"])
+            .arg("1d"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    23
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        total().args(["-u", "days", "36h"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    a total in the calendar unit `days` requires a relative datetime given by -r/--relative
+    ",
+    );
+    assert_cmd_snapshot!(
+        total().args(["-u", "hours", "1mo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    span `1mo` has non-zero years or months, which requires a relative datetime given by -r/--relative
+    ",
+    );
+    assert_cmd_snapshot!(
+        total().arg("1h"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -u/--unit is required
+    ",
+    );
+}
@@ -0,0 +1,66 @@
+use crate::command::assert_cmd_snapshot;
+
+fn until() -> crate::command::Command {
+    crate::bttf(["span", "until"])
+}
+
+/// Tests that the endpoints of each span are printed as they were
+/// interpreted, with the relative datetime as the start.
+#[test]
+fn with_endpoints() {
+    assert_cmd_snapshot!(
+        until()
+            .args(["--with-endpoints", "-r", "2024-03-09T12[Europe/London]"])
+            .args(["2024-03-10T12[America/New_York]", "2024-03-09T12Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-03-09T12:00:00+00:00[Europe/London] → 2024-03-10T12:00:00-04:00[America/New_York] = 28h
+    2024-03-09T12:00:00+00:00[Europe/London] → 2024-03-09T12:00:00Z[Etc/Unknown] = 0s
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        until()
+            .args(["--with-endpoints", "-l", "day"])
+            .stdin("2024-08-01T00:00-04[America/New_York]\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55-04:00[America/New_York] → 2024-08-01T00:00:00-04:00[America/New_York] = 11d 7h 29m 5s
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `-s/--smallest` rounds spans and that it can't be bigger than
+/// `-l/--largest`.
+#[test]
+fn smallest() {
+    assert_cmd_snapshot!(
+        until().args(["-l", "day", "-s", "hour", "2024-08-01T00:00:30"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    11d 7h
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        until().args(["-l", "minute", "-s", "hour", "2024-08-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -l/--largest unit (minutes) must be at least as big as -s/--smallest unit (hours)
+    ",
+    );
+}
@@ -0,0 +1,327 @@
+use crate::command::assert_cmd_snapshot;
+
+fn exec() -> crate::command::Command {
+    crate::bttf(["tag", "exec"])
+}
+
+/// Test that the file path is appended when there are no directives.
+#[cfg(unix)]
+#[test]
+fn append() {
+    assert_cmd_snapshot!(
+        exec().args(["printf", "%s\\n"]).stdin("foo/bar baz.txt\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"foo/bar baz.txt"}],"data":{"text":"foo/bar baz.txt\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+/// Test each of the supported directives, including on paths with spaces.
+#[cfg(unix)]
+#[test]
+fn directives() {
+    assert_cmd_snapshot!(
+        exec()
+            .args(["printf", "%s\\n", "{}", "{path}", "{name}", "{dir}"])
+            .stdin("foo/bar baz.txt\nquux\n/a b/c\n/top\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"foo/bar baz.txt"},{"value":"foo/bar baz.txt"},{"value":"bar baz.txt"},{"value":"foo"}],"data":{"text":"foo/bar baz.txt\n"}}
+    {"tags":[{"value":"quux"},{"value":"quux"},{"value":"quux"},{"value":"."}],"data":{"text":"quux\n"}}
+    {"tags":[{"value":"/a b/c"},{"value":"/a b/c"},{"value":"c"},{"value":"/a b"}],"data":{"text":"/a b/c\n"}}
+    {"tags":[{"value":"/top"},{"value":"/top"},{"value":"top"},{"value":"/"}],"data":{"text":"/top\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        exec()
+            .args(["printf", "%s\\n", "dir={dir} name={name}"])
+            .stdin("some dir/some file\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"dir=some dir name=some file"}],"data":{"text":"some dir/some file\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+/// Test that braces and backslashes can be escaped.
+#[cfg(unix)]
+#[test]
+fn escape() {
+    assert_cmd_snapshot!(
+        exec()
+            .args(["printf", "%s\\n", r"\{name\}={name}", r"a\\b {}"])
+            .stdin("foo/bar\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"{name}=bar"},{"value":"a\\b foo/bar"}],"data":{"text":"foo/bar\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+/// Test that invalid templates are reported as errors.
+#[test]
+fn invalid() {
+    assert_cmd_snapshot!(
+        exec().args(["printf", "{nope}"]).stdin("foo\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid command argument `{nope}`: unrecognized format directive `{nope}`, allowed directives are `{}`, `{path}`, `{name}` and `{dir}`
+    ",
+    );
+    assert_cmd_snapshot!(
+        exec().args(["printf", "{name"]).stdin("foo\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid command argument `{name`: found unclosed brace, which might be an invalid format directive (to write a brace literally, escape it with a backslash)
+    ",
+    );
+    assert_cmd_snapshot!(
+        exec().args(["printf", r"{name}\"]).stdin("foo\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid command argument `{name}\`: found dangling backslash (to write a backslash literally, escape it with a backslash)
+    ",
+    );
+}
+
+/// Test that `--batch` passes multiple file paths to each command and maps
+/// each line of output back to its file path.
+#[cfg(unix)]
+#[test]
+fn batch() {
+    assert_cmd_snapshot!(
+        exec()
+            .args(["--batch", "2", "printf", "%s\\n"])
+            .stdin("foo/bar baz.txt\nquux\n/a b/c\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"foo/bar baz.txt"}],"data":{"text":"foo/bar baz.txt\n"}}
+    {"tags":[{"value":"quux"}],"data":{"text":"quux\n"}}
+    {"tags":[{"value":"/a b/c"}],"data":{"text":"/a b/c\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        exec()
+            .args(["--batch", "3", "printf", "%s\\n", "{name}"])
+            .stdin("foo/bar baz.txt\nquux\n/a b/c\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"bar baz.txt"}],"data":{"text":"foo/bar baz.txt\n"}}
+    {"tags":[{"value":"quux"}],"data":{"text":"quux\n"}}
+    {"tags":[{"value":"c"}],"data":{"text":"/a b/c\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    // An empty line of output means the corresponding path gets no tag.
+    assert_cmd_snapshot!(
+        exec()
+            .args(["--batch", "2", "sh", "-c", "echo; echo \"$2\"", "sh"])
+            .stdin("foo\nbar\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"data":{"text":"foo\n"}}
+    {"tags":[{"value":"bar"}],"data":{"text":"bar\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+/// Test that `--batch` reports an error when the command doesn't print
+/// exactly one line per file path.
+#[cfg(unix)]
+#[test]
+fn batch_mismatch() {
+    assert_cmd_snapshot!(
+        exec().args(["--batch", "2", "echo"]).stdin("foo\nbar\n"),
+        @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    command "echo" "foo" "bar" printed 1 line(s) of output for 2 file path(s), but --batch requires exactly one line of output per file path
+    "#,
+    );
+}
+
+/// Test that tags can be read from stderr, or from both streams, for a
+/// command that writes to both. With `--stderr-tag`, stderr provides the tags
+/// and stdout provides the data.
+#[cfg(unix)]
+#[test]
+fn stderr_tag() {
+    let script = "echo \"out $1\"; echo \"err $1\" >&2";
+    assert_cmd_snapshot!(
+        exec().args(["sh", "-c", script, "sh"]).stdin("foo\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"out foo"}],"data":{"text":"foo\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        exec().args(["--stderr-tag", "sh", "-c", script, "sh"]).stdin("foo\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"err foo"}],"data":{"text":"out foo\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        exec()
+            .args(["--stderr-tag=merge", "sh", "-c", script, "sh"])
+            .stdin("foo\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"out foo"},{"value":"err foo"}],"data":{"text":"foo\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        exec()
+            .args(["--stderr-tag=only", "--batch", "2", "sh", "-c"])
+            .args(["for p; do echo \"$p\" >&2; done", "sh"])
+            .stdin("foo\nbar\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"foo"}],"data":{"text":"foo\n"}}
+    {"tags":[{"value":"bar"}],"data":{"text":"bar\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        exec().args(["--stderr-tag=merge", "--batch", "2", "echo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --stderr-tag=merge cannot be used with --batch, since lines from stdout and stderr can't be matched up with file paths
+    ",
+    );
+    // With `--batch`, each line printed to stdout is the data for the
+    // corresponding file path.
+    let script = "for p; do echo \"out $p\"; echo \"err $p\" >&2; done";
+    assert_cmd_snapshot!(
+        exec()
+            .args(["--stderr-tag", "--batch", "2", "sh", "-c", script, "sh"])
+            .stdin("foo\nbar\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"err foo"}],"data":{"text":"out foo\n"}}
+    {"tags":[{"value":"err bar"}],"data":{"text":"out bar\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        exec()
+            .args(["--stderr-tag", "--batch", "2", "sh", "-c"])
+            .args(["echo out; for p; do echo \"$p\" >&2; done", "sh"])
+            .stdin("foo\nbar\n"),
+        @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    command "sh" "-c" "echo out; for p; do echo \"$p\" >&2; done" "sh" "foo" "bar" printed 1 line(s) to stdout for 2 file path(s), but --batch with --stderr-tag requires that stdout is either empty or has exactly one line per file path
+    "#,
+    );
+}
+
+/// Test that output is printed in input order even when commands finish in a
+/// different order than they were started.
+#[cfg(unix)]
+#[test]
+fn threads_ordering() {
+    let script = "sleep \"$1\"; echo \"slept $1\"";
+    assert_cmd_snapshot!(
+        exec().args(["-j4", "sh", "-c", script, "sh"]).stdin("0.3\n0.2\n0.1\n0\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"slept 0.3"}],"data":{"text":"0.3\n"}}
+    {"tags":[{"value":"slept 0.2"}],"data":{"text":"0.2\n"}}
+    {"tags":[{"value":"slept 0.1"}],"data":{"text":"0.1\n"}}
+    {"tags":[{"value":"slept 0"}],"data":{"text":"0\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+/// Test that a `--now` flag meant for the command being run is passed through
+/// to it instead of being treated as bttf's global `--now` flag.
+#[cfg(unix)]
+#[test]
+fn now_passthrough() {
+    assert_cmd_snapshot!(
+        exec()
+            .args(["printf", "%s %s %s\\n", "--now", "2025-01-01", "{}"])
+            .stdin("foo\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"--now 2025-01-01 foo"}],"data":{"text":"foo\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
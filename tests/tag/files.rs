@@ -176,3 +176,183 @@ fn auto_time_zone() {
     "#,
     );
 }
+
+#[test]
+fn explicit_regex_with_capture() {
+    let tmp = TempDir::new();
+    tmp.create("foo", "Date: 2025-03-15\nUpdated: 12/01/2024\n");
+
+    assert_cmd_snapshot!(
+        tmp.bttf([
+            "tag",
+            "files",
+            "--all",
+            "-e", r"Date: (?<d>[0-9]{4}-[0-9]{2}-[0-9]{2})",
+            "--capture", "d",
+            "-e", r"Updated: (?<u>[0-9]{2}/[0-9]{2}/[0-9]{4})",
+            "--capture", "u",
+            "foo",
+        ]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15"},{"value":"12/01/2024"}],"data":{"text":"foo\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+
+    // Without `--all`, the leftmost match wins.
+    assert_cmd_snapshot!(
+        tmp.bttf([
+            "tag",
+            "files",
+            "-e", r"Updated: (?<u>[0-9]{2}/[0-9]{2}/[0-9]{4})",
+            "--capture", "u",
+            "-e", r"Date: (?<d>[0-9]{4}-[0-9]{2}-[0-9]{2})",
+            "--capture", "d",
+            "foo",
+        ]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15"}],"data":{"text":"foo\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn explicit_regex_with_capture_errors() {
+    let tmp = TempDir::new();
+    tmp.create("foo", "Date: 2025-03-15\n");
+
+    assert_cmd_snapshot!(
+        tmp.bttf([
+            "tag",
+            "files",
+            "-e", r"Date: (?<d>[0-9]{4}-[0-9]{2}-[0-9]{2})",
+            "--capture", "nope",
+            "foo",
+        ]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    capture group `nope` given by --capture was not found in pattern `Date: (?<d>[0-9]{4}-[0-9]{2}-[0-9]{2})`
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "--capture", "d", "foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --capture must come after the -e/--regex flag it applies to
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "-e", "[0-9]", "-e", "(unclosed", "foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -e/--regex: failed to compile pattern `(unclosed`:
+    regex parse error:
+        (unclosed
+        ^
+    error: unclosed group
+    ",
+    );
+}
+
+/// Tests that `--since` and `--until` are inclusive and that files without
+/// any tags in range are omitted.
+#[test]
+fn since_until() {
+    let tmp = TempDir::new();
+    tmp.create("before", "2025-02-28T23:59:59Z");
+    tmp.create("start", "2025-03-01T00:00:00Z");
+    tmp.create("end", "2025-03-31T23:59:59Z");
+    tmp.create("after", "2025-04-01T00:00:00Z");
+    tmp.create(
+        "straddle",
+        "2025-02-28T23:59:59Z 2025-03-15T12:00:00Z 2025-04-01T00:00:00Z",
+    );
+    let files = ["before", "start", "end", "after", "straddle"];
+    let range =
+        ["--since", "2025-03-01T00Z", "--until", "2025-03-31T23:59:59Z"];
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "-j1"]).args(range).args(files),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-01T00:00:00Z"}],"data":{"text":"start\n"}}
+    {"tags":[{"value":"2025-03-31T23:59:59Z"}],"data":{"text":"end\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    // With `--all`, only the in-range tags are kept.
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "-j1", "--all"]).args(range).args(files),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-01T00:00:00Z"}],"data":{"text":"start\n"}}
+    {"tags":[{"value":"2025-03-31T23:59:59Z"}],"data":{"text":"end\n"}}
+    {"tags":[{"value":"2025-03-15T12:00:00Z"}],"data":{"text":"straddle\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    // Either end may be omitted.
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "-j1", "--all"])
+            .args(["--since", "2025-03-31T23:59:59Z"])
+            .args(files),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-31T23:59:59Z"}],"data":{"text":"end\n"}}
+    {"tags":[{"value":"2025-04-01T00:00:00Z"}],"data":{"text":"after\n"}}
+    {"tags":[{"value":"2025-04-01T00:00:00Z"}],"data":{"text":"straddle\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn since_until_invalid_tag() {
+    let tmp = TempDir::new();
+    tmp.create("foo", "Date: 03/15/2025");
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "-e", "[0-9]{2}/[0-9]{2}/[0-9]{4}"])
+            .args(["--since", "2025-03-01", "foo"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    foo: failed to parse tag `03/15/2025` as a datetime for --since/--until: unrecognized datetime `03/15/2025`
+    ",
+    );
+}
@@ -76,3 +76,329 @@ fn custom_regex() {
     "#,
     );
 }
+
+/// Tests that `-f/--format` parses the text of each tag as a datetime, and
+/// that text that doesn't parse isn't tagged.
+#[test]
+fn custom_regex_format() {
+    let stdin = "\
+GET /a [2024/07/20 16.30.55] 200
+GET /b [2024/13/20 16.30.55] 404
+GET /c 200
+";
+    assert_cmd_snapshot!(
+        lines()
+            .args(["-e", r"\[(?<tag>[0-9]{4}/[0-9]{2}/[0-9]{2} [0-9.]{8})\]"])
+            .args(["-f", "%Y/%m/%d %H.%M.%S"])
+            .stdin(stdin),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2024-07-20T16:30:55-04:00[America/New_York]","range":[8,27]}],"data":{"text":"GET /a [2024/07/20 16.30.55] 200\n"}}
+    {"data":{"text":"GET /b [2024/13/20 16.30.55] 404\n"}}
+    {"data":{"text":"GET /c 200\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    // The parsed datetimes can be used by other flags, like `--dedupe`.
+    assert_cmd_snapshot!(
+        lines()
+            .args(["-e", r"@(?<tag>[0-9]+)", "-f", "%s", "--dedupe"])
+            .stdin("a @1721507455\nb @1721507455\nc @1721507456\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2024-07-20T20:30:55Z[Etc/Unknown]","range":[3,13]}],"data":{"text":"a @1721507455\n"}}
+    {"tags":[{"value":"2024-07-20T20:30:56Z[Etc/Unknown]","range":[3,13]}],"data":{"text":"c @1721507456\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+const DUPLICATES: &str = "\
+2025-03-15T10:00:00Z a
+2025-03-15T06:00:00-04 b
+2025-03-15T10:00:30Z c
+no datetime here
+2025-03-15T10:00:00Z d
+2025-03-15T10:02:00Z e
+";
+
+#[test]
+fn dedupe() {
+    assert_cmd_snapshot!(
+        lines().arg("--dedupe").stdin(DUPLICATES).pipe(crate::bttf(["untag"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:00:00Z a
+    2025-03-15T10:00:30Z c
+    no datetime here
+    2025-03-15T10:02:00Z e
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn dedupe_adjacent() {
+    assert_cmd_snapshot!(
+        lines()
+            .arg("--dedupe-adjacent")
+            .stdin(DUPLICATES)
+            .pipe(crate::bttf(["untag"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:00:00Z a
+    2025-03-15T10:00:30Z c
+    no datetime here
+    2025-03-15T10:00:00Z d
+    2025-03-15T10:02:00Z e
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn dedupe_window() {
+    assert_cmd_snapshot!(
+        lines()
+            .args(["--dedupe", "--dedupe-window", "1m"])
+            .stdin(DUPLICATES)
+            .pipe(crate::bttf(["untag"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:00:00Z a
+    no datetime here
+    2025-03-15T10:02:00Z e
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        lines()
+            .args(["--dedupe-adjacent", "--dedupe-window", "1m"])
+            .stdin(DUPLICATES)
+            .pipe(crate::bttf(["untag"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:00:00Z a
+    no datetime here
+    2025-03-15T10:02:00Z e
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        lines().args(["--dedupe-window", "1m"]).stdin(DUPLICATES),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --dedupe-window requires either --dedupe or --dedupe-adjacent
+    ",
+    );
+}
+
+/// Tests that only lines without a datetime get a synthetic tag, and that
+/// the synthetic datetime is derived from the position of the line.
+#[test]
+fn synthetic() {
+    let stdin = "\
+foo
+2025-01-01T00Z bar
+baz
+";
+    assert_cmd_snapshot!(
+        lines()
+            .args(["--synthetic", "--start", "2025-03-15", "--step", "1mo"])
+            .stdin(stdin),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15T00:00:00-04:00[America/New_York]","synthetic":true}],"data":{"text":"foo\n"}}
+    {"tags":[{"value":"2025-01-01T00Z","range":[0,14]}],"data":{"text":"2025-01-01T00Z bar\n"}}
+    {"tags":[{"value":"2025-05-15T00:00:00-04:00[America/New_York]","synthetic":true}],"data":{"text":"baz\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        lines().arg("--synthetic").stdin(stdin).pipe(crate::bttf([
+            "untag",
+            "-f",
+            "{tag} {data}",
+        ])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1970-01-01T00:00:00+00:00[UTC] foo
+    2025-01-01T00Z 2025-01-01T00Z bar
+    1970-01-01T00:00:02+00:00[UTC] baz
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        lines().args(["--step", "1m"]).stdin(stdin),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --step requires --synthetic
+    ",
+    );
+}
+
+/// Tests that automatic extraction can be restricted to particular formats.
+#[test]
+fn timestamp_format() {
+    let line = "Sat, 15 Mar 2025 10:00:00 -0400 sent 2025-03-15 14:00Z \
+                via 2025-03-16T00:00Z[Europe/Paris]\n";
+    assert_cmd_snapshot!(
+        lines().args(["--all", "--timestamp-format", "rfc3339"]).stdin(line),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15 14:00Z","range":[37,54]},{"value":"2025-03-16T00:00Z","range":[59,76]}],"data":{"text":"Sat, 15 Mar 2025 10:00:00 -0400 sent 2025-03-15 14:00Z via 2025-03-16T00:00Z[Europe/Paris]\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        lines()
+            .args(["--all", "--timestamp-format", "rfc2822"])
+            .args(["--timestamp-format", "rfc9557"])
+            .stdin(line),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"Sat, 15 Mar 2025 10:00:00 -0400","range":[0,31]},{"value":"2025-03-16T00:00Z[Europe/Paris]","range":[59,90]}],"data":{"text":"Sat, 15 Mar 2025 10:00:00 -0400 sent 2025-03-15 14:00Z via 2025-03-16T00:00Z[Europe/Paris]\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        lines().args(["--timestamp-format", "rfc9110,rfc1123"]).stdin(line),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --timestamp-format: unknown timestamp format `rfc1123` (supported formats are rfc9557, rfc3339, rfc9110, rfc2822, iso8601, unix and friendly)
+    ",
+    );
+    assert_cmd_snapshot!(
+        lines()
+            .args(["--auto", "timezone", "--timestamp-format", "rfc3339"])
+            .stdin(line),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --timestamp-format can only be used when extracting datetimes, but `--auto timezone` was given
+    ",
+    );
+}
+
+/// Tests the formats that are only recognized when asked for explicitly.
+#[test]
+fn timestamp_format_opt_in() {
+    let line = "v2025.01.02 built 2025-01-02 at 2025-03-15T10:00Z \
+                ts=1741000000 ms=1741000000123 port=8080 3 days ago\n";
+    assert_cmd_snapshot!(
+        lines().arg("--all").stdin(line),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15T10:00Z","range":[32,49]}],"data":{"text":"v2025.01.02 built 2025-01-02 at 2025-03-15T10:00Z ts=1741000000 ms=1741000000123 port=8080 3 days ago\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        lines().args(["--all", "--timestamp-format", "iso8601"]).stdin(line),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-01-02","range":[18,28]},{"value":"2025-03-15T10:00","range":[32,48]}],"data":{"text":"v2025.01.02 built 2025-01-02 at 2025-03-15T10:00Z ts=1741000000 ms=1741000000123 port=8080 3 days ago\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        lines()
+            .args(["--all", "--timestamp-format", "rfc3339,iso8601"])
+            .stdin(line),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-01-02","range":[18,28]},{"value":"2025-03-15T10:00Z","range":[32,49]}],"data":{"text":"v2025.01.02 built 2025-01-02 at 2025-03-15T10:00Z ts=1741000000 ms=1741000000123 port=8080 3 days ago\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        lines().args(["--all", "--timestamp-format", "unix"]).stdin(line),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"1741000000","range":[53,63]},{"value":"1741000000123","range":[67,80]}],"data":{"text":"v2025.01.02 built 2025-01-02 at 2025-03-15T10:00Z ts=1741000000 ms=1741000000123 port=8080 3 days ago\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        lines().args(["--all", "--timestamp-format", "friendly"]).stdin(line),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"3 days ago","range":[91,101]}],"data":{"text":"v2025.01.02 built 2025-01-02 at 2025-03-15T10:00Z ts=1741000000 ms=1741000000123 port=8080 3 days ago\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        lines()
+            .args(["--timestamp-format", "unix"])
+            .stdin(line)
+            .pipe(crate::bttf(["time", "epoch", "--parse", "-u", "auto"])),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-03T11:06:40Z[Etc/Unknown]","range":[53,63]}],"data":{"text":"v2025.01.02 built 2025-01-02 at 2025-03-15T10:00Z ts=1741000000 ms=1741000000123 port=8080 3 days ago\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
@@ -0,0 +1,127 @@
+use crate::command::assert_cmd_snapshot;
+
+use crate::TempDir;
+
+fn tagged(text: &str, tag: &str) -> String {
+    format!(
+        r#"{{"tags":[{{"value":"{tag}"}}],"data":{{"text":"{text}\n"}}}}{}"#,
+        "\n",
+    )
+}
+
+#[test]
+fn interleave() {
+    let tmp = TempDir::new();
+    tmp.create(
+        "a",
+        [
+            tagged("a1", "2025-03-15T09:00Z"),
+            tagged("a2", "2025-03-15T11:00Z"),
+            tagged("a3", "2025-03-15T12:00Z"),
+        ]
+        .concat(),
+    );
+    // These are in a different time zone, but still sorted by instant.
+    tmp.create(
+        "b",
+        [
+            tagged("b1", "2025-03-15T06:00-04[America/New_York]"),
+            tagged("b2", "2025-03-15T07:00-04[America/New_York]"),
+            tagged("b3", "2025-03-15T09:00-04[America/New_York]"),
+        ]
+        .concat(),
+    );
+
+    // Lines with equal instants (a2 and b2) are printed in the order of the
+    // files given.
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "merge", "a", "b"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15T09:00Z"}],"data":{"text":"a1\n"}}
+    {"tags":[{"value":"2025-03-15T06:00-04[America/New_York]"}],"data":{"text":"b1\n"}}
+    {"tags":[{"value":"2025-03-15T11:00Z"}],"data":{"text":"a2\n"}}
+    {"tags":[{"value":"2025-03-15T07:00-04[America/New_York]"}],"data":{"text":"b2\n"}}
+    {"tags":[{"value":"2025-03-15T12:00Z"}],"data":{"text":"a3\n"}}
+    {"tags":[{"value":"2025-03-15T09:00-04[America/New_York]"}],"data":{"text":"b3\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "merge", "b", "a"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15T09:00Z"}],"data":{"text":"a1\n"}}
+    {"tags":[{"value":"2025-03-15T06:00-04[America/New_York]"}],"data":{"text":"b1\n"}}
+    {"tags":[{"value":"2025-03-15T07:00-04[America/New_York]"}],"data":{"text":"b2\n"}}
+    {"tags":[{"value":"2025-03-15T11:00Z"}],"data":{"text":"a2\n"}}
+    {"tags":[{"value":"2025-03-15T12:00Z"}],"data":{"text":"a3\n"}}
+    {"tags":[{"value":"2025-03-15T09:00-04[America/New_York]"}],"data":{"text":"b3\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn stdin() {
+    let tmp = TempDir::new();
+    tmp.create("a", "2025-03-15T09:00Z\n2025-03-15T11:00Z\n");
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "merge", "a", "-"])
+            .stdin("2025-03-15T10:00Z\n2025-03-15T12:00Z\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T09:00Z
+    2025-03-15T10:00Z
+    2025-03-15T11:00Z
+    2025-03-15T12:00Z
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn check_sorted() {
+    let tmp = TempDir::new();
+    tmp.create("a", "2025-03-15T09:00Z\n2025-03-15T11:00Z\n");
+    tmp.create("b", "2025-03-15T12:00Z\n2025-03-15T10:00Z\n");
+
+    // Without checking, unsorted inputs result in unsorted output.
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "merge", "a", "b"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T09:00Z
+    2025-03-15T11:00Z
+    2025-03-15T12:00Z
+    2025-03-15T10:00Z
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "merge", "--check-sorted", "a", "b"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    2025-03-15T09:00Z
+    2025-03-15T11:00Z
+    2025-03-15T12:00Z
+
+    ----- stderr -----
+    b: line 2 is before line 1, but inputs must be sorted
+    ",
+    );
+}
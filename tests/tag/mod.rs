@@ -1,2 +1,5 @@
+mod exec;
 mod files;
 mod lines;
+mod merge;
+mod stat;
@@ -0,0 +1,149 @@
+use crate::{NOW, TempDir, bttf, command::assert_cmd_snapshot};
+
+/// Tests that `--relative` tags each file with its age relative to the
+/// current time, with files from the future having a negative age.
+#[test]
+fn relative() {
+    let tmp = TempDir::new();
+    tmp.create("old", "");
+    tmp.create("new", "");
+    tmp.create("future", "");
+    tmp.set_modified(
+        "old",
+        &NOW.checked_sub(jiff::Span::new().days(30)).unwrap(),
+    );
+    tmp.set_modified(
+        "new",
+        &NOW.checked_sub(jiff::Span::new().minutes(90)).unwrap(),
+    );
+    tmp.set_modified(
+        "future",
+        &NOW.checked_add(jiff::Span::new().hours(1)).unwrap(),
+    );
+    let files = ["old", "new", "future"];
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "stat", "-j1", "modified"]).args(files),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2024-06-20T20:30:55Z[Etc/Unknown]","label":"mtime"}],"data":{"text":"old\n"}}
+    {"tags":[{"value":"2024-07-20T19:00:55Z[Etc/Unknown]","label":"mtime"}],"data":{"text":"new\n"}}
+    {"tags":[{"value":"2024-07-20T21:30:55Z[Etc/Unknown]","label":"mtime"}],"data":{"text":"future\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "stat", "-j1", "--relative", "modified"]).args(files),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"720h","label":"mtime"}],"data":{"text":"old\n"}}
+    {"tags":[{"value":"1h 30m","label":"mtime"}],"data":{"text":"new\n"}}
+    {"tags":[{"value":"1h ago","label":"mtime"}],"data":{"text":"future\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "stat", "-j1", "--relative", "-l", "days", "modified"])
+            .args(files),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"30d","label":"mtime"}],"data":{"text":"old\n"}}
+    {"tags":[{"value":"1h 30m","label":"mtime"}],"data":{"text":"new\n"}}
+    {"tags":[{"value":"1h ago","label":"mtime"}],"data":{"text":"future\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn largest_requires_relative() {
+    let tmp = TempDir::new();
+    tmp.create("foo", "");
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "stat", "-l", "days", "modified", "foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -l/--largest can only be used with --relative
+    ",
+    );
+}
+
+/// Tests that `--time` selects the kinds of metadata to extract, that every
+/// positional argument is then a file path and that each tag is labeled with
+/// the kind of metadata it came from.
+#[test]
+fn time() {
+    let tmp = TempDir::new();
+    tmp.create("foo", "");
+    tmp.create("bar", "");
+    tmp.set_modified(
+        "foo",
+        &NOW.checked_sub(jiff::Span::new().days(30)).unwrap(),
+    );
+    tmp.set_modified(
+        "bar",
+        &NOW.checked_sub(jiff::Span::new().minutes(90)).unwrap(),
+    );
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "stat", "-j1", "--time", "mtime", "foo", "bar"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2024-06-20T20:30:55Z[Etc/Unknown]","label":"mtime"}],"data":{"text":"foo\n"}}
+    {"tags":[{"value":"2024-07-20T19:00:55Z[Etc/Unknown]","label":"mtime"}],"data":{"text":"bar\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "stat", "-j1", "--time", "mtime", "--relative"])
+            .args(["foo", "bar"])
+            .pipe(bttf(["untag", "-f", "{label}\t{tag}\t{data}"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    mtime	720h	foo
+    mtime	1h 30m	bar
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "stat", "modified", "--time", "mtime", "foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --time cannot be used with the <kinds> argument
+    ",
+    );
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "stat", "--time", "mtimes", "foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --time: unknown file metadata kind: `mtimes`
+    ",
+    );
+}
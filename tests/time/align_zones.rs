@@ -0,0 +1,107 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        bttf(["time", "align-zones", "America/New_York", "Europe/London"])
+            .args(["--date", "2024-07-20"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T09:00:00-04:00[America/New_York]
+    2024-07-20T10:00:00-04:00[America/New_York]
+    2024-07-20T11:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn barely_overlapping() {
+    // 19:00 in New York is 08:00 the next day in Tokyo, so the windows only
+    // overlap for the last hour of New York's window.
+    assert_cmd_snapshot!(
+        bttf(["time", "align-zones", "America/New_York", "Asia/Tokyo"])
+            .args(["--window", "9-20", "--window", "8-18"])
+            .args(["--date", "2024-07-20"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T19:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["time", "align-zones", "America/New_York", "Asia/Tokyo"])
+            .args(["--window", "9-20", "--window", "8-18"])
+            .args(["--date", "2024-07-20", "--step", "30m"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T19:00:00-04:00[America/New_York]
+    2024-07-20T19:30:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn no_common_slot() {
+    assert_cmd_snapshot!(
+        bttf(["time", "align-zones"])
+            .args(["America/New_York", "Europe/London", "Asia/Tokyo"])
+            .args(["--date", "2024-07-20"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    no common slot on `2024-07-20` is within the window of every time zone
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        bttf(["time", "align-zones", "America/New_York", "Europe/London"])
+            .args(["-w", "9-17", "-w", "9-17", "-w", "9-17"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    got 3 windows for 2 time zones, but expected either one window or exactly one window per time zone
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["time", "align-zones", "America/New_York", "-w", "17-9"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -w/--window: start of window `17-9` must be before its end
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["time", "align-zones", "America/New_York", "--step", "1d"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    step `1d` must not contain calendar units
+    ",
+    );
+}
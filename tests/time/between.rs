@@ -0,0 +1,143 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn between() -> crate::command::Command {
+    bttf(["time", "between"])
+}
+
+/// Tests that datetimes exactly on the boundaries are included by default
+/// and excluded when requested.
+#[test]
+fn boundaries() {
+    let input = "\
+2025-03-01T00:00:00-05[America/New_York]
+2025-03-01T05:00:00Z
+2025-03-15T12:00:00Z
+2025-04-01T00:00:00-04[America/New_York]
+2025-04-01T00:00:00Z
+";
+    let range = ["--start", "2025-03-01", "--end", "2025-04-01", "--print"];
+    assert_cmd_snapshot!(
+        between().args(range).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    true
+    true
+    true
+    true
+    true
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        between().args(range).arg("--exclude-start").stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    false
+    false
+    true
+    true
+    true
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        between().args(range).arg("--exclude-end").stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    true
+    true
+    true
+    false
+    true
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that the exit status reflects whether any datetime is out of range.
+#[test]
+fn predicate() {
+    assert_cmd_snapshot!(
+        between()
+            .args(["--start", "2025-03-01", "--end", "2025-04-01"])
+            .args(["2025-03-01", "2025-04-01"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        between()
+            .args(["--start", "2025-03-01", "--end", "2025-04-01"])
+            .args(["--exclude-end", "2025-03-01", "2025-04-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    1 datetime(s) not between 2025-03-01T00:00:00-05:00[America/New_York] (inclusive) and 2025-04-01T00:00:00-04:00[America/New_York] (exclusive)
+    ",
+    );
+    assert_cmd_snapshot!(
+        between().args(["--end", "now", "2024-07-21"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    1 datetime(s) not before 2024-07-20T16:30:55-04:00[America/New_York] (inclusive)
+    ",
+    );
+    assert_cmd_snapshot!(
+        between().arg("2024-07-21"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    at least one of --start or --end is required
+    ",
+    );
+}
+
+/// Tests that tags are replaced with the result of each check.
+#[test]
+fn tagged() {
+    let stdin = "\
+2025-03-10T00Z
+2025-05-10T00Z
+";
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines"]).stdin(stdin).pipe(between().args([
+            "--start",
+            "2025-03-01",
+            "--end",
+            "2025-04-01",
+            "--print",
+        ])),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":true,"range":[0,14]}],"data":{"text":"2025-03-10T00Z\n"}}
+    {"tags":[{"value":false,"range":[0,14]}],"data":{"text":"2025-05-10T00Z\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
@@ -0,0 +1,114 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn clamp() -> crate::command::Command {
+    bttf(["time", "clamp"])
+}
+
+/// Tests that datetimes at the bounds are unchanged and that datetimes beyond
+/// the bounds are clamped in their original time zone.
+#[test]
+fn bounds() {
+    let input = "\
+9999-12-30T22:00:00.999999999Z
+9999-12-30T22:00:01Z
+9999-12-31T23:59:59Z
++010000-01-01T00:00:00Z
+9999-12-31T12:00:00[Asia/Tokyo]
+-009999-01-02T01:59:59Z
+-009999-01-01T00:00:00Z
+-010000-06-01T00:00:00-05:00
+2025-03-15T12:00:00Z
+";
+    assert_cmd_snapshot!(
+        clamp().stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    9999-12-30T22:00:00.999999999Z[Etc/Unknown]
+    9999-12-30T22:00:00.999999999Z[Etc/Unknown]
+    9999-12-30T22:00:00.999999999Z[Etc/Unknown]
+    9999-12-30T22:00:00.999999999Z[Etc/Unknown]
+    9999-12-31T07:00:00.999999999+09:00[Asia/Tokyo]
+    -009999-01-02T01:59:59Z[Etc/Unknown]
+    -009999-01-02T01:59:59Z[Etc/Unknown]
+    -009999-01-01T20:59:59-05:00[-05:00]
+    2025-03-15T12:00:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests each of the overflow policies.
+#[test]
+fn on_overflow() {
+    let input = "\
+2025-03-15T12:00:00Z
+9999-12-31T23:59:59Z
+";
+    assert_cmd_snapshot!(
+        clamp().args(["--on-overflow", "skip"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T12:00:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["--on-overflow", "error"]).stdin(input),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    2025-03-15T12:00:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    line 2 of <stdin>: datetime `9999-12-31T23:59:59Z` is outside the range supported by bttf (-009999-01-02T01:59:59Z to 9999-12-30T22:00:00.999999999Z)
+    ",
+    );
+    // Inputs that aren't datetimes are always errors.
+    assert_cmd_snapshot!(
+        clamp().args(["--on-overflow", "skip"]).arg("foo"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid datetime: unrecognized datetime `foo`
+    ",
+    );
+}
+
+/// Tests that out of range tags are clamped or dropped individually.
+#[test]
+fn tagged() {
+    let stdin = r#"{"tags":[{"value":"2025-03-15T12:00:00Z","range":[6,26]},{"value":"9999-12-31T23:59:59Z","range":[31,51]}],"data":{"text":"start=2025-03-15T12:00:00Z end=9999-12-31T23:59:59Z\n"}}
+"#;
+    assert_cmd_snapshot!(
+        clamp().stdin(stdin),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15T12:00:00Z[Etc/Unknown]","range":[6,26]},{"value":"9999-12-30T22:00:00.999999999Z[Etc/Unknown]","range":[31,51]}],"data":{"text":"start=2025-03-15T12:00:00Z end=9999-12-31T23:59:59Z\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        clamp().args(["--on-overflow", "skip"]).stdin(stdin),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-15T12:00:00Z[Etc/Unknown]","range":[6,26]}],"data":{"text":"start=2025-03-15T12:00:00Z end=9999-12-31T23:59:59Z\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
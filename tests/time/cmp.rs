@@ -157,3 +157,74 @@ fn multi_tag() {
     "#,
     );
 }
+
+/// Tests that `--within` treats datetimes close to the reference time as
+/// equal, for both untagged and tagged data.
+#[test]
+fn within() {
+    let dts = [
+        "2025-03-01T11:59:54Z",
+        "2025-03-01T11:59:57Z",
+        "2025-03-01T12:00:05Z",
+    ];
+    assert_cmd_snapshot!(
+        cmp().args(["eq", "--within", "5s", "2025-03-01T12Z"]).args(dts),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-01T11:59:57Z[Etc/Unknown]
+    2025-03-01T12:00:05Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        cmp().args(["ne", "--within", "5s", "2025-03-01T12Z"]).args(dts),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-01T11:59:54Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        cmp().args(["lt", "--within", "5s", "2025-03-01T12Z"]).args(dts),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-01T11:59:54Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        cmp().args(["ge", "--within", "-5s", "2025-03-01T12Z"]).args(dts),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-01T11:59:57Z[Etc/Unknown]
+    2025-03-01T12:00:05Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines"])
+            .stdin("a 2025-03-01T07:00:02-05:00[America/New_York]\nb 2025-03-01T12:00:30Z\n")
+            .pipe(cmp().args(["eq", "--within", "PT5S", "2025-03-01T12Z"]))
+            .pipe(bttf(["untag", "-f", "{data}"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    a 2025-03-01T07:00:02-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
@@ -0,0 +1,213 @@
+use crate::command::assert_cmd_snapshot;
+
+fn cron() -> crate::command::Command {
+    crate::bttf(["time", "cron"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        cron().args(["-c", "5", "*/15 9-17 * * 1-5"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-22T09:00:00-04:00[America/New_York]
+    2024-07-22T09:15:00-04:00[America/New_York]
+    2024-07-22T09:30:00-04:00[America/New_York]
+    2024-07-22T09:45:00-04:00[America/New_York]
+    2024-07-22T10:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().args(["-c", "3", "--start", "2025-01-01", "5/20 * * JAN-mar *"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-01-01T00:05:00-05:00[America/New_York]
+    2025-01-01T00:25:00-05:00[America/New_York]
+    2025-01-01T00:45:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn shorthands() {
+    let start = "2025-03-15T12:00[Asia/Tokyo]";
+    assert_cmd_snapshot!(
+        cron().args(["-c", "2", "--start", start, "@daily"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-16T00:00:00+09:00[Asia/Tokyo]
+    2025-03-17T00:00:00+09:00[Asia/Tokyo]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().args(["-c", "2", "--start", start, "@weekly"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-16T00:00:00+09:00[Asia/Tokyo]
+    2025-03-23T00:00:00+09:00[Asia/Tokyo]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().args(["-c", "2", "--start", start, "@annually"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2026-01-01T00:00:00+09:00[Asia/Tokyo]
+    2027-01-01T00:00:00+09:00[Asia/Tokyo]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that when both the day of the month and the day of the week are
+/// restricted, a day matching either one fires.
+#[test]
+fn day_or_weekday() {
+    assert_cmd_snapshot!(
+        cron().args(["-c", "4", "--start", "2025-03-01", "0 12 1,15 * fri"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-01T12:00:00-05:00[America/New_York]
+    2025-03-07T12:00:00-05:00[America/New_York]
+    2025-03-14T12:00:00-04:00[America/New_York]
+    2025-03-15T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // With a `*`, the fields are combined with "and" instead.
+    assert_cmd_snapshot!(
+        cron().args(["-c", "2", "--start", "2025-03-01", "0 12 */13 * 7"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-04-27T12:00:00-04:00[America/New_York]
+    2025-06-01T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that fire times in a gap are skipped and fire times in a fold are
+/// printed for each offset.
+#[test]
+fn dst() {
+    assert_cmd_snapshot!(
+        cron().args(["-c", "3", "--start", "2025-03-08", "30 2 * * *"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-08T02:30:00-05:00[America/New_York]
+    2025-03-10T02:30:00-04:00[America/New_York]
+    2025-03-11T02:30:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().args(["-c", "3", "--start", "2025-11-01", "30 1 * * *"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-11-01T01:30:00-04:00[America/New_York]
+    2025-11-02T01:30:00-04:00[America/New_York]
+    2025-11-02T01:30:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        cron().arg("61 * * * *"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid minute field `61` in cron expression: value `61` is not in the range 0-59
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().arg("* * *"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid cron expression `* * *`: expected 5 fields, but found 3
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().arg("0 0 5-1 * *"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid day of month field `5-1` in cron expression: range `5-1` has a start greater than its end
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().arg("*/0 * * * *"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid minute field `*/0` in cron expression: invalid step `0`
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().arg("0 0 30 2 *"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    cron expression never fires, since none of its days of the month occur in any of its months
+    ",
+    );
+    assert_cmd_snapshot!(
+        cron().arg("@reboot"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    unsupported cron shorthand `@reboot` (supported shorthands are @yearly, @annually, @monthly, @weekly, @daily, @midnight and @hourly)
+    ",
+    );
+}
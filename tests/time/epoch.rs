@@ -0,0 +1,270 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn epoch() -> crate::command::Command {
+    bttf(["time", "epoch"])
+}
+
+/// Tests converting datetimes to timestamps in each unit, including
+/// datetimes before the Unix epoch.
+#[test]
+fn units() {
+    let input = "\
+2024-07-20T20:30:55.123456789Z
+1970-01-01T00:00:00Z
+1969-12-31T23:59:59.5Z
+1900-01-01T00:00:00Z
+";
+    assert_cmd_snapshot!(
+        epoch().stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721507455
+    0
+    0
+    -2208988800
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["-u", "ms"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721507455123
+    0
+    -500
+    -2208988800000
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["-u", "us"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721507455123456
+    0
+    -500000
+    -2208988800000000
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["-u", "ns"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721507455123456789
+    0
+    -500000000
+    -2208988800000000000
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests converting timestamps in each unit back to datetimes, including
+/// negative timestamps.
+#[test]
+fn parse_units() {
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "1721507455", "0", "-1", "-2208988800"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55Z[Etc/Unknown]
+    1970-01-01T00:00:00Z[Etc/Unknown]
+    1969-12-31T23:59:59Z[Etc/Unknown]
+    1900-01-01T00:00:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "-u", "ms", "1721507455123", "-500"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55.123Z[Etc/Unknown]
+    1969-12-31T23:59:59.5Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "-u", "us", "1721507455123456", "-500"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55.123456Z[Etc/Unknown]
+    1969-12-31T23:59:59.9995Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "-u", "ns", "1721507455123456789", "-500"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55.123456789Z[Etc/Unknown]
+    1969-12-31T23:59:59.9999995Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that converting to a timestamp and back is lossless.
+#[test]
+fn roundtrip() {
+    assert_cmd_snapshot!(
+        epoch()
+            .args(["-u", "ns", "2024-07-20T20:30:55.123456789Z"])
+            .pipe(epoch().args(["--parse", "-u", "ns"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55.123456789Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `auto` guesses the unit from the magnitude of each timestamp.
+#[test]
+fn parse_auto() {
+    let input = "\
+1721507455
+1721507455123
+1721507455123456
+1721507455123456789
+-1721507455
+-1721507455123
+-1721507455123456
+-1721507455123456789
+";
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "-u", "auto"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55Z[Etc/Unknown]
+    2024-07-20T20:30:55.123Z[Etc/Unknown]
+    2024-07-20T20:30:55.123456Z[Etc/Unknown]
+    2024-07-20T20:30:55.123456789Z[Etc/Unknown]
+    1915-06-14T03:29:05Z[Etc/Unknown]
+    1915-06-14T03:29:04.877Z[Etc/Unknown]
+    1915-06-14T03:29:04.876544Z[Etc/Unknown]
+    1915-06-14T03:29:04.876543211Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that tagged data is passed through in both directions.
+#[test]
+fn tagged() {
+    let input =
+        r#"{"tags":[{"value":"2024-07-20T20:30:55Z"}],"data":{"text":"foo"}}"#;
+    assert_cmd_snapshot!(
+        epoch().args(["-u", "ms"]).stdin(input),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"1721507455000"}],"data":{"text":"foo"}}
+
+    ----- stderr -----
+    "#,
+    );
+    let input =
+        r#"{"tags":[{"value":"1721507455000"}],"data":{"text":"foo"}}"#;
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "-u", "ms"]).stdin(input),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2024-07-20T20:30:55Z[Etc/Unknown]"}],"data":{"text":"foo"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "abc"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid epoch timestamp `abc`: expected an integer
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "1.5"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid epoch timestamp `1.5`: expected an integer
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["--parse", "999999999999999"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid epoch timestamp `999999999999999`: parameter 'Unix timestamp seconds' is not in the required range of -377705023201..=253402207200
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["-u", "auto", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -u/--unit auto can only be used with --parse
+    ",
+    );
+    assert_cmd_snapshot!(
+        epoch().args(["-u", "m", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -u/--unit: unknown epoch unit `m`, expected one of `s`, `ms`, `us` or `ns`
+    ",
+    );
+}
@@ -280,7 +280,7 @@ fn positional_or_stdin() {
     success: true
     exit_code: 0
     ----- stdout -----
-    Sat, 15 Mar 2025 12:00:00 +0000
+    Sat, 15 Mar 2025 12:00:00 -0000
     Sun, 15 Mar 2026 12:00:00 -0400
 
     ----- stderr -----
@@ -293,7 +293,7 @@ fn positional_or_stdin() {
     success: true
     exit_code: 0
     ----- stdout -----
-    Sat, 15 Mar 2025 12:00:00 +0000
+    Sat, 15 Mar 2025 12:00:00 -0000
     Sun, 15 Mar 2026 12:00:00 -0400
 
     ----- stderr -----
@@ -349,6 +349,36 @@ fn rfc2822() {
     );
 }
 
+/// Test that a datetime with an unknown time zone uses `-0000` in RFC 2822,
+/// and that it round trips.
+#[test]
+fn rfc2822_unknown_offset() {
+    assert_cmd_snapshot!(
+        fmt().args(["--rfc2822", "2024-07-20T20:30:55Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Sat, 20 Jul 2024 20:30:55 -0000
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--rfc2822", "2024-07-20T20:30:55Z"])
+            .pipe(fmt().arg("--rfc3339")),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55Z
+
+    ----- stderr -----
+    ",
+    );
+}
+
 /// Test that RFC 9110 works.
 #[test]
 fn rfc9110() {
@@ -365,6 +395,90 @@ fn rfc9110() {
     );
 }
 
+/// Test the convenience flags for common formats.
+#[test]
+fn convenience_flags() {
+    let datetimes = [
+        "2025-03-15T10:23:00Z",
+        "2025-03-15T10:23:00+00:00[UTC]",
+        "2025-03-15T10:23:00-04:00[America/New_York]",
+        "2025-03-15T10:23:00+05:30[Asia/Kolkata]",
+    ];
+    assert_cmd_snapshot!(
+        fmt().arg("--rfc2822").args(datetimes),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Sat, 15 Mar 2025 10:23:00 -0000
+    Sat, 15 Mar 2025 10:23:00 +0000
+    Sat, 15 Mar 2025 10:23:00 -0400
+    Sat, 15 Mar 2025 10:23:00 +0530
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().arg("--rfc3339").args(datetimes),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:23:00Z
+    2025-03-15T10:23:00+00:00
+    2025-03-15T10:23:00-04:00
+    2025-03-15T10:23:00+05:30
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().arg("--http-date").args(datetimes),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Sat, 15 Mar 2025 10:23:00 GMT
+    Sat, 15 Mar 2025 10:23:00 GMT
+    Sat, 15 Mar 2025 14:23:00 GMT
+    Sat, 15 Mar 2025 04:53:00 GMT
+
+    ----- stderr -----
+    ",
+    );
+    // HTTP dates are always in GMT, even when converting to another time
+    // zone first.
+    assert_cmd_snapshot!(
+        fmt().args(["--http-date", "--tz", "Asia/Tokyo"]).args(datetimes),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Sat, 15 Mar 2025 10:23:00 GMT
+    Sat, 15 Mar 2025 10:23:00 GMT
+    Sat, 15 Mar 2025 14:23:00 GMT
+    Sat, 15 Mar 2025 04:53:00 GMT
+
+    ----- stderr -----
+    ",
+    );
+    // The last format given wins.
+    assert_cmd_snapshot!(
+        fmt().args(["--http-date", "-f", "%H:%M", "--rfc3339"]).args(datetimes),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:23:00Z
+    2025-03-15T10:23:00+00:00
+    2025-03-15T10:23:00-04:00
+    2025-03-15T10:23:00+05:30
+
+    ----- stderr -----
+    ",
+    );
+}
+
 /// Test that strftime works.
 #[test]
 fn strftime() {
@@ -380,3 +494,723 @@ fn strftime() {
     ",
     );
 }
+
+/// Test that `--tz` converts to the given time zone before formatting.
+/// Tests that `%Q` prints the IANA time zone identifier when there is one,
+/// and falls back to the offset otherwise.
+#[test]
+fn iana_identifier() {
+    let input = "\
+2025-03-15T10:23[America/New_York]
+2025-03-15T10:23-04:00
+2025-03-15T10:23+05:30[+05:30]
+2025-03-15T10:23Z
+";
+    assert_cmd_snapshot!(
+        fmt().args(["-f", "%Q %:Q %Z"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/New_York America/New_York EDT
+    -0400 -04:00 -04
+    +0530 +05:30 +05:30
+    +0000 +00:00 UTC
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn tz() {
+    assert_cmd_snapshot!(
+        fmt().args([
+            "--tz",
+            "Asia/Tokyo",
+            "-f",
+            "%Y-%m-%d %H:%M %Z",
+            "2024-07-20T16:30:55-04:00[America/New_York]",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-21 05:30 JST
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args([
+            "--tz",
+            "Europe/London",
+            "-f",
+            "%Y-%m-%d %H:%M %Z",
+            "2024-07-20T16:30:55-04:00[America/New_York]",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20 21:30 BST
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--utc", "-f", "rfc9557", "now"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--utc", "--tz", "Asia/Tokyo", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --tz and --utc cannot be used together
+    ",
+    );
+}
+
+/// Tests that `--pad` changes the default padding of numeric directives.
+#[test]
+fn pad() {
+    let dt = "2025-03-05T09:05:07-05[America/New_York]";
+    assert_cmd_snapshot!(
+        fmt().args(["--pad", "none", "-f", "%m/%d %H:%M:%S %j", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    3/5 9:5:7 64
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--pad", "space", "-f", "%m/%d %H:%M:%S %j", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+     3/ 5  9: 5: 7  64
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--pad", "zero", "-f", "%e %k %l", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    05 09 09
+
+    ----- stderr -----
+    ",
+    );
+    // Composite directives are expanded and non-numeric directives are
+    // left alone.
+    assert_cmd_snapshot!(
+        fmt().args(["--pad", "none", "-f", "%F %T %b %Z %%d", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-3-5 9:5:7 Mar EST %d
+
+    ----- stderr -----
+    ",
+    );
+    // Explicit flags take precedence.
+    assert_cmd_snapshot!(
+        fmt().args(["--pad", "none", "-f", "%_d %0H %4Y %M", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+     5 09 2025 5
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--pad", "none", "-f", "rfc3339", dt]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --pad can only be used with strftime formats, but got rfc3339
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--pad", "wat", "-f", "%d", dt]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --pad: unknown padding option `wat`, expected one of `none`, `zero` or `space`
+    ",
+    );
+}
+
+/// Tests each `--if-unknown-zone` mode on a zoneless datetime, along with a
+/// datetime in a known time zone that should never be affected.
+#[test]
+fn if_unknown_zone() {
+    let input = "\
+2025-03-05T09:05:07Z
+2025-03-05T09:05:07-05[America/New_York]
+";
+    let format = ["-f", "%H:%M %z %:z %Z"];
+    assert_cmd_snapshot!(
+        fmt().args(format).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    09:05 +0000 +00:00 UTC
+    09:05 -0500 -05:00 EST
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(format).args(["--if-unknown-zone", "error"]).stdin(input),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    line 1 of <stdin>: datetime `2025-03-05T09:05:07Z[Etc/Unknown]` has an unknown time zone, which usually means it was parsed from a timestamp with a `Z` offset
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(format).args(["--if-unknown-zone", "utc"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    09:05 +0000 +00:00 UTC
+    09:05 -0500 -05:00 EST
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--if-unknown-zone", "utc"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T09:05:07+00:00[UTC]
+    2025-03-05T09:05:07-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(format).args(["--if-unknown-zone", "?"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    09:05 ? ? ?
+    09:05 -0500 -05:00 EST
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--if-unknown-zone", "?"]).stdin(input),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --if-unknown-zone can only substitute text into strftime formats, but got rfc9557
+    ",
+    );
+}
+
+#[test]
+fn align() {
+    let stdin = "\
+2025-03-01T09:05-05[America/New_York]
+2025-05-17T12:30-04[America/New_York]
+2025-09-10T23:59Z
+";
+    assert_cmd_snapshot!(
+        fmt().args(["-f", "%A, %B %-d at %-I:%M %p %Z"]).stdin(stdin),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Saturday, March 1 at 9:05 AM EST
+    Saturday, May 17 at 12:30 PM EDT
+    Wednesday, September 10 at 11:59 PM UTC
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--align", "auto", "-f", "%A, %B %-d at %-I:%M %p %Z"])
+            .stdin(stdin),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Saturday , March      1 at  9:05 AM EST
+    Saturday , May       17 at 12:30 PM EDT
+    Wednesday, September 10 at 11:59 PM UTC
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--align", "left", "-f", "[%a] %-d|%-H"]).stdin(stdin),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [Sat] 1 |9
+    [Sat] 17|12
+    [Wed] 10|23
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--align", "right", "-f", "[%a] %-d|%-H"]).stdin(stdin),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [Sat]  1| 9
+    [Sat] 17|12
+    [Wed] 10|23
+
+    ----- stderr -----
+    ",
+    );
+    // Text substituted for an unknown time zone counts toward the width.
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--align", "auto", "-f", "%Z %-d"])
+            .args(["--if-unknown-zone", "unknown"])
+            .stdin(stdin),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    EST      1
+    EDT     17
+    unknown 10
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--align", "auto", "--rfc2822"]).stdin(stdin),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --align can only be used with strftime formats, but got rfc2822
+    ",
+    );
+}
+
+/// Tests that `--calendar` overrides only the calendar system of the locale.
+#[cfg(feature = "locale")]
+#[test]
+fn calendar() {
+    let fmt = || {
+        crate::bttf(["time", "fmt", "-f", "%x | %c"])
+            .env("BTTF_LOCALE", "en-US")
+            .arg("2025-03-15T10:23-04[America/New_York]")
+    };
+    assert_cmd_snapshot!(
+        fmt().args(["--calendar", "japanese"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Mar 15, 7 Reiwa | Sat, Mar 15, 7 Reiwa, 10:23:00 AM EDT
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--calendar", "buddhist"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Mar 15, 2568 BE | Sat, Mar 15, 2568 BE, 10:23:00 AM EDT
+
+    ----- stderr -----
+    ",
+    );
+    // The calendar from the locale itself is overridden too.
+    assert_cmd_snapshot!(
+        fmt()
+            .env("BTTF_LOCALE", "en-US-u-ca-buddhist")
+            .args(["--calendar", "gregorian"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Mar 15, 2025 | Sat, Mar 15, 2025, 10:23:00 AM EDT
+
+    ----- stderr -----
+    ",
+    );
+    // Directives that don't use the locale always use the Gregorian calendar.
+    assert_cmd_snapshot!(
+        fmt().args(["--calendar", "japanese", "-f", "%Y-%m-%d"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--calendar", "mayan"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --calendar: unrecognized calendar `mayan` (supported calendars are: gregorian, buddhist, chinese, coptic, dangi, ethioaa, ethiopic, hebrew, indian, islamic, iso8601, japanese, persian and roc)
+    ",
+    );
+}
+
+/// Tests that only the Gregorian calendar is available without the `locale`
+/// feature.
+#[cfg(not(feature = "locale"))]
+#[test]
+fn calendar_disabled() {
+    let fmt = || crate::bttf(["time", "fmt", "-f", "%Y-%m-%d", "2025-03-15"]);
+    assert_cmd_snapshot!(
+        fmt().args(["--calendar", "gregorian"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--calendar", "japanese"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --calendar: bttf must be compiled with the `locale` feature to format datetimes with the `japanese` calendar
+    ",
+    );
+}
+
+/// Tests each `--on-error` mode with a mix of good and bad inputs.
+#[test]
+fn on_error() {
+    let input = "\
+2025-03-15T10:23-04
+foo
+2025-03-16T10:23-04
+bar
+";
+    assert_cmd_snapshot!(
+        fmt().args(["--on-error", "stop", "-f", "%F"]).stdin(input),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    2025-03-15
+
+    ----- stderr -----
+    line 2 of <stdin>: invalid datetime: unrecognized datetime `foo`
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--on-error", "skip", "-f", "%F"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15
+    2025-03-16
+
+    ----- stderr -----
+    line 2 of <stdin>: invalid datetime: unrecognized datetime `foo`
+    line 4 of <stdin>: invalid datetime: unrecognized datetime `bar`
+    skipped 2 datetime(s) that failed to parse or format
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--on-error", "empty", "-f", "%F"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15
+
+    2025-03-16
+
+
+    ----- stderr -----
+    line 2 of <stdin>: invalid datetime: unrecognized datetime `foo`
+    line 4 of <stdin>: invalid datetime: unrecognized datetime `bar`
+    ",
+    );
+    // Formatting errors are handled too, and not just parse errors.
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--on-error", "empty", "--if-unknown-zone", "error"])
+            .args(["-f", "%F", "2025-03-15T10:23Z", "2025-03-16T10:23-04"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    2025-03-16
+
+    ----- stderr -----
+    datetime `2025-03-15T10:23:00Z[Etc/Unknown]` has an unknown time zone, which usually means it was parsed from a timestamp with a `Z` offset
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--on-error", "skip", "--align", "auto", "-f", "%A %-d"])
+            .stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Saturday 15
+    Sunday   16
+
+    ----- stderr -----
+    line 2 of <stdin>: invalid datetime: unrecognized datetime `foo`
+    line 4 of <stdin>: invalid datetime: unrecognized datetime `bar`
+    skipped 2 datetime(s) that failed to parse or format
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--on-error", "quux", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --on-error: unknown mode `quux`, expected one of `stop`, `skip` or `empty`
+    ",
+    );
+}
+
+#[test]
+fn no_zone_suffix() {
+    let dt = "2025-03-15T10:23:00.5-04[America/New_York]";
+    assert_cmd_snapshot!(
+        fmt().arg(dt),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:23:00.5-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--no-zone-suffix", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:23:00.5-04:00
+
+    ----- stderr -----
+    ",
+    );
+    // The annotation is dropped after converting to another time zone too.
+    assert_cmd_snapshot!(
+        fmt().args(["--no-zone-suffix", "--tz", "Asia/Kolkata", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T19:53:00.5+05:30
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `%Ns` formats a Unix timestamp in nanoseconds.
+#[test]
+fn epoch_nanosecond() {
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["-f", "%Ns"])
+            .arg("2024-07-20T16:30:55.123456789Z")
+            .arg("2024-07-20T16:30:55Z")
+            .arg("1969-12-31T23:59:59.5Z"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721493055123456789
+    1721493055000000000
+    -500000000
+
+    ----- stderr -----
+    ",
+    );
+    // `%%Ns` is a literal `%` followed by `Ns`.
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["-f", "%s %Ns %%Ns"])
+            .arg("2024-07-20T16:30:55.123456789Z"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721493055 1721493055123456789 %Ns
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `%3s`, `%6s` and `%9s` format Unix timestamps in milliseconds,
+/// microseconds and nanoseconds, truncating toward zero like `%s`.
+#[test]
+fn epoch_units() {
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["-f", "%s %3s %6s %9s"])
+            .arg("2024-07-20T16:20:55.123456789Z")
+            .arg("1969-12-31T23:59:59.9999995Z"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721492455 1721492455123 1721492455123456 1721492455123456789
+    0 0 0 -500
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests the ISO 8601 week date directives, including dates at the end of
+/// December in week 1 of the next year and dates at the start of January in
+/// the last week of the previous year.
+#[test]
+fn week_date_directives() {
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["-f", "%F %G-W%V-%u"])
+            .args(["2024-07-20", "2024-12-30", "2025-01-01"])
+            .args(["2021-01-01", "2021-01-03", "2021-01-04"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20 2024-W29-6
+    2024-12-30 2025-W01-1
+    2025-01-01 2025-W01-3
+    2021-01-01 2020-W53-5
+    2021-01-03 2020-W53-7
+    2021-01-04 2021-W01-1
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `--output json` prints an object per datetime, including the
+/// formatted datetime, and that it rejects tagged data.
+#[test]
+fn output_json() {
+    assert_cmd_snapshot!(
+        fmt()
+            .args(["--output", "json", "-f", "%A"])
+            .args(["2024-07-20T16:30:55.5-04[America/New_York]", "1970-01-01T00Z"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"formatted":"Saturday","timestamp":"2024-07-20T20:30:55.5Z","zoned":"2024-07-20T16:30:55.5-04:00[America/New_York]","offset":"-04:00","epoch_seconds":1721507455,"epoch_nanoseconds":1721507455500000000}
+    {"formatted":"Thursday","timestamp":"1970-01-01T00:00:00Z","zoned":"1970-01-01T00:00:00Z[Etc/Unknown]","offset":"+00:00","epoch_seconds":0,"epoch_nanoseconds":0}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        crate::bttf(["tag", "lines"])
+            .stdin("x 2024-07-20T20:30:55Z y\n")
+            .pipe(fmt().args(["--output", "json", "--tz", "Asia/Tokyo"])),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    line 1 of <stdin>: --output json cannot be used with tagged data (use `bttf untag --output json` to print tagged data as JSON)
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--output", "json", "--align", "auto", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --align cannot be used with --output json
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt().args(["--output", "xml", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --output: unrecognized output mode `xml`, expected `text` or `json`
+    ",
+    );
+}
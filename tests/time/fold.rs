@@ -0,0 +1,95 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn fold() -> crate::command::Command {
+    bttf(["time", "fold"])
+}
+
+/// Tests that a fold has two instants and other wall clock times have one.
+#[test]
+fn basic() {
+    let input = "\
+2024-11-03T00:30
+2024-11-03T01:00
+2024-11-03T01:59:59.999999999
+2024-11-03T02:00
+2024-11-03
+";
+    assert_cmd_snapshot!(
+        fold().args(["--tz", "America/New_York"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-11-03T00:30:00-04:00[America/New_York]
+    2024-11-03T01:00:00-04:00[America/New_York]
+    2024-11-03T01:00:00-05:00[America/New_York]
+    2024-11-03T01:59:59.999999999-04:00[America/New_York]
+    2024-11-03T01:59:59.999999999-05:00[America/New_York]
+    2024-11-03T02:00:00-05:00[America/New_York]
+    2024-11-03T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // Lord Howe Island has a 30 minute fold.
+    assert_cmd_snapshot!(
+        fold().args(["--tz", "Australia/Lord_Howe", "2024-04-07T01:45"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-04-07T01:45:00+11:00[Australia/Lord_Howe]
+    2024-04-07T01:45:00+10:30[Australia/Lord_Howe]
+
+    ----- stderr -----
+    ",
+    );
+    // Fixed offsets never have folds or gaps.
+    assert_cmd_snapshot!(
+        fold().args(["--tz", "+05:30", "2024-11-03T01:30"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-11-03T01:30:00+05:30[+05:30]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests the handling of wall clock times that fall in a gap.
+#[test]
+fn gap() {
+    let input = "\
+2024-03-10T01:59
+2024-03-10T02:30
+2024-03-10T03:00
+";
+    assert_cmd_snapshot!(
+        fold().args(["--tz", "America/New_York"]).stdin(input),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    2024-03-10T01:59:00-05:00[America/New_York]
+
+    ----- stderr -----
+    line 2 of <stdin>: wall clock time `2024-03-10T02:30:00` does not exist in time zone `America/New_York` (it falls in a gap between offsets -05 and -04)
+    ",
+    );
+    assert_cmd_snapshot!(
+        fold()
+            .args(["--tz", "America/New_York", "--on-gap", "skip"])
+            .stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-03-10T01:59:00-05:00[America/New_York]
+    2024-03-10T03:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
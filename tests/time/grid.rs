@@ -0,0 +1,183 @@
+use crate::command::assert_cmd_snapshot;
+
+fn grid() -> crate::command::Command {
+    crate::bttf(["time", "grid"])
+}
+
+/// Tests that a range that isn't aligned to the grid is still covered by
+/// buckets aligned to the Unix epoch, including a partial trailing bucket.
+#[test]
+fn unaligned() {
+    assert_cmd_snapshot!(
+        grid().args([
+            "--start",
+            "2025-03-05T12:07:13Z",
+            "--end",
+            "2025-03-05T12:52Z",
+            "-b",
+            "15m",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:00:00Z[Etc/Unknown]
+    2025-03-05T12:15:00Z[Etc/Unknown]
+    2025-03-05T12:30:00Z[Etc/Unknown]
+    2025-03-05T12:45:00Z[Etc/Unknown]
+    2025-03-05T13:00:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    // When the end is on the grid, there's no trailing bucket after it.
+    assert_cmd_snapshot!(
+        grid().args([
+            "--start",
+            "2025-03-05T12:07:13Z",
+            "--end",
+            "2025-03-05T12:45Z",
+            "-b",
+            "15m",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:00:00Z[Etc/Unknown]
+    2025-03-05T12:15:00Z[Etc/Unknown]
+    2025-03-05T12:30:00Z[Etc/Unknown]
+    2025-03-05T12:45:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that buckets are aligned to the origin given.
+#[test]
+fn origin() {
+    assert_cmd_snapshot!(
+        grid().args([
+            "--start",
+            "2025-03-05T12:07Z",
+            "--end",
+            "2025-03-05T12:40Z",
+            "-b",
+            "15m",
+            "--origin",
+            "2025-03-05T00:05Z",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:05:00Z[Etc/Unknown]
+    2025-03-05T12:20:00Z[Etc/Unknown]
+    2025-03-05T12:35:00Z[Etc/Unknown]
+    2025-03-05T12:50:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    // The origin may come after the range and may be a Unix timestamp.
+    assert_cmd_snapshot!(
+        grid().args([
+            "--start",
+            "1970-01-01T00:00Z",
+            "--end",
+            "1970-01-01T00:20Z",
+            "-b",
+            "15m",
+            "--origin",
+            "86520",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1969-12-31T23:47:00Z[Etc/Unknown]
+    1970-01-01T00:02:00Z[Etc/Unknown]
+    1970-01-01T00:17:00Z[Etc/Unknown]
+    1970-01-01T00:32:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn labels() {
+    assert_cmd_snapshot!(
+        grid().args([
+            "--start",
+            "2025-03-05T12:07-05[America/New_York]",
+            "--end",
+            "2025-03-05T12:40-05[America/New_York]",
+            "-b",
+            "15m",
+            "--labels",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:00:00-05:00[America/New_York]	2025-03-05T12:15:00-05:00[America/New_York]
+    2025-03-05T12:15:00-05:00[America/New_York]	2025-03-05T12:30:00-05:00[America/New_York]
+    2025-03-05T12:30:00-05:00[America/New_York]	2025-03-05T12:45:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        grid().args(["--start", "2025-03-05T12:07Z", "-b", "15m"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --end is required
+    ",
+    );
+    assert_cmd_snapshot!(
+        grid().args([
+            "--start",
+            "2025-03-05T12:07Z",
+            "--end",
+            "2025-03-05T12:07Z",
+            "-b",
+            "15m",
+        ]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    end of range `2025-03-05T12:07:00Z[Etc/Unknown]` must be after its start `2025-03-05T12:07:00Z[Etc/Unknown]`
+    ",
+    );
+    assert_cmd_snapshot!(
+        grid().args([
+            "--start",
+            "2025-03-05T12:07Z",
+            "--end",
+            "2025-03-05T13:07Z",
+            "-b",
+            "1mo",
+        ]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    bucket span `1mo` must not contain units of months or years
+    ",
+    );
+}
@@ -1,4 +1,4 @@
-use crate::command::assert_cmd_snapshot;
+use crate::{bttf, command::assert_cmd_snapshot};
 
 fn inn() -> crate::command::Command {
     crate::bttf(["time", "in"])
@@ -153,3 +153,82 @@ fn invalid_datetime() {
     ",
     );
 }
+
+/// Tests that `--output json` prints an object per converted datetime.
+#[test]
+fn output_json() {
+    assert_cmd_snapshot!(
+        inn().args(["--output", "json", "2024-07-20T20:30:55Z"])
+            .args(["Asia/Kolkata", "America/St_Johns"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"timestamp":"2024-07-20T20:30:55Z","zoned":"2024-07-21T02:00:55+05:30[Asia/Kolkata]","offset":"+05:30","epoch_seconds":1721507455,"epoch_nanoseconds":1721507455000000000}
+    {"timestamp":"2024-07-20T20:30:55Z","zoned":"2024-07-20T18:00:55-02:30[America/St_Johns]","offset":"-02:30","epoch_seconds":1721507455,"epoch_nanoseconds":1721507455000000000}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+/// Tests that `-z/--zone` converts each datetime into every time zone given,
+/// in order, for positional arguments and tagged data.
+#[test]
+fn zones() {
+    assert_cmd_snapshot!(
+        inn()
+            .args(["-z", "America/New_York", "-z", "Asia/Tokyo"])
+            .args(["2025-03-01T12Z", "2025-06-01T12Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-01T07:00:00-05:00[America/New_York]
+    2025-03-01T21:00:00+09:00[Asia/Tokyo]
+    2025-06-01T08:00:00-04:00[America/New_York]
+    2025-06-01T21:00:00+09:00[Asia/Tokyo]
+
+    ----- stderr -----
+    ",
+    );
+    // The first positional argument is a datetime when `-z/--zone` is given,
+    // even when it comes before the flag.
+    assert_cmd_snapshot!(
+        inn().args(["2025-03-01T12Z", "-z", "Europe/London", "-z", "UTC"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-01T12:00:00+00:00[Europe/London]
+    2025-03-01T12:00:00+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines"])
+            .stdin("deploy at 2025-03-01T12Z\n")
+            .pipe(inn().args(["-z", "Asia/Tokyo", "-z", "Europe/London"])),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"2025-03-01T21:00:00+09:00[Asia/Tokyo]","range":[10,24]}],"data":{"text":"deploy at 2025-03-01T12Z\n"}}
+    {"tags":[{"value":"2025-03-01T12:00:00+00:00[Europe/London]","range":[10,24]}],"data":{"text":"deploy at 2025-03-01T12Z\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        inn().args(["-z", "Asia/Tokyo", "UTC", "2025-03-01T12Z"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    when -z/--zone is given, all positional arguments must be datetimes, but got time zone `UTC`
+    ",
+    );
+}
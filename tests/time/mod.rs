@@ -1,10 +1,25 @@
 mod add;
+mod align_zones;
+mod between;
+mod clamp;
 mod cmp;
+mod cron;
+mod epoch;
 mod fmt;
+mod fold;
+mod grid;
 mod inn;
+mod monotonic_check;
+mod nearest;
 mod of;
+mod overlap;
 mod parse;
 mod relative;
 mod round;
+mod rrule;
 mod seq;
+mod skew;
 mod sort;
+mod sort_key;
+mod sun;
+mod weeknum_range;
@@ -0,0 +1,131 @@
+use crate::command::assert_cmd_snapshot;
+
+fn check() -> crate::command::Command {
+    crate::bttf(["time", "monotonic-check"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        check().args(["2025-03-15T10:00Z", "2025-03-15T10:00Z", "2025-03-16"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        check().args(["2025-03-15T10:00Z", "2025-03-15T09:00Z", "2025-03-15"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    argument 2 `2025-03-15T09:00Z` is before argument 1 `2025-03-15T10:00Z`
+    ",
+    );
+}
+
+/// Tests that datetimes are compared by instant, even when they're in
+/// different time zones.
+#[test]
+fn across_zones() {
+    // The wall clock time goes backwards from Tokyo to New York, but the
+    // instant goes forward (14:00 UTC to 14:30 UTC). Then the wall clock time
+    // goes forwards from New York to London, but the instant goes backward
+    // (14:30 UTC to 14:15 UTC).
+    assert_cmd_snapshot!(
+        check().stdin(
+            "2025-03-15T23:00+09[Asia/Tokyo]\n\
+             2025-03-15T10:30-04[America/New_York]\n\
+             2025-03-15T14:15+00[Europe/London]\n",
+        ),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    line 3 `2025-03-15T14:15+00[Europe/London]` is before line 2 `2025-03-15T10:30-04[America/New_York]`
+    ",
+    );
+}
+
+#[test]
+fn strict() {
+    let input = "2025-03-15T10:00Z\n2025-03-15T06:00-04\n";
+    assert_cmd_snapshot!(
+        check().stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        check().arg("--strict").stdin(input),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    line 2 `2025-03-15T06:00-04` is equal to line 1 `2025-03-15T10:00Z`
+    ",
+    );
+    assert_cmd_snapshot!(
+        check().args(["--strict", "--allow-equal"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn fix() {
+    assert_cmd_snapshot!(
+        check().arg("--fix").stdin(
+            "2025-03-15T10:00-04[America/New_York]\n\
+             2025-03-15T13:00+00[Europe/London]\n\
+             2025-03-15T11:00-04[America/New_York]\n\
+             2025-03-15T11:00-04[America/New_York]\n",
+        ),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:00-04[America/New_York]
+    2025-03-15T11:00-04[America/New_York]
+    2025-03-15T11:00-04[America/New_York]
+
+    ----- stderr -----
+    dropped 1 out-of-order datetime(s)
+    ",
+    );
+    assert_cmd_snapshot!(
+        check().args(["--fix", "--strict"]).stdin(
+            "2025-03-15T10:00-04[America/New_York]\n\
+             2025-03-15T14:00+00[Europe/London]\n\
+             2025-03-15T11:00-04[America/New_York]\n",
+        ),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:00-04[America/New_York]
+    2025-03-15T11:00-04[America/New_York]
+
+    ----- stderr -----
+    dropped 1 out-of-order datetime(s)
+    ",
+    );
+}
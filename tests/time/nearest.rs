@@ -0,0 +1,167 @@
+use crate::command::assert_cmd_snapshot;
+
+fn nearest() -> crate::command::Command {
+    crate::bttf(["time", "nearest"])
+}
+
+/// Tests each mode on a 5 minute grid anchored at the Unix epoch.
+#[test]
+fn modes() {
+    let input = "\
+2025-03-05T12:00:00Z
+2025-03-05T12:02:29Z
+2025-03-05T12:02:30Z
+2025-03-05T12:04:59-05[America/New_York]
+";
+    assert_cmd_snapshot!(
+        nearest().args(["-g", "5m"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:00:00Z[Etc/Unknown]
+    2025-03-05T12:00:00Z[Etc/Unknown]
+    2025-03-05T12:05:00Z[Etc/Unknown]
+    2025-03-05T12:05:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        nearest().args(["-g", "5m", "-m", "floor"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:00:00Z[Etc/Unknown]
+    2025-03-05T12:00:00Z[Etc/Unknown]
+    2025-03-05T12:00:00Z[Etc/Unknown]
+    2025-03-05T12:00:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        nearest().args(["-g", "5m", "-m", "ceil"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:00:00Z[Etc/Unknown]
+    2025-03-05T12:05:00Z[Etc/Unknown]
+    2025-03-05T12:05:00Z[Etc/Unknown]
+    2025-03-05T12:05:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that the grid is anchored at the origin given.
+#[test]
+fn epoch() {
+    let input = "2025-03-05T12:00:00Z\n2025-03-05T12:03:00Z\n";
+    assert_cmd_snapshot!(
+        nearest()
+            .args(["-g", "5m", "-m", "floor", "--epoch", "2025-03-05T00:02Z"])
+            .stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T11:57:00Z[Etc/Unknown]
+    2025-03-05T12:02:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    // An origin after the datetimes being snapped works too.
+    assert_cmd_snapshot!(
+        nearest()
+            .args(["-g", "5m", "-m", "floor", "--epoch", "2030-01-01T00:02Z"])
+            .stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T11:57:00Z[Etc/Unknown]
+    2025-03-05T12:02:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    // Unix seconds are accepted.
+    assert_cmd_snapshot!(
+        nearest().args(["-g", "5m", "--epoch", "120"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:02:00Z[Etc/Unknown]
+    2025-03-05T12:02:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    // Datetimes before the origin and the Unix epoch are handled correctly.
+    assert_cmd_snapshot!(
+        nearest().args(["-g", "1d", "-m", "floor", "1969-12-31T23:59:59Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1969-12-31T00:00:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that invalid grids are rejected.
+#[test]
+fn invalid_grid() {
+    assert_cmd_snapshot!(
+        nearest().arg("2025-03-05T12:00Z"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -g/--grid is required
+    ",
+    );
+    assert_cmd_snapshot!(
+        nearest().args(["-g", "1mo", "2025-03-05T12:00Z"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    grid span `1mo` must not contain units of months or years
+    ",
+    );
+    assert_cmd_snapshot!(
+        nearest().args(["-g", "-5m", "2025-03-05T12:00Z"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    grid span `5m ago` must be positive
+    ",
+    );
+    assert_cmd_snapshot!(
+        nearest().args(["-g", "5m", "-m", "round", "2025-03-05T12:00Z"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -m/--mode: unknown snapping mode `round`
+    ",
+    );
+}
@@ -253,3 +253,163 @@ fn various_end() {
     ",
     );
 }
+
+/// Tests that plain weeks start on the weekday given by `--week-start`, and
+/// on Monday by default.
+#[test]
+fn week_start() {
+    // 2024-07-20 is a Saturday.
+    let dt = "2024-07-20T16:30:55-04[America/New_York]";
+    assert_cmd_snapshot!(
+        start().args(["week", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-15T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        end().args(["week", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-21T23:59:59.999999999-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        start().args(["week", "--week-start", "sunday", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-14T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        end().args(["week", "--week-start", "sunday", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T23:59:59.999999999-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // A week starting on the same weekday as the datetime starts that day.
+    assert_cmd_snapshot!(
+        start().args(["week", "--week-start", "SA", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        end().args(["week", "--week-start", "sat", dt]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-26T23:59:59.999999999-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        start().args(["week", "--week-start", "someday", dt]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --week-start: unrecognized weekday: `someday`
+    ",
+    );
+}
+
+/// Tests the start and end of quarters, including quarters of a fiscal year
+/// that doesn't start in January.
+#[test]
+fn quarter() {
+    let dts = [
+        "2024-01-01T00:00-05[America/New_York]",
+        "2024-05-15T12:00+01[Europe/London]",
+        "2024-12-31T23:59+11[Australia/Sydney]",
+    ];
+    assert_cmd_snapshot!(
+        start().arg("quarter").args(dts),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-01-01T00:00:00-05:00[America/New_York]
+    2024-04-01T00:00:00+01:00[Europe/London]
+    2024-10-01T00:00:00+10:00[Australia/Sydney]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        end().arg("quarter").args(dts),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-03-31T23:59:59.999999999-04:00[America/New_York]
+    2024-06-30T23:59:59.999999999+01:00[Europe/London]
+    2024-12-31T23:59:59.999999999+11:00[Australia/Sydney]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        start().args(["q", "--fiscal-start", "february"]).args(dts),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2023-11-01T00:00:00-04:00[America/New_York]
+    2024-05-01T00:00:00+01:00[Europe/London]
+    2024-11-01T00:00:00+11:00[Australia/Sydney]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        end().args(["q", "--fiscal-start", "2"]).args(dts),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-01-31T23:59:59.999999999-05:00[America/New_York]
+    2024-07-31T23:59:59.999999999+01:00[Europe/London]
+    2025-01-31T23:59:59.999999999+11:00[Australia/Sydney]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        start().args(["quarter", "--fiscal-start", "13", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --fiscal-start: parsed `13` as an integer month, but it's not in the required range of `1..=12`
+    ",
+    );
+}
@@ -0,0 +1,176 @@
+use crate::command::assert_cmd_snapshot;
+
+fn overlap() -> crate::command::Command {
+    crate::bttf(["time", "overlap"])
+}
+
+const INTERVALS: &str = "\
+2025-03-15T09:00Z\t2025-03-15T12:00Z
+2025-03-15T10:00Z\t2025-03-15T11:00Z
+2025-03-15T11:00Z\t2025-03-15T11:30Z
+2025-03-15T13:00Z\t2025-03-15T14:00Z
+";
+
+#[test]
+fn adjacent() {
+    assert_cmd_snapshot!(
+        overlap().stdin(INTERVALS),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T09:00Z	2025-03-15T12:00Z	2025-03-15T10:00Z	2025-03-15T11:00Z
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        overlap().arg("--adjacent").stdin(INTERVALS),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T09:00Z	2025-03-15T12:00Z	2025-03-15T10:00Z	2025-03-15T11:00Z
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn all_pairs() {
+    assert_cmd_snapshot!(
+        overlap().arg("--all-pairs").stdin(INTERVALS),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T09:00Z	2025-03-15T12:00Z	2025-03-15T10:00Z	2025-03-15T11:00Z
+    2025-03-15T09:00Z	2025-03-15T12:00Z	2025-03-15T11:00Z	2025-03-15T11:30Z
+
+    ----- stderr -----
+    ",
+    );
+    // Input order doesn't matter, but pairs are still reported in input
+    // order.
+    let reversed: String =
+        INTERVALS.lines().rev().map(|line| format!("{line}\n")).collect();
+    assert_cmd_snapshot!(
+        overlap().arg("--all-pairs").stdin(reversed),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T11:00Z	2025-03-15T11:30Z	2025-03-15T09:00Z	2025-03-15T12:00Z
+    2025-03-15T10:00Z	2025-03-15T11:00Z	2025-03-15T09:00Z	2025-03-15T12:00Z
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn touching() {
+    let touching = "\
+2025-03-15T09:00Z\t2025-03-15T10:00Z
+2025-03-15T06:00-04\t2025-03-15T11:00Z
+";
+    assert_cmd_snapshot!(
+        overlap().stdin(touching),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        overlap().arg("--closed").stdin(touching),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T09:00Z	2025-03-15T10:00Z	2025-03-15T06:00-04	2025-03-15T11:00Z
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        overlap().args(["--all-pairs", "--closed"]).stdin(touching),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T09:00Z	2025-03-15T10:00Z	2025-03-15T06:00-04	2025-03-15T11:00Z
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn quiet() {
+    assert_cmd_snapshot!(
+        overlap().arg("--quiet").stdin(INTERVALS),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    found 1 pair(s) of overlapping intervals
+    ",
+    );
+    assert_cmd_snapshot!(
+        overlap().args(["--all-pairs", "-q"]).stdin(INTERVALS),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    found 2 pair(s) of overlapping intervals
+    ",
+    );
+    let touching = "\
+2025-03-15T09:00Z\t2025-03-15T10:00Z
+2025-03-15T10:00Z\t2025-03-15T11:00Z
+";
+    assert_cmd_snapshot!(
+        overlap().arg("--quiet").stdin(touching),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn invalid() {
+    assert_cmd_snapshot!(
+        overlap().stdin("2025-03-15T10:00Z\t2025-03-15T09:00Z\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    <stdin>: line 1: interval end `2025-03-15T09:00:00Z[Etc/Unknown]` precedes its start `2025-03-15T10:00:00Z[Etc/Unknown]`
+    ",
+    );
+    assert_cmd_snapshot!(
+        overlap().stdin("2025-03-15T10:00Z 2025-03-15T09:00Z\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    <stdin>: line 1: expected two tab separated datetimes, but got `2025-03-15T10:00Z 2025-03-15T09:00Z`
+    ",
+    );
+}
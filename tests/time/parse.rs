@@ -119,6 +119,67 @@ fn strptime() {
 }
 
 /// Test that flexible datetime parsing works.
+/// Tests that a trailing time zone annotation sets the time zone of an
+/// epoch timestamp, which otherwise has no time zone.
+#[test]
+fn strptime_annotation() {
+    let input = "\
+1721507455
+1721507455[Europe/London]
+1721507455[Asia/Tokyo]
+1721507455[!+05:30]
+";
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%s"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55Z[Etc/Unknown]
+    2024-07-20T21:30:55+01:00[Europe/London]
+    2024-07-21T05:30:55+09:00[Asia/Tokyo]
+    2024-07-21T02:00:55+05:30[+05:30]
+
+    ----- stderr -----
+    ",
+    );
+    // Civil datetimes are interpreted in the annotated time zone, rather than
+    // in the system time zone.
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%Y-%m-%d %H:%M", "2024-07-20 12:00[Asia/Tokyo]"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T12:00:00+09:00[Asia/Tokyo]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%s", "1721507455[Mars/Olympus]"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `1721507455[Mars/Olympus]` for format `%s` failed: parsed apparent IANA time zone identifier, but the tzdb lookup failed: failed to find time zone `Mars/Olympus` in time zone database
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%s", "1721507455[u-ca=iso8601]"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `1721507455[u-ca=iso8601]` for format `%s` failed: strptime expects to consume the entire input, but `[u-ca=iso8601]` remains unparsed
+    ",
+    );
+}
+
 #[test]
 fn flexible() {
     assert_cmd_snapshot!(
@@ -150,3 +211,994 @@ fn flexible() {
     ",
     );
 }
+
+/// Test that `--show-format` reports the format recognized for each input.
+#[test]
+fn show_format() {
+    assert_cmd_snapshot!(
+        parse().args(["-f", "flexible", "--show-format"]).stdin(
+            "2025-03-15T10:00-04[America/New_York]\n\
+             2025-03-15T10:00-04\n\
+             2025-03-15T10:00Z\n\
+             2025-03-15 10:00\n\
+             2025-03-15\n\
+             Sat, 15 Mar 2025 10:00:00 -0400\n\
+             Sat, 15 Mar 2025 14:00:00 GMT\n\
+             now\n\
+             1 hour ago\n\
+             next sat\n",
+        ),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15T10:00:00-04:00[America/New_York]
+    2025-03-15T10:00:00-04:00[-04:00]
+    2025-03-15T10:00:00Z[Etc/Unknown]
+    2025-03-15T10:00:00-04:00[America/New_York]
+    2025-03-15T00:00:00-04:00[America/New_York]
+    2025-03-15T10:00:00-04:00[-04:00]
+    2025-03-15T14:00:00+00:00[UTC]
+    2024-07-20T16:30:55-04:00[America/New_York]
+    2024-07-20T15:30:55-04:00[America/New_York]
+    2024-07-27T16:30:55-04:00[America/New_York]
+
+    ----- stderr -----
+    2025-03-15T10:00-04[America/New_York]: rfc9557
+    2025-03-15T10:00-04: rfc3339
+    2025-03-15T10:00Z: rfc3339
+    2025-03-15 10:00: local
+    2025-03-15: local
+    Sat, 15 Mar 2025 10:00:00 -0400: rfc2822
+    Sat, 15 Mar 2025 14:00:00 GMT: rfc2822
+    now: relative
+    1 hour ago: relative
+    next sat: relative
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "flexible", "--show-format=column"])
+            .args(["2025-03-15", "-1d", "2025-03-15T10:00Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    local	2025-03-15T00:00:00-04:00[America/New_York]
+    relative	2024-07-19T16:30:55-04:00[America/New_York]
+    rfc3339	2025-03-15T10:00:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%m/%d/%y", "--show-format=column", "03/15/25"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    %m/%d/%y	2025-03-15T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["--show-format=column", "2025-03-15T10:00-04[America/New_York]"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    rfc9557	2025-03-15T10:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "rfc2822", "--show-format=column"])
+            .arg("Sat, 15 Mar 2025 10:00:00 -0400"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    rfc2822	2025-03-15T10:00:00-04:00[-04:00]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["--show-format=nope", "2025-03-15T10:00Z"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    unrecognized --show-format value `nope`, expected `stderr` or `column`
+    ",
+    );
+}
+
+/// Tests that ISO 8601 ordinal dates are recognized by flexible parsing.
+#[test]
+fn ordinal() {
+    let input = "\
+2024-001
+2024-366
+2023-365
+2024-202T10:00Z
+2024-202T10:00+09[Asia/Tokyo]
+2024-03-15
+";
+    assert_cmd_snapshot!(
+        parse().args(["-f", "flexible", "--show-format=column"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ordinal	2024-01-01T00:00:00-05:00[America/New_York]
+    ordinal	2024-12-31T00:00:00-05:00[America/New_York]
+    ordinal	2023-12-31T00:00:00-05:00[America/New_York]
+    ordinal	2024-07-20T10:00:00Z[Etc/Unknown]
+    ordinal	2024-07-20T10:00:00+09:00[Asia/Tokyo]
+    local	2024-03-15T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "flexible", "2023-366"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `2023-366` for format flexible failed: invalid ISO 8601 ordinal date `2023-366`: number of days for `2023` is invalid, must be in range `1..=365`
+    ",
+    );
+}
+
+/// Tests that ISO 8601 week dates are recognized by flexible parsing, and
+/// that weeks always start on Monday.
+#[test]
+fn week_date() {
+    let input = "\
+2024-W29-6
+2020-W53-7
+2025-W01-1
+2024-W29-6T10:00Z
+2024-W29
+";
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "flexible", "--show-format=column", "-i"])
+            .stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    week-date	2024-07-20T00:00:00-04:00[America/New_York]
+    week-date	2021-01-03T00:00:00-05:00[America/New_York]
+    week-date	2024-12-30T00:00:00-05:00[America/New_York]
+    week-date	2024-07-20T10:00:00Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "flexible", "2021-W53-1"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `2021-W53-1` for format flexible failed: invalid ISO 8601 week date `2021-W53-1`: week number `53` (for weeks starting on Monday) is invalid for year `2021`
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "flexible", "2024-W01-8"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `2024-W01-8` for format flexible failed: invalid ISO 8601 week date `2024-W01-8`: parameter 'weekday (Monday 1-indexed)' is not in the required range of 1..=7
+    ",
+    );
+}
+
+/// Tests the `--rfc2822` flag and which time zone names it accepts.
+#[test]
+fn rfc2822_flag() {
+    assert_cmd_snapshot!(
+        parse().args([
+            "--rfc2822",
+            "Sat, 20 Jul 2024 16:30:55 -0400",
+            "Sat, 20 Jul 2024 16:30:55 +0000",
+            "20 Jul 2024 16:30 +0530",
+            "Sat, 20 Jul 2024 16:30:55 GMT",
+            "Sat, 20 Jul 2024 16:30:55 EST",
+            "Sat, 20 Jul 2024 16:30:55 PDT",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55-04:00[-04:00]
+    2024-07-20T16:30:55+00:00[UTC]
+    2024-07-20T16:30:00+05:30[+05:30]
+    2024-07-20T16:30:55+00:00[UTC]
+    2024-07-20T16:30:55-05:00[-05:00]
+    2024-07-20T16:30:55-07:00[-07:00]
+
+    ----- stderr -----
+    ",
+    );
+    // Ambiguous or unrecognized time zone names are rejected instead of
+    // being silently treated as UTC.
+    assert_cmd_snapshot!(
+        parse().args(["--rfc2822", "Sat, 20 Jul 2024 16:30:55 IST"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `Sat, 20 Jul 2024 16:30:55 IST` for format rfc2822 failed: unrecognized or ambiguous RFC 2822 time zone `IST` (the supported time zone names are UT, UTC, GMT, Z, EST, EDT, CST, CDT, MST, MDT, PST, PDT)
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["--rfc2822", "Sat, 20 Jul 2024 16:30:55 A"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `Sat, 20 Jul 2024 16:30:55 A` for format rfc2822 failed: unrecognized or ambiguous RFC 2822 time zone `A` (the supported time zone names are UT, UTC, GMT, Z, EST, EDT, CST, CDT, MST, MDT, PST, PDT)
+    ",
+    );
+    // The same applies when detecting RFC 2822 with flexible parsing.
+    assert_cmd_snapshot!(
+        parse().args(["-f", "flexible", "Sat, 20 Jul 2024 16:30:55 BST"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `Sat, 20 Jul 2024 16:30:55 BST` for format flexible failed: unrecognized or ambiguous RFC 2822 time zone `BST` (the supported time zone names are UT, UTC, GMT, Z, EST, EDT, CST, CDT, MST, MDT, PST, PDT)
+    ",
+    );
+}
+
+/// Tests the obsolete parts of RFC 2822, along with the `-0000` offset.
+#[test]
+fn rfc2822_obsolete() {
+    // Two digit years are interpreted relative to 1950.
+    assert_cmd_snapshot!(
+        parse().args([
+            "--rfc2822",
+            "Sat, 20 Jul 24 16:30:55 -0400",
+            "20 Jul 49 16:30 -0400",
+            "20 Jul 50 16:30 -0400",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55-04:00[-04:00]
+    2049-07-20T16:30:00-04:00[-04:00]
+    1950-07-20T16:30:00-04:00[-04:00]
+
+    ----- stderr -----
+    ",
+    );
+    // `-0000` means the offset is unknown, just like `Z` in RFC 3339.
+    assert_cmd_snapshot!(
+        parse().args(["--rfc2822", "Sat, 20 Jul 2024 20:30:55 -0000"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    // The day of the week must match the date.
+    assert_cmd_snapshot!(
+        parse().args(["--rfc2822", "Fri, 20 Jul 2024 16:30:55 -0400"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `Fri, 20 Jul 2024 16:30:55 -0400` for format rfc2822 failed: failed to parse RFC 2822 datetime into Jiff zoned datetime: found parsed weekday of `Friday`, but parsed datetime has weekday `Saturday`
+    ",
+    );
+}
+
+/// Tests the `--http-date` flag, and that HTTP dates are also recognized by
+/// flexible parsing.
+#[test]
+fn http_date() {
+    assert_cmd_snapshot!(
+        parse().args(["--http-date", "Sat, 20 Jul 2024 20:30:55 GMT"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:30:55+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "flexible", "--show-format=column"])
+            .stdin("Sat, 20 Jul 2024 20:30:55 GMT\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    rfc2822	2024-07-20T20:30:55+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that an offset inconsistent with its time zone is reported with a
+/// warning by default and rejected with `--strict`.
+#[test]
+fn offset_mismatch() {
+    let input = "\
+2024-07-20T12:00:00-08:00[America/New_York]
+2024-07-20T12:00:00-04:00[America/New_York]
+";
+    assert_cmd_snapshot!(
+        parse().stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:00:00-04:00[America/New_York]
+    2024-07-20T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    warning: datetime `2024-07-20T12:00:00-08:00[America/New_York]` has offset `-08`, but time zone `America/New_York` has offset `-04` at that instant
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("--strict").stdin(input),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    line 1 of <stdin>: datetime `2024-07-20T12:00:00-08:00[America/New_York]` has offset `-08`, but time zone `America/New_York` has offset `-04` at that instant
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["--strict", "-i"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "%Y-%m-%d %H:%M %z %Q", "--strict"])
+            .arg("2024-07-20 12:00 -0800 America/New_York"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    datetime `2024-07-20 12:00 -0800 America/New_York` has offset `-08`, but time zone `America/New_York` has offset `-04` at that instant
+    ",
+    );
+    // Both offsets are valid in a fold.
+    assert_cmd_snapshot!(
+        parse()
+            .arg("--strict")
+            .arg("2024-11-03T01:30-05[America/New_York]")
+            .arg("2024-11-03T01:30-04[America/New_York]"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-11-03T01:30:00-05:00[America/New_York]
+    2024-11-03T01:30:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // A critical annotation is always rejected.
+    assert_cmd_snapshot!(
+        parse().arg("2024-07-20T12:00-08[!America/New_York]"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `2024-07-20T12:00-08[!America/New_York]` for format rfc9557 failed: datetime could not resolve to a timestamp since `reject` conflict resolution was chosen, and because datetime has offset `-08`, but the time zone `America/New_York` for the given datetime unambiguously has offset `-04`
+    ",
+    );
+}
+
+#[test]
+fn default_time() {
+    assert_cmd_snapshot!(
+        parse().args(["-f", "flexible"])
+            .args(["--default-time", "12:00:00"])
+            .args(["2024-07-20", "2024-202", "2024-07-20T03:00", "tomorrow"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T12:00:00-04:00[America/New_York]
+    2024-07-20T12:00:00-04:00[America/New_York]
+    2024-07-20T03:00:00-04:00[America/New_York]
+    2024-07-21T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%Y-%m-%d", "--default-time", "12:00"])
+            .args(["2024-07-20", "2024-07-20[Asia/Tokyo]"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T12:00:00-04:00[America/New_York]
+    2024-07-20T12:00:00+09:00[Asia/Tokyo]
+
+    ----- stderr -----
+    ",
+    );
+    // Midnight doesn't exist on this date in Sao Paulo, so it's moved
+    // forward past the gap by default. But noon exists.
+    assert_cmd_snapshot!(
+        parse().arg("2018-11-04[America/Sao_Paulo]"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2018-11-04T01:00:00-02:00[America/Sao_Paulo]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().arg("2018-11-04[America/Sao_Paulo]")
+            .args(["--default-time", "12:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2018-11-04T12:00:00-02:00[America/Sao_Paulo]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `--locale` parses datetimes formatted in that locale.
+#[cfg(feature = "locale")]
+#[test]
+fn locale_round_trip() {
+    let fmt = |locale: &str, format: &str| {
+        crate::bttf(["time", "fmt", "-f", format])
+            .env("BTTF_LOCALE", locale)
+            .env("TZ", "UTC")
+            .args(["2024-07-20T16:30:55Z", "2024-02-05T09:05:00Z"])
+    };
+    let parse = |locale: &str, format: &str| {
+        parse().env("TZ", "UTC").args(["--locale", locale, "-f", format])
+    };
+
+    assert_cmd_snapshot!(
+        fmt("fr-FR", "%x"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    20 juil. 2024
+    5 févr. 2024
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt("fr-FR", "%x").pipe(parse("fr-FR", "%d %b %Y")),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T00:00:00+00:00[UTC]
+    2024-02-05T00:00:00+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt("fr-FR", "%c"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    sam. 20 juil. 2024, 16:30:55 UTC
+    lun. 5 févr. 2024, 09:05:00 UTC
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt("fr-FR", "%c").pipe(parse("fr-FR", "%a %d %b %Y, %T UTC")),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55+00:00[UTC]
+    2024-02-05T09:05:00+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt("de-DE", "%c"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Sa., 20.07.2024, 16:30:55 UTC
+    Mo., 05.02.2024, 09:05:00 UTC
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt("de-DE", "%c").pipe(parse("de-DE", "%a, %d.%m.%Y, %T UTC")),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55+00:00[UTC]
+    2024-02-05T09:05:00+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+    // In Spanish, `mar` is an abbreviation for both Tuesday and March. It is
+    // resolved by the directive it is parsed by.
+    let fmt = |locale: &str, format: &str| {
+        crate::bttf(["time", "fmt", "-f", format])
+            .env("BTTF_LOCALE", locale)
+            .env("TZ", "UTC")
+            .args(["2024-07-23T10:00:00Z", "2024-03-05T10:00:00Z"])
+    };
+    assert_cmd_snapshot!(
+        fmt("es-ES", "%c"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    mar, 23 jul 2024, 10:00:00 UTC
+    mar, 5 mar 2024, 10:00:00 UTC
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt("es-ES", "%c").pipe(parse("es-ES", "%a, %d %b %Y, %T UTC")),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-23T10:00:00+00:00[UTC]
+    2024-03-05T10:00:00+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        fmt("es-ES", "%x").pipe(parse("es-ES", "%d %b %Y")),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-23T00:00:00+00:00[UTC]
+    2024-03-05T00:00:00+00:00[UTC]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests how `--locale` matches localized month and weekday names.
+#[cfg(feature = "locale")]
+#[test]
+fn locale_names() {
+    // Names are case insensitive, and abbreviations may omit their period.
+    assert_cmd_snapshot!(
+        parse()
+            .args(["--locale", "fr", "-f", "%A %d %B %Y"])
+            .args(["SAMEDI 20 JUILLET 2024", "sam 20 juil 2024"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T00:00:00-04:00[America/New_York]
+    2024-07-20T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // Both `%b` and `%B` accept abbreviated and full names, as well as
+    // unambiguous prefixes of full names.
+    assert_cmd_snapshot!(
+        parse()
+            .args(["--locale", "de", "-f", "%d. %b %Y"])
+            .args(["5. März 2024", "5. Sept. 2024", "5. Septem 2024"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-03-05T00:00:00-05:00[America/New_York]
+    2024-09-05T00:00:00-04:00[America/New_York]
+    2024-09-05T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse()
+            .args(["--locale", "fr", "-f", "%d %b %Y"])
+            .arg("20 jui 2024"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    `jui` is ambiguous in locale `fr`, since it could be any of: juin, juillet (June, July)
+    ",
+    );
+    // The weekday must still match the date.
+    assert_cmd_snapshot!(
+        parse()
+            .args(["--locale", "fr", "-f", "%A %d %B %Y"])
+            .arg("vendredi 20 juillet 2024"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing `vendredi 20 juillet 2024` in locale `fr` failed: parsing datetime `Friday 20 July 2024` for format `%A %d %B %Y` failed: date required to parse datetime: parsed weekday `Friday` does not match weekday `Saturday` from parsed date
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["--locale", "fr", "20 juillet 2024"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --locale can only be used with strptime formats, but got rfc9557
+    ",
+    );
+    // The `und` locale doesn't translate anything.
+    assert_cmd_snapshot!(
+        parse()
+            .args(["--locale", "und", "-f", "%d %B %Y"])
+            .arg("20 July 2024"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that `%Ns` parses a Unix timestamp in nanoseconds, and that it
+/// round trips with `time fmt`.
+#[test]
+fn epoch_nanosecond() {
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "%Ns"])
+            .arg("1721492455123456789")
+            .arg("-500000000")
+            .arg("1721492455123456789[Europe/London]"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:20:55.123456789Z[Etc/Unknown]
+    1969-12-31T23:59:59.5Z[Etc/Unknown]
+    2024-07-20T17:20:55.123456789+01:00[Europe/London]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "ts=%Ns %Q"])
+            .arg("ts=1721492455123456789 America/New_York"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T12:20:55.123456789-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "%Ns"])
+            .arg("1721492455123456789")
+            .pipe(crate::bttf(["time", "fmt", "-f", "%Ns"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721492455123456789
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%Ns", "soon"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `soon` for format `%Ns` failed: %Ns failed: expected a Unix timestamp in nanoseconds, but found no digits
+    ",
+    );
+}
+
+/// Tests that `%3s`, `%6s` and `%9s` parse Unix timestamps in milliseconds,
+/// microseconds and nanoseconds.
+#[test]
+fn epoch_units() {
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%3s", "1721492455123", "-1"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:20:55.123Z[Etc/Unknown]
+    1969-12-31T23:59:59.999Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%6s", "1721492455123456", "-1"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:20:55.123456Z[Etc/Unknown]
+    1969-12-31T23:59:59.999999Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "%6s"])
+            .arg("1721492455123456")
+            .pipe(crate::bttf(["time", "fmt", "-f", "%6s"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1721492455123456
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%3s", "99999999999999999999"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `99999999999999999999` for format `%3s` failed: %3s failed: invalid Unix timestamp `99999999999999999999` in milliseconds: expected an integer
+    ",
+    );
+}
+
+/// Tests that ISO 8601 week dates parse with the `%G` and `%V` directives,
+/// with the weekday defaulting to Monday when it's missing.
+#[test]
+fn week_date_directives() {
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "%G-W%V"])
+            .args(["2024-W29", "2025-W01", "2020-W53"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-15T00:00:00-04:00[America/New_York]
+    2024-12-30T00:00:00-05:00[America/New_York]
+    2020-12-28T00:00:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse()
+            .args(["-f", "%G-W%V-%u"])
+            .args(["2025-W01-2", "2020-W53-5"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-12-31T00:00:00-05:00[America/New_York]
+    2021-01-01T00:00:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        crate::bttf(["time", "fmt", "-f", "%G-W%V", "2021-01-03"])
+            .pipe(parse().args(["-f", "%G-W%V"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2020-12-28T00:00:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "%G-W%V", "2021-W53"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsing datetime `2021-W53` for format `%G-W%V` failed: date required to parse datetime: invalid ISO 8601 week date: parameter 'iso-week' is not in the required range of 1..=53
+    ",
+    );
+}
+
+/// Tests that `--try` uses the first format that succeeds for each input.
+#[test]
+fn try_formats() {
+    assert_cmd_snapshot!(
+        parse()
+            .args(["--try", "rfc3339", "--try", "%s"])
+            .args(["--try", "%Y-%m-%d %H:%M:%S", "--show-format=column"])
+            .stdin("2024-07-20T16:30:55Z\n1721507455\n2024-07-20 12:00:00\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    rfc3339	2024-07-20T16:30:55Z[Etc/Unknown]
+    %s	2024-07-20T20:30:55Z[Etc/Unknown]
+    %Y-%m-%d %H:%M:%S	2024-07-20T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // Tagged data with multiple tags may use a different format per tag.
+    assert_cmd_snapshot!(
+        parse()
+            .args(["--try", "rfc3339", "--try", "%s", "--show-format=column"])
+            .stdin(concat!(
+                r#"{"tags":["#,
+                r#"{"value":"1721507455","range":[0,10]},"#,
+                r#"{"value":"2024-07-20T16:30:55Z","range":[11,31]}"#,
+                r#"],"data":{"text":"1721507455 2024-07-20T16:30:55Z"}}"#,
+            )),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    %s,rfc3339	{"tags":[{"value":"2024-07-20T20:30:55Z[Etc/Unknown]","range":[0,10]},{"value":"2024-07-20T16:30:55Z[Etc/Unknown]","range":[11,31]}],"data":{"text":"1721507455 2024-07-20T16:30:55Z"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        parse().args(["--try", "rfc3339", "--try", "%s", "foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    `foo` did not match any of the formats tried: rfc3339, `%s`
+    ",
+    );
+    assert_cmd_snapshot!(
+        parse().args(["-f", "rfc3339", "--try", "%s", "foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --try cannot be combined with rfc3339 given to -f/--format, instead give each format to --try
+    ",
+    );
+}
+
+/// Tests that `--output json` prints an object per parsed datetime.
+#[test]
+fn output_json() {
+    assert_cmd_snapshot!(
+        parse()
+            .args(["--output", "json", "-f", "%s"])
+            .stdin("1721507455\n-1\n"),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"timestamp":"2024-07-20T20:30:55Z","zoned":"2024-07-20T20:30:55Z[Etc/Unknown]","offset":"+00:00","epoch_seconds":1721507455,"epoch_nanoseconds":1721507455000000000}
+    {"timestamp":"1969-12-31T23:59:59Z","zoned":"1969-12-31T23:59:59Z[Etc/Unknown]","offset":"+00:00","epoch_seconds":-1,"epoch_nanoseconds":-1000000000}
+
+    ----- stderr -----
+    "#,
+    );
+    // Tag values must remain strings so that tagged data can be untagged.
+    // So JSON output is rejected for tagged data, and `untag --output json`
+    // is the way to get it as JSON.
+    assert_cmd_snapshot!(
+        crate::bttf(["tag", "lines"])
+            .stdin("x 2024-07-20T16:30:55-04[America/New_York] y\n")
+            .pipe(parse().args(["--output", "json"])),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    line 1 of <stdin>: --output json cannot be used with tagged data (use `bttf untag --output json` to print tagged data as JSON)
+    ",
+    );
+    assert_cmd_snapshot!(
+        crate::bttf(["tag", "lines"])
+            .stdin("x 2024-07-20T16:30:55-04[America/New_York] y\n")
+            .pipe(parse())
+            .pipe(crate::bttf(["untag", "--output", "json"])),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tag":"2024-07-20T16:30:55-04:00[America/New_York]","data":"x 2024-07-20T16:30:55-04[America/New_York] y"}
+
+    ----- stderr -----
+    "#,
+    );
+}
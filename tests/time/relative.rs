@@ -35,6 +35,24 @@ fn basic() {
     );
 }
 
+/// Tests that `--now` overrides `BTTF_NOW` when given after the command name.
+#[test]
+fn now_flag() {
+    assert_cmd_snapshot!(
+        relative()
+            .env("BTTF_NOW", "2030-01-01T00:00:00Z")
+            .args(["--now", "2025-04-01T17:00-04", "this sat", "now"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-04-05T17:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
 #[test]
 fn invalid() {
     assert_cmd_snapshot!(
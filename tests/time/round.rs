@@ -168,6 +168,125 @@ fn dst_time() {
     );
 }
 
+/// Tests that rounding with an origin lands on `origin + k*increment`, even
+/// when the origin isn't aligned to a unit boundary.
+#[test]
+fn origin_phase() {
+    let input = "\
+2025-03-05T12:13-05[America/New_York]
+2025-03-05T12:15-05[America/New_York]
+2025-03-05T12:18-05[America/New_York]
+2025-03-05T12:22:59-05[America/New_York]
+2025-03-04T23:59-05[America/New_York]
+";
+    let args = ["-sminute", "-i10", "--origin", "2025-03-05T00:03"];
+    assert_cmd_snapshot!(
+        round().args(args).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:13:00-05:00[America/New_York]
+    2025-03-05T12:13:00-05:00[America/New_York]
+    2025-03-05T12:23:00-05:00[America/New_York]
+    2025-03-05T12:23:00-05:00[America/New_York]
+    2025-03-05T00:03:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(args).arg("-mfloor").stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:13:00-05:00[America/New_York]
+    2025-03-05T12:13:00-05:00[America/New_York]
+    2025-03-05T12:13:00-05:00[America/New_York]
+    2025-03-05T12:13:00-05:00[America/New_York]
+    2025-03-04T23:53:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(args).arg("-mceil").stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-05T12:13:00-05:00[America/New_York]
+    2025-03-05T12:23:00-05:00[America/New_York]
+    2025-03-05T12:23:00-05:00[America/New_York]
+    2025-03-05T12:23:00-05:00[America/New_York]
+    2025-03-05T00:03:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that an origin may be given as a Unix timestamp in seconds.
+#[test]
+fn origin_unix_seconds() {
+    assert_cmd_snapshot!(
+        round().args([
+            "-ssecond",
+            "-i7",
+            "-mfloor",
+            "--origin",
+            "5",
+            "1970-01-01T00:00:20Z",
+            "1970-01-01T00:00:02Z",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1970-01-01T00:00:19Z[Etc/Unknown]
+    1969-12-31T23:59:58Z[Etc/Unknown]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that days are always 24 hours when rounding relative to an origin,
+/// unlike normal rounding to days.
+#[test]
+fn origin_days() {
+    assert_cmd_snapshot!(
+        round().args([
+            "-sday",
+            "-i2",
+            "-mfloor",
+            "--origin",
+            "2025-03-07T06:00[America/New_York]",
+            "2025-03-10T12:00[America/New_York]",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-09T07:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sweek", "--origin", "0", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    rounding relative to an origin only works for units of days or lower
+    ",
+    );
+}
+
 #[test]
 fn errors() {
     assert_cmd_snapshot!(
@@ -236,7 +355,49 @@ fn errors() {
     ----- stdout -----
 
     ----- stderr -----
-    failed rounding datetime: increment for rounding to 'minutes' must divide into `60` evenly
+    increment `16` for rounding to minutes must evenly divide the 60 minutes in an hour, so it must be one of: 1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30
+    ",
+    );
+}
+
+/// Tests snapping datetimes to a grid with an increment.
+#[test]
+fn increment() {
+    assert_cmd_snapshot!(
+        round()
+            .args(["-sminute", "-i5"])
+            .args(["2024-07-20T16:32", "2024-07-20T16:33", "2024-07-20T23:58"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:00-04:00[America/New_York]
+    2024-07-20T16:35:00-04:00[America/New_York]
+    2024-07-21T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-sminute", "-i60", "2024-07-20T16:32"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    increment `60` for rounding to minutes must evenly divide the 60 minutes in an hour, so it must be one of: 1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30
+    ",
+    );
+    assert_cmd_snapshot!(
+        round().args(["-shour", "-i5", "2024-07-20T16:32"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    increment `5` for rounding to hours must evenly divide the 24 hours in a day, so it must be one of: 1, 2, 3, 4, 6, 8, 12
     ",
     );
 }
@@ -0,0 +1,290 @@
+use crate::command::assert_cmd_snapshot;
+
+fn rrule() -> crate::command::Command {
+    crate::bttf(["time", "rrule"])
+}
+
+// N.B. As with `bttf time seq`, the recurrence rule engine itself is tested
+// extensively via unit tests. These tests focus on parsing RRULE strings and
+// on the CLI interaction points.
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        rrule().args(["FREQ=MONTHLY;BYDAY=1FR;COUNT=3"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-08-02T16:30:55-04:00[America/New_York]
+    2024-09-06T16:30:55-04:00[America/New_York]
+    2024-10-04T16:30:55-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule()
+            .args(["--start", "19970902T090000[America/New_York]"])
+            .arg("RRULE:FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=TU,TH;COUNT=4"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1997-09-02T09:00:00-04:00[America/New_York]
+    1997-09-04T09:00:00-04:00[America/New_York]
+    1997-09-16T09:00:00-04:00[America/New_York]
+    1997-09-18T09:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // Names are case insensitive.
+    assert_cmd_snapshot!(
+        rrule()
+            .args(["--start", "2025-01-01T09:00"])
+            .arg("freq=yearly;bymonth=1,2;byday=-1su;count=2"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-01-26T09:00:00-05:00[America/New_York]
+    2025-02-23T09:00:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn until() {
+    // A UTC datetime, a local datetime and a date.
+    assert_cmd_snapshot!(
+        rrule()
+            .args(["--start", "2025-03-08T12:00"])
+            .arg("FREQ=DAILY;UNTIL=20250310T160000Z"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-08T12:00:00-05:00[America/New_York]
+    2025-03-09T12:00:00-04:00[America/New_York]
+    2025-03-10T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule()
+            .args(["--start", "2025-03-08T12:00"])
+            .arg("FREQ=DAILY;UNTIL=20250309T120000"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-08T12:00:00-05:00[America/New_York]
+    2025-03-09T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule()
+            .args(["--start", "2025-03-08T12:00"])
+            .arg("FREQ=DAILY;UNTIL=20250309"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-08T12:00:00-05:00[America/New_York]
+    2025-03-09T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // The earlier of `UNTIL` and `-u/--until` wins.
+    assert_cmd_snapshot!(
+        rrule()
+            .args(["--start", "2025-03-08T12:00", "-u", "2025-03-09T12:00"])
+            .arg("FREQ=DAILY;UNTIL=20250320"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-08T12:00:00-05:00[America/New_York]
+    2025-03-09T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn count() {
+    // The smaller of `COUNT` and `-c/--count` wins.
+    assert_cmd_snapshot!(
+        rrule().args(["-c", "2", "FREQ=HOURLY;COUNT=5"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55-04:00[America/New_York]
+    2024-07-20T17:30:55-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().args(["-c", "5", "FREQ=HOURLY;COUNT=2"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55-04:00[America/New_York]
+    2024-07-20T17:30:55-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    // The flags may be combined with the other kind of limit in the rule.
+    assert_cmd_snapshot!(
+        rrule()
+            .args(["--start", "2025-03-08T12:00", "-c", "5"])
+            .arg("FREQ=DAILY;UNTIL=20250309"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-08T12:00:00-05:00[America/New_York]
+    2025-03-09T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        rrule(),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    missing required <rrule>
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("BYDAY=MO"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    RRULE is missing required `FREQ` part
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=DAILY;FREQ=WEEKLY"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    RRULE part `FREQ` was given more than once
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=DAILY;BYFOO=1"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid RRULE part `BYFOO=1`: unsupported RRULE part `BYFOO`
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=DAILY;COUNT"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid RRULE part `COUNT` (expected `NAME=VALUE`)
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=FORTNIGHTLY"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid RRULE part `FREQ=FORTNIGHTLY`: unrecognized frequency: `fortnightly`
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=MONTHLY;BYDAY=1XX"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid RRULE part `BYDAY=1XX`: unrecognized weekday: `xx`
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=DAILY;BYHOUR=9-17"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid RRULE part `BYHOUR=9-17`: invalid integer `9-17`: invalid digit found in string
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=DAILY;COUNT=2;UNTIL=20250309"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    'until' and 'count' cannot both be used in the same rule
+    ",
+    );
+    // Errors from the recurrence rule engine are reported as-is.
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=DAILY;BYDAY=1FR"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    numbered weekday `1-Fri` is only allowed at yearly or monthly frequencies
+    ",
+    );
+    assert_cmd_snapshot!(
+        rrule().arg("FREQ=DAILY;BYMONTH=13"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid 'by month' value `13` (values must be in range 1..=12)
+    ",
+    );
+}
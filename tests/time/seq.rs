@@ -657,6 +657,56 @@ fn by_week_day() {
     );
 }
 
+/// Tests that the frequency may be omitted when weekdays are given, and
+/// that the result matches the equivalent daily and weekly rules.
+#[test]
+fn by_week_day_without_frequency() {
+    let range = ["--until", "2025-03-31", "2025-03-01"];
+    let cmd = || seq().args(["--by-weekday", "mon,wed,fri"]).args(range);
+    assert_cmd_snapshot!(
+        cmd(),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-03T00:00:00-05:00[America/New_York]
+    2025-03-05T00:00:00-05:00[America/New_York]
+    2025-03-07T00:00:00-05:00[America/New_York]
+    2025-03-10T00:00:00-04:00[America/New_York]
+    2025-03-12T00:00:00-04:00[America/New_York]
+    2025-03-14T00:00:00-04:00[America/New_York]
+    2025-03-17T00:00:00-04:00[America/New_York]
+    2025-03-19T00:00:00-04:00[America/New_York]
+    2025-03-21T00:00:00-04:00[America/New_York]
+    2025-03-24T00:00:00-04:00[America/New_York]
+    2025-03-26T00:00:00-04:00[America/New_York]
+    2025-03-28T00:00:00-04:00[America/New_York]
+    2025-03-31T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    let implicit = cmd().snapshot();
+    let daily = seq().args(["daily", "-w", "mon,wed,fri"]).args(range);
+    let weekly = seq().args(["weekly", "-w", "mon,wed,fri"]).args(range);
+    assert_eq!(implicit.stdout(), daily.snapshot().stdout());
+    assert_eq!(implicit.stdout(), weekly.snapshot().stdout());
+
+    // Without any weekdays, the frequency is still required.
+    assert_cmd_snapshot!(
+        seq().args(["-c1", "2025-03-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    missing required <frequency>
+    ",
+    );
+}
+
 #[test]
 fn by_hour() {
     assert_cmd_snapshot!(
@@ -761,3 +811,221 @@ fn by_second() {
     ",
     );
 }
+
+#[test]
+fn jitter() {
+    assert_cmd_snapshot!(
+        seq().args(["-c5", "minutely", "--jitter", "30s", "--seed", "42"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:31:07.678360883-04:00[America/New_York]
+    2024-07-20T16:32:12.41568114-04:00[America/New_York]
+    2024-07-20T16:32:30.215262973-04:00[America/New_York]
+    2024-07-20T16:34:01.770452737-04:00[America/New_York]
+    2024-07-20T16:35:24.616822352-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    // A different seed gives a different (but still deterministic) result.
+    assert_cmd_snapshot!(
+        seq().args(["-c5", "minutely", "--jitter", "30s", "--seed", "7"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:47.893344815-04:00[America/New_York]
+    2024-07-20T16:31:33.830193834-04:00[America/New_York]
+    2024-07-20T16:33:08.720993848-04:00[America/New_York]
+    2024-07-20T16:34:19.268254455-04:00[America/New_York]
+    2024-07-20T16:35:03.257434644-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    // With jitter bigger than the interval, the sequence is clamped to
+    // remain in chronological order by default...
+    assert_cmd_snapshot!(
+        seq().args(["-c5", "secondly", "--jitter", "5s", "--seed", "42"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55.876908676-04:00[America/New_York]
+    2024-07-20T16:30:55.876908676-04:00[America/New_York]
+    2024-07-20T16:30:58-04:00[America/New_York]
+    2024-07-20T16:30:58.2685061-04:00[America/New_York]
+    2024-07-20T16:30:58.2685061-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    // ... but can be reordered when requested.
+    assert_cmd_snapshot!(
+        seq()
+            .args(["-c5", "secondly", "--jitter", "5s", "--seed", "42"])
+            .arg("--allow-reorder"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T16:30:55.876908676-04:00[America/New_York]
+    2024-07-20T16:30:51.343059901-04:00[America/New_York]
+    2024-07-20T16:30:59.090801095-04:00[America/New_York]
+    2024-07-20T16:30:58.2685061-04:00[America/New_York]
+    2024-07-20T16:30:54.179924757-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        seq().args(["-c5", "daily", "--jitter", "1mo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    jitter span `1mo` must not contain units of months or years
+    ",
+    );
+}
+
+#[test]
+fn step() {
+    assert_cmd_snapshot!(
+        seq().args(["--step", "1h30m", "-c4", "2025-03-10T09:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-10T09:00:00-04:00[America/New_York]
+    2025-03-10T10:30:00-04:00[America/New_York]
+    2025-03-10T12:00:00-04:00[America/New_York]
+    2025-03-10T13:30:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    // The ISO 8601 format works too, and `-u/--until` is inclusive.
+    assert_cmd_snapshot!(
+        seq().args(["--step", "P2W", "-u", "2025-02-12", "2025-01-01"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-01-01T00:00:00-05:00[America/New_York]
+    2025-01-15T00:00:00-05:00[America/New_York]
+    2025-01-29T00:00:00-05:00[America/New_York]
+    2025-02-12T00:00:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    // Calendar units are added in the datetime's time zone, so a day
+    // across a DST transition keeps the same clock time, unlike 24 hours.
+    assert_cmd_snapshot!(
+        seq().args(["--step", "1d", "-c3", "2025-03-08T12:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-08T12:00:00-05:00[America/New_York]
+    2025-03-09T12:00:00-04:00[America/New_York]
+    2025-03-10T12:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        seq().args(["--step", "24h", "-c3", "2025-03-08T12:00"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-08T12:00:00-05:00[America/New_York]
+    2025-03-09T13:00:00-04:00[America/New_York]
+    2025-03-10T13:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    // Each datetime is an offset from the start, so the day of the month
+    // doesn't drift after being clamped.
+    assert_cmd_snapshot!(
+        seq().args(["--step", "1mo", "-c4", "2025-01-31"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-01-31T00:00:00-05:00[America/New_York]
+    2025-02-28T00:00:00-05:00[America/New_York]
+    2025-03-31T00:00:00-04:00[America/New_York]
+    2025-04-30T00:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+
+    // Negative steps go backwards, with `-u/--until` as a lower bound.
+    assert_cmd_snapshot!(
+        seq().args(["--step", "-1w", "-u", "2025-01-10", "2025-02-01"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-02-01T00:00:00-05:00[America/New_York]
+    2025-01-25T00:00:00-05:00[America/New_York]
+    2025-01-18T00:00:00-05:00[America/New_York]
+    2025-01-11T00:00:00-05:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn step_errors() {
+    assert_cmd_snapshot!(
+        seq().args(["--step", "0s", "2025-01-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --step span `0s` must be non-zero
+    ",
+    );
+    assert_cmd_snapshot!(
+        seq().args(["--step", "1d", "daily", "2025-01-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --step cannot be used with a <frequency>
+    ",
+    );
+    assert_cmd_snapshot!(
+        seq().args(["--step", "1d", "-w", "mon", "2025-01-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --step cannot be used with recurrence rule flags like -w/--week-day or -d/--day
+    ",
+    );
+}
@@ -0,0 +1,148 @@
+use crate::command::assert_cmd_snapshot;
+
+fn skew() -> crate::command::Command {
+    crate::bttf(["time", "skew"])
+}
+
+const PAIRS: &str = "\
+2025-03-15T10:00:00Z\t2025-03-15T10:00:02.5Z
+2025-03-15T10:00:00-04\t2025-03-15T09:59:59-04
+2025-03-15T10:00:00[America/New_York]\t2025-03-15T14:00:00.25Z
+";
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        skew().stdin(PAIRS),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2s 500ms
+    1s ago
+    250ms
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn abs() {
+    assert_cmd_snapshot!(
+        skew().arg("--abs").stdin(PAIRS),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2s 500ms
+    1s
+    250ms
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn summary() {
+    assert_cmd_snapshot!(
+        skew().arg("--summary").stdin(PAIRS),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2s 500ms
+    1s ago
+    250ms
+    min: 1s ago
+    max: 2s 500ms
+    mean: 583ms 333µs 333ns
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        skew().arg("--summary").arg("--abs").stdin(PAIRS),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2s 500ms
+    1s
+    250ms
+    min: 250ms
+    max: 2s 500ms
+    mean: 1s 250ms
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        skew().arg("--summary").stdin(""),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn threshold() {
+    assert_cmd_snapshot!(
+        skew().args(["-t", "3s"]).stdin(PAIRS),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2s 500ms
+    1s ago
+    250ms
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        skew().args(["-t", "2s"]).stdin(PAIRS),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    2s 500ms
+    1s ago
+    250ms
+
+    ----- stderr -----
+    found 1 pair(s) with a skew exceeding 2s
+    ",
+    );
+}
+
+#[test]
+fn invalid() {
+    assert_cmd_snapshot!(
+        skew().stdin("2025-03-15T10:00:00Z 2025-03-15T10:00:02Z\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    <stdin>: line 1: expected two tab separated datetimes, but got `2025-03-15T10:00:00Z 2025-03-15T10:00:02Z`
+    ",
+    );
+    assert_cmd_snapshot!(
+        skew().stdin("2025-03-15T10:00:00Z\tfoo\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    <stdin>: line 1: unrecognized datetime `foo`
+    ",
+    );
+}
@@ -72,6 +72,84 @@ fn simple_tagged() {
     );
 }
 
+/// Tests that the sort is stable for tagged data (even when reversed) and
+/// that `-u/--unique` keeps only the first of each run of equal instants.
+#[test]
+fn stable_and_unique() {
+    let stdin = "\
+a 2024-07-20T16:30:55-04:00[America/New_York]
+b 2024-07-19T16:30:55-04:00[America/New_York]
+c 2024-07-20T20:30:55Z
+d 2024-07-19T16:30:55-04:00[America/New_York]
+e 2024-07-21T05:30:55+09:00[Asia/Tokyo]
+";
+
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines"])
+            .stdin(stdin)
+            .pipe(sort())
+            .pipe(bttf(["untag", "-f", "{data}"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    b 2024-07-19T16:30:55-04:00[America/New_York]
+    d 2024-07-19T16:30:55-04:00[America/New_York]
+    a 2024-07-20T16:30:55-04:00[America/New_York]
+    c 2024-07-20T20:30:55Z
+    e 2024-07-21T05:30:55+09:00[Asia/Tokyo]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines"])
+            .stdin(stdin)
+            .pipe(sort().arg("-r"))
+            .pipe(bttf(["untag", "-f", "{data}"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    a 2024-07-20T16:30:55-04:00[America/New_York]
+    c 2024-07-20T20:30:55Z
+    e 2024-07-21T05:30:55+09:00[Asia/Tokyo]
+    b 2024-07-19T16:30:55-04:00[America/New_York]
+    d 2024-07-19T16:30:55-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines"])
+            .stdin(stdin)
+            .pipe(sort().arg("-u"))
+            .pipe(bttf(["untag", "-f", "{data}"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    b 2024-07-19T16:30:55-04:00[America/New_York]
+    a 2024-07-20T16:30:55-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        sort().args(["--numeric-epoch", "-r", "-u", "3", "1", "3", "2", "1"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    3
+    2
+    1
+
+    ----- stderr -----
+    ",
+    );
+}
+
 #[test]
 fn multiple_tagged_simple() {
     let stdin = "\
@@ -134,3 +212,97 @@ fn multiple_tagged_same_first_tag() {
     "#,
     );
 }
+
+/// Tests that `--numeric-epoch` sorts integers numerically, stably and prints
+/// them unchanged.
+#[test]
+fn numeric_epoch() {
+    let input = "\
+1741190400
++1741186800
+-86400
+01741186800
+0
+";
+    assert_cmd_snapshot!(
+        sort().arg("--numeric-epoch").stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -86400
+    0
+    +1741186800
+    01741186800
+    1741190400
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        sort().args(["--numeric-epoch", "-r"]).stdin(input),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1741190400
+    +1741186800
+    01741186800
+    0
+    -86400
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        sort().args(["--numeric-epoch", "--epoch", "ms"]).stdin(
+            "1741190400000\n1741186800000\n",
+        ),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    1741186800000
+    1741190400000
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn numeric_epoch_invalid() {
+    assert_cmd_snapshot!(
+        sort().arg("--numeric-epoch").stdin("1741190400\n2025-03-05T12Z\n"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    line 2 of <stdin>: invalid epoch timestamp `2025-03-05T12Z`: expected an integer
+    ",
+    );
+    assert_cmd_snapshot!(
+        sort().arg("--numeric-epoch").arg("1741190400000"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid epoch timestamp `1741190400000`: parameter 'Unix timestamp seconds' is not in the required range of -377705023201..=253402207200
+    ",
+    );
+    assert_cmd_snapshot!(
+        sort().args(["--epoch", "ms", "1741190400000"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --epoch requires --numeric-epoch
+    ",
+    );
+}
@@ -0,0 +1,77 @@
+use crate::command::assert_cmd_snapshot;
+
+fn sort_key() -> crate::command::Command {
+    crate::bttf(["time", "sort-key"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        sort_key().args([
+            "1970-01-01T00:00:00Z",
+            "1969-12-31T23:59:59.999999999Z",
+            "2024-07-20T16:30:55-04:00[America/New_York]",
+            "2024-07-20T20:30:55+00:00[UTC]",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    377705023201000000000
+    377705023200999999999
+    379426530656000000000
+    379426530656000000000
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that the minimum and maximum datetimes have keys of the same width.
+#[test]
+fn extremes() {
+    assert_cmd_snapshot!(
+        sort_key().args([
+            "-009999-01-02T01:59:59Z",
+            "9999-12-30T22:00:00.999999999Z",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    000000000000000000000
+    631107230401999999999
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that keys increase with instants, even across time zones and the
+/// Unix epoch. The datetimes here are in ascending order of instants.
+#[test]
+fn sorts_like_instants() {
+    assert_cmd_snapshot!(
+        sort_key().args([
+            "-000044-03-15T12:00:00Z",
+            "0001-01-01T00:00:00Z",
+            "1969-07-20T20:17:40Z",
+            "2024-07-20T21:00:00+01:00[Europe/London]",
+            "2024-07-20T16:30:55-04:00[America/New_York]",
+            "2500-01-01T00:00:00+14:00",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    314155706401000000000
+    315569426401000000000
+    377690840261000000000
+    379426528801000000000
+    379426530656000000000
+    394430198401000000000
+
+    ----- stderr -----
+    ",
+    );
+}
@@ -0,0 +1,202 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn sunrise() -> crate::command::Command {
+    bttf(["time", "sunrise"])
+}
+
+fn sunset() -> crate::command::Command {
+    bttf(["time", "sunset"])
+}
+
+/// Checks that sunrise and sunset are within a couple minutes of published
+/// times for a few locations and dates.
+///
+/// The sunrise equation is an approximation, so we don't expect an exact
+/// match. The published times are rounded to the minute.
+#[test]
+fn known() {
+    let cases = [
+        // New York City, summer and winter.
+        ("40.71", "-74.01", "2024-07-20", "05:43", "20:22"),
+        ("40.71", "-74.01", "2024-12-21", "07:17", "16:32"),
+        // Greenwich, on the March equinox.
+        ("51.48", "0", "2024-03-20[Europe/London]", "06:02", "18:13"),
+        // Sydney, summer in the southern hemisphere.
+        ("-33.87", "151.21", "2024-12-21[Australia/Sydney]", "05:41", "20:05"),
+        // Tokyo, on the June solstice.
+        ("35.68", "139.69", "2024-06-21[Asia/Tokyo]", "04:25", "19:00"),
+    ];
+    for (lat, lon, date, rise, set) in cases {
+        for (cmd, expected) in [(sunrise(), rise), (sunset(), set)] {
+            let snap = cmd.args(["--lat", lat, "--lon", lon, date]).snapshot();
+            let got: jiff::Zoned =
+                snap.stdout().to_string().trim().parse().unwrap();
+            let expected: jiff::civil::Time = expected.parse().unwrap();
+            let expected = got.with().time(expected).build().unwrap();
+            let diff = got.duration_until(&expected).unsigned_abs();
+            assert!(
+                diff <= std::time::Duration::from_secs(120),
+                "for {lat}, {lon} on {date}, got {got} but expected \
+                 {expected}",
+            );
+        }
+    }
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        sunrise().args(["--lat", "40.71", "--lon", "-74.01", "now"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T05:41:58-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        sunset().args(["--lat", "40.71", "--lon", "-74.01", "now"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-07-20T20:22:30-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that sunset can fall on the day after the date given, since the
+/// sun sets just after midnight in Reykjavik around the June solstice.
+#[test]
+fn after_midnight() {
+    assert_cmd_snapshot!(
+        sunset().args([
+            "--lat",
+            "64.15",
+            "--lon",
+            "-21.94",
+            "2024-06-21[Atlantic/Reykjavik]",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-06-22T00:04:04+00:00[Atlantic/Reykjavik]
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that polar night and midnight sun are reported as errors.
+#[test]
+fn polar() {
+    let args = ["--lat", "69.65", "--lon", "18.96"];
+    assert_cmd_snapshot!(
+        sunrise().args(args).arg("2024-12-21[Europe/Oslo]"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    the sun never rises on 2024-12-21 at latitude 69.65, longitude 18.96 (it stays below the horizon all day)
+    ",
+    );
+    assert_cmd_snapshot!(
+        sunset().args(args).arg("2024-12-21[Europe/Oslo]"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    the sun never sets on 2024-12-21 at latitude 69.65, longitude 18.96 (it stays below the horizon all day)
+    ",
+    );
+    assert_cmd_snapshot!(
+        sunrise().args(args).arg("2024-06-21[Europe/Oslo]"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    the sun never rises on 2024-06-21 at latitude 69.65, longitude 18.96 (it stays above the horizon all day)
+    ",
+    );
+    assert_cmd_snapshot!(
+        sunset().args(args).arg("2024-06-21[Europe/Oslo]"),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    the sun never sets on 2024-06-21 at latitude 69.65, longitude 18.96 (it stays above the horizon all day)
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        sunrise().args(["--lat", "40.71", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    missing required --lon flag
+    ",
+    );
+    assert_cmd_snapshot!(
+        sunrise().args(["--lon", "-74.01", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    missing required --lat flag
+    ",
+    );
+    assert_cmd_snapshot!(
+        sunrise().args(["--lat", "91", "--lon", "-74.01", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --lat: latitude `91` must be between -90 and 90
+    ",
+    );
+    assert_cmd_snapshot!(
+        sunrise().args(["--lat", "40.71", "--lon", "-181", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --lon: longitude `-181` must be between -180 and 180
+    ",
+    );
+    assert_cmd_snapshot!(
+        sunrise().args(["--lat", "north", "--lon", "-74.01", "now"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --lat: invalid float literal
+    ",
+    );
+}
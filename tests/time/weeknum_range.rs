@@ -0,0 +1,119 @@
+use bstr::ByteSlice;
+
+use crate::command::assert_cmd_snapshot;
+
+fn weeknum_range() -> crate::command::Command {
+    crate::bttf(["time", "weeknum-range"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        weeknum_range().args(["--start", "2025-01-01", "--until", "2025-01-31"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-W01
+    2025-W02
+    2025-W03
+    2025-W04
+    2025-W05
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        weeknum_range()
+            .args(["--start", "2025-01-01", "--until", "2025-01-31"])
+            .arg("--with-date"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-W01	2024-12-30
+    2025-W02	2025-01-06
+    2025-W03	2025-01-13
+    2025-W04	2025-01-20
+    2025-W05	2025-01-27
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// 2020 has 53 ISO weeks. Its first week starts in 2019 and its last week
+/// ends in 2021.
+#[test]
+fn long_year() {
+    let snap = weeknum_range()
+        .args(["--start", "2020-01-01", "--until", "2020-12-31"])
+        .arg("--with-date")
+        .snapshot();
+    let lines: Vec<&[u8]> = snap.stdout().lines().collect();
+    assert_eq!(lines.len(), 53);
+    assert_eq!(lines[0].as_bstr(), "2020-W01\t2019-12-30");
+    assert_eq!(lines[52].as_bstr(), "2020-W53\t2020-12-28");
+
+    // The next year starts right after week 53.
+    assert_cmd_snapshot!(
+        weeknum_range()
+            .args(["--start", "2020-12-27", "--until", "2021-01-04"])
+            .arg("--with-date"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2020-W52	2020-12-21
+    2020-W53	2020-12-28
+    2021-W01	2021-01-04
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn week_start() {
+    assert_cmd_snapshot!(
+        weeknum_range()
+            .args(["--start", "2024-12-25", "--until", "2025-01-10"])
+            .args(["--week-start", "sun", "--with-date"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024-W52	2024-12-22
+    2025-W01	2024-12-29
+    2025-W02	2025-01-05
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        weeknum_range().args(["--start", "2025-01-31", "--until", "2025-01-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    end of range `2025-01-01` must not be before its start `2025-01-31`
+    ",
+    );
+    assert_cmd_snapshot!(
+        weeknum_range().args(["--start", "2025-01-31"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --until is required
+    ",
+    );
+}
@@ -0,0 +1,153 @@
+use crate::command::assert_cmd_snapshot;
+
+fn at() -> crate::command::Command {
+    crate::bttf(["tz", "at"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        at().args(["Asia/Kolkata", "now", "2025-01-01T00:00Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +05:30
+    +05:30
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        at().arg("--abbrev").arg("Europe/London").stdin(
+            "2025-01-15T12:00Z\n2025-07-15T12:00[America/Los_Angeles]\n",
+        ),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +00:00 GMT
+    +01:00 BST
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests the instants just before and at the transitions into and out of
+/// daylight saving time.
+#[test]
+fn transitions() {
+    assert_cmd_snapshot!(
+        at().args(["--abbrev", "America/New_York"]).args([
+            "2025-03-09T06:59:59.999999999Z",
+            "2025-03-09T07:00:00Z",
+            "2025-11-02T05:59:59.999999999Z",
+            "2025-11-02T06:00:00Z",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -05:00 EST
+    -04:00 EDT
+    -04:00 EDT
+    -05:00 EST
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn dst() {
+    assert_cmd_snapshot!(
+        at().args(["--dst", "America/New_York"]).args([
+            "now",
+            "2025-01-15T12:00Z",
+            "2025-07-15T12:00-07",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -04:00 dst
+    -05:00 std
+    -04:00 dst
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        at().args(["--abbrev", "--dst", "Europe/Dublin"]).args([
+            "2025-01-15T12:00Z",
+            "2025-07-15T12:00Z",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +00:00 GMT dst
+    +01:00 IST std
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that civil datetimes annotated with the time zone in a gap or fold
+/// use the instant chosen by the `compatible` disambiguation strategy.
+#[test]
+fn dst_gap_and_fold() {
+    assert_cmd_snapshot!(
+        at().args(["--dst", "America/New_York"]).args([
+            "2025-03-09T01:59[America/New_York]",
+            "2025-03-09T02:30[America/New_York]",
+            "2025-11-02T01:30[America/New_York]",
+            "2025-11-02T02:00[America/New_York]",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -05:00 std
+    -04:00 dst
+    -04:00 dst
+    -05:00 std
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn dst_tagged() {
+    assert_cmd_snapshot!(
+        crate::bttf(["tag", "lines"])
+            .stdin("foo 2025-01-15T12:00Z bar\n")
+            .pipe(at().args(["--dst", "Asia/Kolkata"])),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"+05:30 std","range":[4,21]}],"data":{"text":"foo 2025-01-15T12:00Z bar\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn missing_time_zone() {
+    assert_cmd_snapshot!(
+        at(),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    missing required <time-zone> argument
+    ",
+    );
+}
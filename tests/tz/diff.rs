@@ -0,0 +1,293 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn diff() -> crate::command::Command {
+    bttf(["tz", "diff"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        diff().args(["America/New_York", "Asia/Tokyo"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +13:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(["Asia/Tokyo", "America/New_York"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -13:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(["UTC", "UTC"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +00:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(["Asia/Kolkata", "Asia/Kathmandu"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +00:15
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(["UTC", "-00:00:30"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -00:00:30
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests the difference around a transition to daylight saving time that
+/// happens in only one of the two time zones.
+#[test]
+fn one_sided_transition() {
+    let zones = ["America/New_York", "Europe/London"];
+    assert_cmd_snapshot!(
+        diff().args(zones).args(["--at", "2025-03-09T06:59:59Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +05:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(zones).args(["--at", "2025-03-09T07:00:00Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +04:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(zones).args(["--at", "2025-03-30T00:59:59Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +04:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(zones).args(["--at", "2025-03-30T01:00:00Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +05:00
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn until() {
+    let zones = ["America/New_York", "Europe/London"];
+    assert_cmd_snapshot!(
+        diff().args(zones).args(["--at", "2025-01-01", "--until", "2026-01-01"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-01-01T00:00:00-05:00[America/New_York]	+05:00
+    2025-03-09T03:00:00-04:00[America/New_York]	+04:00
+    2025-03-29T21:00:00-04:00[America/New_York]	+05:00
+    2025-10-25T21:00:00-04:00[America/New_York]	+04:00
+    2025-11-02T01:00:00-05:00[America/New_York]	+05:00
+
+    ----- stderr -----
+    ",
+    );
+    // Transitions in both time zones that don't change the difference aren't
+    // reported.
+    assert_cmd_snapshot!(
+        diff()
+            .args(["America/New_York", "America/Chicago"])
+            .args(["--at", "2025-01-01", "--until", "2026-01-01"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-01-01T00:00:00-05:00[America/New_York]	-01:00
+    2025-03-09T03:00:00-04:00[America/New_York]	-02:00
+    2025-03-09T04:00:00-04:00[America/New_York]	-01:00
+    2025-11-02T01:00:00-05:00[America/New_York]	+00:00
+    2025-11-02T02:00:00-05:00[America/New_York]	-01:00
+
+    ----- stderr -----
+    ",
+    );
+    // The end of the range is inclusive.
+    assert_cmd_snapshot!(
+        diff()
+            .args(zones)
+            .args(["--at", "2025-03-01T00:00Z", "--until", "2025-03-09T07:00Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-01T00:00:00Z[Etc/Unknown]	+05:00
+    2025-03-09T07:00:00Z[Etc/Unknown]	+04:00
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        diff().args(["UTC"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    missing required <time-zone> arguments
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(["UTC", "Mars/Olympus"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    parsed apparent IANA time zone identifier, but the tzdb lookup failed: failed to find time zone `Mars/Olympus` in time zone database
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(["UTC", "UTC", "UTC"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    unrecognized datetime `UTC`
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(["UTC", "UTC", "--at", "2025-01-01", "--until", "2024-01-01"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --until datetime `2024-01-01T00:00:00-05:00[America/New_York]` must be after --at datetime `2025-01-01T00:00:00-05:00[America/New_York]`
+    ",
+    );
+}
+
+/// Tests that the difference is computed at the instant of each datetime
+/// given, including tagged datetimes on stdin.
+#[test]
+fn datetimes() {
+    assert_cmd_snapshot!(
+        diff().args([
+            "America/Los_Angeles",
+            "Europe/Berlin",
+            "2025-03-01T12Z",
+            "2025-03-15T12Z",
+            "2025-04-15T12Z",
+            "2025-10-30T12Z",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +09:00
+    +08:00
+    +09:00
+    +08:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff()
+            .args(["America/Los_Angeles", "Europe/Berlin", "-"])
+            .stdin("2025-03-15T12Z\n2025-04-15T12Z\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +08:00
+    +09:00
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines"])
+            .stdin("meeting at 2025-03-15T12Z\n")
+            .pipe(diff().args(["America/Los_Angeles", "Europe/Berlin", "-"])),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"+08:00","range":[11,25]}],"data":{"text":"meeting at 2025-03-15T12Z\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        diff().args(["UTC", "Asia/Tokyo", "--at", "now", "2025-03-15"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --at and --until cannot be used with <datetime> arguments
+    ",
+    );
+    assert_cmd_snapshot!(
+        diff().args(["UTC", "Asia/Tokyo", "-", "2025-03-15"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    stdin (`-`) cannot be combined with other <datetime> arguments
+    ",
+    );
+}
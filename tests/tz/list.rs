@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use bstr::ByteSlice;
 
-use crate::bttf;
+use crate::{bttf, command::assert_cmd_snapshot};
 
 #[test]
 fn basic() {
@@ -15,3 +17,230 @@ fn basic() {
     assert!(!stdout.contains_str("posix/America/New_York"));
     assert!(!stdout.contains_str("right/America/New_York"));
 }
+
+/// Returns true when link information is available to bttf.
+///
+/// Links are read from the system's `tzdata.zi` file, which isn't present on
+/// every system (e.g., macOS or minimal Linux images). Tests that need link
+/// information are skipped when it's missing.
+fn has_links() -> bool {
+    let dir = std::env::var_os("TZDIR")
+        .map_or_else(|| PathBuf::from("/usr/share/zoneinfo"), PathBuf::from);
+    let exists = dir.join("tzdata.zi").exists();
+    if !exists {
+        eprintln!("skipping test since `tzdata.zi` could not be found");
+    }
+    exists
+}
+
+#[test]
+fn canonical_only() {
+    if !has_links() {
+        return;
+    }
+    let snap = bttf(["tz", "list", "--canonical-only"]).snapshot();
+    let stdout = snap.stdout();
+    assert!(stdout.lines().any(|line| line == b"America/New_York"));
+    assert!(!stdout.lines().any(|line| line == b"US/Eastern"));
+}
+
+#[test]
+fn links() {
+    if !has_links() {
+        return;
+    }
+    let snap = bttf(["tz", "list", "--links"]).snapshot();
+    let stdout = snap.stdout();
+    assert!(
+        stdout.lines().any(|line| line == b"US/Eastern\tAmerica/New_York")
+    );
+    assert!(!stdout.lines().any(|line| line.starts_with(b"America/New_York")));
+}
+
+#[test]
+fn resolve() {
+    if !has_links() {
+        return;
+    }
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--resolve", "US/Eastern"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/New_York
+
+    ----- stderr -----
+    ",
+    );
+    // Names are matched case insensitively.
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--resolve", "us/pacific"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/Los_Angeles
+
+    ----- stderr -----
+    ",
+    );
+    // Canonical time zones resolve to themselves.
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--resolve", "America/New_York"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/New_York
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--resolve", "Nowhere/Foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    unknown time zone `Nowhere/Foo`
+    ",
+    );
+}
+
+#[test]
+fn filter() {
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "europe/l"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Europe/Lisbon
+    Europe/Ljubljana
+    Europe/London
+    Europe/Luxembourg
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--regex", "^america/(new_york|chicago)$"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/Chicago
+    America/New_York
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--regex"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --regex requires a <pattern>
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--regex", "("]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid regex `(`: regex parse error:
+        (
+        ^
+    error: unclosed group
+    ",
+    );
+}
+
+#[test]
+fn filter_links() {
+    if !has_links() {
+        return;
+    }
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--links", "us/"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    US/Alaska	America/Anchorage
+    US/Aleutian	America/Adak
+    US/Arizona	America/Phoenix
+    US/Central	America/Chicago
+    US/East-Indiana	America/Indiana/Indianapolis
+    US/Eastern	America/New_York
+    US/Hawaii	Pacific/Honolulu
+    US/Indiana-Starke	America/Indiana/Knox
+    US/Michigan	America/Detroit
+    US/Mountain	America/Denver
+    US/Pacific	America/Los_Angeles
+    US/Samoa	Pacific/Pago_Pago
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn offset() {
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--offset", "--regex", "^(america/new_york|utc)$"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/New_York	-04:00	EDT
+    UTC	+00:00	UTC
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--offset", "--at", "2025-01-15T12Z"])
+            .args(["--regex", "^(america/new_york|asia/kolkata)$"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/New_York	-05:00	EST
+    Asia/Kolkata	+05:30	IST
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--at", "2025-01-15T12Z"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --at requires --offset
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "list", "--offset", "--links"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --offset cannot be used with --links
+    ",
+    );
+}
@@ -1,3 +1,8 @@
+mod at;
 mod compatible;
+mod diff;
 mod list;
+mod now;
+mod offset;
+mod search;
 mod seq;
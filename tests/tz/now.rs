@@ -0,0 +1,83 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        bttf(["tz", "now"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/New_York -04:00 EDT
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn at() {
+    assert_cmd_snapshot!(
+        bttf(["tz", "now", "--at", "2025-12-25"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    America/New_York -05:00 EST
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "now", "--at", "2025-12-25T00:00Z"]).env("TZ", "Europe/London"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Europe/London +00:00 GMT
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn fixed() {
+    assert_cmd_snapshot!(
+        bttf(["tz", "now"]).env("TZ", "UTC"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    UTC +00:00 UTC
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        bttf(["tz", "now"]).env("TZ", "EST5EDT,M3.2.0,M11.1.0"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    EST5EDT,M3.2.0,M11.1.0 -04:00 EDT
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn json() {
+    assert_cmd_snapshot!(
+        bttf(["tz", "now", "--json"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"abbreviation":"EDT","dst":true,"offset":"-04:00","time_zone":"America/New_York"}
+
+    ----- stderr -----
+    "#,
+    );
+}
@@ -0,0 +1,110 @@
+use crate::command::assert_cmd_snapshot;
+
+fn offset() -> crate::command::Command {
+    crate::bttf(["tz", "offset"])
+}
+
+#[test]
+fn basic() {
+    assert_cmd_snapshot!(
+        offset().args([
+            "America/New_York",
+            "now",
+            "2025-01-15T12:00Z",
+            "2025-07-15T12:00-07",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -04:00 dst
+    -05:00 std
+    -04:00 dst
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        offset()
+            .arg("Europe/London")
+            .stdin("2025-01-15T12:00Z\n2025-07-15T12:00Z\n"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    +00:00 std
+    +01:00 dst
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that civil datetimes in a gap or fold use the offset chosen by the
+/// `compatible` disambiguation strategy.
+#[test]
+fn gap_and_fold() {
+    assert_cmd_snapshot!(
+        offset().args([
+            "America/New_York",
+            "2025-03-09T01:59[America/New_York]",
+            "2025-03-09T02:30[America/New_York]",
+            "2025-11-02T01:30[America/New_York]",
+            "2025-11-02T02:00[America/New_York]",
+        ]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -05:00 std
+    -04:00 dst
+    -04:00 dst
+    -05:00 std
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn tagged() {
+    assert_cmd_snapshot!(
+        crate::bttf(["tag", "lines"])
+            .stdin("foo 2025-01-15T12:00Z bar\n")
+            .pipe(offset().arg("Asia/Kolkata")),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tags":[{"value":"+05:30 std","range":[4,21]}],"data":{"text":"foo 2025-01-15T12:00Z bar\n"}}
+
+    ----- stderr -----
+    "#,
+    );
+}
+
+#[test]
+fn errors() {
+    assert_cmd_snapshot!(
+        offset(),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    missing required <time-zone> argument
+    ",
+    );
+    assert_cmd_snapshot!(
+        offset().args(["UTC", "foo"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    invalid datetime: unrecognized datetime `foo`
+    ",
+    );
+}
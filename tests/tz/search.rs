@@ -0,0 +1,76 @@
+use crate::{bttf, command::assert_cmd_snapshot};
+
+fn search() -> crate::command::Command {
+    bttf(["tz", "search"])
+}
+
+/// Tests that `IST` is ambiguous, and that which time zones use it depends
+/// on the time of year.
+#[test]
+fn ambiguous() {
+    assert_cmd_snapshot!(
+        search().args(["IST", "--at", "2025-01-15T12:00Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Asia/Jerusalem	+02:00
+    Asia/Tel_Aviv	+02:00
+    Israel	+02:00
+    Asia/Calcutta	+05:30
+    Asia/Kolkata	+05:30
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        search().args(["IST", "--at", "2025-07-15T12:00Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Eire	+01:00
+    Europe/Dublin	+01:00
+    Asia/Calcutta	+05:30
+    Asia/Kolkata	+05:30
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn case_insensitive() {
+    assert_cmd_snapshot!(
+        search().args(["bst", "--at", "2025-07-15T12:00Z"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Europe/Belfast	+01:00
+    Europe/Guernsey	+01:00
+    Europe/Isle_of_Man	+01:00
+    Europe/Jersey	+01:00
+    Europe/London	+01:00
+    GB	+01:00
+    GB-Eire	+01:00
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn no_match() {
+    assert_cmd_snapshot!(
+        search().args(["BST", "--at", "2025-01-15T12:00Z"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    no time zones use the abbreviation `BST` at `2025-01-15T12:00:00Z[Etc/Unknown]`
+    ",
+    );
+}
@@ -60,6 +60,88 @@ fn seq_past() {
     );
 }
 
+#[test]
+fn seq_group_by_year() {
+    assert_cmd_snapshot!(
+        seq().args(["Australia/Sydney", "-c5", "--group-by", "year"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024
+      2024-10-06T03:00:00+11:00[Australia/Sydney]
+    2025
+      2025-04-06T02:00:00+10:00[Australia/Sydney]
+      2025-10-05T03:00:00+11:00[Australia/Sydney]
+    2026
+      2026-04-05T02:00:00+10:00[Australia/Sydney]
+      2026-10-04T03:00:00+11:00[Australia/Sydney]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        seq().args(["America/New_York", "-c3", "-p", "--group-by", "year"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2024
+      2024-03-10T03:00:00-04:00[America/New_York]
+    2023
+      2023-11-05T01:00:00-05:00[America/New_York]
+      2023-03-12T03:00:00-04:00[America/New_York]
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        seq().args(["Australia/Sydney", "-c5", "--group-by", "year", "--json"]),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"year":2024,"transitions":["2024-10-06T03:00:00+11:00[Australia/Sydney]"]}
+    {"year":2025,"transitions":["2025-04-06T02:00:00+10:00[Australia/Sydney]","2025-10-05T03:00:00+11:00[Australia/Sydney]"]}
+    {"year":2026,"transitions":["2026-04-05T02:00:00+10:00[Australia/Sydney]","2026-10-04T03:00:00+11:00[Australia/Sydney]"]}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        seq().args(["UTC", "--group-by", "year", "--json"]),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ",
+    );
+    assert_cmd_snapshot!(
+        seq().args(["Australia/Sydney", "-c5", "--json"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --json requires --group-by
+    ",
+    );
+    assert_cmd_snapshot!(
+        seq().args(["Australia/Sydney", "-c5", "--group-by", "month"]),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --group-by: unknown grouping `month`
+    ",
+    );
+}
+
 #[test]
 fn seq_distant_past() {
     assert_cmd_snapshot!(
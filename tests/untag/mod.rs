@@ -44,6 +44,38 @@ fn substitute() {
     );
 }
 
+/// Tests that everything outside of the tags is restored byte-for-byte,
+/// including irregular whitespace and line terminators.
+#[test]
+fn substitute_preserves_layout() {
+    let stdin = "  \t2024-07-19T00Z   \t| 2024-07-21T00Z\t \r\n\
+\t\n\
+\x20\x20\x20\n\
+2024-07-19T00Z\x20\x20";
+    let snap = bttf(["tag", "lines", "--all"])
+        .stdin(stdin)
+        .pipe(bttf(["time", "parse", "--format=rfc3339"]))
+        .pipe(bttf(["untag", "--substitute"]))
+        .snapshot();
+    let expected = "  \t2024-07-19T00:00:00Z[Etc/Unknown]   \t| \
+2024-07-21T00:00:00Z[Etc/Unknown]\t \r\n\
+\t\n\
+\x20\x20\x20\n\
+2024-07-19T00:00:00Z[Etc/Unknown]\x20\x20";
+    assert_eq!(snap.stdout(), expected);
+}
+
+/// Tests that tags are substituted by their position in the data, even when
+/// they aren't sorted, and that overlapping or out of bounds ranges are
+/// ignored.
+#[test]
+fn substitute_unsorted_ranges() {
+    let stdin = r#"{"tags":[{"value":"second","range":[6,7]},{"value":"first","range":[2,3]},{"value":"overlap","range":[2,5]},{"value":"oob","range":[20,30]}],"data":{"text":" \ta\t  b \n"}}
+"#;
+    let snap = bttf(["untag", "--substitute"]).stdin(stdin).snapshot();
+    assert_eq!(snap.stdout(), " \tfirst\t  second \n");
+}
+
 #[test]
 fn format_single_tag() {
     let tmp = TempDir::new();
@@ -183,7 +215,7 @@ fn format_invalid_directive() {
     ----- stdout -----
 
     ----- stderr -----
-    -f/--format: unrecognized format directive `{tagg}`, allowed directives are `{tag}` and `{data}`
+    -f/--format: unrecognized format directive `{tagg}`, allowed directives are `{tag}`, `{tag:<format>}`, `{label}` and `{data}`
     ",
     );
 
@@ -196,7 +228,7 @@ fn format_invalid_directive() {
     ----- stdout -----
 
     ----- stderr -----
-    -f/--format: unrecognized format directive `{tag:{data}`, allowed directives are `{tag}` and `{data}`
+    -f/--format: unrecognized format directive `{tag:{data}`, allowed directives are `{tag}`, `{tag:<format>}`, `{label}` and `{data}`
     ",
     );
 
@@ -239,3 +271,206 @@ fn format_invalid_directive() {
     ",
     );
 }
+
+#[test]
+fn format_tag_strftime() {
+    let tmp = TempDir::new();
+    tmp.create("foo", "2025-03-15T00-04 Springsteen");
+    tmp.create("bar", "2025-03-15T00+11 Zevon");
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "foo", "bar"])
+            .pipe(bttf(["untag", "-f", "{tag:%Y-%m-%d} {data}"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-03-15 foo
+    2025-03-15 bar
+
+    ----- stderr -----
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "foo", "bar"])
+            .pipe(bttf(["untag", "-f", r"{data}\t{tag:%a %b %-d %H:%M %:z} ({tag})"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    foo	Sat Mar 15 00:00 -04:00 (2025-03-15T00-04)
+    bar	Sat Mar 15 00:00 +11:00 (2025-03-15T00+11)
+
+    ----- stderr -----
+    ",
+    );
+
+    // A closing brace can be escaped inside the format string.
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "foo"])
+            .pipe(bttf(["untag", "-f", r"{tag:%Y\}}"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025}
+
+    ----- stderr -----
+    ",
+    );
+}
+
+#[test]
+fn format_tag_strftime_invalid() {
+    let tmp = TempDir::new();
+    tmp.create("foo", "2025-03-15T00-04 Springsteen");
+
+    // The tag has to be a datetime in order to reformat it.
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines", "--auto", "timezone"])
+            .stdin("Hopper America/New_York\n")
+            .pipe(bttf(["untag", "-f", "{tag:%Y}"])),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    <stdin>: line 1: failed to reformat tag `America/New_York` with format `%Y`, since it is not a valid datetime: unrecognized datetime `America/New_York`
+    ",
+    );
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "foo"]).pipe(bttf(["untag", "-f", "{tag:}"])),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    -f/--format: format directive `{tag:}` requires a non-empty format string after the `:`
+    ",
+    );
+}
+
+#[test]
+fn format_drop_synthetic() {
+    let stdin = "\
+foo
+2025-01-01T00Z bar
+";
+    assert_cmd_snapshot!(
+        bttf(["tag", "lines", "--synthetic"])
+            .stdin(stdin)
+            .pipe(bttf(["untag", "--drop-synthetic", "-f", "{tag} {data}"])),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2025-01-01T00Z 2025-01-01T00Z bar
+
+    ----- stderr -----
+    ",
+    );
+}
+
+/// Tests that each line of output is written as soon as the corresponding
+/// line of input is read, instead of after all input has been read.
+#[test]
+fn streaming() {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        sync::mpsc,
+        time::Duration,
+    };
+
+    let line = r#"{"tags":[{"value":"2024-07-19T00Z","range":[3,17]}],"data":{"text":"at 2024-07-19T00Z ok\n"}}"#;
+    let modes: &[(&[&str], &str)] = &[
+        (&[], "at 2024-07-19T00Z ok\n"),
+        (&["-s"], "at 2024-07-19T00Z ok\n"),
+        (&["-f", "{tag}|{data}"], "2024-07-19T00Z|at 2024-07-19T00Z ok\n"),
+    ];
+    for &(args, expected) in modes {
+        let mut child = bttf(["untag"])
+            .args(args)
+            .std()
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        // Lines are read on a separate thread so that we can give up after
+        // a timeout instead of blocking forever if untag buffers its input.
+        let (send, recv) = mpsc::channel();
+        let reader = std::thread::spawn(move || {
+            for got in stdout.lines() {
+                if send.send(got.unwrap()).is_err() {
+                    break;
+                }
+            }
+        });
+        for _ in 0..3 {
+            writeln!(stdin, "{line}").unwrap();
+            stdin.flush().unwrap();
+            let Ok(got) = recv.recv_timeout(Duration::from_secs(10)) else {
+                child.kill().unwrap();
+                panic!("timed out waiting for output, args: {args:?}");
+            };
+            assert_eq!(format!("{got}\n"), expected, "args: {args:?}");
+        }
+        drop(stdin);
+        assert!(child.wait().unwrap().success());
+        reader.join().unwrap();
+    }
+}
+
+/// Tests that `--output json` prints one JSON object per tag, with special
+/// characters escaped and labels included when present.
+#[test]
+fn output_json() {
+    let tmp = TempDir::new();
+    tmp.create("foo", "2025-03-15T00-04 Springsteen 2024-10-01T00-04");
+    tmp.create("bar", "2025-03-15T00+11 Zevon");
+    tmp.create("quux", "no datetimes here");
+
+    assert_cmd_snapshot!(
+        tmp.bttf(["tag", "files", "--all", "foo", "bar", "quux"])
+            .pipe(bttf(["untag", "--output", "json"])),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tag":"2025-03-15T00-04","data":"foo"}
+    {"tag":"2024-10-01T00-04","data":"foo"}
+    {"tag":"2025-03-15T00+11","data":"bar"}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        bttf(["untag", "--output", "json"]).stdin(
+            r#"{"tags":[{"value":"2025-03-15T00-04","label":"mtime"}],"data":{"text":"foo \"bar\"\tbaz\n"}}"#,
+        ),
+        @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"tag":"2025-03-15T00-04","label":"mtime","data":"foo \"bar\"\tbaz"}
+
+    ----- stderr -----
+    "#,
+    );
+    assert_cmd_snapshot!(
+        bttf(["untag", "--output", "json", "-f", "{tag}"]).stdin(""),
+        @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    --output json cannot be used with -f/--format
+    ",
+    );
+}